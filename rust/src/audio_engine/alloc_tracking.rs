@@ -0,0 +1,79 @@
+//! Heap-tracking global allocator for tests.
+//!
+//! Tracking is opt-in per thread, so parallel tests and the harness itself are not
+//! attributed to the code under test.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct TrackingAllocator;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+    static PEAK_BYTES: Cell<isize> = const { Cell::new(0) };
+    static ALLOCATOR_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_allocation(delta: isize) {
+    let _ = TRACKING.try_with(|tracking| {
+        if !tracking.get() {
+            return;
+        }
+        ALLOCATOR_CALLS.with(|calls| calls.set(calls.get() + 1));
+        LIVE_BYTES.with(|live| {
+            let next = live.get() + delta;
+            live.set(next);
+            PEAK_BYTES.with(|peak| peak.set(peak.get().max(next)));
+        });
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_allocation(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_allocation(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+fn track<T>(f: impl FnOnce() -> T) -> T {
+    LIVE_BYTES.with(|live| live.set(0));
+    PEAK_BYTES.with(|peak| peak.set(0));
+    ALLOCATOR_CALLS.with(|calls| calls.set(0));
+    TRACKING.with(|tracking| tracking.set(true));
+    let result = f();
+    TRACKING.with(|tracking| tracking.set(false));
+    result
+}
+
+/// Runs `f` and returns its result with the peak heap bytes allocated on this thread.
+pub(crate) fn track_peak_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let result = track(f);
+    (result, PEAK_BYTES.with(Cell::get) as usize)
+}
+
+/// Runs `f` and returns its result with the number of allocations, reallocations and frees
+/// it made on this thread.
+pub(crate) fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let result = track(f);
+    (result, ALLOCATOR_CALLS.with(Cell::get))
+}
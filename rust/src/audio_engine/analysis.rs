@@ -3,7 +3,8 @@ use crate::{
     messages::{SampleAnalysis, SampleBuffer},
 };
 use stratum_dsp::{
-    AnalysisConfig, AnalysisResult, analyze_audio, features::chroma::extractor::compute_stft,
+    AnalysisConfig, AnalysisResult, analyze_audio,
+    features::{chroma::extractor::compute_stft, onset::energy_flux::detect_energy_flux_onsets},
 };
 
 const TEMPO_CANDIDATE_TOP_N: usize = 25;
@@ -35,6 +36,7 @@ const SPECTRAL_TEMPO_FOUR_FIFTHS_RATIO: f64 = 0.80;
 const SPECTRAL_TEMPO_COMMON_RATIO_TOLERANCE: f64 = 0.04;
 const SPECTRAL_TEMPO_MIN_BPM: f64 = 50.0;
 const SPECTRAL_TEMPO_MAX_BPM: f64 = 190.0;
+const ONSET_THRESHOLD_DB: f32 = -20.0;
 
 /// Analyze audio using stratum-dsp.
 pub fn analyze_sample(
@@ -44,7 +46,9 @@ pub fn analyze_sample(
     let mono = map_channels(sample.samples.to_vec(), sample.channels, 1)
         .map_err(|err| format!("analysis failed: {err}"))?;
 
-    let result = analyze_audio(&mono, sample_rate_hz, analysis_config())
+    let config = analysis_config();
+    let onsets_s = onset_times_s(&mono, sample_rate_hz, &config);
+    let result = analyze_audio(&mono, sample_rate_hz, config)
         .map_err(|err| format!("analysis failed: {err}"))?;
 
     let candidates = tempo_candidates_from_result(&result);
@@ -68,9 +72,32 @@ pub fn analyze_sample(
         bpm,
         key: result.key.name(),
         beat_grid: result.beat_grid,
+        bpm_confidence: result.bpm_confidence,
+        key_confidence: result.key_confidence,
+        grid_stability: result.grid_stability,
+        onsets_s,
     })
 }
 
+/// Energy-flux onset times in seconds from the start of the (untrimmed) sample.
+///
+/// Onsets are a best-effort extra, so a detector failure yields an empty list instead of
+/// failing the whole analysis.
+fn onset_times_s(mono: &[f32], sample_rate_hz: u32, config: &AnalysisConfig) -> Vec<f32> {
+    if sample_rate_hz == 0 {
+        return Vec::new();
+    }
+
+    detect_energy_flux_onsets(mono, config.frame_size, config.hop_size, ONSET_THRESHOLD_DB)
+        .map(|onsets| {
+            onsets
+                .into_iter()
+                .map(|sample| (sample as f64 / f64::from(sample_rate_hz)) as f32)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn analysis_config() -> AnalysisConfig {
     let mut config = AnalysisConfig::default();
     config.emit_tempogram_candidates = true;
@@ -763,6 +790,24 @@ mod tests {
         assert!((bpm - 120.0).abs() < 0.005);
     }
 
+    #[test]
+    fn onset_times_follow_click_positions() {
+        let sample_rate_hz = 44_100;
+        let samples = synthetic_click_track(sample_rate_hz, 120.0, 16);
+
+        let onsets = onset_times_s(&samples, sample_rate_hz, &analysis_config());
+
+        assert!(!onsets.is_empty());
+        assert!(onsets.windows(2).all(|pair| pair[0] < pair[1]));
+        for onset in &onsets {
+            let nearest_beat_s = (onset / 0.5).round() * 0.5;
+            assert!(
+                (onset - nearest_beat_s).abs() < 0.05,
+                "onset {onset} s is not near a click"
+            );
+        }
+    }
+
     #[test]
     fn fixed_tempo_refinement_rejects_sparse_transients() {
         let sample_rate_hz = 44_100;
@@ -7,38 +7,144 @@
 //! - Error handling for audio stream operations
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{BufferSize, Stream, StreamConfig};
+use cpal::{BufferSize, FromSample, SizedSample, Stream, StreamConfig, SupportedBufferSize};
 use env_logger::{Builder, Env};
+use pyo3::Python;
+use pyo3_log::Caching;
 use rtrb::{Consumer, Producer, RingBuffer};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
+use crate::audio_engine::automation::{
+    AutomationCapture, AutomationRecorder, automation_capture_channel,
+};
 use crate::audio_engine::buffer_retirement::{
     AudioBufferRetirement, AudioBufferRetirementWorker, create_audio_buffer_retirement,
 };
-use crate::audio_engine::constants::{MAX_VOICES, NUM_SAMPLES};
-use crate::audio_engine::mixer::{RtMixer, RtRenderPadActivity};
+use crate::audio_engine::clock::{
+    AudioClock, AudioClockTimestamp, PlaybackClockMapping, monotonic_ns,
+};
+use crate::audio_engine::constants::{
+    MAX_OUTPUT_CHANNELS, NUM_PADS, NUM_SAMPLES, OUTPUT_BUFFER_FRAMES,
+    OUTPUT_CONVERSION_SCRATCH_FRAMES, OUTPUT_SAMPLE_RATE_ALTERNATIVES_HZ,
+    OUTPUT_SAMPLE_RATE_FALLBACK_HZ, OUTPUT_SAMPLE_RATE_MAX_HZ, OUTPUT_SAMPLE_RATE_MIN_HZ,
+    SEQUENCER_TRACKS, VOLUME_MAX,
+};
+use crate::audio_engine::deferred_control::{
+    ControlProducer, DeferredControlMessages, DeferredControlQueue,
+};
+use crate::audio_engine::errors::DeviceError;
+use crate::audio_engine::mixer::{RtMixer, RtRenderPadActivity, is_valid_keyboard_mode};
 use crate::audio_engine::scheduler::{
     FixedCapacityScheduler, ScheduledCommand, TransportScheduler,
 };
+use crate::audio_engine::sequencer::StepSequencer;
 use crate::audio_engine::transport::{QuantizeGrid, TransportTimeline};
 use crate::messages::{AudioMessage, ControlMessage, ControlParameterMessage, TriggerQuantization};
 
 pub(crate) const MAX_CONTROL_MESSAGES_PER_CALLBACK: usize = 64;
 pub(crate) const MAX_PARAMETER_MESSAGES_PER_CALLBACK: usize = 64;
 
+/// Capacity of the outgoing ring for one-shot messages (acks, faults, query answers).
+const RELIABLE_AUDIO_MESSAGE_CAPACITY: usize = 256;
+
+/// Capacity of the outgoing ring for meters and playheads, which may drop when full.
+const TELEMETRY_AUDIO_MESSAGE_CAPACITY: usize = 1024;
+
+/// Capacity of the ring carrying stream errors from the CPAL error callback.
+const DEVICE_ERROR_CAPACITY: usize = 16;
+
 /// Handle to the audio stream with associated message channels
 pub struct AudioStreamHandle {
     pub stream: Stream,
     _retirement_worker: AudioBufferRetirementWorker,
-    pub producer: Arc<Mutex<Producer<ControlMessage>>>,
+    pub(crate) producer: Arc<Mutex<ControlProducer>>,
     pub(crate) parameter_producer: Arc<Mutex<Producer<ControlParameterMessage>>>,
-    pub consumer: Arc<Mutex<Consumer<AudioMessage>>>,
+    pub consumer: Arc<Mutex<AudioMessageReceiver>>,
     pub output_channels: usize,
     pub output_sample_rate: u32,
+    pub output_sample_format: OutputSampleFormat,
+    /// Fixed callback buffer size, or `None` when the backend default is used.
+    pub output_buffer_frames: Option<u32>,
+    /// Capacity of the control and parameter message rings.
+    pub control_buffer_capacity: usize,
+    stream_errors: Arc<AtomicU64>,
+    clipped_samples: Arc<AtomicU64>,
+    limiter_meter: Arc<LimiterReductionMeter>,
+    pub(crate) automation_recorder: Mutex<AutomationRecorder>,
+    /// Pads whose delay line was already sent to the callback.
+    pub(crate) pad_delay_line_sent: [AtomicBool; NUM_SAMPLES],
+    audio_clock: Arc<AudioClock>,
+    faulted: Arc<AtomicBool>,
+}
+
+impl AudioStreamHandle {
+    /// Whether the audio callback panicked and the stream now only outputs silence.
+    pub fn is_faulted(&self) -> bool {
+        self.faulted.load(Ordering::Acquire)
+    }
+
+    /// Returns how many device errors the stream reported since the last call.
+    ///
+    /// The cpal error callback may run on the audio thread, so it only counts errors;
+    /// the caller logs them from a Python thread.
+    pub fn take_stream_error_count(&self) -> u64 {
+        self.stream_errors.swap(0, Ordering::Relaxed)
+    }
+
+    /// Output samples the final safety clamp limited to full scale since the stream started.
+    pub fn clip_count(&self) -> u64 {
+        self.clipped_samples.load(Ordering::Relaxed)
+    }
+
+    /// Current and recent-peak master limiter gain reduction in dB published by the callback.
+    pub fn limiter_reduction_db(&self) -> (f32, f32) {
+        self.limiter_meter.read()
+    }
+
+    /// Latest output frame and monotonic time published by the audio callback.
+    pub(crate) fn audio_clock(&self) -> Option<AudioClockTimestamp> {
+        self.audio_clock.read()
+    }
+
+    /// Shared audio clock, for threads that follow the output frame.
+    pub(crate) fn shared_audio_clock(&self) -> Arc<AudioClock> {
+        Arc::clone(&self.audio_clock)
+    }
+}
+
+/// Environment variable that keeps Rust logs on stderr via `env_logger` instead of
+/// forwarding them to Python's `logging` module.
+pub const ENV_LOGGER_OPT_OUT_VAR: &str = "FLITZIS_LOOPER_ENV_LOGGER";
+
+/// Python logger that all Rust log records are forwarded under.
+const PYTHON_LOGGER_NAME: &str = "flitzis_looper";
+
+/// Setup and configure the logger for audio operations.
+///
+/// Records are forwarded to Python's `logging` under the `flitzis_looper` logger, so the
+/// Python-side levels and handlers apply. Setting `FLITZIS_LOOPER_ENV_LOGGER` keeps the
+/// `env_logger` output on stderr instead, which is handy for CLI debugging.
+///
+/// The forwarding logger acquires the GIL for every record, so nothing on the audio
+/// thread may log; realtime diagnostics go through the `AudioMessage` ring instead.
+pub fn setup_logger(py: Python<'_>) {
+    if std::env::var_os(ENV_LOGGER_OPT_OUT_VAR).is_some() {
+        setup_env_logger();
+        return;
+    }
+
+    match pyo3_log::Logger::new(py, Caching::Loggers) {
+        Ok(logger) => {
+            // Ignore initialization errors, only the first logger takes effect.
+            let _ = logger.set_prefix(PYTHON_LOGGER_NAME).install();
+        }
+        Err(_) => setup_env_logger(),
+    }
 }
 
-/// Setup and configure the logger for audio operations
-pub fn setup_logger() {
+fn setup_env_logger() {
     // Default to `info` to avoid extremely expensive debug/trace logging during analysis.
     // Users can override via `RUST_LOG`, e.g. `RUST_LOG=debug` when troubleshooting.
     Builder::from_env(Env::default().default_filter_or("info"))
@@ -57,18 +163,77 @@ impl AudioMessageSink for Producer<AudioMessage> {
     }
 }
 
-fn schedule_immediate_command<
-    const CAPACITY: usize,
-    S: AudioMessageSink,
-    R: AudioBufferRetirement,
->(
+/// Audio-thread side of the outgoing message rings.
+///
+/// Telemetry goes to its own ring, so a burst of meters can never take the slot of an ack,
+/// a fault or a query answer. Both rings drop on overflow rather than block the callback.
+struct AudioMessageSender {
+    reliable: Producer<AudioMessage>,
+    telemetry: Producer<AudioMessage>,
+}
+
+impl AudioMessageSink for AudioMessageSender {
+    fn push_audio_message(&mut self, message: AudioMessage) {
+        let ring = if message.is_telemetry() {
+            &mut self.telemetry
+        } else {
+            &mut self.reliable
+        };
+        let _ = ring.push(message);
+    }
+}
+
+/// Python side of the outgoing message rings.
+///
+/// Device errors come first, then one-shot messages, then telemetry.
+pub struct AudioMessageReceiver {
+    device_errors: Consumer<cpal::StreamError>,
+    reliable: Consumer<AudioMessage>,
+    telemetry: Consumer<AudioMessage>,
+}
+
+impl AudioMessageReceiver {
+    pub fn pop(&mut self) -> Option<AudioMessage> {
+        // The error callback only moves the error into its ring; format it here, off the
+        // audio thread.
+        if let Ok(error) = self.device_errors.pop() {
+            return Some(AudioMessage::DeviceError(error.to_string()));
+        }
+        self.reliable.pop().or_else(|_| self.telemetry.pop()).ok()
+    }
+}
+
+fn audio_message_channels() -> (
+    AudioMessageSender,
+    Producer<cpal::StreamError>,
+    AudioMessageReceiver,
+) {
+    let (device_error_producer, device_error_consumer) = RingBuffer::new(DEVICE_ERROR_CAPACITY);
+    let (reliable_producer, reliable_consumer) = RingBuffer::new(RELIABLE_AUDIO_MESSAGE_CAPACITY);
+    let (telemetry_producer, telemetry_consumer) =
+        RingBuffer::new(TELEMETRY_AUDIO_MESSAGE_CAPACITY);
+
+    (
+        AudioMessageSender {
+            reliable: reliable_producer,
+            telemetry: telemetry_producer,
+        },
+        device_error_producer,
+        AudioMessageReceiver {
+            device_errors: device_error_consumer,
+            reliable: reliable_consumer,
+            telemetry: telemetry_consumer,
+        },
+    )
+}
+
+fn schedule_immediate_command<const CAPACITY: usize, S: AudioMessageSink>(
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     callback_start_frame: u64,
     command: ScheduledCommand,
     mixer: &mut RtMixer,
     transport: &mut TransportTimeline,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
     if scheduler.schedule(callback_start_frame, command).is_ok() {
         drain_scheduler_due_at_callback_start(
@@ -77,7 +242,6 @@ fn schedule_immediate_command<
             mixer,
             transport,
             audio_messages,
-            retirement,
         );
     } else {
         execute_scheduled_command(
@@ -86,18 +250,47 @@ fn schedule_immediate_command<
             callback_start_frame,
             command,
             audio_messages,
-            retirement,
         );
     }
 }
 
 // Keep callback hot-path state borrows explicit instead of hiding them in a context struct.
 #[allow(clippy::too_many_arguments)]
-fn schedule_play_sample_command<
-    const CAPACITY: usize,
-    S: AudioMessageSink,
-    R: AudioBufferRetirement,
->(
+fn schedule_play_sample_command<const CAPACITY: usize, S: AudioMessageSink>(
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    callback_start_frame: u64,
+    trigger_quantization: TriggerQuantization,
+    transport: &mut TransportTimeline,
+    command: ScheduledCommand,
+    mixer: &mut RtMixer,
+    audio_messages: &mut S,
+) {
+    let Some(target_frame) = quantized_target_frame(transport, trigger_quantization) else {
+        schedule_immediate_command(
+            scheduler,
+            callback_start_frame,
+            command,
+            mixer,
+            transport,
+            audio_messages,
+        );
+        return;
+    };
+
+    if scheduler.schedule(target_frame, command).is_ok() {
+        drain_scheduler_due_at_callback_start(
+            scheduler,
+            callback_start_frame,
+            mixer,
+            transport,
+            audio_messages,
+        );
+    }
+}
+
+// Keep callback hot-path state borrows explicit instead of hiding them in a context struct.
+#[allow(clippy::too_many_arguments)]
+fn schedule_exclusive_play_sample_command<const CAPACITY: usize, S: AudioMessageSink>(
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     callback_start_frame: u64,
     trigger_quantization: TriggerQuantization,
@@ -106,9 +299,8 @@ fn schedule_play_sample_command<
     volume: f32,
     mixer: &mut RtMixer,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
-    let command = ScheduledCommand::PlaySample { id, volume };
+    let command = ScheduledCommand::StopAllThenPlaySample { id, volume };
 
     let Some(target_frame) = quantized_target_frame(transport, trigger_quantization) else {
         schedule_immediate_command(
@@ -118,7 +310,6 @@ fn schedule_play_sample_command<
             mixer,
             transport,
             audio_messages,
-            retirement,
         );
         return;
     };
@@ -130,31 +321,71 @@ fn schedule_play_sample_command<
             mixer,
             transport,
             audio_messages,
-            retirement,
         );
     }
 }
 
 // Keep callback hot-path state borrows explicit instead of hiding them in a context struct.
 #[allow(clippy::too_many_arguments)]
-fn schedule_exclusive_play_sample_command<
-    const CAPACITY: usize,
-    S: AudioMessageSink,
-    R: AudioBufferRetirement,
->(
+fn schedule_timed_play_sample_command<const CAPACITY: usize, S: AudioMessageSink>(
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     callback_start_frame: u64,
     trigger_quantization: TriggerQuantization,
     transport: &mut TransportTimeline,
     id: usize,
     volume: f32,
+    frames: u64,
     mixer: &mut RtMixer,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
-    let command = ScheduledCommand::StopAllThenPlaySample { id, volume };
+    // Both the start and the stop must fit, otherwise the voice could play until stopped.
+    if scheduler.capacity() - scheduler.len() < 2 {
+        return;
+    }
 
-    let Some(target_frame) = quantized_target_frame(transport, trigger_quantization) else {
+    let start_frame =
+        quantized_target_frame(transport, trigger_quantization).unwrap_or(callback_start_frame);
+    let stop_frame = start_frame.saturating_add(frames);
+    let start = ScheduledCommand::PlaySampleUntil {
+        id,
+        volume,
+        stop_frame,
+    };
+    let stop = ScheduledCommand::AutoStopSample { id, stop_frame };
+
+    if scheduler.schedule(start_frame, start).is_ok()
+        && scheduler.schedule(stop_frame, stop).is_ok()
+    {
+        drain_scheduler_due_at_callback_start(
+            scheduler,
+            callback_start_frame,
+            mixer,
+            transport,
+            audio_messages,
+        );
+    }
+}
+
+fn is_scene_launch(command: &ScheduledCommand) -> bool {
+    matches!(command, ScheduledCommand::LaunchScene { .. })
+}
+
+/// Schedule a scene switch on the next bar, or now without a master tempo.
+///
+/// Only the latest scene launch stays pending.
+fn schedule_scene_launch<const CAPACITY: usize, S: AudioMessageSink>(
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    callback_start_frame: u64,
+    transport: &mut TransportTimeline,
+    bank: usize,
+    pads: u64,
+    mixer: &mut RtMixer,
+    audio_messages: &mut S,
+) {
+    scheduler.cancel_matching(is_scene_launch);
+    let command = ScheduledCommand::LaunchScene { bank, pads };
+
+    let Some(target_frame) = transport.next_bar_frame() else {
         schedule_immediate_command(
             scheduler,
             callback_start_frame,
@@ -162,7 +393,6 @@ fn schedule_exclusive_play_sample_command<
             mixer,
             transport,
             audio_messages,
-            retirement,
         );
         return;
     };
@@ -174,11 +404,16 @@ fn schedule_exclusive_play_sample_command<
             mixer,
             transport,
             audio_messages,
-            retirement,
         );
+    } else {
+        audio_messages.push_audio_message(AudioMessage::SceneDropped { bank });
     }
 }
 
+fn latency_frames(latency_ns: u64, sample_rate_hz: u32) -> u64 {
+    (latency_ns as f64 * f64::from(sample_rate_hz) / 1_000_000_000.0).round() as u64
+}
+
 fn quantized_target_frame(
     transport: &TransportTimeline,
     trigger_quantization: TriggerQuantization,
@@ -215,17 +450,12 @@ fn anchor_transport_phase_from_pad_at_frame(
     transport.set_master_bpm_and_anchor_bar_phase_at_frame(bpm, bar_phase_beats, output_frame)
 }
 
-fn drain_scheduler_due_at_callback_start<
-    const CAPACITY: usize,
-    S: AudioMessageSink,
-    R: AudioBufferRetirement,
->(
+fn drain_scheduler_due_at_callback_start<const CAPACITY: usize, S: AudioMessageSink>(
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     callback_start_frame: u64,
     mixer: &mut RtMixer,
     transport: &mut TransportTimeline,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
     while let Some(event) = scheduler.pop_due_at_callback_start(callback_start_frame) {
         execute_scheduled_command(
@@ -234,25 +464,51 @@ fn drain_scheduler_due_at_callback_start<
             event.execution_frame,
             event.command,
             audio_messages,
-            retirement,
         );
     }
 }
 
-fn execute_scheduled_command<S: AudioMessageSink, R: AudioBufferRetirement>(
+/// Schedule the triggers of every sequencer step due in this callback and report the steps.
+fn schedule_sequencer_steps<const CAPACITY: usize, S: AudioMessageSink>(
+    sequencer: &mut StepSequencer,
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    transport: &TransportTimeline,
+    callback_end_frame: u64,
+    audio_messages: &mut S,
+) {
+    while let Some(tick) = sequencer.next_tick(transport, callback_end_frame) {
+        for track in 0..SEQUENCER_TRACKS {
+            let Some((step, event)) = sequencer.track_step(track, tick.grid_index) else {
+                continue;
+            };
+
+            audio_messages.push_audio_message(AudioMessage::SequencerStep { track, step });
+            if let Some(event) = event {
+                let _ = scheduler.schedule(
+                    tick.frame,
+                    ScheduledCommand::PlaySample {
+                        id: event.id,
+                        volume: event.volume,
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn execute_scheduled_command<S: AudioMessageSink>(
     mixer: &mut RtMixer,
     _transport: &mut TransportTimeline,
     output_frame: u64,
     command: ScheduledCommand,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
     match command {
         ScheduledCommand::PlaySample { id, volume } => {
-            let started =
-                mixer.play_sample_at_output_frame_rt(id, volume, output_frame, retirement);
+            let started = mixer.play_sample_at_output_frame(id, volume, output_frame);
 
             if started {
+                publish_voice_steal(mixer, audio_messages);
                 audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
             } else {
                 audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
@@ -263,42 +519,180 @@ fn execute_scheduled_command<S: AudioMessageSink, R: AudioBufferRetirement>(
                 return;
             }
 
-            stop_all_samples(mixer, audio_messages, retirement);
-            let started =
-                mixer.play_sample_at_output_frame_rt(id, volume, output_frame, retirement);
+            stop_all_samples(mixer, audio_messages);
+            let started = mixer.play_sample_at_output_frame(id, volume, output_frame);
+
+            if started {
+                audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
+            }
+        }
+        ScheduledCommand::PlaySampleWith {
+            id,
+            volume,
+            options,
+            handle,
+        } => {
+            if mixer.play_sample_with_at_output_frame(id, volume, options, output_frame) {
+                publish_voice_steal(mixer, audio_messages);
+                mixer.set_voice_handle(id, handle);
+                audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
+            } else {
+                audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+                audio_messages.push_audio_message(AudioMessage::TriggerDropped { handle });
+            }
+        }
+        ScheduledCommand::PlaySampleCued { id, volume } => {
+            if mixer.play_sample_cued_at_output_frame(id, volume, output_frame) {
+                publish_voice_steal(mixer, audio_messages);
+                audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
+            } else {
+                audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+            }
+        }
+        ScheduledCommand::PlaySampleUntil {
+            id,
+            volume,
+            stop_frame,
+        } => {
+            let started = mixer.play_sample_at_output_frame(id, volume, output_frame);
 
             if started {
+                publish_voice_steal(mixer, audio_messages);
+                mixer.set_auto_stop_frame(id, stop_frame);
                 audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
+            } else {
+                audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+            }
+        }
+        ScheduledCommand::AutoStopSample { id, stop_frame } => {
+            if mixer.auto_stop_sample(id, stop_frame) {
+                audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
             }
         }
         ScheduledCommand::StopSample { id } => {
-            mixer.stop_sample_rt(id, retirement);
+            mixer.stop_sample(id);
             audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+            stop_choke_group_members(mixer, id, audio_messages);
+        }
+        ScheduledCommand::StopVoice { handle } => {
+            if let Some(id) = mixer.stop_voice(handle) {
+                audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+            }
         }
         ScheduledCommand::StopAll => {
-            stop_all_samples(mixer, audio_messages, retirement);
+            stop_all_samples(mixer, audio_messages);
+        }
+        ScheduledCommand::LaunchScene { bank, pads } => {
+            launch_scene(mixer, output_frame, bank, pads, audio_messages);
         }
     }
 }
 
-fn stop_all_samples<S: AudioMessageSink, R: AudioBufferRetirement>(
+/// Reports the voice the last trigger stole, stopping the victim pad if it has no voice left.
+fn publish_voice_steal<S: AudioMessageSink>(mixer: &mut RtMixer, audio_messages: &mut S) {
+    let Some(steal) = mixer.take_voice_steal() else {
+        return;
+    };
+
+    if !mixer.sample_is_active(steal.stolen_id) {
+        audio_messages.push_audio_message(AudioMessage::SampleStopped {
+            id: steal.stolen_id,
+        });
+    }
+    audio_messages.push_audio_message(AudioMessage::VoiceStolen {
+        id: steal.id,
+        priority: steal.priority,
+        stolen_id: steal.stolen_id,
+        stolen_priority: steal.stolen_priority,
+    });
+}
+
+/// Stop the bank's pads that leave the scene before starting the ones that join it, so the
+/// freed voices are available; pads that stay in the scene keep playing untouched.
+fn launch_scene<S: AudioMessageSink>(
     mixer: &mut RtMixer,
+    output_frame: u64,
+    bank: usize,
+    pads: u64,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
-    for voice in &mut mixer.voices {
-        if voice.active {
-            let id = voice.sample_id;
-            voice.stop_rt(retirement);
+    let first_id = bank * NUM_PADS;
+    for pad in 0..NUM_PADS {
+        let id = first_id + pad;
+        if pads & (1 << pad) == 0 && mixer.sample_is_active(id) {
+            mixer.stop_sample(id);
+            audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+        }
+    }
+
+    for pad in 0..NUM_PADS {
+        let id = first_id + pad;
+        if pads & (1 << pad) != 0
+            && !mixer.sample_is_active(id)
+            && mixer.play_sample_at_output_frame(id, VOLUME_MAX, output_frame)
+        {
+            publish_voice_steal(mixer, audio_messages);
+            audio_messages.push_audio_message(AudioMessage::SampleStarted { id });
+        }
+    }
+
+    audio_messages.push_audio_message(AudioMessage::SceneLaunched { bank });
+}
+
+fn stop_choke_group_members<S: AudioMessageSink>(
+    mixer: &mut RtMixer,
+    id: usize,
+    audio_messages: &mut S,
+) {
+    let Some(group) = mixer.choke_group_stopping_with(id) else {
+        return;
+    };
+
+    // Members fade out like the stopped pad; pads already fading were reported before.
+    for member in 0..NUM_SAMPLES {
+        if member != id
+            && mixer.pad_choke_group(member) == Some(group)
+            && mixer.sample_is_active(member)
+        {
+            mixer.stop_sample(member);
+            audio_messages.push_audio_message(AudioMessage::SampleStopped { id: member });
+        }
+    }
+}
+
+fn set_keyboard_mode<S: AudioMessageSink>(
+    mixer: &mut RtMixer,
+    bank: usize,
+    mode: Option<(usize, usize)>,
+    audio_messages: &mut S,
+) {
+    if !is_valid_keyboard_mode(bank, mode) {
+        return;
+    }
+
+    for id in bank * NUM_PADS..(bank + 1) * NUM_PADS {
+        if mixer.sample_is_active(id) {
             audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
         }
     }
+    mixer.set_keyboard_mode(bank, mode);
+}
+
+fn stop_all_samples<S: AudioMessageSink>(mixer: &mut RtMixer, audio_messages: &mut S) {
+    for voice in &mixer.voices {
+        if voice.active {
+            audio_messages.push_audio_message(AudioMessage::SampleStopped {
+                id: voice.sample_id,
+            });
+        }
+    }
+    mixer.stop_all();
 }
 
-// Keep scheduler, transport, mixer, output, and retirement ownership visible in the render path.
+// Keep scheduler, transport, mixer, and output ownership visible in the render path.
 #[cfg(test)]
 #[allow(clippy::too_many_arguments)]
-fn render_scheduled_audio<const CAPACITY: usize, S: AudioMessageSink, R: AudioBufferRetirement>(
+fn render_scheduled_audio<const CAPACITY: usize, S: AudioMessageSink>(
     mixer: &mut RtMixer,
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     output: &mut [f32],
@@ -307,7 +701,6 @@ fn render_scheduled_audio<const CAPACITY: usize, S: AudioMessageSink, R: AudioBu
     channels: usize,
     transport: &mut TransportTimeline,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
     let mut pad_activity = RtRenderPadActivity::default();
     render_scheduled_audio_tracking_pads(
@@ -320,17 +713,12 @@ fn render_scheduled_audio<const CAPACITY: usize, S: AudioMessageSink, R: AudioBu
         channels,
         transport,
         audio_messages,
-        retirement,
     );
 }
 
-// Keep scheduler, transport, mixer, output, telemetry, and retirement ownership visible.
+// Keep scheduler, transport, mixer, output, and telemetry ownership visible.
 #[allow(clippy::too_many_arguments)]
-fn render_scheduled_audio_tracking_pads<
-    const CAPACITY: usize,
-    S: AudioMessageSink,
-    R: AudioBufferRetirement,
->(
+fn render_scheduled_audio_tracking_pads<const CAPACITY: usize, S: AudioMessageSink>(
     mixer: &mut RtMixer,
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
     output: &mut [f32],
@@ -340,7 +728,6 @@ fn render_scheduled_audio_tracking_pads<
     channels: usize,
     transport: &mut TransportTimeline,
     audio_messages: &mut S,
-    retirement: &mut R,
 ) {
     output.fill(0.0);
     pad_peaks.fill(0.0);
@@ -352,7 +739,6 @@ fn render_scheduled_audio_tracking_pads<
         mixer,
         transport,
         audio_messages,
-        retirement,
     );
 
     if channels == 0 {
@@ -380,7 +766,6 @@ fn render_scheduled_audio_tracking_pads<
                 rendered_until_frame,
                 callback_end_frame,
                 channels,
-                retirement,
             );
             break;
         };
@@ -396,7 +781,6 @@ fn render_scheduled_audio_tracking_pads<
                 rendered_until_frame,
                 callback_end_frame,
                 channels,
-                retirement,
             );
             break;
         }
@@ -412,7 +796,6 @@ fn render_scheduled_audio_tracking_pads<
                 rendered_until_frame,
                 next_target_frame,
                 channels,
-                retirement,
             );
             rendered_until_frame = next_target_frame;
         }
@@ -426,15 +809,26 @@ fn render_scheduled_audio_tracking_pads<
                 event.execution_frame,
                 event.command,
                 audio_messages,
-                retirement,
             );
         }
     }
+
+    publish_ended_one_shots(mixer, audio_messages);
+}
+
+/// Reports pads whose one-shot voices played to their end and left the pad idle.
+fn publish_ended_one_shots<S: AudioMessageSink>(mixer: &mut RtMixer, audio_messages: &mut S) {
+    while let Some(id) = mixer.take_ended_one_shot() {
+        if !mixer.sample_is_active(id) {
+            audio_messages.push_audio_message(AudioMessage::VoiceFinished { id });
+            audio_messages.push_audio_message(AudioMessage::SampleStopped { id });
+        }
+    }
 }
 
 // Keep segment frame bounds and realtime state explicit for in-buffer scheduling tests.
 #[allow(clippy::too_many_arguments)]
-fn render_mixer_segment<R: AudioBufferRetirement>(
+fn render_mixer_segment(
     mixer: &mut RtMixer,
     output: &mut [f32],
     pad_peaks: &mut [f32; NUM_SAMPLES],
@@ -444,7 +838,6 @@ fn render_mixer_segment<R: AudioBufferRetirement>(
     segment_start_frame: u64,
     segment_end_frame: u64,
     channels: usize,
-    retirement: &mut R,
 ) {
     if segment_end_frame <= segment_start_frame {
         return;
@@ -460,7 +853,6 @@ fn render_mixer_segment<R: AudioBufferRetirement>(
         segment_peaks,
         segment_start_frame,
         pad_activity,
-        retirement,
     );
 
     for id in pad_activity.iter() {
@@ -468,6 +860,29 @@ fn render_mixer_segment<R: AudioBufferRetirement>(
     }
 }
 
+/// Master limiter gain reduction the callback publishes for the control thread.
+#[derive(Default)]
+struct LimiterReductionMeter {
+    reduction_db: AtomicU32,
+    peak_reduction_db: AtomicU32,
+}
+
+impl LimiterReductionMeter {
+    fn publish(&self, (reduction_db, peak_reduction_db): (f32, f32)) {
+        self.reduction_db
+            .store(reduction_db.to_bits(), Ordering::Relaxed);
+        self.peak_reduction_db
+            .store(peak_reduction_db.to_bits(), Ordering::Relaxed);
+    }
+
+    fn read(&self) -> (f32, f32) {
+        (
+            f32::from_bits(self.reduction_db.load(Ordering::Relaxed)),
+            f32::from_bits(self.peak_reduction_db.load(Ordering::Relaxed)),
+        )
+    }
+}
+
 fn master_output_peak(output: &[f32]) -> f32 {
     output.iter().fold(0.0_f32, |peak, sample| {
         if sample.is_finite() {
@@ -478,6 +893,22 @@ fn master_output_peak(output: &[f32]) -> f32 {
     })
 }
 
+/// Final safety stage before samples leave the callback.
+///
+/// Clamps to full scale so neither a disabled limiter nor a DSP bug upstream can hand a backend
+/// values it would wrap into full-scale noise; NaN becomes silence. Returns how many samples
+/// had to be changed.
+fn clamp_output(output: &mut [f32]) -> u64 {
+    let mut clipped = 0;
+    for sample in output.iter_mut() {
+        let clamped = sample.clamp(-1.0, 1.0);
+        let clamped = if clamped.is_nan() { 0.0 } else { clamped };
+        clipped += u64::from(clamped.to_bits() != sample.to_bits());
+        *sample = clamped;
+    }
+    clipped
+}
+
 fn publish_master_peak_telemetry<S: AudioMessageSink>(
     audio_messages: &mut S,
     master_peak: f32,
@@ -496,14 +927,32 @@ fn publish_master_peak_telemetry<S: AudioMessageSink>(
     }
 }
 
-fn publish_pad_telemetry<S: AudioMessageSink>(
+fn publish_limiter_meter_telemetry<S: AudioMessageSink>(
     audio_messages: &mut S,
-    mixer: &RtMixer,
-    pad_peaks: &[f32; NUM_SAMPLES],
-    pad_activity: &RtRenderPadActivity,
+    (reduction_db, peak_reduction_db): (f32, f32),
     frame_clock: u64,
     emit_interval_frames: u64,
-    last_pad_emit_frame: &mut u64,
+    last_limiter_emit_frame: &mut u64,
+) {
+    if frame_clock.wrapping_sub(*last_limiter_emit_frame) < emit_interval_frames {
+        return;
+    }
+
+    *last_limiter_emit_frame = frame_clock;
+    audio_messages.push_audio_message(AudioMessage::LimiterMeter {
+        reduction_db,
+        peak_reduction_db,
+    });
+}
+
+fn publish_pad_telemetry<S: AudioMessageSink>(
+    audio_messages: &mut S,
+    mixer: &RtMixer,
+    pad_peaks: &[f32; NUM_SAMPLES],
+    pad_activity: &RtRenderPadActivity,
+    frame_clock: u64,
+    emit_interval_frames: u64,
+    last_pad_emit_frame: &mut u64,
 ) {
     if frame_clock.wrapping_sub(*last_pad_emit_frame) < emit_interval_frames {
         return;
@@ -526,32 +975,134 @@ fn publish_pad_telemetry<S: AudioMessageSink>(
     }
 }
 
+fn publish_transient_snaps<S: AudioMessageSink>(audio_messages: &mut S, mixer: &mut RtMixer) {
+    mixer.drain_transient_snaps(|id, offset_frames| {
+        audio_messages.push_audio_message(AudioMessage::TransientSnap { id, offset_frames });
+    });
+}
+
+fn publish_sample_swaps<S: AudioMessageSink, R: AudioBufferRetirement>(
+    audio_messages: &mut S,
+    mixer: &mut RtMixer,
+    retirement: &mut R,
+) {
+    mixer.drain_sample_swaps(retirement, |id, entry| {
+        audio_messages.push_audio_message(AudioMessage::PlaylistEntryActive { id, entry });
+    });
+}
+
+/// Runs one audio callback body and fails safe if it panics.
+///
+/// A panic fills `data` with silence, marks the stream as faulted and reports a single
+/// `EngineFault`. Engine state may be half-updated after unwinding, so every later callback
+/// outputs silence without running `render` again.
+fn run_callback_guarded<S: AudioMessageSink>(
+    faulted: &AtomicBool,
+    data: &mut [f32],
+    audio_messages: &mut S,
+    render: impl FnOnce(&mut [f32], &mut S),
+) {
+    if faulted.load(Ordering::Acquire) {
+        data.fill(0.0);
+        return;
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| render(data, audio_messages)));
+    if result.is_err() {
+        data.fill(0.0);
+        faulted.store(true, Ordering::Release);
+        audio_messages.push_audio_message(AudioMessage::EngineFault());
+    }
+}
+
 fn control_message_retirement_slots_needed(message: &ControlMessage) -> usize {
     match message {
-        ControlMessage::LoadSample { .. } | ControlMessage::PublishPreparedStems { .. } => 2,
-        ControlMessage::StopSample { .. } => MAX_VOICES,
-        ControlMessage::UnloadSample { .. } => MAX_VOICES + 2,
-        ControlMessage::StopAll() | ControlMessage::PlaySampleExclusive { .. } => MAX_VOICES,
+        // Voices hold no buffers; the fifth slot is a replaced buffer still fading out, the
+        // sixth an onset table arriving with a sample the mixer rejects.
+        ControlMessage::LoadSample { .. } => 6,
+        ControlMessage::UnloadSample { .. } => 5,
+        ControlMessage::PublishPreparedStems { .. } => 2,
+        ControlMessage::QueueSampleSwap { .. } => 1,
+        ControlMessage::PublishPadOnsets { .. } => 1,
         _ => 0,
     }
 }
 
+/// Applies the messages that arrived since the last callback, before the block renders.
+///
+/// Parameter updates apply first, then control messages strictly in the order they were
+/// sent. The parameter queue only carries updates sent while no control message waited;
+/// later ones queue behind the control messages as `ApplyParameter`. Everything therefore
+/// applies in the order it was sent, so a gain set before a trigger shapes the triggered
+/// voice from its first frame and a gain set after it ramps the playing voice.
+#[allow(clippy::too_many_arguments)]
+fn drain_callback_messages<
+    const CAPACITY: usize,
+    const DEFERRED: usize,
+    S: AudioMessageSink,
+    R: AudioBufferRetirement,
+>(
+    consumer: &mut Consumer<ControlMessage>,
+    parameter_consumer: &mut Consumer<ControlParameterMessage>,
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    deferred: &mut DeferredControlMessages<DEFERRED>,
+    callback_start_frame: u64,
+    trigger_quantization: &mut TriggerQuantization,
+    transport: &mut TransportTimeline,
+    sequencer: &mut StepSequencer,
+    mixer: &mut RtMixer,
+    audio_messages: &mut S,
+    retirement: &mut R,
+    mut capture: Option<&mut AutomationCapture>,
+) {
+    drain_parameter_messages(parameter_consumer, mixer, transport, capture.as_deref_mut());
+    drain_control_messages(
+        consumer,
+        scheduler,
+        deferred,
+        callback_start_frame,
+        trigger_quantization,
+        transport,
+        sequencer,
+        mixer,
+        audio_messages,
+        retirement,
+        capture,
+    );
+}
+
 // Keep queue, scheduler, transport, mixer, telemetry, and retirement state explicit in the callback.
 #[allow(clippy::too_many_arguments)]
-fn drain_control_messages<const CAPACITY: usize, S: AudioMessageSink, R: AudioBufferRetirement>(
+fn drain_control_messages<
+    const CAPACITY: usize,
+    const DEFERRED: usize,
+    S: AudioMessageSink,
+    R: AudioBufferRetirement,
+>(
     consumer: &mut Consumer<ControlMessage>,
     scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    deferred: &mut DeferredControlMessages<DEFERRED>,
     callback_start_frame: u64,
     trigger_quantization: &mut TriggerQuantization,
     transport: &mut TransportTimeline,
+    sequencer: &mut StepSequencer,
     mixer: &mut RtMixer,
     audio_messages: &mut S,
     retirement: &mut R,
+    mut capture: Option<&mut AutomationCapture>,
 ) -> usize {
     let mut processed = 0;
 
     while processed < MAX_CONTROL_MESSAGES_PER_CALLBACK {
         let needed_retirement_slots = match consumer.peek() {
+            Ok(ControlMessage::AtFrame(_)) => 0,
+            // A stamped message needs retirement room only once it is due.
+            Ok(_) if deferred.has_pending_stamp() => {
+                if deferred.is_full() {
+                    break;
+                }
+                0
+            }
             Ok(message) => control_message_retirement_slots_needed(message),
             Err(_) => break,
         };
@@ -565,6 +1116,21 @@ fn drain_control_messages<const CAPACITY: usize, S: AudioMessageSink, R: AudioBu
         let Ok(message) = consumer.pop() else {
             break;
         };
+        processed += 1;
+
+        if let ControlMessage::AtFrame(target_frame) = message {
+            deferred.set_pending_stamp(target_frame);
+            continue;
+        }
+        if let ControlMessage::ApplyParameter(parameter) = message {
+            let mut pending = PendingControlParameters::default();
+            pending.record(parameter);
+            pending.apply_to(mixer, transport, capture.as_deref_mut());
+            continue;
+        }
+        let Err(message) = deferred.defer_stamped(message) else {
+            continue;
+        };
 
         process_control_message(
             message,
@@ -572,16 +1138,142 @@ fn drain_control_messages<const CAPACITY: usize, S: AudioMessageSink, R: AudioBu
             callback_start_frame,
             trigger_quantization,
             transport,
+            sequencer,
             mixer,
             audio_messages,
             retirement,
         );
-        processed += 1;
     }
 
     processed
 }
 
+/// Applies the deferred control messages due at `frame`, in order.
+///
+/// A due message that needs more retirement slots than are free waits, together with the
+/// messages behind it, for a later segment.
+#[allow(clippy::too_many_arguments)]
+fn apply_due_deferred_control_messages<
+    const CAPACITY: usize,
+    const DEFERRED: usize,
+    S: AudioMessageSink,
+    R: AudioBufferRetirement,
+>(
+    deferred: &mut DeferredControlMessages<DEFERRED>,
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    frame: u64,
+    trigger_quantization: &mut TriggerQuantization,
+    transport: &mut TransportTimeline,
+    sequencer: &mut StepSequencer,
+    mixer: &mut RtMixer,
+    audio_messages: &mut S,
+    retirement: &mut R,
+) {
+    while let Some(message) = deferred.peek_due(frame) {
+        let needed_retirement_slots = control_message_retirement_slots_needed(message);
+        if needed_retirement_slots > 0
+            && retirement.available_retirement_slots() < needed_retirement_slots
+        {
+            break;
+        }
+
+        let Some(message) = deferred.pop_due(frame) else {
+            break;
+        };
+        process_control_message(
+            message,
+            scheduler,
+            frame,
+            trigger_quantization,
+            transport,
+            sequencer,
+            mixer,
+            audio_messages,
+            retirement,
+        );
+    }
+}
+
+/// Renders one callback buffer, split at the target frames of deferred control messages.
+///
+/// Each segment applies the deferred messages due at its first frame, renders through
+/// [`render_scheduled_audio_tracking_pads`] and advances the transport. Without deferred
+/// messages inside the buffer this is a single segment over the whole buffer.
+#[allow(clippy::too_many_arguments)]
+fn render_callback_segments<
+    const CAPACITY: usize,
+    const DEFERRED: usize,
+    S: AudioMessageSink,
+    R: AudioBufferRetirement,
+>(
+    deferred: &mut DeferredControlMessages<DEFERRED>,
+    scheduler: &mut FixedCapacityScheduler<CAPACITY>,
+    trigger_quantization: &mut TriggerQuantization,
+    transport: &mut TransportTimeline,
+    sequencer: &mut StepSequencer,
+    mixer: &mut RtMixer,
+    output: &mut [f32],
+    pad_peaks: &mut [f32; NUM_SAMPLES],
+    pad_activity: &mut RtRenderPadActivity,
+    channels: usize,
+    audio_messages: &mut S,
+    retirement: &mut R,
+) {
+    pad_peaks.fill(0.0);
+    pad_activity.clear();
+
+    let frames = output.len().checked_div(channels).unwrap_or(0);
+    let callback_end_frame = transport.output_frame().saturating_add(frames as u64);
+    let mut segment_peaks = [0.0_f32; NUM_SAMPLES];
+    let mut segment_activity = RtRenderPadActivity::default();
+    let mut rendered_frames = 0;
+
+    loop {
+        let segment_start_frame = transport.output_frame();
+        apply_due_deferred_control_messages(
+            deferred,
+            scheduler,
+            segment_start_frame,
+            trigger_quantization,
+            transport,
+            sequencer,
+            mixer,
+            audio_messages,
+            retirement,
+        );
+
+        let segment_end_frame = deferred
+            .peek_next_target_frame()
+            .filter(|frame| *frame > segment_start_frame && *frame < callback_end_frame)
+            .unwrap_or(callback_end_frame);
+        let segment_frames = (segment_end_frame - segment_start_frame) as usize;
+        let start = rendered_frames * channels;
+        let end = (rendered_frames + segment_frames) * channels;
+
+        render_scheduled_audio_tracking_pads(
+            mixer,
+            scheduler,
+            &mut output[start..end],
+            &mut segment_peaks,
+            &mut segment_activity,
+            segment_start_frame,
+            channels,
+            transport,
+            audio_messages,
+        );
+        for id in segment_activity.iter() {
+            pad_peaks[id] = pad_peaks[id].max(segment_peaks[id]);
+            pad_activity.record(id);
+        }
+
+        transport.advance_by_rendered_frames(segment_frames);
+        rendered_frames += segment_frames;
+        if rendered_frames >= frames {
+            break;
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct ParameterDrainResult {
     messages_drained: usize,
@@ -608,6 +1300,12 @@ struct PendingPadEq {
     high_db: f32,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct PendingPadMute {
+    id: usize,
+    muted: bool,
+}
+
 struct PendingControlParameters {
     volume: Option<f32>,
     speed: Option<f32>,
@@ -618,6 +1316,8 @@ struct PendingControlParameters {
     pad_gain_count: usize,
     pad_eq: [PendingPadEq; MAX_PARAMETER_MESSAGES_PER_CALLBACK],
     pad_eq_count: usize,
+    pad_mute: [PendingPadMute; MAX_PARAMETER_MESSAGES_PER_CALLBACK],
+    pad_mute_count: usize,
 }
 
 impl Default for PendingControlParameters {
@@ -640,6 +1340,11 @@ impl Default for PendingControlParameters {
                 high_db: 0.0,
             }; MAX_PARAMETER_MESSAGES_PER_CALLBACK],
             pad_eq_count: 0,
+            pad_mute: [PendingPadMute {
+                id: 0,
+                muted: false,
+            }; MAX_PARAMETER_MESSAGES_PER_CALLBACK],
+            pad_mute_count: 0,
         }
     }
 }
@@ -664,6 +1369,17 @@ impl PendingControlParameters {
             } => {
                 self.record_pad_eq(id, low_db, mid_db, high_db);
             }
+            ControlParameterMessage::SetPadParams { id, params } => {
+                if let Some(gain_db) = params.gain_db {
+                    self.record_pad_gain(id, gain_db);
+                }
+                if let Some([low_db, mid_db, high_db]) = params.eq_db {
+                    self.record_pad_eq(id, low_db, mid_db, high_db);
+                }
+                if let Some(muted) = params.mute {
+                    self.record_pad_mute(id, muted);
+                }
+            }
         }
     }
 
@@ -725,32 +1441,80 @@ impl PendingControlParameters {
         }
     }
 
-    fn apply_to(self, mixer: &mut RtMixer, transport: &mut TransportTimeline) -> usize {
+    fn record_pad_mute(&mut self, id: usize, muted: bool) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+        if let Some(pending) = self.pad_mute[..self.pad_mute_count]
+            .iter_mut()
+            .find(|pending| pending.id == id)
+        {
+            pending.muted = muted;
+            return;
+        }
+        if self.pad_mute_count < self.pad_mute.len() {
+            self.pad_mute[self.pad_mute_count] = PendingPadMute { id, muted };
+            self.pad_mute_count += 1;
+        }
+    }
+
+    /// Applies the pending updates, recording the automatable ones into `capture`.
+    fn apply_to(
+        self,
+        mixer: &mut RtMixer,
+        transport: &mut TransportTimeline,
+        mut capture: Option<&mut AutomationCapture>,
+    ) -> usize {
+        let frame = transport.output_frame();
+        let mut record = |parameter| {
+            if let Some(capture) = capture.as_deref_mut() {
+                capture.record(frame, parameter);
+            }
+        };
         let mut applied = 0;
 
         if let Some(volume) = self.volume {
             mixer.set_volume(volume);
+            record(ControlParameterMessage::SetVolume(volume));
             applied += 1;
         }
         if let Some(speed) = self.speed {
             mixer.set_speed(speed);
+            record(ControlParameterMessage::SetSpeed(speed));
             applied += 1;
         }
         if let Some(bpm) = self.master_bpm {
             mixer.set_master_bpm(bpm);
-            transport.set_master_bpm_preserving_bar_phase_at_frame(bpm, transport.output_frame());
+            transport.set_master_bpm_preserving_bar_phase_at_frame(bpm, frame);
+            record(ControlParameterMessage::SetMasterBpm(bpm));
             applied += 1;
         }
+        // Pad BPM is sample metadata rather than a performance move and is not recorded.
         for pending in self.pad_bpm[..self.pad_bpm_count].iter().copied() {
             mixer.set_pad_bpm(pending.id, pending.bpm);
             applied += 1;
         }
         for pending in self.pad_gain[..self.pad_gain_count].iter().copied() {
             mixer.set_pad_gain(pending.id, pending.gain_db);
+            record(ControlParameterMessage::SetPadGain {
+                id: pending.id,
+                gain_db: pending.gain_db,
+            });
             applied += 1;
         }
         for pending in self.pad_eq[..self.pad_eq_count].iter().copied() {
             mixer.set_pad_eq(pending.id, pending.low_db, pending.mid_db, pending.high_db);
+            record(ControlParameterMessage::SetPadEq {
+                id: pending.id,
+                low_db: pending.low_db,
+                mid_db: pending.mid_db,
+                high_db: pending.high_db,
+            });
+            applied += 1;
+        }
+        // Mute is a toggle rather than a continuous move and is not recorded.
+        for pending in self.pad_mute[..self.pad_mute_count].iter().copied() {
+            mixer.set_pad_fade_mute(pending.id, pending.muted);
             applied += 1;
         }
 
@@ -762,6 +1526,7 @@ fn drain_parameter_messages(
     consumer: &mut Consumer<ControlParameterMessage>,
     mixer: &mut RtMixer,
     transport: &mut TransportTimeline,
+    capture: Option<&mut AutomationCapture>,
 ) -> ParameterDrainResult {
     let mut pending = PendingControlParameters::default();
     let mut messages_drained = 0;
@@ -777,7 +1542,7 @@ fn drain_parameter_messages(
 
     ParameterDrainResult {
         messages_drained,
-        parameters_applied: pending.apply_to(mixer, transport),
+        parameters_applied: pending.apply_to(mixer, transport, capture),
     }
 }
 
@@ -789,6 +1554,7 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
     callback_start_frame: u64,
     trigger_quantization: &mut TriggerQuantization,
     transport: &mut TransportTimeline,
+    sequencer: &mut StepSequencer,
     mixer: &mut RtMixer,
     audio_messages: &mut S,
     retirement: &mut R,
@@ -797,12 +1563,64 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
         ControlMessage::Ping() => {
             audio_messages.push_audio_message(AudioMessage::Pong());
         }
-        ControlMessage::LoadSample { id, sample } => {
-            mixer.load_sample_rt(id, sample, retirement);
+        #[cfg(test)]
+        ControlMessage::PanicForTest() => panic!("injected audio thread panic"),
+        // Stamps are taken off the queue while draining.
+        ControlMessage::AtFrame(_) => {}
+        // Replayed automation is not recorded again; live updates never carry a stamp.
+        ControlMessage::SetParameter(parameter) | ControlMessage::ApplyParameter(parameter) => {
+            let mut pending = PendingControlParameters::default();
+            pending.record(parameter);
+            pending.apply_to(mixer, transport, None);
+        }
+        ControlMessage::QueryPadLatency { id } => {
+            let frames = mixer.pad_latency_frames(id);
+            audio_messages.push_audio_message(AudioMessage::PadLatency { id, frames });
+        }
+        ControlMessage::QueryState() => {
+            audio_messages.push_audio_message(AudioMessage::StateSnapshot {
+                active_voices: mixer.active_voice_count() as u8,
+                loaded_mask: mixer.loaded_slot_mask(),
+            });
+        }
+        ControlMessage::QueryRemaining { id } => {
+            let remaining = mixer.pad_remaining(id);
+            audio_messages.push_audio_message(AudioMessage::PadRemaining {
+                id,
+                remaining_frames: remaining.map(|(frames, _)| frames),
+                remaining_s: remaining.map(|(_, seconds)| seconds),
+            });
+        }
+        ControlMessage::LoadSample { id, sample, onsets } => {
+            let active_before = mixer.active_pads();
+            let loaded = mixer.load_sample_rt(id, sample, retirement);
+            if let Some(onsets) = onsets {
+                if loaded {
+                    mixer.publish_pad_onsets_rt(id, onsets, retirement);
+                } else {
+                    retirement.retire_onsets(onsets);
+                }
+            }
+            for (pad, active) in active_before.into_iter().enumerate() {
+                if active && !mixer.sample_is_active(pad) {
+                    audio_messages.push_audio_message(AudioMessage::SampleStopped { id: pad });
+                }
+            }
+        }
+        ControlMessage::QueueSampleSwap {
+            id,
+            entry,
+            sample,
+            bpm,
+        } => {
+            mixer.queue_sample_swap_rt(id, entry, sample, bpm, retirement);
         }
         ControlMessage::PublishPreparedStems { id, stems } => {
             mixer.publish_prepared_stems_rt(id, stems, retirement);
         }
+        ControlMessage::PublishPadOnsets { id, onsets } => {
+            mixer.publish_pad_onsets_rt(id, onsets, retirement);
+        }
         ControlMessage::SetStemMixMode {
             id,
             mode,
@@ -823,11 +1641,30 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
                 callback_start_frame,
                 *trigger_quantization,
                 transport,
-                id,
-                volume,
+                ScheduledCommand::PlaySample { id, volume },
+                mixer,
+                audio_messages,
+            );
+        }
+        ControlMessage::PlaySampleWith {
+            id,
+            volume,
+            options,
+            handle,
+        } => {
+            schedule_play_sample_command(
+                scheduler,
+                callback_start_frame,
+                *trigger_quantization,
+                transport,
+                ScheduledCommand::PlaySampleWith {
+                    id,
+                    volume,
+                    options,
+                    handle,
+                },
                 mixer,
                 audio_messages,
-                retirement,
             );
         }
         ControlMessage::PlaySampleExclusive { id, volume } => {
@@ -840,9 +1677,34 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
                 volume,
                 mixer,
                 audio_messages,
-                retirement,
             );
         }
+        ControlMessage::PlaySampleFor { id, volume, frames } => {
+            schedule_timed_play_sample_command(
+                scheduler,
+                callback_start_frame,
+                *trigger_quantization,
+                transport,
+                id,
+                volume,
+                frames,
+                mixer,
+                audio_messages,
+            );
+        }
+        ControlMessage::PlaySampleCued { id, volume } => {
+            schedule_immediate_command(
+                scheduler,
+                callback_start_frame,
+                ScheduledCommand::PlaySampleCued { id, volume },
+                mixer,
+                transport,
+                audio_messages,
+            );
+        }
+        ControlMessage::PromoteToMaster { id } => {
+            mixer.promote_to_master(id);
+        }
         ControlMessage::StopSample { id } => {
             schedule_immediate_command(
                 scheduler,
@@ -851,7 +1713,16 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
                 mixer,
                 transport,
                 audio_messages,
-                retirement,
+            );
+        }
+        ControlMessage::StopVoice { handle } => {
+            schedule_immediate_command(
+                scheduler,
+                callback_start_frame,
+                ScheduledCommand::StopVoice { handle },
+                mixer,
+                transport,
+                audio_messages,
             );
         }
         ControlMessage::StopAll() => {
@@ -862,9 +1733,28 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
                 mixer,
                 transport,
                 audio_messages,
-                retirement,
             );
         }
+        ControlMessage::PreviewLoopRegion { id, start_s, end_s } => {
+            mixer.preview_loop_region(id, start_s, end_s);
+        }
+        ControlMessage::StopPreviewLoop() => {
+            mixer.stop_preview_loop();
+        }
+        ControlMessage::LaunchScene { bank, pads } => {
+            schedule_scene_launch(
+                scheduler,
+                callback_start_frame,
+                transport,
+                bank,
+                pads,
+                mixer,
+                audio_messages,
+            );
+        }
+        ControlMessage::CancelScene() => {
+            scheduler.cancel_matching(is_scene_launch);
+        }
         ControlMessage::UnloadSample { id } => {
             mixer.unload_sample_rt(id, retirement);
         }
@@ -874,72 +1764,737 @@ fn process_control_message<const CAPACITY: usize, S: AudioMessageSink, R: AudioB
         ControlMessage::SetKeyLock(enabled) => {
             mixer.set_key_lock(enabled);
         }
+        ControlMessage::SetMasterDcBlocker(enabled) => {
+            mixer.set_master_dc_blocker(enabled);
+        }
+        ControlMessage::SetMasterLimiter(enabled) => {
+            mixer.set_master_limiter(enabled);
+        }
+        ControlMessage::SetLimiterMeterEvents(enabled) => {
+            mixer.set_limiter_meter_events(enabled);
+        }
+        ControlMessage::SetMonoOutput(enabled) => {
+            mixer.set_mono_output(enabled);
+        }
+        ControlMessage::SetMonitorOutput {
+            source,
+            first_channel,
+        } => {
+            mixer.set_monitor_output(source, first_channel);
+        }
+        ControlMessage::SetTempoGlide(glide_ms) => {
+            mixer.set_tempo_glide_ms(glide_ms);
+        }
+        ControlMessage::SetFadeSamples(samples) => {
+            mixer.set_stop_fade_samples(samples);
+        }
+        ControlMessage::SetOutputTrim { channel, gain } => {
+            mixer.set_output_trim(channel, gain);
+        }
         ControlMessage::SetPadKeyLock { id, enabled } => {
             mixer.set_pad_key_lock(id, enabled);
         }
+        ControlMessage::SetPadChokeGroup { id, group } => {
+            mixer.set_pad_choke_group(id, group);
+        }
+        ControlMessage::SetChokeGroupStopAll { group, enabled } => {
+            mixer.set_choke_group_stop_all(group, enabled);
+        }
         ControlMessage::SetPadTimingMetadata { id, metadata } => {
             mixer.set_pad_timing_metadata(id, metadata);
         }
+        ControlMessage::SetPadTransientSnap { id, window_ms } => {
+            mixer.set_pad_transient_snap(id, window_ms);
+        }
         ControlMessage::AnchorTransportPhaseFromPad { id } => {
             anchor_transport_phase_from_pad(mixer, transport, id);
         }
         ControlMessage::SetPadLoopRegion { id, start_s, end_s } => {
             mixer.set_pad_loop_region(id, start_s, end_s);
         }
-        ControlMessage::SetTriggerQuantization(mode) => {
-            *trigger_quantization = mode;
+        ControlMessage::SetPadFadeMute { id, muted } => {
+            mixer.set_pad_fade_mute(id, muted);
         }
-        ControlMessage::PauseSample { id } => {
-            mixer.pause_sample_at_output_frame(id, callback_start_frame);
+        ControlMessage::SetPadSlices { id, slices } => {
+            mixer.set_pad_slices(id, slices);
         }
-        ControlMessage::ResumeSample { id } => {
-            mixer.resume_sample_at_output_frame(id, callback_start_frame);
+        ControlMessage::SetKeyboardMode { bank, mode } => {
+            set_keyboard_mode(mixer, bank, mode, audio_messages);
         }
-        ControlMessage::SeekSample { id, position_s } => {
-            mixer.seek_sample_at_output_frame(id, position_s, callback_start_frame);
+        ControlMessage::SetPadPriority { id, priority } => {
+            mixer.set_pad_priority(id, priority);
         }
-    }
-}
-
-/// Create and configure the audio stream
-///
-/// This function:
-/// 1. Sets up the default audio device
-/// 2. Configures the stream with appropriate parameters
-/// 3. Creates ring buffers for message passing
-/// 4. Initializes the mixer
-/// 5. Builds and returns the audio stream
-pub fn create_audio_stream() -> Result<AudioStreamHandle, Box<dyn std::error::Error>> {
-    setup_logger();
+        ControlMessage::SetVoiceStealPolicy(policy) => {
+            mixer.set_voice_steal_policy(policy);
+        }
+        ControlMessage::SetDefaultLoopMode(mode) => {
+            mixer.set_default_loop_mode(mode);
+        }
+        ControlMessage::SetPadLoopMode { id, mode } => {
+            mixer.set_pad_loop_mode(id, mode);
+        }
+        ControlMessage::SetPadCue { id, enabled } => {
+            mixer.set_pad_cue(id, enabled);
+        }
+        ControlMessage::SetCueSplit(enabled) => {
+            mixer.set_cue_split(enabled);
+        }
+        ControlMessage::SetPadClip {
+            id,
+            enabled,
+            ceiling,
+        } => {
+            mixer.set_pad_clip(id, enabled, ceiling);
+        }
+        ControlMessage::SetPadDelay { id, delay_ms, line } => {
+            if let Some(line) = line {
+                mixer.set_pad_delay_line(id, line);
+            }
+            mixer.set_pad_delay(id, delay_ms);
+        }
+        ControlMessage::SetSpeakerLayout(layout) => {
+            mixer.set_speaker_layout(layout);
+        }
+        ControlMessage::SetPadPosition {
+            id,
+            azimuth_degrees,
+        } => {
+            mixer.set_pad_position(id, azimuth_degrees);
+        }
+        ControlMessage::AutomatePadEq {
+            id,
+            band,
+            target_db,
+            duration_frames,
+        } => {
+            mixer.automate_pad_eq(id, band, target_db, duration_frames);
+        }
+        ControlMessage::StoreSnapshot { slot } => {
+            mixer.store_snapshot(slot);
+        }
+        ControlMessage::MorphToSnapshot {
+            slot,
+            duration_frames,
+        } => {
+            mixer.morph_to_snapshot(slot, duration_frames);
+        }
+        ControlMessage::SetPadFades { id, fades } => {
+            mixer.set_pad_fades(id, fades);
+        }
+        ControlMessage::SetVelocityAttack(attack) => {
+            mixer.set_velocity_attack(attack);
+        }
+        ControlMessage::SetPadRateRatio { id, ratio } => {
+            mixer.set_pad_rate_ratio(id, ratio);
+        }
+        ControlMessage::SetTriggerQuantization(mode) => {
+            *trigger_quantization = mode;
+        }
+        ControlMessage::SetSwing(percent) => {
+            transport.set_swing_percent(percent);
+        }
+        ControlMessage::SetLatencyCompensation(enabled) => {
+            transport.set_latency_compensation(enabled);
+        }
+        ControlMessage::BeginSequence { track, steps } => {
+            sequencer.begin_sequence(track, steps);
+        }
+        ControlMessage::SetSequenceStep { track, step, event } => {
+            sequencer.set_sequence_step(track, step, event);
+        }
+        ControlMessage::CommitSequence { track } => {
+            sequencer.commit_sequence(track);
+        }
+        ControlMessage::SetSequencerTrackMute { track, muted } => {
+            sequencer.set_track_muted(track, muted);
+        }
+        ControlMessage::SetSequencerRunning(running) => {
+            sequencer.set_running(running);
+        }
+        ControlMessage::PauseSample { id } => {
+            mixer.pause_sample_at_output_frame(id, callback_start_frame);
+        }
+        ControlMessage::ResumeSample { id } => {
+            mixer.resume_sample_at_output_frame(id, callback_start_frame);
+        }
+        ControlMessage::Stutter {
+            id,
+            slice_frames,
+            on,
+        } => {
+            mixer.set_stutter(id, slice_frames, on);
+        }
+        ControlMessage::ResetPadDsp { id } => {
+            mixer.reset_pad_dsp(id);
+        }
+        ControlMessage::SeekSample { id, position_s } => {
+            if mixer.seek_sample_at_output_frame(id, position_s, callback_start_frame) {
+                audio_messages.push_audio_message(AudioMessage::SeekAcknowledged { id });
+            }
+        }
+    }
+}
 
-    let host = cpal::default_host();
-    let device = host
-        .default_output_device()
-        .ok_or("No audio device found")?;
+/// Pick the output sample rate for a requested rate.
+///
+/// Returns `requested` when any supported range contains it, otherwise the closest
+/// range boundary. Falls back to `default_rate` when the device reports no ranges.
+pub(crate) fn select_output_sample_rate(
+    requested: u32,
+    supported_ranges: &[(u32, u32)],
+    default_rate: u32,
+) -> u32 {
+    if supported_ranges
+        .iter()
+        .any(|&(min, max)| (min..=max).contains(&requested))
+    {
+        return requested;
+    }
 
-    let config = device.default_output_config()?;
-    let sample_rate = config.sample_rate();
-    let sample_rate_hz = sample_rate;
-    let channels = config.channels();
+    supported_ranges
+        .iter()
+        .map(|&(min, max)| requested.clamp(min, max.max(min)))
+        .min_by_key(|&candidate| (candidate.abs_diff(requested), candidate))
+        .unwrap_or(default_rate)
+}
 
-    log::info!(
-        "Starting AudioEngine... ({} ch@{} Hz)",
-        channels,
-        sample_rate_hz
+/// Output configuration range offered by a device for the f32 sample format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OutputConfigRange {
+    pub channels: u16,
+    pub min_sample_rate: u32,
+    pub max_sample_rate: u32,
+}
+
+impl std::fmt::Display for OutputConfigRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ch@{}-{} Hz",
+            self.channels, self.min_sample_rate, self.max_sample_rate
+        )
+    }
+}
+
+/// Output channel count and sample rate used to build the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OutputConfigChoice {
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+impl OutputConfigChoice {
+    fn is_usable(self) -> bool {
+        is_usable_channel_count(self.channels)
+            && (OUTPUT_SAMPLE_RATE_MIN_HZ..=OUTPUT_SAMPLE_RATE_MAX_HZ).contains(&self.sample_rate)
+    }
+}
+
+fn is_usable_channel_count(channels: u16) -> bool {
+    (1..=MAX_OUTPUT_CHANNELS).contains(&channels)
+}
+
+/// Clips a supported range to the accepted sample-rate bounds.
+fn usable_sample_rate_range(range: &OutputConfigRange) -> Option<(u32, u32)> {
+    let min = range.min_sample_rate.max(OUTPUT_SAMPLE_RATE_MIN_HZ);
+    let max = range.max_sample_rate.min(OUTPUT_SAMPLE_RATE_MAX_HZ);
+    (is_usable_channel_count(range.channels) && min <= max).then_some((min, max))
+}
+
+/// Select a usable output configuration.
+///
+/// The device default is used when it is usable, with `requested_sample_rate` resolved
+/// against the supported ranges for the default channel count. Otherwise the supported
+/// ranges are searched for a usable alternative, preferring stereo.
+pub(crate) fn select_output_config(
+    default: Option<OutputConfigChoice>,
+    supported: &[OutputConfigRange],
+    requested_sample_rate: Option<u32>,
+) -> Result<OutputConfigChoice, DeviceError> {
+    if let Some(default) = default.filter(|default| default.is_usable()) {
+        let sample_rate = match requested_sample_rate {
+            Some(requested) => {
+                let ranges: Vec<(u32, u32)> = supported
+                    .iter()
+                    .filter(|range| range.channels == default.channels)
+                    .filter_map(usable_sample_rate_range)
+                    .collect();
+                select_output_sample_rate(requested, &ranges, default.sample_rate)
+            }
+            None => default.sample_rate,
+        };
+
+        return Ok(OutputConfigChoice {
+            channels: default.channels,
+            sample_rate,
+        });
+    }
+
+    let preferred_rate = requested_sample_rate.unwrap_or(OUTPUT_SAMPLE_RATE_FALLBACK_HZ);
+    let candidates = supported.iter().filter_map(|range| {
+        let (min, max) = usable_sample_rate_range(range)?;
+        Some(OutputConfigChoice {
+            channels: range.channels,
+            sample_rate: preferred_rate.clamp(min, max),
+        })
+    });
+
+    candidates
+        .clone()
+        .find(|choice| choice.channels == 2)
+        .or_else(|| candidates.clone().next())
+        .ok_or_else(|| DeviceError::NoUsableConfig {
+            default: default.map_or_else(
+                || "unavailable".to_string(),
+                |default| format!("{} ch@{} Hz", default.channels, default.sample_rate),
+            ),
+            supported: if supported.is_empty() {
+                "none".to_string()
+            } else {
+                supported
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        })
+}
+
+/// Device sample formats the engine can output, in order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputSampleFormat {
+    F32,
+    I16,
+    U16,
+}
+
+impl OutputSampleFormat {
+    const PREFERENCE: [OutputSampleFormat; 3] = [
+        OutputSampleFormat::F32,
+        OutputSampleFormat::I16,
+        OutputSampleFormat::U16,
+    ];
+
+    fn from_cpal(format: cpal::SampleFormat) -> Option<Self> {
+        match format {
+            cpal::SampleFormat::F32 => Some(OutputSampleFormat::F32),
+            cpal::SampleFormat::I16 => Some(OutputSampleFormat::I16),
+            cpal::SampleFormat::U16 => Some(OutputSampleFormat::U16),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputSampleFormat::F32 => "f32",
+            OutputSampleFormat::I16 => "i16",
+            OutputSampleFormat::U16 => "u16",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputSampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Pick the sample format to open the stream with.
+///
+/// The device's native (default) format wins when the engine can output it, so no
+/// conversion is done needlessly. Otherwise the supported formats are tried in the order
+/// f32, i16, u16. Returns `None` when the device offers none of them.
+pub(crate) fn select_output_sample_format(
+    native: Option<OutputSampleFormat>,
+    supported: &[OutputSampleFormat],
+) -> Option<OutputSampleFormat> {
+    if let Some(native) = native
+        && (supported.is_empty() || supported.contains(&native))
+    {
+        return Some(native);
+    }
+
+    OutputSampleFormat::PREFERENCE
+        .into_iter()
+        .find(|format| supported.contains(format))
+}
+
+/// One output stream configuration tried while negotiating with the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StreamAttempt {
+    pub choice: OutputConfigChoice,
+    pub sample_format: OutputSampleFormat,
+    /// Fixed callback buffer size, or `None` for the backend default.
+    pub buffer_frames: Option<u32>,
+}
+
+impl StreamAttempt {
+    fn buffer_size(self) -> BufferSize {
+        self.buffer_frames
+            .map_or(BufferSize::Default, BufferSize::Fixed)
+    }
+}
+
+impl std::fmt::Display for StreamAttempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ch@{} Hz, {}, ",
+            self.choice.channels, self.choice.sample_rate, self.sample_format
+        )?;
+        match self.buffer_frames {
+            Some(frames) => write!(f, "{frames} frames"),
+            None => write!(f, "default buffer"),
+        }
+    }
+}
+
+/// Orders the stream configurations to try, most preferred first.
+///
+/// The selected configuration is tried with the fixed buffer size, then with that size
+/// clamped to the device's buffer bounds, then with the backend default buffer size.
+/// Finally, alternative sample rates supported for the same channel count are tried with
+/// the default buffer size. Duplicates are skipped.
+///
+/// A `requested_buffer_frames` size is the only attempt, so it is never silently replaced.
+pub(crate) fn stream_negotiation_ladder(
+    selected: OutputConfigChoice,
+    sample_format: OutputSampleFormat,
+    requested_buffer_frames: Option<u32>,
+    buffer_frames_range: Option<(u32, u32)>,
+    supported: &[OutputConfigRange],
+) -> Vec<StreamAttempt> {
+    if let Some(frames) = requested_buffer_frames {
+        return vec![StreamAttempt {
+            sample_format,
+            choice: selected,
+            buffer_frames: Some(frames),
+        }];
+    }
+
+    let mut attempts = Vec::new();
+    let mut push = |attempt: StreamAttempt| {
+        if !attempts.contains(&attempt) {
+            attempts.push(attempt);
+        }
+    };
+
+    push(StreamAttempt {
+        sample_format,
+        choice: selected,
+        buffer_frames: Some(OUTPUT_BUFFER_FRAMES),
+    });
+    if let Some((min, max)) = buffer_frames_range
+        && min <= max
+        && max > 0
+    {
+        push(StreamAttempt {
+            sample_format,
+            choice: selected,
+            buffer_frames: Some(OUTPUT_BUFFER_FRAMES.clamp(min.max(1), max)),
+        });
+    }
+    push(StreamAttempt {
+        sample_format,
+        choice: selected,
+        buffer_frames: None,
+    });
+
+    let ranges: Vec<(u32, u32)> = supported
+        .iter()
+        .filter(|range| range.channels == selected.channels)
+        .filter_map(usable_sample_rate_range)
+        .collect();
+    for sample_rate in OUTPUT_SAMPLE_RATE_ALTERNATIVES_HZ {
+        if ranges
+            .iter()
+            .any(|&(min, max)| (min..=max).contains(&sample_rate))
+        {
+            push(StreamAttempt {
+                sample_format,
+                choice: OutputConfigChoice {
+                    channels: selected.channels,
+                    sample_rate,
+                },
+                buffer_frames: None,
+            });
+        }
+    }
+
+    attempts
+}
+
+/// Names of the host's output devices, in `host.output_devices()` order.
+///
+/// The position in this list is the index accepted by `create_audio_stream`, which stays
+/// unambiguous when several identical interfaces share a name.
+pub fn output_device_names() -> Result<Vec<String>, cpal::DevicesError> {
+    Ok(cpal::default_host()
+        .output_devices()?
+        .map(|device| output_device_name(&device))
+        .collect())
+}
+
+fn output_device_name(device: &cpal::Device) -> String {
+    device
+        .description()
+        .map(|description| description.name().to_string())
+        .unwrap_or_else(|_| "Unknown device".to_string())
+}
+
+/// Position of the first device called `name` in `output_device_names()` order.
+fn output_device_index(names: &[String], name: &str) -> Option<usize> {
+    names.iter().position(|device_name| device_name == name)
+}
+
+/// Output device to open instead of the host default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputDeviceChoice {
+    /// Position in `output_device_names()` order.
+    Index(usize),
+    /// First device with this name.
+    Name(String),
+}
+
+fn select_output_device(
+    host: &cpal::Host,
+    device: Option<&OutputDeviceChoice>,
+) -> Result<cpal::Device, Box<dyn std::error::Error>> {
+    let Some(device) = device else {
+        return Ok(host
+            .default_output_device()
+            .ok_or("No audio device found")?);
+    };
+
+    // A name resolves against the same enumeration the device is taken from, so a device
+    // plugged in or out in between cannot shift it onto another one.
+    let devices: Vec<cpal::Device> = host.output_devices()?.collect();
+    let count = devices.len();
+    let index = match device {
+        OutputDeviceChoice::Index(index) => *index,
+        OutputDeviceChoice::Name(name) => {
+            let names: Vec<String> = devices.iter().map(output_device_name).collect();
+            output_device_index(&names, name).ok_or_else(|| DeviceError::DeviceNameNotFound {
+                name: name.clone(),
+                available: names.join(", "),
+            })?
+        }
+    };
+    devices
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| DeviceError::DeviceIndexOutOfRange { index, count }.into())
+}
+
+/// Create and configure the audio stream
+///
+/// This function:
+/// 1. Sets up the default audio device, or the one `device` picks from
+///    `host.output_devices()`
+/// 2. Configures the stream with appropriate parameters
+/// 3. Creates ring buffers for message passing, the incoming ones holding
+///    `buffer_capacity` messages each
+/// 4. Initializes the mixer
+/// 5. Builds and returns the audio stream
+///
+/// When `requested_sample_rate` is not supported by the device, the nearest supported
+/// rate is used instead and the substitution is logged. If the device rejects the
+/// stream, the configurations from `stream_negotiation_ladder` are tried in order and
+/// an error listing every attempt is returned only when all of them fail. A
+/// `requested_buffer_frames` size is not negotiated: if the device rejects it,
+/// `DeviceError::BufferSizeRejected` is returned.
+pub fn create_audio_stream(
+    requested_sample_rate: Option<u32>,
+    device: Option<&OutputDeviceChoice>,
+    buffer_capacity: usize,
+    requested_buffer_frames: Option<u32>,
+) -> Result<AudioStreamHandle, Box<dyn std::error::Error>> {
+    let host = cpal::default_host();
+    let device = select_output_device(&host, device)?;
+
+    let device_default = device.default_output_config().ok();
+    let default_config = device_default.as_ref().map(|config| OutputConfigChoice {
+        channels: config.channels(),
+        sample_rate: config.sample_rate(),
+    });
+    let supported_ranges: Vec<(OutputSampleFormat, OutputConfigRange)> = device
+        .supported_output_configs()
+        .map(|configs| {
+            configs
+                .filter_map(|range| {
+                    let format = OutputSampleFormat::from_cpal(range.sample_format())?;
+                    Some((
+                        format,
+                        OutputConfigRange {
+                            channels: range.channels(),
+                            min_sample_rate: range.min_sample_rate(),
+                            max_sample_rate: range.max_sample_rate(),
+                        },
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let native_format = device_default
+        .as_ref()
+        .and_then(|config| OutputSampleFormat::from_cpal(config.sample_format()));
+    let mut supported_formats: Vec<OutputSampleFormat> = Vec::new();
+    for (format, _) in &supported_ranges {
+        if !supported_formats.contains(format) {
+            supported_formats.push(*format);
+        }
+    }
+    let sample_format = select_output_sample_format(native_format, &supported_formats)
+        .unwrap_or(OutputSampleFormat::F32);
+    if sample_format != OutputSampleFormat::F32 {
+        log::info!("Output stream uses {sample_format} samples; rendered f32 is converted");
+    }
+    let supported_configs: Vec<OutputConfigRange> = supported_ranges
+        .iter()
+        .filter(|(format, _)| *format == sample_format)
+        .map(|(_, range)| *range)
+        .collect();
+
+    let selected = select_output_config(default_config, &supported_configs, requested_sample_rate)?;
+    if default_config.is_some_and(|default| !default.is_usable()) {
+        log::warn!(
+            "Default output configuration is unusable; using {} ch@{} Hz",
+            selected.channels,
+            selected.sample_rate
+        );
+    }
+    if let Some(requested) = requested_sample_rate
+        && requested != selected.sample_rate
+    {
+        log::warn!(
+            "Requested sample rate {requested} Hz is not supported by the output device; using {} Hz",
+            selected.sample_rate
+        );
+    }
+
+    let buffer_frames_range = device
+        .default_output_config()
+        .ok()
+        .and_then(|config| match *config.buffer_size() {
+            SupportedBufferSize::Range { min, max } => Some((min, max)),
+            SupportedBufferSize::Unknown => None,
+        });
+
+    let attempts = stream_negotiation_ladder(
+        selected,
+        sample_format,
+        requested_buffer_frames,
+        buffer_frames_range,
+        &supported_configs,
     );
+    let mut failures = Vec::with_capacity(attempts.len());
+    for attempt in attempts {
+        log::info!("Trying output stream {attempt}");
+        match build_audio_stream(&device, attempt, buffer_capacity) {
+            Ok(handle) => {
+                log::info!("Starting AudioEngine... ({attempt})");
+                return Ok(handle);
+            }
+            Err(err) => {
+                log::warn!("Output stream {attempt} was rejected: {err}");
+                if let Some(frames) = requested_buffer_frames {
+                    return Err(DeviceError::BufferSizeRejected {
+                        frames,
+                        supported: buffer_frames_range.map_or_else(
+                            || "unknown".to_string(),
+                            |(min, max)| format!("{min}..={max} frames"),
+                        ),
+                        reason: err.to_string(),
+                    }
+                    .into());
+                }
+                failures.push(format!("{attempt}: {err}"));
+            }
+        }
+    }
+
+    Err(DeviceError::StreamBuildFailed {
+        attempts: failures.join("; "),
+    }
+    .into())
+}
+
+/// Renders f32 audio through `scratch` and converts it into a device buffer of `T`.
+///
+/// Larger device buffers are rendered in several scratch-sized passes, so the callback
+/// never allocates. Only the first pass starts at the reported output latency; later passes
+/// get `None` and keep the previous measurement.
+fn render_converted<T: SizedSample + FromSample<f32>>(
+    data: &mut [T],
+    scratch: &mut [f32],
+    mut output_latency_ns: Option<u64>,
+    render: &mut impl FnMut(&mut [f32], Option<u64>),
+) {
+    for chunk in data.chunks_mut(scratch.len()) {
+        let scratch = &mut scratch[..chunk.len()];
+        render(scratch, output_latency_ns.take());
+        for (output, sample) in chunk.iter_mut().zip(scratch.iter()) {
+            *output = T::from_sample(*sample);
+        }
+    }
+}
+
+/// Time between the callback and its first frame reaching the device, if the backend
+/// reports usable timestamps.
+fn output_latency_ns(info: &cpal::OutputCallbackInfo) -> Option<u64> {
+    let timestamp = info.timestamp();
+    let latency = timestamp.playback.duration_since(&timestamp.callback)?;
+    (!latency.is_zero()).then_some(latency.as_nanos() as u64)
+}
+
+fn build_converting_output_stream<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    mut render: impl FnMut(&mut [f32], Option<u64>) + Send + 'static,
+    on_error: impl FnMut(cpal::StreamError) + Send + 'static,
+) -> Result<Stream, cpal::BuildStreamError> {
+    let mut scratch =
+        vec![0.0_f32; OUTPUT_CONVERSION_SCRATCH_FRAMES * usize::from(config.channels)];
+
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], info: &cpal::OutputCallbackInfo| {
+            render_converted(data, &mut scratch, output_latency_ns(info), &mut render);
+        },
+        on_error,
+        None,
+    )
+}
+
+/// Builds the stream and its engine state for one negotiated configuration.
+fn build_audio_stream(
+    device: &cpal::Device,
+    attempt: StreamAttempt,
+    buffer_capacity: usize,
+) -> Result<AudioStreamHandle, cpal::BuildStreamError> {
+    let channels = attempt.choice.channels;
+    let sample_rate = attempt.choice.sample_rate;
+    let sample_rate_hz = sample_rate;
 
     // Create ring buffer for incoming messages (Python->Rust)
-    let (producer_in, mut consumer_in) = RingBuffer::new(1024);
+    let (producer_in, mut consumer_in) = RingBuffer::new(buffer_capacity);
 
     // Create ring buffer for fast parameter updates (Python->Rust)
-    let (parameter_producer_in, mut parameter_consumer_in) = RingBuffer::new(1024);
+    let (parameter_producer_in, mut parameter_consumer_in) = RingBuffer::new(buffer_capacity);
+
+    // Create ring buffer for recorded automation (Rust->Python)
+    let (mut automation_capture, automation_recorder) = automation_capture_channel();
 
-    // Create ring buffer for outgoing messages (Rust->Python)
-    let (mut producer_out, consumer_out) = RingBuffer::new(1024);
+    // Create ring buffers for outgoing messages (Rust->Python)
+    let (mut producer_out, mut device_errors, consumer_out) = audio_message_channels();
 
     let mut mixer = RtMixer::new(channels as usize, sample_rate_hz as f32);
+    mixer.set_master_dc_blocker(true);
     let mut transport = TransportTimeline::new(sample_rate_hz);
+    let mut sequencer = StepSequencer::new();
     let mut scheduler = TransportScheduler::new();
+    let mut deferred_control = DeferredControlQueue::new();
     let mut trigger_quantization = TriggerQuantization::Immediate;
     let (mut retired_buffers, retirement_worker) = create_audio_buffer_retirement();
 
@@ -948,84 +2503,178 @@ pub fn create_audio_stream() -> Result<AudioStreamHandle, Box<dyn std::error::Er
     let mut pad_activity = RtRenderPadActivity::default();
     let mut last_pad_emit_frame = 0_u64;
     let mut last_master_emit_frame = 0_u64;
+    let mut last_limiter_emit_frame = 0_u64;
+    let stream_errors = Arc::new(AtomicU64::new(0));
+    let stream_errors_rt = Arc::clone(&stream_errors);
+    let clipped_samples = Arc::new(AtomicU64::new(0));
+    let clipped_samples_rt = Arc::clone(&clipped_samples);
+    let limiter_meter = Arc::new(LimiterReductionMeter::default());
+    let limiter_meter_rt = Arc::clone(&limiter_meter);
+    let audio_clock = Arc::new(AudioClock::default());
+    let audio_clock_rt = Arc::clone(&audio_clock);
+    let mut playback_mapping = PlaybackClockMapping::new(sample_rate_hz);
+    let mut last_output_latency_ns = None;
+    // Pin the shared epoch before the first callback reads it.
+    monotonic_ns();
+    let faulted = Arc::new(AtomicBool::new(false));
+    let faulted_rt = Arc::clone(&faulted);
 
     // Create stream config
     let stream_config = StreamConfig {
         channels,
         sample_rate,
-        buffer_size: BufferSize::Fixed(512),
+        buffer_size: attempt.buffer_size(),
     };
 
-    // Create audio stream with callback
-    let stream = device.build_output_stream(
-        &stream_config,
-        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-            let buffer_start_frame = transport.output_frame();
-
-            drain_control_messages(
-                &mut consumer_in,
-                &mut scheduler,
-                buffer_start_frame,
-                &mut trigger_quantization,
-                &mut transport,
-                &mut mixer,
-                &mut producer_out,
-                &mut retired_buffers,
-            );
-
-            drain_parameter_messages(&mut parameter_consumer_in, &mut mixer, &mut transport);
-
-            // Render audio + compute per-pad peaks.
-            render_scheduled_audio_tracking_pads(
-                &mut mixer,
-                &mut scheduler,
-                data,
-                &mut pad_peaks,
-                &mut pad_activity,
-                buffer_start_frame,
-                channels as usize,
-                &mut transport,
-                &mut producer_out,
-                &mut retired_buffers,
-            );
-            let master_peak = master_output_peak(data);
-
-            let frames = data.len() / channels as usize;
-            transport.advance_by_rendered_frames(frames);
-            let frame_clock = transport.output_frame();
-
-            publish_pad_telemetry(
-                &mut producer_out,
-                &mixer,
-                &pad_peaks,
-                &pad_activity,
-                frame_clock,
-                emit_interval_frames,
-                &mut last_pad_emit_frame,
-            );
+    // The engine always renders f32; other device formats are converted per callback.
+    let mut render = move |data: &mut [f32], reported_latency_ns: Option<u64>| {
+        run_callback_guarded(
+            &faulted_rt,
+            data,
+            &mut producer_out,
+            |data, producer_out| {
+                let buffer_start_frame = transport.output_frame();
+                let callback_ns = monotonic_ns();
+                if let Some(latency_ns) = reported_latency_ns {
+                    last_output_latency_ns = Some(latency_ns);
+                    playback_mapping
+                        .observe(buffer_start_frame, callback_ns.saturating_add(latency_ns));
+                    transport.set_output_latency_frames(Some(latency_frames(
+                        latency_ns,
+                        sample_rate_hz,
+                    )));
+                }
+                audio_clock_rt.publish(AudioClockTimestamp {
+                    output_frame: buffer_start_frame,
+                    monotonic_ns: callback_ns,
+                    output_latency_ns: last_output_latency_ns,
+                    playback_ns: playback_mapping
+                        .playback_ns_at(buffer_start_frame)
+                        .map(|playback_ns| playback_ns.max(0.0) as u64),
+                    sample_rate_hz: playback_mapping.sample_rate_hz(),
+                });
+
+                drain_callback_messages(
+                    &mut consumer_in,
+                    &mut parameter_consumer_in,
+                    &mut scheduler,
+                    &mut deferred_control,
+                    buffer_start_frame,
+                    &mut trigger_quantization,
+                    &mut transport,
+                    &mut sequencer,
+                    &mut mixer,
+                    producer_out,
+                    &mut retired_buffers,
+                    Some(&mut automation_capture),
+                );
+
+                let frames = data.len() / channels as usize;
+                schedule_sequencer_steps(
+                    &mut sequencer,
+                    &mut scheduler,
+                    &transport,
+                    buffer_start_frame.saturating_add(frames as u64),
+                    producer_out,
+                );
+
+                // Render audio + compute per-pad peaks; this advances the transport.
+                render_callback_segments(
+                    &mut deferred_control,
+                    &mut scheduler,
+                    &mut trigger_quantization,
+                    &mut transport,
+                    &mut sequencer,
+                    &mut mixer,
+                    data,
+                    &mut pad_peaks,
+                    &mut pad_activity,
+                    channels as usize,
+                    producer_out,
+                    &mut retired_buffers,
+                );
+                let master_peak = master_output_peak(data);
+                let clipped = clamp_output(data);
+                if clipped > 0 {
+                    clipped_samples_rt.fetch_add(clipped, Ordering::Relaxed);
+                }
+                let limiter_reduction = mixer.limiter_reduction_db();
+                limiter_meter_rt.publish(limiter_reduction);
+
+                let frame_clock = transport.output_frame();
+
+                publish_pad_telemetry(
+                    producer_out,
+                    &mixer,
+                    &pad_peaks,
+                    &pad_activity,
+                    frame_clock,
+                    emit_interval_frames,
+                    &mut last_pad_emit_frame,
+                );
+                publish_transient_snaps(producer_out, &mut mixer);
+                publish_sample_swaps(producer_out, &mut mixer, &mut retired_buffers);
+                mixer.retire_faded_samples(&mut retired_buffers);
+
+                publish_master_peak_telemetry(
+                    producer_out,
+                    master_peak,
+                    frame_clock,
+                    emit_interval_frames,
+                    &mut last_master_emit_frame,
+                );
+                if mixer.limiter_meter_events() {
+                    publish_limiter_meter_telemetry(
+                        producer_out,
+                        limiter_reduction,
+                        frame_clock,
+                        emit_interval_frames,
+                        &mut last_limiter_emit_frame,
+                    );
+                }
+            },
+        );
+    };
+    let on_error = move |err| {
+        stream_errors_rt.fetch_add(1, Ordering::Relaxed);
+        let _ = device_errors.push(err);
+    };
 
-            publish_master_peak_telemetry(
-                &mut producer_out,
-                master_peak,
-                frame_clock,
-                emit_interval_frames,
-                &mut last_master_emit_frame,
-            );
-        },
-        |err| {
-            log::error!("Audio stream error: {}", err);
-        },
-        None,
-    )?;
+    let stream = match attempt.sample_format {
+        OutputSampleFormat::F32 => device.build_output_stream(
+            &stream_config,
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                render(data, output_latency_ns(info))
+            },
+            on_error,
+            None,
+        )?,
+        OutputSampleFormat::I16 => {
+            build_converting_output_stream::<i16>(device, &stream_config, render, on_error)?
+        }
+        OutputSampleFormat::U16 => {
+            build_converting_output_stream::<u16>(device, &stream_config, render, on_error)?
+        }
+    };
 
     Ok(AudioStreamHandle {
         stream,
+        output_sample_format: attempt.sample_format,
+        output_buffer_frames: attempt.buffer_frames,
+        control_buffer_capacity: buffer_capacity,
         _retirement_worker: retirement_worker,
-        producer: Arc::new(Mutex::new(producer_in)),
+        producer: Arc::new(Mutex::new(ControlProducer::new(producer_in))),
         parameter_producer: Arc::new(Mutex::new(parameter_producer_in)),
         consumer: Arc::new(Mutex::new(consumer_out)),
         output_channels: channels as usize,
         output_sample_rate: sample_rate_hz,
+        stream_errors,
+        clipped_samples,
+        limiter_meter,
+        automation_recorder: Mutex::new(automation_recorder),
+        pad_delay_line_sent: std::array::from_fn(|_| AtomicBool::new(false)),
+        audio_clock,
+        faulted,
     })
 }
 
@@ -1038,9 +2687,16 @@ pub fn start_stream(stream: &Stream) -> Result<(), Box<dyn std::error::Error>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::audio_engine::alloc_tracking::count_allocations;
+    use crate::audio_engine::automation::AutomationEvent;
     use crate::audio_engine::buffer_retirement::ImmediateAudioBufferRetirement;
-    use crate::audio_engine::constants::PAD_EQ_DB_MIN;
-    use crate::messages::{PadTimingMetadata, SampleBuffer};
+    use crate::audio_engine::constants::{
+        CONTROL_BUFFER_CAPACITY_DEFAULT, PAD_EQ_DB_MIN, PAD_FADE_MUTE_MS,
+    };
+    use crate::messages::{
+        LoopMode, PadFades, PadParams, PadTimingMetadata, SampleBuffer, SequencerStep,
+        TriggerOptions,
+    };
     use std::sync::Arc;
 
     impl AudioMessageSink for Vec<AudioMessage> {
@@ -1087,25 +2743,580 @@ mod tests {
             producer.push(ControlMessage::Ping()).unwrap();
         }
         let mut mixer = RtMixer::new(1, 44_100.0);
-        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut sequencer = StepSequencer::new();
+        let mut trigger_quantization = TriggerQuantization::Immediate;
+        let mut messages = Vec::new();
+
+        let processed = drain_control_messages(
+            &mut consumer,
+            &mut scheduler,
+            &mut DeferredControlQueue::new(),
+            0,
+            &mut trigger_quantization,
+            &mut transport,
+            &mut sequencer,
+            &mut mixer,
+            &mut messages,
+            &mut ImmediateAudioBufferRetirement,
+            None,
+        );
+
+        assert_eq!(processed, MAX_CONTROL_MESSAGES_PER_CALLBACK);
+        assert_eq!(messages.len(), MAX_CONTROL_MESSAGES_PER_CALLBACK);
+        assert_eq!(consumer.slots(), 3);
+    }
+
+    #[test]
+    fn callback_panic_outputs_silence_and_reports_fault_once() {
+        let (mut producer, mut consumer) = RingBuffer::new(4);
+        producer.push(ControlMessage::PanicForTest()).unwrap();
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut sequencer = StepSequencer::new();
+        let mut trigger_quantization = TriggerQuantization::Immediate;
+        let faulted = AtomicBool::new(false);
+        let mut messages = Vec::new();
+        let mut output = vec![1.0_f32; 64];
+
+        run_callback_guarded(&faulted, &mut output, &mut messages, |data, messages| {
+            data.fill(0.5);
+            drain_control_messages(
+                &mut consumer,
+                &mut scheduler,
+                &mut DeferredControlQueue::new(),
+                0,
+                &mut trigger_quantization,
+                &mut transport,
+                &mut sequencer,
+                &mut mixer,
+                messages,
+                &mut ImmediateAudioBufferRetirement,
+                None,
+            );
+        });
+
+        assert!(faulted.load(Ordering::Acquire));
+        assert!(output.iter().all(|sample| *sample == 0.0));
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], AudioMessage::EngineFault()));
+
+        output.fill(1.0);
+        let mut rendered_after_fault = false;
+        run_callback_guarded(&faulted, &mut output, &mut messages, |_, _| {
+            rendered_after_fault = true;
+        });
+
+        assert!(!rendered_after_fault);
+        assert!(output.iter().all(|sample| *sample == 0.0));
+        assert_eq!(messages.len(), 1);
+    }
+
+    struct DeferredControlHarness {
+        consumer: Consumer<ControlMessage>,
+        parameter_consumer: Consumer<ControlParameterMessage>,
+        scheduler: FixedCapacityScheduler<8>,
+        deferred: DeferredControlQueue,
+        trigger_quantization: TriggerQuantization,
+        transport: TransportTimeline,
+        sequencer: StepSequencer,
+        mixer: RtMixer,
+        messages: Vec<AudioMessage>,
+    }
+
+    impl DeferredControlHarness {
+        /// Mono engine with pad 0 loaded as a constant signal, no pad fades and hard stops.
+        fn new(consumer: Consumer<ControlMessage>) -> Self {
+            Self::with_parameters(consumer, RingBuffer::new(1).1)
+        }
+
+        fn with_parameters(
+            consumer: Consumer<ControlMessage>,
+            parameter_consumer: Consumer<ControlParameterMessage>,
+        ) -> Self {
+            let mut mixer = RtMixer::new(1, 48_000.0);
+            assert!(mixer.set_stop_fade_samples(0));
+            mixer.load_sample(0, create_test_sample(1, 48_000, 0.25));
+            assert!(mixer.set_pad_fades(
+                0,
+                Some(PadFades {
+                    fade_in_ms: 0.0,
+                    fade_out_ms: 0.0,
+                }),
+            ));
+
+            Self {
+                consumer,
+                parameter_consumer,
+                scheduler: FixedCapacityScheduler::new(),
+                deferred: DeferredControlQueue::new(),
+                trigger_quantization: TriggerQuantization::Immediate,
+                transport: TransportTimeline::new(48_000),
+                sequencer: StepSequencer::new(),
+                mixer,
+                messages: Vec::new(),
+            }
+        }
+
+        fn callback(&mut self, frames: usize) -> Vec<f32> {
+            let mut output = vec![0.0_f32; frames];
+            let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+            let mut pad_activity = RtRenderPadActivity::default();
+            drain_callback_messages(
+                &mut self.consumer,
+                &mut self.parameter_consumer,
+                &mut self.scheduler,
+                &mut self.deferred,
+                self.transport.output_frame(),
+                &mut self.trigger_quantization,
+                &mut self.transport,
+                &mut self.sequencer,
+                &mut self.mixer,
+                &mut self.messages,
+                &mut ImmediateAudioBufferRetirement,
+                None,
+            );
+            render_callback_segments(
+                &mut self.deferred,
+                &mut self.scheduler,
+                &mut self.trigger_quantization,
+                &mut self.transport,
+                &mut self.sequencer,
+                &mut self.mixer,
+                &mut output,
+                &mut pad_peaks,
+                &mut pad_activity,
+                1,
+                &mut self.messages,
+                &mut ImmediateAudioBufferRetirement,
+            );
+            output
+        }
+    }
+
+    #[test]
+    fn stamped_stop_silences_output_from_its_exact_target_frame() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        producer.push(ControlMessage::AtFrame(700)).unwrap();
+        producer.push(ControlMessage::StopSample { id: 0 }).unwrap();
+
+        let first = harness.callback(512);
+        assert!(first.iter().all(|sample| *sample > 0.0));
+        assert_eq!(harness.deferred.len(), 1);
+
+        let second = harness.callback(512);
+        assert!(second[..188].iter().all(|sample| *sample > 0.0));
+        assert!(second[188..].iter().all(|sample| *sample == 0.0));
+        assert_eq!(harness.transport.output_frame(), 1_024);
+        assert_stopped(&harness.messages, 1, 0);
+    }
+
+    #[test]
+    fn stamped_messages_do_not_hold_back_unstamped_ones_and_late_stamps_apply_at_once() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        producer.push(ControlMessage::AtFrame(48_000)).unwrap();
+        producer.push(ControlMessage::StopSample { id: 0 }).unwrap();
+        producer.push(ControlMessage::Ping()).unwrap();
+
+        let first = harness.callback(256);
+        assert!(first.iter().all(|sample| *sample > 0.0));
+        assert!(matches!(harness.messages[1], AudioMessage::Pong()));
+
+        // A stamp the output has already passed applies at the start of the next buffer.
+        producer.push(ControlMessage::AtFrame(100)).unwrap();
+        producer.push(ControlMessage::StopAll()).unwrap();
+        let second = harness.callback(256);
+        assert!(second.iter().all(|sample| *sample == 0.0));
+        assert_eq!(harness.deferred.len(), 1);
+    }
+
+    #[test]
+    fn stamped_set_parameter_applies_at_its_exact_target_frame() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        producer.push(ControlMessage::AtFrame(700)).unwrap();
+        producer
+            .push(ControlMessage::SetParameter(
+                ControlParameterMessage::SetVolume(0.5),
+            ))
+            .unwrap();
+
+        let first = harness.callback(512);
+        assert!(first.iter().all(|sample| (*sample - 0.25).abs() < 1e-6));
+
+        let second = harness.callback(512);
+        assert!(
+            second[..188]
+                .iter()
+                .all(|sample| (*sample - 0.25).abs() < 1e-6)
+        );
+        assert!(
+            second[188..]
+                .iter()
+                .all(|sample| (*sample - 0.125).abs() < 1e-6)
+        );
+    }
+
+    #[test]
+    fn same_block_gain_and_play_apply_in_the_order_sent() {
+        let half_gain_db = -20.0 * 2.0_f32.log10();
+
+        // A gain sent before the trigger shapes the voice from its first frame.
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let (mut parameters, parameter_consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::with_parameters(consumer, parameter_consumer);
+        parameters
+            .push(ControlParameterMessage::SetPadGain {
+                id: 0,
+                gain_db: half_gain_db,
+            })
+            .unwrap();
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        let output = harness.callback(256);
+        assert!(output.iter().all(|sample| (*sample - 0.125).abs() < 1e-5));
+
+        // A gain sent behind the waiting trigger ramps the voice it started.
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        producer
+            .push(ControlMessage::ApplyParameter(
+                ControlParameterMessage::SetPadGain {
+                    id: 0,
+                    gain_db: half_gain_db,
+                },
+            ))
+            .unwrap();
+        let output = harness.callback(1_024);
+        assert!((output[0] - 0.25).abs() < 1e-3);
+        assert!(output.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+        assert!((output[1_023] - 0.125).abs() < 1e-5);
+    }
+
+    #[test]
+    fn set_pad_eq_sent_after_an_eq_automation_cancels_it() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::AutomatePadEq {
+                id: 0,
+                band: 1,
+                target_db: PAD_EQ_DB_MIN,
+                duration_frames: 4_800,
+            })
+            .unwrap();
+        producer
+            .push(ControlMessage::ApplyParameter(
+                ControlParameterMessage::SetPadEq {
+                    id: 0,
+                    low_db: 0.0,
+                    mid_db: 0.0,
+                    high_db: 0.0,
+                },
+            ))
+            .unwrap();
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+
+        let output = harness.callback(9_600);
+        assert!(output.iter().all(|sample| (*sample - 0.25).abs() < 1e-3));
+    }
+
+    #[test]
+    fn same_block_seek_and_play_apply_in_the_order_sent() {
+        // A play followed by a seek moves the new voice.
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        producer
+            .push(ControlMessage::SeekSample {
+                id: 0,
+                position_s: 0.5,
+            })
+            .unwrap();
+        harness.callback(64);
+        assert_eq!(active_voice_frame(&harness.mixer, 0), Some(24_000 + 64));
+
+        // A seek before the play finds the pad idle, so the voice starts at the loop start.
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::SeekSample {
+                id: 0,
+                position_s: 0.5,
+            })
+            .unwrap();
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        harness.callback(64);
+        assert_eq!(active_voice_frame(&harness.mixer, 0), Some(64));
+    }
+
+    #[test]
+    fn finished_one_shots_report_voice_finished_but_stopped_ones_do_not() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        harness.mixer.set_default_loop_mode(LoopMode::OneShot);
+        harness
+            .mixer
+            .load_sample(1, create_test_sample(1, 48_000, 0.25));
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        harness.callback(1_024);
+        producer.push(ControlMessage::StopSample { id: 0 }).unwrap();
+        // Render past any stop fade, so the voice is gone before checking what it reported.
+        harness.callback(harness.mixer.stop_fade_samples() as usize + 64);
+        assert_eq!(harness.mixer.active_voice_count(), 0);
+        assert!(
+            !harness
+                .messages
+                .iter()
+                .any(|message| matches!(message, AudioMessage::VoiceFinished { .. }))
+        );
+
+        harness.messages.clear();
+        producer
+            .push(ControlMessage::PlaySample { id: 1, volume: 1.0 })
+            .unwrap();
+        harness.callback(1_024);
+        assert_started(&harness.messages, 0, 1);
+        harness.messages.clear();
+        for _ in 0..47 {
+            harness.callback(1_024);
+        }
+        assert!(!harness.mixer.sample_is_active(1));
+        assert!(matches!(
+            harness.messages[..],
+            [
+                AudioMessage::VoiceFinished { id: 1 },
+                AudioMessage::SampleStopped { id: 1 }
+            ]
+        ));
+    }
+
+    #[test]
+    fn state_query_reports_active_voices_and_loaded_slots() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        harness
+            .mixer
+            .load_sample(5, create_test_sample(1, 48_000, 0.25));
+        // A keyboard bank lets a third pad play slot 5 without loading its own slot.
+        assert!(harness.mixer.set_keyboard_mode(1, Some((5, 0))));
+        for id in [0, 5, NUM_PADS + 2] {
+            producer
+                .push(ControlMessage::PlaySample { id, volume: 1.0 })
+                .unwrap();
+        }
+        harness.callback(64);
+        harness.messages.clear();
+
+        producer.push(ControlMessage::QueryState()).unwrap();
+        harness.callback(64);
+        let [
+            snapshot @ AudioMessage::StateSnapshot {
+                active_voices,
+                loaded_mask,
+            },
+        ] = &harness.messages[..]
+        else {
+            panic!("expected one state snapshot, got {:?}", harness.messages);
+        };
+        assert_eq!(*active_voices, 3);
+        assert_eq!(loaded_mask, &[0b10_0001, 0, 0, 0]);
+        let loaded_slots = snapshot.loaded_slots().unwrap();
+        assert_eq!(loaded_slots.len(), NUM_SAMPLES);
+        let loaded: Vec<usize> = (0..NUM_SAMPLES).filter(|&id| loaded_slots[id]).collect();
+        assert_eq!(loaded, [0, 5]);
+    }
+
+    #[test]
+    fn reloading_a_playing_slot_reports_its_pad_stopped() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        harness.callback(64);
+        harness.messages.clear();
+
+        producer
+            .push(ControlMessage::LoadSample {
+                id: 0,
+                sample: create_test_sample(1, 48_000, 0.5),
+                onsets: None,
+            })
+            .unwrap();
+        harness.callback(64);
+        assert_stopped(&harness.messages, 0, 0);
+        assert!(!harness.mixer.sample_is_active(0));
+    }
+
+    #[test]
+    fn loaded_sample_carries_its_analyzed_onsets() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+
+        producer
+            .push(ControlMessage::LoadSample {
+                id: 1,
+                sample: create_test_sample(1, 48_000, 0.5),
+                onsets: Some(Arc::from([240_usize, 24_000].as_slice())),
+            })
+            .unwrap();
+        harness.callback(64);
+
+        harness.mixer.set_pad_transient_snap(1, Some(20.0));
+        assert!(harness.mixer.play_sample(1, 1.0));
+        let mut snaps = Vec::new();
+        harness
+            .mixer
+            .drain_transient_snaps(|id, offset| snaps.push((id, offset)));
+        assert_eq!(snaps, vec![(1, 240)]);
+    }
+
+    #[test]
+    fn seek_to_zero_restarts_the_waveform_and_is_acknowledged() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::new(consumer);
+        let ramp: Vec<f32> = (0..48_000).map(|frame| frame as f32 / 48_000.0).collect();
+        harness.mixer.load_sample(
+            0,
+            SampleBuffer {
+                channels: 1,
+                samples: Arc::from(ramp.clone().into_boxed_slice()),
+            },
+        );
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        harness.callback(1_024);
+        harness.messages.clear();
+
+        producer
+            .push(ControlMessage::SeekSample {
+                id: 0,
+                position_s: 0.0,
+            })
+            .unwrap();
+        let output = harness.callback(64);
+        assert_eq!(output[..], ramp[..64]);
+        assert!(matches!(
+            harness.messages[..],
+            [AudioMessage::SeekAcknowledged { id: 0 }]
+        ));
+
+        // Idle pads have nothing to seek and get no acknowledgement.
+        harness.messages.clear();
+        producer
+            .push(ControlMessage::SeekSample {
+                id: 1,
+                position_s: 0.0,
+            })
+            .unwrap();
+        harness.callback(64);
+        assert!(harness.messages.is_empty());
+    }
+
+    #[test]
+    fn same_block_eq_applies_to_the_triggered_voice_from_its_first_frame() {
+        let (mut producer, consumer) = RingBuffer::new(8);
+        let (mut parameters, parameter_consumer) = RingBuffer::new(8);
+        let mut harness = DeferredControlHarness::with_parameters(consumer, parameter_consumer);
+        producer
+            .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        parameters
+            .push(ControlParameterMessage::SetPadEq {
+                id: 0,
+                low_db: PAD_EQ_DB_MIN,
+                mid_db: PAD_EQ_DB_MIN,
+                high_db: PAD_EQ_DB_MIN,
+            })
+            .unwrap();
+
+        let output = harness.callback(256);
+        assert!(output.iter().all(|sample| sample.abs() < 1e-4));
+    }
+
+    #[test]
+    fn parameter_drain_records_applied_updates_at_the_output_frame() {
+        let (mut producer, mut consumer) = RingBuffer::new(8);
+        let (mut capture, mut recorder) = automation_capture_channel();
+        let mut mixer = RtMixer::new(1, 44_100.0);
         let mut transport = TransportTimeline::new(44_100);
-        let mut trigger_quantization = TriggerQuantization::Immediate;
-        let mut messages = Vec::new();
+        transport.advance_by_rendered_frames(256);
 
-        let processed = drain_control_messages(
+        recorder.start(0);
+        producer
+            .push(ControlParameterMessage::SetVolume(0.5))
+            .unwrap();
+        producer
+            .push(ControlParameterMessage::SetVolume(0.25))
+            .unwrap();
+        producer
+            .push(ControlParameterMessage::SetPadBpm {
+                id: 0,
+                bpm: Some(120.0),
+            })
+            .unwrap();
+        producer
+            .push(ControlParameterMessage::SetPadParams {
+                id: 1,
+                params: PadParams {
+                    gain_db: Some(-6.0),
+                    eq_db: None,
+                    mute: None,
+                },
+            })
+            .unwrap();
+        drain_parameter_messages(
             &mut consumer,
-            &mut scheduler,
-            0,
-            &mut trigger_quantization,
-            &mut transport,
             &mut mixer,
-            &mut messages,
-            &mut ImmediateAudioBufferRetirement,
+            &mut transport,
+            Some(&mut capture),
         );
 
-        assert_eq!(processed, MAX_CONTROL_MESSAGES_PER_CALLBACK);
-        assert_eq!(messages.len(), MAX_CONTROL_MESSAGES_PER_CALLBACK);
-        assert_eq!(consumer.slots(), 3);
+        let (events, dropped) = recorder.stop();
+        assert_eq!(dropped, 0);
+        assert_eq!(
+            events,
+            vec![
+                AutomationEvent {
+                    frame: 256,
+                    parameter: ControlParameterMessage::SetVolume(0.25),
+                },
+                AutomationEvent {
+                    frame: 256,
+                    parameter: ControlParameterMessage::SetPadGain {
+                        id: 1,
+                        gain_db: -6.0
+                    },
+                },
+            ]
+        );
     }
 
     #[test]
@@ -1131,7 +3342,7 @@ mod tests {
         let mut transport = TransportTimeline::new(44_100);
         mixer.load_sample(0, create_test_sample(1, 8, 1.0));
 
-        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport);
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
 
         assert_eq!(
             result,
@@ -1174,7 +3385,7 @@ mod tests {
         let mut transport = TransportTimeline::new(44_100);
         mixer.load_sample(0, create_test_sample(1, 8, 0.5));
 
-        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport);
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
 
         assert_eq!(
             result,
@@ -1192,6 +3403,101 @@ mod tests {
         assert!((output[0] - 0.5).abs() < 1e-5);
     }
 
+    #[test]
+    fn parameter_drain_applies_pad_params_bundle_and_coalesces_with_single_updates() {
+        let (mut producer, mut consumer) = RingBuffer::new(4);
+        producer
+            .push(ControlParameterMessage::SetPadEq {
+                id: 0,
+                low_db: PAD_EQ_DB_MIN,
+                mid_db: PAD_EQ_DB_MIN,
+                high_db: PAD_EQ_DB_MIN,
+            })
+            .unwrap();
+        producer
+            .push(ControlParameterMessage::SetPadParams {
+                id: 0,
+                params: PadParams {
+                    gain_db: Some(6.0),
+                    eq_db: Some([0.0, 0.0, 0.0]),
+                    mute: None,
+                },
+            })
+            .unwrap();
+        producer
+            .push(ControlParameterMessage::SetPadParams {
+                id: 0,
+                params: PadParams {
+                    gain_db: None,
+                    eq_db: None,
+                    mute: None,
+                },
+            })
+            .unwrap();
+
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut transport = TransportTimeline::new(44_100);
+        mixer.load_sample(0, create_test_sample(1, 8, 0.5));
+
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
+
+        assert_eq!(
+            result,
+            ParameterDrainResult {
+                messages_drained: 3,
+                parameters_applied: 2
+            }
+        );
+
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![0.0; 1];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        let expected = 10.0_f32.powf(6.0 / 20.0) * 0.5;
+        assert!((output[0] - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn parameter_drain_applies_pad_params_mute_as_a_fade_mute() {
+        let (mut producer, mut consumer) = RingBuffer::new(4);
+        for mute in [Some(false), Some(true)] {
+            producer
+                .push(ControlParameterMessage::SetPadParams {
+                    id: 0,
+                    params: PadParams {
+                        mute,
+                        ..PadParams::default()
+                    },
+                })
+                .unwrap();
+        }
+
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut transport = TransportTimeline::new(44_100);
+        mixer.load_sample(0, create_test_sample(1, 20_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
+
+        assert_eq!(
+            result,
+            ParameterDrainResult {
+                messages_drained: 2,
+                parameters_applied: 1
+            }
+        );
+
+        let ramp_frames = (44_100.0 * PAD_FADE_MUTE_MS / 1000.0).round() as usize;
+        let mut output = vec![0.0; ramp_frames + 64];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output[0] > 0.49);
+        assert!(output[ramp_frames..].iter().all(|sample| *sample == 0.0));
+        assert!(mixer.sample_is_active(0));
+    }
+
     #[test]
     fn parameter_drain_ignores_invalid_pad_ids_while_coalescing_touched_ids() {
         let (mut producer, mut consumer) = RingBuffer::new(8);
@@ -1228,7 +3534,7 @@ mod tests {
         mixer.load_sample(1, create_test_sample(1, 8, 1.0));
         mixer.set_pad_bpm(2, Some(100.0));
 
-        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport);
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
 
         assert_eq!(
             result,
@@ -1264,7 +3570,7 @@ mod tests {
         transport.advance_by_rendered_frames(10);
         assert_eq!(transport.bar_phase_beats(), Some(1.0));
 
-        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport);
+        let result = drain_parameter_messages(&mut consumer, &mut mixer, &mut transport, None);
 
         assert_eq!(
             result,
@@ -1321,6 +3627,202 @@ mod tests {
         assert!((pad_peaks[1] - 0.6).abs() < 1e-5);
     }
 
+    #[test]
+    fn overdriven_mix_is_clamped_and_counted() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        for id in 0..3 {
+            mixer.load_sample(
+                id,
+                SampleBuffer {
+                    channels: 1,
+                    samples: Arc::from(vec![0.9_f32; 256].into_boxed_slice()),
+                },
+            );
+            assert!(mixer.play_sample(id, 1.0));
+        }
+        let mut output = vec![0.0; 64];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(master_output_peak(&output) > 2.0);
+        assert_eq!(clamp_output(&mut output), 64);
+        assert!(output.iter().all(|sample| *sample == 1.0));
+
+        let mut output = [-3.0, 0.5, f32::NAN, 1.0, -1.0];
+        assert_eq!(clamp_output(&mut output), 2);
+        assert_eq!(output, [-1.0, 0.5, 0.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn callback_path_does_not_allocate() {
+        let sample_rate_hz = 48_000_u32;
+        let channels = 2;
+        let (mut producer, mut consumer) = RingBuffer::new(1024);
+        let (mut parameter_producer, mut parameter_consumer) = RingBuffer::new(1024);
+        let (mut sender, _device_errors, _receiver) = audio_message_channels();
+        let mut mixer = RtMixer::new(channels, sample_rate_hz as f32);
+        mixer.set_master_dc_blocker(true);
+        let mut transport = TransportTimeline::new(sample_rate_hz);
+        let mut sequencer = StepSequencer::new();
+        let mut scheduler = TransportScheduler::new();
+        let mut deferred_control = DeferredControlQueue::new();
+        let mut trigger_quantization = TriggerQuantization::Immediate;
+        let (mut retirement, _retirement_worker) = create_audio_buffer_retirement();
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut pad_activity = RtRenderPadActivity::default();
+        let mut last_pad_emit_frame = 0_u64;
+        let mut last_master_emit_frame = 0_u64;
+        let mut output = vec![0.0_f32; 512 * channels];
+
+        let mut callback = |output: &mut [f32]| {
+            let buffer_start_frame = transport.output_frame();
+            drain_callback_messages(
+                &mut consumer,
+                &mut parameter_consumer,
+                &mut scheduler,
+                &mut deferred_control,
+                buffer_start_frame,
+                &mut trigger_quantization,
+                &mut transport,
+                &mut sequencer,
+                &mut mixer,
+                &mut sender,
+                &mut retirement,
+                None,
+            );
+            render_callback_segments(
+                &mut deferred_control,
+                &mut scheduler,
+                &mut trigger_quantization,
+                &mut transport,
+                &mut sequencer,
+                &mut mixer,
+                output,
+                &mut pad_peaks,
+                &mut pad_activity,
+                channels,
+                &mut sender,
+                &mut retirement,
+            );
+            let master_peak = master_output_peak(output);
+            clamp_output(output);
+            let frame_clock = transport.output_frame();
+            publish_pad_telemetry(
+                &mut sender,
+                &mixer,
+                &pad_peaks,
+                &pad_activity,
+                frame_clock,
+                4_800,
+                &mut last_pad_emit_frame,
+            );
+            publish_transient_snaps(&mut sender, &mut mixer);
+            publish_sample_swaps(&mut sender, &mut mixer, &mut retirement);
+            mixer.retire_faded_samples(&mut retirement);
+            publish_master_peak_telemetry(
+                &mut sender,
+                master_peak,
+                frame_clock,
+                4_800,
+                &mut last_master_emit_frame,
+            );
+        };
+
+        let voices = 16;
+        for id in 0..voices {
+            let sample = create_test_sample(channels, 96_000, 0.05);
+            producer
+                .push(ControlMessage::LoadSample {
+                    id,
+                    sample,
+                    onsets: None,
+                })
+                .unwrap();
+            producer
+                .push(ControlMessage::SetPadKeyLock {
+                    id,
+                    enabled: id % 2 == 0,
+                })
+                .unwrap();
+            producer
+                .push(ControlMessage::PlaySample { id, volume: 1.0 })
+                .unwrap();
+            parameter_producer
+                .push(ControlParameterMessage::SetPadEq {
+                    id,
+                    low_db: PAD_EQ_DB_MIN,
+                    mid_db: -3.0,
+                    high_db: 3.0,
+                })
+                .unwrap();
+        }
+        parameter_producer
+            .push(ControlParameterMessage::SetSpeed(1.25))
+            .unwrap();
+        producer.push(ControlMessage::SetBpmLock(true)).unwrap();
+        producer
+            .push(ControlMessage::BeginSequence {
+                track: 0,
+                steps: 16,
+            })
+            .unwrap();
+        for step in (0..16).step_by(2) {
+            let event = Some(SequencerStep {
+                id: step,
+                volume: 0.5,
+            });
+            producer
+                .push(ControlMessage::SetSequenceStep {
+                    track: 0,
+                    step,
+                    event,
+                })
+                .unwrap();
+        }
+        producer
+            .push(ControlMessage::CommitSequence { track: 0 })
+            .unwrap();
+        producer
+            .push(ControlMessage::SetSequencerRunning(true))
+            .unwrap();
+        parameter_producer
+            .push(ControlParameterMessage::SetMasterBpm(128.0))
+            .unwrap();
+        parameter_producer
+            .push(ControlParameterMessage::SetPadBpm {
+                id: 0,
+                bpm: Some(120.0),
+            })
+            .unwrap();
+
+        let ((), allocations) = count_allocations(|| {
+            for _ in 0..64 {
+                callback(&mut output);
+            }
+        });
+        assert_eq!(allocations, 0);
+
+        let replacement = create_test_sample(channels, 96_000, 0.05);
+        producer
+            .push(ControlMessage::LoadSample {
+                id: 1,
+                sample: replacement,
+                onsets: None,
+            })
+            .unwrap();
+        producer.push(ControlMessage::StopSample { id: 2 }).unwrap();
+        producer
+            .push(ControlMessage::UnloadSample { id: 3 })
+            .unwrap();
+        let ((), allocations) = count_allocations(|| {
+            for _ in 0..8 {
+                callback(&mut output);
+            }
+        });
+        assert_eq!(allocations, 0);
+        assert!(output.iter().any(|sample| *sample != 0.0));
+    }
+
     #[test]
     fn master_peak_telemetry_preserves_unclamped_value() {
         let (mut producer, mut consumer) = RingBuffer::<AudioMessage>::new(2);
@@ -1360,6 +3862,80 @@ mod tests {
         assert!(consumer.pop().is_err());
     }
 
+    #[test]
+    fn telemetry_flood_does_not_drop_or_delay_reliable_messages() {
+        let (mut sender, _device_errors, mut receiver) = audio_message_channels();
+        for _ in 0..TELEMETRY_AUDIO_MESSAGE_CAPACITY * 2 {
+            sender.push_audio_message(AudioMessage::MasterPeak { peak: 0.5 });
+        }
+        sender.push_audio_message(AudioMessage::SampleStarted { id: 3 });
+        sender.push_audio_message(AudioMessage::Pong());
+
+        assert!(matches!(
+            receiver.pop(),
+            Some(AudioMessage::SampleStarted { id: 3 })
+        ));
+        assert!(matches!(receiver.pop(), Some(AudioMessage::Pong())));
+
+        let mut telemetry = 0;
+        while let Some(message) = receiver.pop() {
+            assert!(message.is_telemetry());
+            telemetry += 1;
+        }
+        assert_eq!(telemetry, TELEMETRY_AUDIO_MESSAGE_CAPACITY);
+    }
+
+    #[test]
+    fn device_errors_are_received_before_other_messages() {
+        let (mut sender, mut device_errors, mut receiver) = audio_message_channels();
+        sender.push_audio_message(AudioMessage::Pong());
+        device_errors
+            .push(cpal::StreamError::DeviceNotAvailable)
+            .unwrap();
+
+        match receiver.pop() {
+            Some(AudioMessage::DeviceError(message)) => assert!(!message.is_empty()),
+            other => panic!("expected DeviceError, got {other:?}"),
+        }
+        assert!(matches!(receiver.pop(), Some(AudioMessage::Pong())));
+        assert!(receiver.pop().is_none());
+    }
+
+    #[test]
+    fn ping_is_acknowledged_while_telemetry_ring_is_full() {
+        let (mut sender, _device_errors, mut receiver) = audio_message_channels();
+        let (mut producer, mut consumer) = RingBuffer::new(1);
+        let mut scheduler = TransportScheduler::new();
+        let mut trigger_quantization = TriggerQuantization::Immediate;
+        let mut transport = TransportTimeline::new(44_100);
+        let mut sequencer = StepSequencer::new();
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut retirement = ImmediateAudioBufferRetirement;
+        for _ in 0..TELEMETRY_AUDIO_MESSAGE_CAPACITY {
+            sender.push_audio_message(AudioMessage::PadPlayhead {
+                id: 0,
+                position_s: 1.0,
+            });
+        }
+        producer.push(ControlMessage::Ping()).unwrap();
+
+        drain_control_messages(
+            &mut consumer,
+            &mut scheduler,
+            &mut DeferredControlQueue::new(),
+            0,
+            &mut trigger_quantization,
+            &mut transport,
+            &mut sequencer,
+            &mut mixer,
+            &mut sender,
+            &mut retirement,
+            None,
+        );
+
+        assert!(matches!(receiver.pop(), Some(AudioMessage::Pong())));
+    }
+
     #[test]
     fn pad_telemetry_publishes_only_touched_pads_on_shared_interval() {
         let mixer = RtMixer::new(1, 44_100.0);
@@ -1418,34 +3994,34 @@ mod tests {
     }
 
     #[test]
-    fn retirement_slot_estimate_covers_polyphonic_stop_paths() {
+    fn retirement_slot_estimate_only_counts_bank_buffers() {
         assert_eq!(
             control_message_retirement_slots_needed(&ControlMessage::StopSample { id: 0 }),
-            MAX_VOICES
+            0
         );
         assert_eq!(
             control_message_retirement_slots_needed(&ControlMessage::UnloadSample { id: 0 }),
-            MAX_VOICES + 2
+            5
         );
         assert_eq!(
             control_message_retirement_slots_needed(&ControlMessage::StopAll()),
-            MAX_VOICES
+            0
         );
         assert_eq!(
             control_message_retirement_slots_needed(&ControlMessage::PlaySampleExclusive {
                 id: 0,
                 volume: 1.0,
             }),
-            MAX_VOICES
+            0
         );
     }
 
     #[test]
-    fn test_logger_setup() {
+    fn test_env_logger_setup() {
         // This test just verifies that logger setup doesn't panic
         // Multiple calls should be safe (though only the first takes effect)
-        setup_logger();
-        setup_logger(); // Should not panic
+        setup_env_logger();
+        setup_env_logger(); // Should not panic
     }
 
     #[test]
@@ -1456,7 +4032,7 @@ mod tests {
             return; // Skip test if no audio device available
         }
 
-        let result = create_audio_stream();
+        let result = create_audio_stream(None, None, CONTROL_BUFFER_CAPACITY_DEFAULT, None);
         // We expect this to potentially fail in test environments,
         // but we want to ensure the function exists and has the right signature
         match result {
@@ -1469,6 +4045,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn out_of_range_device_index_is_rejected() {
+        let Ok(names) = output_device_names() else {
+            return; // Skip test if the host cannot enumerate devices
+        };
+
+        let error = create_audio_stream(
+            None,
+            Some(&OutputDeviceChoice::Index(names.len())),
+            CONTROL_BUFFER_CAPACITY_DEFAULT,
+            None,
+        )
+        .err()
+        .expect("index past the device list should fail");
+
+        assert_eq!(
+            error.to_string(),
+            DeviceError::DeviceIndexOutOfRange {
+                index: names.len(),
+                count: names.len(),
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn unknown_device_name_is_rejected_with_the_available_names() {
+        let Ok(names) = output_device_names() else {
+            return; // Skip test if the host cannot enumerate devices
+        };
+        let name = "No Such Output Device";
+        assert!(!names.iter().any(|device_name| device_name == name));
+
+        let error = create_audio_stream(
+            None,
+            Some(&OutputDeviceChoice::Name(name.to_string())),
+            CONTROL_BUFFER_CAPACITY_DEFAULT,
+            None,
+        )
+        .err()
+        .expect("a name no device has should fail");
+
+        assert_eq!(
+            error.to_string(),
+            DeviceError::DeviceNameNotFound {
+                name: name.to_string(),
+                available: names.join(", "),
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn output_device_names_match_exactly_and_pick_the_first_duplicate() {
+        let names = ["Built-in Output", "USB Audio", "USB Audio"].map(String::from);
+
+        assert_eq!(output_device_index(&names, "USB Audio"), Some(1));
+        assert_eq!(output_device_index(&names, "usb audio"), None);
+        assert_eq!(output_device_index(&names, "HDMI"), None);
+    }
+
     #[test]
     fn immediate_command_uses_current_frame_scheduler_path() {
         let mut mixer = RtMixer::new(1, 44_100.0);
@@ -1484,7 +4121,6 @@ mod tests {
             &mut mixer,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1498,6 +4134,52 @@ mod tests {
         assert_started(&messages, 0, 0);
     }
 
+    #[test]
+    fn voice_handles_stop_their_voice_and_report_dropped_triggers() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        assert!(mixer.set_stop_fade_samples(0));
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut messages = Vec::new();
+        let play = |id, handle| ScheduledCommand::PlaySampleWith {
+            id,
+            volume: 1.0,
+            options: TriggerOptions::default(),
+            handle,
+        };
+
+        for command in [play(0, 7), play(1, 8)] {
+            schedule_immediate_command(
+                &mut scheduler,
+                0,
+                command,
+                &mut mixer,
+                &mut transport,
+                &mut messages,
+            );
+        }
+        assert!(matches!(
+            messages.last(),
+            Some(AudioMessage::TriggerDropped { handle: 8 })
+        ));
+
+        messages.clear();
+        for handle in [8, 7] {
+            schedule_immediate_command(
+                &mut scheduler,
+                0,
+                ScheduledCommand::StopVoice { handle },
+                &mut mixer,
+                &mut transport,
+                &mut messages,
+            );
+        }
+        assert!(!mixer.sample_is_active(0));
+        assert_eq!(messages.len(), 1);
+        assert_stopped(&messages, 0, 0);
+    }
+
     #[test]
     fn immediate_command_falls_back_when_scheduler_is_full() {
         let mut mixer = RtMixer::new(1, 44_100.0);
@@ -1513,7 +4195,6 @@ mod tests {
             &mut mixer,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(
@@ -1541,11 +4222,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(5));
@@ -1564,7 +4243,6 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(output[0], 0.0);
@@ -1576,6 +4254,198 @@ mod tests {
         assert_started(&messages, 0, 0);
     }
 
+    #[test]
+    fn sequencer_step_triggers_pad_at_grid_frame() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_test_sample(1, 48_000, 0.5));
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        transport.advance_by_rendered_frames(1);
+        let mut sequencer = StepSequencer::new();
+        assert!(sequencer.begin_sequence(0, 16));
+        assert!(sequencer.set_sequence_step(0, 1, Some(SequencerStep { id: 0, volume: 1.0 })));
+        assert!(sequencer.commit_sequence(0));
+        sequencer.set_running(true);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut messages = Vec::new();
+        let mut output = vec![0.0; 8_192];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        schedule_sequencer_steps(
+            &mut sequencer,
+            &mut scheduler,
+            &transport,
+            8_193,
+            &mut messages,
+        );
+        assert_eq!(scheduler.peek_next_target_frame(), Some(6_000));
+        render_scheduled_audio(
+            &mut mixer,
+            &mut scheduler,
+            &mut output,
+            &mut pad_peaks,
+            1,
+            1,
+            &mut transport,
+            &mut messages,
+        );
+
+        assert!(matches!(
+            messages[0],
+            AudioMessage::SequencerStep { track: 0, step: 1 }
+        ));
+        assert_started(&messages, 1, 0);
+        assert!(output[..5_999].iter().all(|sample| *sample == 0.0));
+        assert!((output[5_999] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn scene_launch_switches_bank_on_next_bar_without_retriggering_kept_pads() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        assert!(mixer.set_stop_fade_samples(0));
+        for id in [0, 1, 2] {
+            mixer.load_sample(id, create_test_sample(1, 200_000, 0.25));
+        }
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.play_sample(1, 1.0));
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        transport.advance_by_rendered_frames(1);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut messages = Vec::new();
+        let mut output = vec![0.0; 96_000];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        schedule_scene_launch(
+            &mut scheduler,
+            1,
+            &mut transport,
+            0,
+            0b101,
+            &mut mixer,
+            &mut messages,
+        );
+        assert_eq!(scheduler.peek_next_target_frame(), Some(96_000));
+        render_scheduled_audio(
+            &mut mixer,
+            &mut scheduler,
+            &mut output,
+            &mut pad_peaks,
+            1,
+            1,
+            &mut transport,
+            &mut messages,
+        );
+
+        assert_stopped(&messages, 0, 1);
+        assert_started(&messages, 1, 2);
+        assert!(matches!(
+            messages[2],
+            AudioMessage::SceneLaunched { bank: 0 }
+        ));
+        assert_eq!(messages.len(), 3);
+        assert!((output[95_998] - 0.5).abs() < 1e-5);
+        assert!((output[95_999] - 0.5).abs() < 1e-5);
+        assert!(mixer.sample_is_active(0));
+        assert!(!mixer.sample_is_active(1));
+        assert!(mixer.sample_is_active(2));
+    }
+
+    #[test]
+    fn scene_launch_dropped_by_a_full_scheduler_is_reported() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        transport.advance_by_rendered_frames(1);
+        let mut scheduler = FixedCapacityScheduler::<1>::new();
+        scheduler
+            .schedule(200_000, ScheduledCommand::StopAll)
+            .unwrap();
+        let mut messages = Vec::new();
+
+        schedule_scene_launch(
+            &mut scheduler,
+            1,
+            &mut transport,
+            2,
+            0b1,
+            &mut mixer,
+            &mut messages,
+        );
+
+        assert!(matches!(
+            messages.as_slice(),
+            [AudioMessage::SceneDropped { bank: 2 }]
+        ));
+        assert_eq!(scheduler.peek_next_target_frame(), Some(200_000));
+    }
+
+    #[test]
+    fn cancelled_scene_launch_never_fires() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        transport.advance_by_rendered_frames(1);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut messages = Vec::new();
+
+        schedule_scene_launch(
+            &mut scheduler,
+            1,
+            &mut transport,
+            0,
+            0b1,
+            &mut mixer,
+            &mut messages,
+        );
+        scheduler
+            .schedule(200_000, ScheduledCommand::StopAll)
+            .unwrap();
+        scheduler.cancel_matching(is_scene_launch);
+        drain_scheduler_due_at_callback_start(
+            &mut scheduler,
+            96_000,
+            &mut mixer,
+            &mut transport,
+            &mut messages,
+        );
+
+        assert!(messages.is_empty());
+        assert!(!mixer.sample_is_active(0));
+        assert_eq!(scheduler.peek_next_target_frame(), Some(200_000));
+    }
+
+    #[test]
+    fn swing_change_keeps_already_scheduled_trigger_frame() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        assert!(transport.set_swing_percent(50.0));
+        transport.advance_by_rendered_frames(1);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut messages = Vec::new();
+
+        schedule_play_sample_command(
+            &mut scheduler,
+            1,
+            TriggerQuantization::Grid { step_64ths: 4 },
+            &mut transport,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
+            &mut mixer,
+            &mut messages,
+        );
+        assert!(transport.set_swing_percent(0.0));
+
+        assert_eq!(scheduler.peek_next_target_frame(), Some(7_500));
+        assert_eq!(
+            quantized_target_frame(&transport, TriggerQuantization::Grid { step_64ths: 4 }),
+            Some(6_000)
+        );
+    }
+
     #[test]
     fn quantized_play_schedules_selected_subdivision_frame() {
         let mut mixer = RtMixer::new(1, 10.0);
@@ -1592,11 +4462,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(8));
@@ -1628,11 +4496,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1657,11 +4523,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1685,11 +4549,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(5));
@@ -1713,11 +4575,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1744,11 +4604,9 @@ mod tests {
             transport.output_frame(),
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1794,11 +4652,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            1,
-            1.0,
+            ScheduledCommand::PlaySample { id: 1, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(8));
@@ -1832,11 +4688,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(8));
@@ -1853,7 +4707,6 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(output[..2].iter().all(|sample| *sample == 0.0));
@@ -1865,6 +4718,7 @@ mod tests {
     #[test]
     fn stopping_first_started_pad_keeps_masterclock_phase_for_future_triggers() {
         let mut mixer = RtMixer::new(1, 10.0);
+        assert!(mixer.set_stop_fade_samples(0));
         for id in 0..3 {
             mixer.load_sample(id, create_test_sample(1, 64, 0.25));
         }
@@ -1886,7 +4740,6 @@ mod tests {
             &mut mixer,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(transport.master_bpm(), Some(60.0));
@@ -1899,11 +4752,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            2,
-            1.0,
+            ScheduledCommand::PlaySample { id: 2, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(8));
@@ -1928,11 +4779,9 @@ mod tests {
             0,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            0,
-            1.0,
+            ScheduledCommand::PlaySample { id: 0, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -1945,11 +4794,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            1,
-            1.0,
+            ScheduledCommand::PlaySample { id: 1, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(callback_start_frame, 80);
@@ -1961,6 +4808,7 @@ mod tests {
     #[test]
     fn multi_loop_remains_stable_when_any_one_of_five_pads_stops() {
         let mut mixer = RtMixer::new(1, 10.0);
+        assert!(mixer.set_stop_fade_samples(0));
         for id in 0..6 {
             mixer.load_sample(id, create_test_sample(1, 64, 0.25));
         }
@@ -1982,18 +4830,15 @@ mod tests {
             &mut mixer,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
         schedule_play_sample_command(
             &mut scheduler,
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            5,
-            1.0,
+            ScheduledCommand::PlaySample { id: 5, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(8));
@@ -2080,11 +4925,9 @@ mod tests {
             callback_start_frame,
             TriggerQuantization::Grid { step_64ths: 4 },
             &mut transport,
-            1,
-            1.0,
+            ScheduledCommand::PlaySample { id: 1, volume: 1.0 },
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(
@@ -2105,6 +4948,7 @@ mod tests {
     #[test]
     fn immediate_exclusive_play_stops_all_then_starts_at_current_frame() {
         let mut mixer = RtMixer::new(1, 10.0);
+        assert!(mixer.set_stop_fade_samples(0));
         mixer.load_sample(0, create_test_sample(1, 32, 0.5));
         mixer.load_sample(1, create_test_sample(1, 32, 0.25));
         mixer.set_pad_bpm(1, Some(60.0));
@@ -2128,7 +4972,6 @@ mod tests {
             1.0,
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -2152,6 +4995,7 @@ mod tests {
     #[test]
     fn quantized_exclusive_play_starts_target_at_loop_start() {
         let mut mixer = RtMixer::new(1, 10.0);
+        assert!(mixer.set_stop_fade_samples(0));
         mixer.load_sample(0, create_test_sample(1, 64, 0.5));
         mixer.load_sample(1, create_test_sample(1, 64, 0.25));
         mixer.set_pad_loop_region(1, 0.7, Some(5.0));
@@ -2179,7 +5023,6 @@ mod tests {
             1.0,
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -2197,6 +5040,7 @@ mod tests {
     #[test]
     fn quantized_exclusive_play_switches_pads_at_target_offset() {
         let mut mixer = RtMixer::new(1, 10.0);
+        assert!(mixer.set_stop_fade_samples(0));
         mixer.load_sample(0, create_test_sample(1, 32, 0.5));
         mixer.load_sample(1, create_test_sample(1, 32, 0.25));
         assert!(mixer.play_sample(0, 1.0));
@@ -2216,7 +5060,6 @@ mod tests {
             1.0,
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert_eq!(scheduler.peek_next_target_frame(), Some(5));
@@ -2246,7 +5089,6 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!((output[0] - 0.5).abs() < 1e-5);
@@ -2293,7 +5135,6 @@ mod tests {
             1.0,
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(
@@ -2329,7 +5170,6 @@ mod tests {
             1.0,
             &mut mixer,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(scheduler.is_empty());
@@ -2370,32 +5210,112 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
-        assert!(output[..4].iter().all(|sample| *sample == 0.0));
+        assert!(output[..4].iter().all(|sample| *sample == 0.0));
+        assert!(
+            output[4..]
+                .iter()
+                .all(|sample| (*sample - 0.5).abs() < 1e-5)
+        );
+        assert!((pad_peaks[0] - 0.5).abs() < 1e-5);
+        assert_started(&messages, 0, 0);
+    }
+
+    #[test]
+    fn scheduled_start_inside_oversized_buffer_preserves_target_offset() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 1_000, 0.5));
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        scheduler
+            .schedule(600, ScheduledCommand::PlaySample { id: 0, volume: 1.0 })
+            .unwrap();
+        let mut output = vec![0.0; 700];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut messages = Vec::new();
+
+        render_scheduled_audio(
+            &mut mixer,
+            &mut scheduler,
+            &mut output,
+            &mut pad_peaks,
+            0,
+            1,
+            &mut transport,
+            &mut messages,
+        );
+
+        assert!(output[..600].iter().all(|sample| *sample == 0.0));
+        assert!(
+            output[600..]
+                .iter()
+                .all(|sample| (*sample - 0.5).abs() < 1e-5)
+        );
+        assert_started(&messages, 0, 0);
+    }
+
+    #[test]
+    fn scheduled_stop_inside_buffer_silences_after_target_offset() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        assert!(mixer.set_stop_fade_samples(0));
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        mixer.play_sample(0, 1.0);
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        scheduler
+            .schedule(4, ScheduledCommand::StopSample { id: 0 })
+            .unwrap();
+        let mut output = vec![0.0; 8];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut messages = Vec::new();
+
+        render_scheduled_audio(
+            &mut mixer,
+            &mut scheduler,
+            &mut output,
+            &mut pad_peaks,
+            0,
+            1,
+            &mut transport,
+            &mut messages,
+        );
+
         assert!(
-            output[4..]
+            output[..4]
                 .iter()
                 .all(|sample| (*sample - 0.5).abs() < 1e-5)
         );
+        assert!(output[4..].iter().all(|sample| *sample == 0.0));
         assert!((pad_peaks[0] - 0.5).abs() < 1e-5);
-        assert_started(&messages, 0, 0);
+        assert_stopped(&messages, 0, 0);
     }
 
     #[test]
-    fn scheduled_start_inside_oversized_buffer_preserves_target_offset() {
+    fn timed_play_stops_after_requested_frames() {
         let mut mixer = RtMixer::new(1, 44_100.0);
-        mixer.load_sample(0, create_test_sample(1, 1_000, 0.5));
+        // The auto stop fades out like any stop; without a fade it ends on the exact frame.
+        assert!(mixer.set_stop_fade_samples(0));
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
         let mut scheduler = FixedCapacityScheduler::<8>::new();
         let mut transport = TransportTimeline::new(44_100);
-        scheduler
-            .schedule(600, ScheduledCommand::PlaySample { id: 0, volume: 1.0 })
-            .unwrap();
-        let mut output = vec![0.0; 700];
-        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
         let mut messages = Vec::new();
 
+        schedule_timed_play_sample_command(
+            &mut scheduler,
+            0,
+            TriggerQuantization::Immediate,
+            &mut transport,
+            0,
+            1.0,
+            5,
+            &mut mixer,
+            &mut messages,
+        );
+        assert_eq!(scheduler.peek_next_target_frame(), Some(5));
+
+        let mut output = vec![0.0; 8];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
         render_scheduled_audio(
             &mut mixer,
             &mut scheduler,
@@ -2405,32 +5325,44 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
-        assert!(output[..600].iter().all(|sample| *sample == 0.0));
         assert!(
-            output[600..]
+            output[..5]
                 .iter()
                 .all(|sample| (*sample - 0.5).abs() < 1e-5)
         );
+        assert!(output[5..].iter().all(|sample| *sample == 0.0));
         assert_started(&messages, 0, 0);
+        assert_stopped(&messages, 1, 0);
+        assert!(scheduler.is_empty());
     }
 
     #[test]
-    fn scheduled_stop_inside_buffer_silences_after_target_offset() {
+    fn timed_play_stop_leaves_retriggered_voice_playing() {
         let mut mixer = RtMixer::new(1, 44_100.0);
         mixer.load_sample(0, create_test_sample(1, 32, 0.5));
-        mixer.play_sample(0, 1.0);
         let mut scheduler = FixedCapacityScheduler::<8>::new();
         let mut transport = TransportTimeline::new(44_100);
+        let mut messages = Vec::new();
+
+        schedule_timed_play_sample_command(
+            &mut scheduler,
+            0,
+            TriggerQuantization::Immediate,
+            &mut transport,
+            0,
+            1.0,
+            5,
+            &mut mixer,
+            &mut messages,
+        );
         scheduler
-            .schedule(4, ScheduledCommand::StopSample { id: 0 })
+            .schedule(2, ScheduledCommand::PlaySample { id: 0, volume: 1.0 })
             .unwrap();
+
         let mut output = vec![0.0; 8];
         let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
-        let mut messages = Vec::new();
-
         render_scheduled_audio(
             &mut mixer,
             &mut scheduler,
@@ -2440,22 +5372,42 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
-        assert!(
-            output[..4]
-                .iter()
-                .all(|sample| (*sample - 0.5).abs() < 1e-5)
+        assert!(output.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(6));
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn timed_play_is_dropped_without_room_for_its_stop() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 32, 0.5));
+        let mut scheduler = FixedCapacityScheduler::<1>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut messages = Vec::new();
+
+        schedule_timed_play_sample_command(
+            &mut scheduler,
+            0,
+            TriggerQuantization::Immediate,
+            &mut transport,
+            0,
+            1.0,
+            5,
+            &mut mixer,
+            &mut messages,
         );
-        assert!(output[4..].iter().all(|sample| *sample == 0.0));
-        assert!((pad_peaks[0] - 0.5).abs() < 1e-5);
-        assert_stopped(&messages, 0, 0);
+
+        assert!(scheduler.is_empty());
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+        assert!(messages.is_empty());
     }
 
     #[test]
     fn scheduled_render_tracks_pad_activity_across_split_segments() {
         let mut mixer = RtMixer::new(1, 44_100.0);
+        assert!(mixer.set_stop_fade_samples(0));
         mixer.load_sample(0, create_test_sample(1, 32, 0.5));
         mixer.play_sample(0, 1.0);
         let mut scheduler = FixedCapacityScheduler::<8>::new();
@@ -2478,7 +5430,6 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(pad_activity.contains(0));
@@ -2491,6 +5442,7 @@ mod tests {
     #[test]
     fn same_frame_stop_all_and_start_preserve_stable_order() {
         let mut mixer = RtMixer::new(1, 44_100.0);
+        assert!(mixer.set_stop_fade_samples(0));
         mixer.load_sample(0, create_test_sample(1, 32, 0.8));
         mixer.load_sample(1, create_test_sample(1, 32, 0.25));
         mixer.play_sample(0, 1.0);
@@ -2513,7 +5465,6 @@ mod tests {
             1,
             &mut transport,
             &mut messages,
-            &mut ImmediateAudioBufferRetirement,
         );
 
         assert!(
@@ -2532,4 +5483,372 @@ mod tests {
         assert_stopped(&messages, 0, 0);
         assert_started(&messages, 1, 1);
     }
+
+    #[test]
+    fn select_output_sample_rate_keeps_supported_request() {
+        let ranges = [(44_100, 44_100), (48_000, 96_000)];
+
+        assert_eq!(select_output_sample_rate(88_200, &ranges, 48_000), 88_200);
+        assert_eq!(select_output_sample_rate(44_100, &ranges, 48_000), 44_100);
+    }
+
+    #[test]
+    fn select_output_sample_rate_falls_back_to_nearest_supported_rate() {
+        let ranges = [(44_100, 44_100), (48_000, 96_000)];
+
+        assert_eq!(select_output_sample_rate(192_000, &ranges, 48_000), 96_000);
+        assert_eq!(select_output_sample_rate(22_050, &ranges, 48_000), 44_100);
+        assert_eq!(select_output_sample_rate(46_000, &ranges, 48_000), 44_100);
+    }
+
+    #[test]
+    fn select_output_sample_rate_uses_default_without_supported_ranges() {
+        assert_eq!(select_output_sample_rate(192_000, &[], 48_000), 48_000);
+    }
+
+    #[test]
+    fn stop_sample_stops_choke_group_members_when_enabled() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        for id in 0..3 {
+            mixer.load_sample(id, create_test_sample(1, 64, 0.25));
+            assert!(mixer.play_sample(id, 1.0));
+        }
+        mixer.set_pad_choke_group(0, Some(1));
+        mixer.set_pad_choke_group(1, Some(1));
+        mixer.set_choke_group_stop_all(1, true);
+
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut messages = Vec::new();
+
+        schedule_immediate_command(
+            &mut scheduler,
+            0,
+            ScheduledCommand::StopSample { id: 0 },
+            &mut mixer,
+            &mut transport,
+            &mut messages,
+        );
+
+        // Both members fade out instead of cutting off, and each is reported once.
+        assert!(!mixer.sample_is_active(0));
+        assert!(!mixer.sample_is_active(1));
+        assert_eq!(mixer.active_voice_count(), 3);
+        assert_stopped(&messages, 0, 0);
+        assert_stopped(&messages, 1, 1);
+        assert_eq!(messages.len(), 2);
+
+        let mut output = vec![0.0; mixer.stop_fade_samples() as usize + 1];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output[1] > 0.5);
+        assert_eq!(active_voice_frame(&mixer, 0), None);
+        assert_eq!(active_voice_frame(&mixer, 1), None);
+        assert!(active_voice_frame(&mixer, 2).is_some());
+    }
+
+    #[test]
+    fn stop_sample_leaves_choke_group_members_playing_by_default() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        assert!(mixer.set_stop_fade_samples(0));
+        for id in 0..2 {
+            mixer.load_sample(id, create_test_sample(1, 64, 0.25));
+            assert!(mixer.play_sample(id, 1.0));
+            mixer.set_pad_choke_group(id, Some(1));
+        }
+
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+        let mut transport = TransportTimeline::new(44_100);
+        let mut messages = Vec::new();
+
+        schedule_immediate_command(
+            &mut scheduler,
+            0,
+            ScheduledCommand::StopSample { id: 0 },
+            &mut mixer,
+            &mut transport,
+            &mut messages,
+        );
+
+        assert_eq!(active_voice_frame(&mixer, 0), None);
+        assert!(active_voice_frame(&mixer, 1).is_some());
+        assert_stopped(&messages, 0, 0);
+        assert_eq!(messages.len(), 1);
+    }
+
+    fn range(channels: u16, min_sample_rate: u32, max_sample_rate: u32) -> OutputConfigRange {
+        OutputConfigRange {
+            channels,
+            min_sample_rate,
+            max_sample_rate,
+        }
+    }
+
+    #[test]
+    fn select_output_config_uses_usable_default() {
+        let default = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 44_100,
+        };
+
+        assert_eq!(
+            select_output_config(Some(default), &[range(2, 44_100, 96_000)], None).unwrap(),
+            default
+        );
+    }
+
+    #[test]
+    fn select_output_config_resolves_requested_rate_for_default_channels() {
+        let default = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 44_100,
+        };
+        let supported = [range(1, 8_000, 192_000), range(2, 44_100, 48_000)];
+
+        let selected = select_output_config(Some(default), &supported, Some(96_000)).unwrap();
+
+        assert_eq!(selected.channels, 2);
+        assert_eq!(selected.sample_rate, 48_000);
+    }
+
+    #[test]
+    fn select_output_config_replaces_zero_channel_default() {
+        let default = OutputConfigChoice {
+            channels: 0,
+            sample_rate: 48_000,
+        };
+        let supported = [
+            range(0, 48_000, 48_000),
+            range(1, 44_100, 48_000),
+            range(2, 44_100, 96_000),
+        ];
+
+        assert_eq!(
+            select_output_config(Some(default), &supported, None).unwrap(),
+            OutputConfigChoice {
+                channels: 2,
+                sample_rate: 48_000,
+            }
+        );
+    }
+
+    #[test]
+    fn select_output_config_replaces_absurd_sample_rate_default() {
+        let default = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 4_000_000,
+        };
+        let supported = [range(2, 4_000_000, 4_000_000), range(1, 1_000, 500_000)];
+
+        assert_eq!(
+            select_output_config(Some(default), &supported, Some(1_000_000)).unwrap(),
+            OutputConfigChoice {
+                channels: 1,
+                sample_rate: OUTPUT_SAMPLE_RATE_MAX_HZ,
+            }
+        );
+    }
+
+    #[test]
+    fn select_output_config_reports_offered_configs_when_nothing_is_usable() {
+        let default = OutputConfigChoice {
+            channels: 64,
+            sample_rate: 48_000,
+        };
+        let supported = [range(64, 48_000, 48_000), range(2, 1_000, 4_000)];
+
+        let error = select_output_config(Some(default), &supported, None).unwrap_err();
+        let message = error.to_string();
+
+        assert!(message.contains("default: 64 ch@48000 Hz"));
+        assert!(message.contains("64 ch@48000-48000 Hz, 2 ch@1000-4000 Hz"));
+    }
+
+    #[test]
+    fn select_output_config_without_default_or_supported_configs_fails() {
+        let error = select_output_config(None, &[], None).unwrap_err();
+
+        assert!(matches!(error, DeviceError::NoUsableConfig { .. }));
+        assert!(
+            error
+                .to_string()
+                .contains("default: unavailable; supported: none")
+        );
+    }
+
+    fn attempt(channels: u16, sample_rate: u32, buffer_frames: Option<u32>) -> StreamAttempt {
+        StreamAttempt {
+            choice: OutputConfigChoice {
+                channels,
+                sample_rate,
+            },
+            sample_format: OutputSampleFormat::F32,
+            buffer_frames,
+        }
+    }
+
+    #[test]
+    fn stream_negotiation_ladder_falls_back_from_fixed_to_default_buffer() {
+        let selected = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 44_100,
+        };
+
+        let attempts = stream_negotiation_ladder(
+            selected,
+            OutputSampleFormat::F32,
+            None,
+            Some((1_024, 8_192)),
+            &[],
+        );
+
+        assert_eq!(
+            attempts,
+            vec![
+                attempt(2, 44_100, Some(OUTPUT_BUFFER_FRAMES)),
+                attempt(2, 44_100, Some(1_024)),
+                attempt(2, 44_100, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_negotiation_ladder_skips_bounded_size_equal_to_fixed_request() {
+        let selected = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 48_000,
+        };
+
+        let attempts = stream_negotiation_ladder(
+            selected,
+            OutputSampleFormat::F32,
+            None,
+            Some((64, 4_096)),
+            &[],
+        );
+
+        assert_eq!(
+            attempts,
+            vec![
+                attempt(2, 48_000, Some(OUTPUT_BUFFER_FRAMES)),
+                attempt(2, 48_000, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_negotiation_ladder_tries_supported_alternative_rates_last() {
+        let selected = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 48_000,
+        };
+        let supported = [range(1, 8_000, 192_000), range(2, 44_100, 48_000)];
+
+        let attempts =
+            stream_negotiation_ladder(selected, OutputSampleFormat::F32, None, None, &supported);
+
+        assert_eq!(
+            attempts,
+            vec![
+                attempt(2, 48_000, Some(OUTPUT_BUFFER_FRAMES)),
+                attempt(2, 48_000, None),
+                attempt(2, 44_100, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_negotiation_ladder_tries_a_requested_buffer_size_alone() {
+        let selected = OutputConfigChoice {
+            channels: 2,
+            sample_rate: 48_000,
+        };
+        let supported = [range(2, 44_100, 48_000)];
+
+        let attempts = stream_negotiation_ladder(
+            selected,
+            OutputSampleFormat::F32,
+            Some(128),
+            Some((256, 4_096)),
+            &supported,
+        );
+
+        assert_eq!(attempts, vec![attempt(2, 48_000, Some(128))]);
+        assert_eq!(
+            DeviceError::BufferSizeRejected {
+                frames: 128,
+                supported: "256..=4096 frames".to_string(),
+                reason: "invalid buffer size".to_string(),
+            }
+            .to_string(),
+            "output device rejected a buffer of 128 frames (supported: 256..=4096 frames): \
+             invalid buffer size"
+        );
+    }
+
+    #[test]
+    fn stream_attempt_describes_buffer_choice() {
+        assert_eq!(
+            attempt(2, 48_000, Some(512)).to_string(),
+            "2 ch@48000 Hz, f32, 512 frames"
+        );
+        assert_eq!(
+            attempt(1, 44_100, None).to_string(),
+            "1 ch@44100 Hz, f32, default buffer"
+        );
+    }
+
+    #[test]
+    fn select_output_sample_format_prefers_native_format() {
+        use OutputSampleFormat::{F32, I16, U16};
+
+        assert_eq!(
+            select_output_sample_format(Some(F32), &[F32, I16]),
+            Some(F32)
+        );
+        assert_eq!(
+            select_output_sample_format(Some(I16), &[F32, I16]),
+            Some(I16)
+        );
+        assert_eq!(select_output_sample_format(Some(U16), &[]), Some(U16));
+    }
+
+    #[test]
+    fn select_output_sample_format_falls_back_for_i16_only_device() {
+        use OutputSampleFormat::{F32, I16, U16};
+
+        assert_eq!(select_output_sample_format(Some(F32), &[I16]), Some(I16));
+        assert_eq!(select_output_sample_format(None, &[I16]), Some(I16));
+        assert_eq!(select_output_sample_format(None, &[U16, I16]), Some(I16));
+        assert_eq!(select_output_sample_format(None, &[U16]), Some(U16));
+        assert_eq!(select_output_sample_format(None, &[]), None);
+    }
+
+    #[test]
+    fn render_converted_fills_i16_buffers_larger_than_scratch() {
+        let mut scratch = vec![0.0_f32; 4];
+        let mut next = 0.0_f32;
+        let mut passes = 0;
+        let mut latencies = Vec::new();
+        let mut render = |data: &mut [f32], latency_ns: Option<u64>| {
+            passes += 1;
+            latencies.push(latency_ns);
+            for sample in data {
+                *sample = next;
+                next += 0.1;
+            }
+        };
+        let mut output = [0_i16; 10];
+
+        render_converted(&mut output, &mut scratch, Some(5_000_000), &mut render);
+
+        assert_eq!(passes, 3);
+        assert_eq!(latencies, [Some(5_000_000), None, None]);
+        let expected: Vec<i16> = (0..10)
+            .map(|index| <i16 as cpal::Sample>::from_sample(index as f32 * 0.1))
+            .collect();
+        for (actual, expected) in output.iter().zip(&expected) {
+            assert!((i32::from(*actual) - i32::from(*expected)).abs() <= 1);
+        }
+    }
 }
@@ -0,0 +1,329 @@
+//! Capture and replay of parameter automation.
+//!
+//! While recording, the callback writes every parameter update it applies into a dedicated
+//! ring, stamped with the output frame it took effect at. Replay feeds the events back from a
+//! background thread as frame-stamped [`ControlMessage::SetParameter`] messages, a short
+//! lookahead ahead of the audio clock, so only a few of them wait in the callback's deferred
+//! queue at a time.
+
+use crate::audio_engine::clock::AudioClock;
+use crate::audio_engine::constants::{AUTOMATION_CAPTURE_EVENTS, AUTOMATION_FEED_INTERVAL_MS};
+use crate::audio_engine::deferred_control::ControlProducer;
+use crate::messages::{ControlMessage, ControlParameterMessage};
+use rtrb::{Consumer, Producer, RingBuffer};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// One parameter update and the output frame it took effect at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AutomationEvent {
+    pub frame: u64,
+    pub parameter: ControlParameterMessage,
+}
+
+/// Callback end of the capture ring.
+pub(crate) struct AutomationCapture {
+    producer: Producer<AutomationEvent>,
+    recording: Arc<AtomicBool>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl AutomationCapture {
+    /// Records `parameter` at `frame` while recording; counts it as dropped if the ring is full.
+    pub(crate) fn record(&mut self, frame: u64, parameter: ControlParameterMessage) {
+        if !self.recording.load(Ordering::Acquire) {
+            return;
+        }
+
+        if self
+            .producer
+            .push(AutomationEvent { frame, parameter })
+            .is_err()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Control end of the capture ring.
+pub(crate) struct AutomationRecorder {
+    consumer: Consumer<AutomationEvent>,
+    recording: Arc<AtomicBool>,
+    dropped: Arc<AtomicU64>,
+    /// Output frame the current recording started at.
+    origin_frame: u64,
+}
+
+impl AutomationRecorder {
+    /// Starts a new recording at `origin_frame`, discarding anything left over from the
+    /// previous one.
+    pub(crate) fn start(&mut self, origin_frame: u64) {
+        self.recording.store(false, Ordering::Release);
+        while self.consumer.pop().is_ok() {}
+        self.dropped.store(0, Ordering::Relaxed);
+        self.origin_frame = origin_frame;
+        self.recording.store(true, Ordering::Release);
+    }
+
+    /// Stops recording and returns the captured events, with frames relative to the recording
+    /// start, and the number dropped on overflow.
+    ///
+    /// Updates the callback is applying while this runs may miss the recording.
+    pub(crate) fn stop(&mut self) -> (Vec<AutomationEvent>, u64) {
+        self.recording.store(false, Ordering::Release);
+
+        let mut events = Vec::with_capacity(self.consumer.slots());
+        while let Ok(mut event) = self.consumer.pop() {
+            event.frame = event.frame.saturating_sub(self.origin_frame);
+            events.push(event);
+        }
+        (events, self.dropped.swap(0, Ordering::Relaxed))
+    }
+}
+
+/// Creates the capture ring shared by the callback and the control side.
+pub(crate) fn automation_capture_channel() -> (AutomationCapture, AutomationRecorder) {
+    let (producer, consumer) = RingBuffer::new(AUTOMATION_CAPTURE_EVENTS);
+    let recording = Arc::new(AtomicBool::new(false));
+    let dropped = Arc::new(AtomicU64::new(0));
+
+    (
+        AutomationCapture {
+            producer,
+            recording: Arc::clone(&recording),
+            dropped: Arc::clone(&dropped),
+        },
+        AutomationRecorder {
+            consumer,
+            recording,
+            dropped,
+            origin_frame: 0,
+        },
+    )
+}
+
+/// Recorded events in replay order, shifted by an offset and optionally repeating.
+///
+/// Pass `n` of a looping schedule replays every event `n * loop_frames` later than the first.
+#[derive(Debug)]
+pub(crate) struct AutomationSchedule {
+    events: Vec<AutomationEvent>,
+    offset_frames: u64,
+    loop_frames: Option<u64>,
+    next: usize,
+    pass: u64,
+}
+
+impl AutomationSchedule {
+    pub(crate) fn new(
+        mut events: Vec<AutomationEvent>,
+        offset_frames: u64,
+        loop_frames: Option<u64>,
+    ) -> Self {
+        // Stable, so updates recorded at the same frame keep their order.
+        events.sort_by_key(|event| event.frame);
+
+        Self {
+            events,
+            offset_frames,
+            loop_frames: loop_frames.filter(|frames| *frames > 0),
+            next: 0,
+            pass: 0,
+        }
+    }
+
+    /// Target frame and parameter of the next event, or `None` once a one-shot schedule ends.
+    pub(crate) fn peek(&self) -> Option<(u64, ControlParameterMessage)> {
+        let event = self.events.get(self.next)?;
+        let loop_shift = self
+            .loop_frames
+            .map_or(0, |frames| frames.saturating_mul(self.pass));
+        let target_frame = event
+            .frame
+            .saturating_add(self.offset_frames)
+            .saturating_add(loop_shift);
+        Some((target_frame, event.parameter))
+    }
+
+    pub(crate) fn advance(&mut self) {
+        self.next += 1;
+        if self.next == self.events.len() && self.loop_frames.is_some() {
+            self.next = 0;
+            self.pass += 1;
+        }
+    }
+}
+
+/// Background thread feeding an [`AutomationSchedule`] into the control queue.
+///
+/// Dropping the player stops the feed; events already sent still apply at their frames.
+pub(crate) struct AutomationPlayer {
+    running: Arc<AtomicBool>,
+    feeder: Option<JoinHandle<()>>,
+}
+
+impl AutomationPlayer {
+    pub(crate) fn spawn(
+        mut schedule: AutomationSchedule,
+        producer: Arc<Mutex<ControlProducer>>,
+        clock: Arc<AudioClock>,
+        lookahead_frames: u64,
+    ) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let feeder = {
+            let running = Arc::clone(&running);
+            thread::spawn(move || {
+                while running.load(Ordering::Acquire) {
+                    if let Some(timestamp) = clock.read()
+                        && !feed_due_events(
+                            &mut schedule,
+                            &producer,
+                            timestamp.output_frame.saturating_add(lookahead_frames),
+                        )
+                    {
+                        break;
+                    }
+                    thread::sleep(Duration::from_millis(AUTOMATION_FEED_INTERVAL_MS));
+                }
+                running.store(false, Ordering::Release);
+            })
+        };
+
+        Self {
+            running,
+            feeder: Some(feeder),
+        }
+    }
+
+    /// Whether events are still being fed.
+    pub(crate) fn is_playing(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for AutomationPlayer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        if let Some(feeder) = self.feeder.take() {
+            let _ = feeder.join();
+        }
+    }
+}
+
+/// Sends the events due before `horizon_frame`, stamped with their target frames.
+///
+/// Returns false once the schedule has ended. Events that do not fit the queue are retried on
+/// the next pass.
+fn feed_due_events(
+    schedule: &mut AutomationSchedule,
+    producer: &Mutex<ControlProducer>,
+    horizon_frame: u64,
+) -> bool {
+    let Ok(mut producer) = producer.lock() else {
+        return false;
+    };
+
+    while let Some((target_frame, parameter)) = schedule.peek() {
+        if target_frame >= horizon_frame {
+            return true;
+        }
+
+        producer.stamp_next_message(target_frame);
+        if producer
+            .push(ControlMessage::SetParameter(parameter))
+            .is_err()
+        {
+            return true;
+        }
+        schedule.advance();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume_event(frame: u64, volume: f32) -> AutomationEvent {
+        AutomationEvent {
+            frame,
+            parameter: ControlParameterMessage::SetVolume(volume),
+        }
+    }
+
+    fn drain_targets(schedule: &mut AutomationSchedule, count: usize) -> Vec<u64> {
+        (0..count)
+            .map_while(|_| {
+                let (target_frame, _) = schedule.peek()?;
+                schedule.advance();
+                Some(target_frame)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn capture_records_only_while_recording_and_start_discards_leftovers() {
+        let (mut capture, mut recorder) = automation_capture_channel();
+        capture.record(10, ControlParameterMessage::SetVolume(0.1));
+
+        recorder.start(15);
+        capture.record(20, ControlParameterMessage::SetVolume(0.2));
+        capture.record(30, ControlParameterMessage::SetSpeed(1.5));
+        let (events, dropped) = recorder.stop();
+        capture.record(40, ControlParameterMessage::SetVolume(0.4));
+
+        // Frames count from the recording start.
+        assert_eq!(
+            events,
+            vec![
+                volume_event(5, 0.2),
+                AutomationEvent {
+                    frame: 15,
+                    parameter: ControlParameterMessage::SetSpeed(1.5),
+                },
+            ]
+        );
+        assert_eq!(dropped, 0);
+
+        recorder.start(0);
+        assert_eq!(recorder.stop(), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn schedule_orders_events_and_applies_the_offset_once() {
+        let mut schedule = AutomationSchedule::new(
+            vec![
+                volume_event(300, 0.3),
+                volume_event(100, 0.1),
+                volume_event(100, 0.2),
+            ],
+            1_000,
+            None,
+        );
+
+        assert_eq!(
+            schedule.peek(),
+            Some((1_100, ControlParameterMessage::SetVolume(0.1)))
+        );
+        assert_eq!(drain_targets(&mut schedule, 8), vec![1_100, 1_100, 1_300]);
+        assert_eq!(schedule.peek(), None);
+    }
+
+    #[test]
+    fn looping_schedule_repeats_every_loop_length() {
+        let mut schedule = AutomationSchedule::new(
+            vec![volume_event(0, 0.0), volume_event(250, 1.0)],
+            48,
+            Some(1_000),
+        );
+
+        assert_eq!(
+            drain_targets(&mut schedule, 6),
+            vec![48, 298, 1_048, 1_298, 2_048, 2_298]
+        );
+        assert!(schedule.peek().is_some());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::messages::{PreparedStemSet, SampleBuffer};
+use crate::messages::{OnsetTable, PreparedStemSet, SampleBuffer};
 use rtrb::{Consumer, Producer, PushError, RingBuffer};
 use std::sync::{
     Arc,
@@ -14,11 +14,13 @@ const RETIRED_AUDIO_BUFFER_BACKLOG_CAPACITY: usize = 128;
 pub(crate) enum RetiredAudioBuffer {
     Sample(SampleBuffer),
     PreparedStems(PreparedStemSet),
+    Onsets(OnsetTable),
 }
 
 pub(crate) trait AudioBufferRetirement {
     fn retire_sample(&mut self, sample: SampleBuffer);
     fn retire_prepared_stems(&mut self, stems: PreparedStemSet);
+    fn retire_onsets(&mut self, onsets: OnsetTable);
     fn available_retirement_slots(&mut self) -> usize;
 }
 
@@ -31,6 +33,8 @@ impl AudioBufferRetirement for ImmediateAudioBufferRetirement {
 
     fn retire_prepared_stems(&mut self, _stems: PreparedStemSet) {}
 
+    fn retire_onsets(&mut self, _onsets: OnsetTable) {}
+
     fn available_retirement_slots(&mut self) -> usize {
         usize::MAX
     }
@@ -108,6 +112,10 @@ impl AudioBufferRetirement for RtAudioBufferRetirement {
         self.retire_buffer(RetiredAudioBuffer::PreparedStems(stems));
     }
 
+    fn retire_onsets(&mut self, onsets: OnsetTable) {
+        self.retire_buffer(RetiredAudioBuffer::Onsets(onsets));
+    }
+
     fn available_retirement_slots(&mut self) -> usize {
         self.flush_backlog();
         self.producer
@@ -1,5 +1,14 @@
+use crate::audio_engine::constants::MONO_WIDENING_COMB_GAIN;
 use crate::audio_engine::errors::SampleLoadError;
 
+/// Channel count samples are stored and voices rendered at for a device with
+/// `output_channels`.
+///
+/// Outputs wider than stereo mix stereo voices and pan them across the speakers at mix time.
+pub fn mix_channels(output_channels: usize) -> usize {
+    output_channels.min(2)
+}
+
 /// Maps audio samples from one channel configuration to another.
 ///
 /// Currently supports:
@@ -26,29 +35,88 @@ pub fn map_channels(
         return Ok(samples);
     }
 
+    if !is_supported_mapping(file_channels, output_channels) {
+        return Err(SampleLoadError::UnsupportedChannels {
+            file_channels,
+            output_channels,
+        });
+    }
+
+    let frames = samples.len() / file_channels;
+    let mut out = Vec::with_capacity(frames * output_channels);
+    map_channels_into(&samples, file_channels, output_channels, &mut out)?;
+    Ok(out)
+}
+
+fn is_supported_mapping(file_channels: usize, output_channels: usize) -> bool {
+    file_channels == output_channels || matches!((file_channels, output_channels), (1, 2) | (2, 1))
+}
+
+/// Appends interleaved samples to `out`, converting the channel layout on the way.
+///
+/// Supports the same mappings as [`map_channels`], so decoded packets can be mapped
+/// incrementally into the final buffer without a second full-size copy.
+pub fn map_channels_into(
+    samples: &[f32],
+    file_channels: usize,
+    output_channels: usize,
+    out: &mut Vec<f32>,
+) -> Result<(), SampleLoadError> {
     match (file_channels, output_channels) {
+        // Same channel count: no conversion needed
+        (file_channels, output_channels) if file_channels == output_channels => {
+            out.extend_from_slice(samples);
+        }
         // Mono → Stereo: duplicate each sample
         (1, 2) => {
-            let mut out = Vec::with_capacity(samples.len() * 2);
-            for s in samples {
-                out.push(s);
-                out.push(s);
-            }
-            Ok(out)
+            out.extend(samples.iter().flat_map(|&s| [s, s]));
         }
         // Stereo → Mono: average each frame
         (2, 1) => {
-            let mut out = Vec::with_capacity(samples.len() / 2);
-            for frame in samples.chunks_exact(2) {
-                out.push((frame[0] + frame[1]) * 0.5);
-            }
-            Ok(out)
+            out.extend(
+                samples
+                    .chunks_exact(2)
+                    .map(|frame| (frame[0] + frame[1]) * 0.5),
+            );
         }
         // Unsupported mapping
-        _ => Err(SampleLoadError::UnsupportedChannels {
-            file_channels,
-            output_channels,
-        }),
+        _ => {
+            return Err(SampleLoadError::UnsupportedChannels {
+                file_channels,
+                output_channels,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Widens a mono signal that was duplicated to interleaved stereo, in place.
+///
+/// Applies complementary comb filters: the left channel gets a delayed copy added and the right
+/// channel gets the same copy subtracted. The mid signal `(L + R) / 2` is the original mono
+/// signal scaled by `1 / (1 + MONO_WIDENING_COMB_GAIN)`, so summing to mono only lowers the
+/// level; only the side signal carries the delayed copy, which lowers the L/R correlation.
+/// Each channel on its own is comb filtered, with peaks and notches spaced `1 / delay` apart.
+/// The scale keeps those peaks within the source's peak level, so a full-scale file does not
+/// clip.
+///
+/// Does nothing for a zero delay or a buffer with an odd sample count.
+pub fn widen_mono_upmix(stereo: &mut [f32], delay_frames: usize) {
+    if delay_frames == 0 || !stereo.len().is_multiple_of(2) {
+        return;
+    }
+
+    let scale = 1.0 / (1.0 + MONO_WIDENING_COMB_GAIN);
+    let frames = stereo.len() / 2;
+    // Walk backwards so the delayed frame is still the untouched mono value.
+    for frame in (0..frames).rev() {
+        let dry = stereo[frame * 2];
+        let delayed = frame
+            .checked_sub(delay_frames)
+            .map_or(0.0, |source| MONO_WIDENING_COMB_GAIN * stereo[source * 2]);
+        stereo[frame * 2] = (dry + delayed) * scale;
+        stereo[frame * 2 + 1] = (dry - delayed) * scale;
     }
 }
 
@@ -84,6 +152,77 @@ mod tests {
         assert_eq!(output, input); // Should return unchanged
     }
 
+    #[test]
+    fn test_map_channels_into_appends_mapped_packets() {
+        let mut output = Vec::new();
+
+        map_channels_into(&[0.5, -0.3], 1, 2, &mut output).unwrap();
+        map_channels_into(&[0.8], 1, 2, &mut output).unwrap();
+
+        assert_eq!(output, map_channels(vec![0.5, -0.3, 0.8], 1, 2).unwrap());
+    }
+
+    #[test]
+    fn test_map_channels_into_unsupported_leaves_output_untouched() {
+        let mut output = vec![1.0];
+
+        let result = map_channels_into(&[0.5, -0.3, 0.8, 0.2], 2, 4, &mut output);
+
+        assert!(matches!(
+            result,
+            Err(SampleLoadError::UnsupportedChannels { .. })
+        ));
+        assert_eq!(output, vec![1.0]);
+    }
+
+    #[test]
+    fn test_widen_mono_upmix_keeps_mid_and_decorrelates_sides() {
+        let mono: Vec<f32> = (0..64)
+            .map(|i| ((i * 7) % 11) as f32 / 11.0 - 0.5)
+            .collect();
+        let mut stereo = map_channels(mono.clone(), 1, 2).unwrap();
+
+        widen_mono_upmix(&mut stereo, 3);
+
+        let scale = 1.0 / (1.0 + MONO_WIDENING_COMB_GAIN);
+        for (frame, &dry) in mono.iter().enumerate() {
+            let (left, right) = (stereo[frame * 2], stereo[frame * 2 + 1]);
+            assert!(((left + right) * 0.5 - dry * scale).abs() < 1e-6);
+            let expected_side = if frame >= 3 {
+                MONO_WIDENING_COMB_GAIN * mono[frame - 3] * scale
+            } else {
+                0.0
+            };
+            assert!(((left - right) * 0.5 - expected_side).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_widen_mono_upmix_does_not_clip_full_scale_sources() {
+        let mono: Vec<f32> = (0..64)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let mut stereo = map_channels(mono, 1, 2).unwrap();
+
+        widen_mono_upmix(&mut stereo, 2);
+
+        assert!(stereo.iter().all(|sample| sample.abs() <= 1.0));
+        assert!(
+            stereo
+                .iter()
+                .any(|sample| (sample.abs() - 1.0).abs() < 1e-6)
+        );
+    }
+
+    #[test]
+    fn test_widen_mono_upmix_zero_delay_is_noop() {
+        let mut stereo = vec![0.5, 0.5, -0.3, -0.3];
+
+        widen_mono_upmix(&mut stereo, 0);
+
+        assert_eq!(stereo, vec![0.5, 0.5, -0.3, -0.3]);
+    }
+
     #[test]
     fn test_map_channels_unsupported() {
         let input = vec![0.5, -0.3, 0.8, 0.2];
@@ -0,0 +1,286 @@
+//! Audio clock the callback publishes for the control thread.
+//!
+//! Each callback stores the output frame it starts at together with a monotonic timestamp.
+//! Timestamps share one process-wide epoch, so the clocks of several engines can be compared
+//! to measure how their devices drift against each other.
+//!
+//! When the backend reports callback and playback timestamps, the callback also feeds a
+//! [`PlaybackClockMapping`] that filters them into a frames-to-wallclock mapping, so the
+//! control thread can see when a frame is actually heard and how long the output path is.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering, fence};
+use std::time::Instant;
+
+/// Nanoseconds since the first use of the audio clock in this process.
+pub(crate) fn monotonic_ns() -> u64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+/// An output frame and the monotonic time its callback started.
+///
+/// The latency and playback fields are `None` on backends without usable timestamps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AudioClockTimestamp {
+    pub output_frame: u64,
+    pub monotonic_ns: u64,
+    /// Reported time between the callback and `output_frame` reaching the device.
+    pub output_latency_ns: Option<u64>,
+    /// Filtered monotonic time `output_frame` is played at.
+    pub playback_ns: Option<u64>,
+    /// Measured device rate from the filtered mapping.
+    pub sample_rate_hz: Option<f64>,
+}
+
+/// Frames-to-wallclock mapping filtered from jittery playback timestamps.
+///
+/// A second-order delay-locked loop: every observation nudges the predicted playback time
+/// and the nanoseconds per frame towards the reported one. Observations further off than
+/// [`Self::RESYNC_NS`], or frames going backwards, restart the mapping at that observation.
+#[derive(Debug, Clone)]
+pub(crate) struct PlaybackClockMapping {
+    nominal_ns_per_frame: f64,
+    anchor: Option<(u64, f64)>,
+    ns_per_frame: f64,
+}
+
+impl PlaybackClockMapping {
+    const TIME_GAIN: f64 = 0.01;
+    const PERIOD_GAIN: f64 = 0.000_05;
+    const RESYNC_NS: f64 = 20_000_000.0;
+    /// Measured rates further than this from nominal are treated as a broken timestamp source.
+    const MAX_RATE_DEVIATION: f64 = 0.05;
+
+    pub(crate) fn new(nominal_sample_rate_hz: u32) -> Self {
+        let nominal_ns_per_frame = 1_000_000_000.0 / f64::from(nominal_sample_rate_hz.max(1));
+        Self {
+            nominal_ns_per_frame,
+            anchor: None,
+            ns_per_frame: nominal_ns_per_frame,
+        }
+    }
+
+    /// Feeds the playback time reported for `output_frame`.
+    pub(crate) fn observe(&mut self, output_frame: u64, playback_ns: u64) {
+        let observed_ns = playback_ns as f64;
+        let Some((anchor_frame, anchor_ns)) = self.anchor else {
+            self.resync(output_frame, observed_ns);
+            return;
+        };
+        if output_frame < anchor_frame {
+            self.resync(output_frame, observed_ns);
+            return;
+        }
+
+        let elapsed_frames = (output_frame - anchor_frame) as f64;
+        let predicted_ns = anchor_ns + elapsed_frames * self.ns_per_frame;
+        let error_ns = observed_ns - predicted_ns;
+        if !error_ns.is_finite() || error_ns.abs() > Self::RESYNC_NS {
+            self.resync(output_frame, observed_ns);
+            return;
+        }
+
+        self.anchor = Some((output_frame, predicted_ns + Self::TIME_GAIN * error_ns));
+        if elapsed_frames > 0.0 {
+            let ns_per_frame = self.ns_per_frame + Self::PERIOD_GAIN * error_ns / elapsed_frames;
+            let deviation = (ns_per_frame / self.nominal_ns_per_frame - 1.0).abs();
+            if deviation <= Self::MAX_RATE_DEVIATION {
+                self.ns_per_frame = ns_per_frame;
+            } else {
+                self.resync(output_frame, observed_ns);
+            }
+        }
+    }
+
+    /// Filtered playback time of `output_frame`, or `None` before the first observation.
+    pub(crate) fn playback_ns_at(&self, output_frame: u64) -> Option<f64> {
+        let (anchor_frame, anchor_ns) = self.anchor?;
+        let elapsed_frames = output_frame as f64 - anchor_frame as f64;
+        Some(anchor_ns + elapsed_frames * self.ns_per_frame)
+    }
+
+    /// Measured device rate, or `None` before the first observation.
+    pub(crate) fn sample_rate_hz(&self) -> Option<f64> {
+        self.anchor.map(|_| 1_000_000_000.0 / self.ns_per_frame)
+    }
+
+    fn resync(&mut self, output_frame: u64, observed_ns: f64) {
+        self.anchor = Some((output_frame, observed_ns));
+        self.ns_per_frame = self.nominal_ns_per_frame;
+    }
+}
+
+/// Single-writer seqlock around the latest [`AudioClockTimestamp`].
+///
+/// The callback never waits; readers retry while a write is in progress.
+#[derive(Debug, Default)]
+pub(crate) struct AudioClock {
+    sequence: AtomicU64,
+    output_frame: AtomicU64,
+    monotonic_ns: AtomicU64,
+    output_latency_ns: AtomicU64,
+    playback_ns: AtomicU64,
+    sample_rate_bits: AtomicU64,
+}
+
+impl AudioClock {
+    const READ_ATTEMPTS: usize = 64;
+    /// Stored in place of a missing optional field.
+    const NONE: u64 = u64::MAX;
+
+    /// Publishes a timestamp. Only the audio callback writes.
+    pub(crate) fn publish(&self, timestamp: AudioClockTimestamp) {
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence
+            .store(sequence.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+        self.output_frame
+            .store(timestamp.output_frame, Ordering::Relaxed);
+        self.monotonic_ns
+            .store(timestamp.monotonic_ns, Ordering::Relaxed);
+        self.output_latency_ns.store(
+            timestamp.output_latency_ns.unwrap_or(Self::NONE),
+            Ordering::Relaxed,
+        );
+        self.playback_ns.store(
+            timestamp.playback_ns.unwrap_or(Self::NONE),
+            Ordering::Relaxed,
+        );
+        self.sample_rate_bits.store(
+            timestamp.sample_rate_hz.map_or(Self::NONE, f64::to_bits),
+            Ordering::Relaxed,
+        );
+        self.sequence
+            .store(sequence.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Latest consistent timestamp, or `None` before the first callback.
+    pub(crate) fn read(&self) -> Option<AudioClockTimestamp> {
+        for _ in 0..Self::READ_ATTEMPTS {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before == 0 {
+                return None;
+            }
+            if before % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+
+            let output_frame = self.output_frame.load(Ordering::Relaxed);
+            let monotonic_ns = self.monotonic_ns.load(Ordering::Relaxed);
+            let output_latency_ns = self.output_latency_ns.load(Ordering::Relaxed);
+            let playback_ns = self.playback_ns.load(Ordering::Relaxed);
+            let sample_rate_bits = self.sample_rate_bits.load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == before {
+                return Some(AudioClockTimestamp {
+                    output_frame,
+                    monotonic_ns,
+                    output_latency_ns: (output_latency_ns != Self::NONE)
+                        .then_some(output_latency_ns),
+                    playback_ns: (playback_ns != Self::NONE).then_some(playback_ns),
+                    sample_rate_hz: (sample_rate_bits != Self::NONE)
+                        .then(|| f64::from_bits(sample_rate_bits)),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_reads_none_until_published_then_latest_timestamp() {
+        let clock = AudioClock::default();
+        assert_eq!(clock.read(), None);
+
+        let first = AudioClockTimestamp {
+            output_frame: 512,
+            monotonic_ns: 1_000,
+            output_latency_ns: None,
+            playback_ns: None,
+            sample_rate_hz: None,
+        };
+        clock.publish(first);
+        assert_eq!(clock.read(), Some(first));
+
+        let second = AudioClockTimestamp {
+            output_frame: 1_024,
+            monotonic_ns: 11_667_000,
+            output_latency_ns: Some(5_000_000),
+            playback_ns: Some(16_667_000),
+            sample_rate_hz: Some(48_000.5),
+        };
+        clock.publish(second);
+        assert_eq!(clock.read(), Some(second));
+    }
+
+    /// Deterministic jitter in `-amplitude..amplitude` nanoseconds (splitmix64).
+    fn jitter_ns(step: u64, amplitude: f64) -> f64 {
+        let mut hash = step.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash ^= hash >> 31;
+        (hash as f64 / u64::MAX as f64 * 2.0 - 1.0) * amplitude
+    }
+
+    fn true_playback_ns(frame: u64, rate_hz: f64, offset_ns: f64) -> f64 {
+        offset_ns + frame as f64 * 1_000_000_000.0 / rate_hz
+    }
+
+    #[test]
+    fn mapping_converges_on_jittery_timestamps_of_a_drifting_device() {
+        // 50 ppm fast device, 512-frame callbacks, +-0.2 ms timestamp jitter.
+        let rate_hz = 48_000.0 * (1.0 + 50e-6);
+        let offset_ns = 3_000_000.0;
+        let mut mapping = PlaybackClockMapping::new(48_000);
+        assert_eq!(mapping.playback_ns_at(0), None);
+        assert_eq!(mapping.sample_rate_hz(), None);
+
+        for step in 0..4_000 {
+            let frame = step * 512;
+            let observed = true_playback_ns(frame, rate_hz, offset_ns) + jitter_ns(step, 200_000.0);
+            mapping.observe(frame, observed as u64);
+        }
+
+        let frame = 4_000 * 512;
+        let error_ns =
+            mapping.playback_ns_at(frame).unwrap() - true_playback_ns(frame, rate_hz, offset_ns);
+        assert!(error_ns.abs() < 50_000.0, "error {error_ns} ns");
+        let measured_rate = mapping.sample_rate_hz().unwrap();
+        assert!(
+            (measured_rate / rate_hz - 1.0).abs() < 15e-6,
+            "measured {measured_rate} Hz"
+        );
+    }
+
+    #[test]
+    fn mapping_resyncs_when_timestamps_jump_or_frames_restart() {
+        let mut mapping = PlaybackClockMapping::new(48_000);
+        for step in 0..100 {
+            let frame = step * 256;
+            mapping.observe(frame, true_playback_ns(frame, 48_000.0, 0.0) as u64);
+        }
+
+        // The device reopened one second later.
+        let jumped = true_playback_ns(100 * 256, 48_000.0, 1_000_000_000.0) as u64;
+        mapping.observe(100 * 256, jumped);
+        assert_eq!(mapping.playback_ns_at(100 * 256), Some(jumped as f64));
+        assert_eq!(mapping.sample_rate_hz(), Some(48_000.0));
+
+        mapping.observe(0, 42);
+        assert_eq!(mapping.playback_ns_at(0), Some(42.0));
+    }
+
+    #[test]
+    fn monotonic_ns_never_goes_backwards() {
+        let first = monotonic_ns();
+        let second = monotonic_ns();
+        assert!(second >= first);
+    }
+}
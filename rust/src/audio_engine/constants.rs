@@ -12,12 +12,68 @@ pub const NUM_PADS: usize = GRID_SIZE.pow(2);
 /// Total number of sample slots (pads × banks).
 pub const NUM_SAMPLES: usize = NUM_PADS * NUM_BANKS;
 
+/// Number of u64 words in a bitmask with one bit per sample slot.
+pub const SLOT_MASK_WORDS: usize = NUM_SAMPLES.div_ceil(64);
+
 /// Maximum number of voices that can be active simultaneously.
 pub const MAX_VOICES: usize = 32;
 
+/// Voice slots beyond `MAX_VOICES` where stolen and stopped voices finish their fade-out.
+pub const VOICE_FADE_SLOTS: usize = 8;
+
+/// Total voice slots: the playing pool plus the fade-out slots.
+pub const VOICE_SLOTS: usize = MAX_VOICES + VOICE_FADE_SLOTS;
+
+/// Number of pad choke groups.
+pub const NUM_CHOKE_GROUPS: usize = 8;
+
+/// Maximum number of output channels accepted from a device configuration.
+pub const MAX_OUTPUT_CHANNELS: u16 = 32;
+
+/// Minimum output sample rate accepted from a device configuration.
+pub const OUTPUT_SAMPLE_RATE_MIN_HZ: u32 = 8_000;
+
+/// Maximum output sample rate accepted from a device configuration.
+pub const OUTPUT_SAMPLE_RATE_MAX_HZ: u32 = 384_000;
+
+/// Sample rate preferred when the device default configuration is unusable.
+pub const OUTPUT_SAMPLE_RATE_FALLBACK_HZ: u32 = 48_000;
+
+/// Output callback buffer size requested from the device before falling back.
+pub const OUTPUT_BUFFER_FRAMES: u32 = 512;
+
+/// Frames rendered per conversion pass for devices without an f32 stream format.
+pub const OUTPUT_CONVERSION_SCRATCH_FRAMES: usize = 4096;
+
+/// Sample rates tried, in order, when no stream could be built at the selected rate.
+pub const OUTPUT_SAMPLE_RATE_ALTERNATIVES_HZ: [u32; 4] = [48_000, 44_100, 96_000, 88_200];
+
+/// Maximum number of source frames decoded for a single sample (30 minutes at 48 kHz).
+pub const MAX_SAMPLE_FRAMES: u64 = 48_000 * 60 * 30;
+
+/// Default capacity of the control and parameter message rings, see `AudioEngine.run()`.
+pub const CONTROL_BUFFER_CAPACITY_DEFAULT: usize = 1024;
+
+/// Largest accepted capacity of the control and parameter message rings.
+pub const CONTROL_BUFFER_CAPACITY_MAX: usize = 65_536;
+
 /// Maximum number of accepted absolute-frame scheduler events.
 pub const MAX_SCHEDULED_EVENTS: usize = 1024;
 
+/// Maximum number of frame-stamped control messages waiting for their target frame.
+///
+/// Further stamped messages wait in the control queue, holding up the messages behind them.
+pub const MAX_DEFERRED_CONTROL_MESSAGES: usize = 64;
+
+/// Parameter updates one automation recording holds; later updates are dropped.
+pub const AUTOMATION_CAPTURE_EVENTS: usize = 32_768;
+
+/// How far ahead of the audio clock automation replay sends its events.
+pub const AUTOMATION_LOOKAHEAD_MS: f64 = 100.0;
+
+/// Interval at which automation replay checks the audio clock.
+pub const AUTOMATION_FEED_INTERVAL_MS: u64 = 10;
+
 /// Minimum playback speed multiplier (50%).
 pub const SPEED_MIN: f32 = 0.5;
 
@@ -42,6 +98,69 @@ pub const PAD_GAIN_DB_DEFAULT: f32 = 0.0;
 /// Per-pad Gain/Trim smoothing time in milliseconds.
 pub const PAD_GAIN_SMOOTH_MS: f32 = 10.0;
 
+/// Ramp time in milliseconds for muting or unmuting a pad with a fade.
+pub const PAD_FADE_MUTE_MS: f32 = 30.0;
+
+/// Default length in milliseconds of the fade applied to voices stopped without a pad fade.
+pub const STOP_FADE_MS_DEFAULT: f32 = 5.0;
+
+/// Longest stop fade in output frames.
+pub const STOP_FADE_SAMPLES_MAX: u32 = 65_536;
+
+/// Ramp time in milliseconds for fading a cue-only voice into the master mix.
+pub const CUE_PROMOTE_MS: f32 = 10.0;
+
+/// Largest transposition in semitones for pads of a bank in keyboard mode.
+pub const KEYBOARD_SEMITONES_MAX: i32 = 12;
+
+/// Default time constant in milliseconds for gliding a voice to a new tempo ratio.
+pub const TEMPO_GLIDE_MS_DEFAULT: f32 = 50.0;
+
+/// Longest tempo glide time constant in milliseconds.
+pub const TEMPO_GLIDE_MS_MAX: f32 = 2_000.0;
+
+/// Largest per-output-channel calibration trim (linear gain, +6 dB).
+pub const OUTPUT_TRIM_MAX: f32 = 2.0;
+
+/// Longest per-pad fade-in or fade-out time in milliseconds.
+pub const PAD_FADE_MS_MAX: f32 = 10_000.0;
+
+/// Longest per-pad output delay in milliseconds; sets the size of the per-slot delay lines.
+pub const PAD_DELAY_MS_MAX: f32 = 100.0;
+
+/// Crossfade time in milliseconds between the old and new tap when a pad's delay changes.
+pub const PAD_DELAY_CROSSFADE_MS: f32 = 10.0;
+
+/// Widest window in milliseconds a start position may be snapped to an onset within.
+pub const TRANSIENT_SNAP_WINDOW_MS_MAX: f32 = 250.0;
+
+/// Most onsets kept per slot for transient snapping.
+pub const PAD_ONSETS_MAX: usize = 8_192;
+
+/// Default voice priority of a pad; a full voice pool only steals from lower priorities.
+pub const PAD_PRIORITY_DEFAULT: u8 = 128;
+
+/// Lowest per-pad soft-clip ceiling (linear, about -26 dBFS).
+pub const PAD_CLIP_CEILING_MIN: f32 = 0.05;
+
+/// Largest number of equal slices a pad can be chopped into for slice triggering.
+pub const PAD_SLICES_MAX: u16 = 64;
+
+/// Largest numerator or denominator accepted for a per-pad rational rate ratio.
+pub const PAD_RATE_RATIO_TERM_MAX: u16 = 16;
+
+/// Largest swing amount in percent; delays off-beat grid positions by half a step.
+pub const SWING_PERCENT_MAX: f32 = 100.0;
+
+/// Number of step sequencer tracks.
+pub const SEQUENCER_TRACKS: usize = 8;
+
+/// Step sequencer resolution: one step per 1/16 note.
+pub const SEQUENCER_STEPS_PER_BAR: usize = 16;
+
+/// Longest step sequence: four bars of 1/16 notes.
+pub const SEQUENCER_STEPS_MAX: usize = SEQUENCER_STEPS_PER_BAR * 4;
+
 /// Minimum per-band EQ gain in dB.
 ///
 /// This represents a DJ-style "Kill" position, and is mapped to a linear gain of 0.0.
@@ -49,3 +168,40 @@ pub const PAD_EQ_DB_MIN: f32 = -60.0;
 
 /// Maximum per-band EQ gain in dB.
 pub const PAD_EQ_DB_MAX: f32 = 6.0;
+
+/// Number of per-pad EQ bands (low, mid, high).
+pub const PAD_EQ_BANDS: usize = 3;
+
+/// Longest interval between parameter updates while an EQ automation or snapshot morph runs,
+/// in frames.
+///
+/// About 2.7 ms at 48 kHz. The per-frame DSP parameter smoothing slews between updates, so
+/// ramps do not zipper.
+pub const PAD_EQ_AUTOMATION_UPDATE_FRAMES: usize = 128;
+
+/// Number of stored mixer parameter snapshots (A and B).
+pub const MIXER_SNAPSHOT_SLOTS: usize = 2;
+
+/// Longest comb delay accepted for mono-to-stereo widening, in milliseconds.
+pub const MONO_WIDENING_MS_MAX: f32 = 30.0;
+
+/// Gain of the delayed copy added to the left and subtracted from the right channel when
+/// widening mono sources.
+pub const MONO_WIDENING_COMB_GAIN: f32 = 0.5;
+
+/// Default fixed silence-trim threshold in dBFS.
+pub const SILENCE_TRIM_DB_DEFAULT: f32 = -60.0;
+
+/// Lowest fixed silence-trim threshold in dBFS.
+pub const SILENCE_TRIM_DB_MIN: f32 = -120.0;
+
+/// Capabilities compiled into this build, as reported by `AudioEngine.build_info()`.
+///
+/// None of these are optional cargo features yet, so every build reports all of them.
+pub const BUILD_FEATURES: &[&str] = &["analysis", "key_lock", "simd", "stems", "stretch"];
+
+/// Busy-wait iterations a control call spends on a contended producer lock before yielding.
+pub const PRODUCER_LOCK_SPINS: u32 = 64;
+
+/// Thread yields a control call allows after spinning before it gives up with `QueueBusy`.
+pub const PRODUCER_LOCK_YIELDS: u32 = 16;
@@ -0,0 +1,257 @@
+//! Control messages stamped with a target output frame.
+//!
+//! The control side precedes a message with [`ControlMessage::AtFrame`]. The callback moves
+//! stamped messages into a fixed-capacity queue and splits its render at their target frames,
+//! so they apply on the exact frame instead of at the next buffer boundary.
+
+use crate::audio_engine::constants::MAX_DEFERRED_CONTROL_MESSAGES;
+use crate::messages::ControlMessage;
+use rtrb::{Producer, PushError};
+use std::thread::{self, ThreadId};
+
+pub(crate) type DeferredControlQueue = DeferredControlMessages<MAX_DEFERRED_CONTROL_MESSAGES>;
+
+/// Control-thread end of the control queue.
+///
+/// [`Self::stamp_next_message`] makes the next message pushed from the same thread apply at a
+/// target frame. Other threads sharing the producer (sample loading, input mapping) are never
+/// stamped.
+pub(crate) struct ControlProducer {
+    producer: Producer<ControlMessage>,
+    stamp: Option<(ThreadId, u64)>,
+}
+
+impl ControlProducer {
+    pub(crate) fn new(producer: Producer<ControlMessage>) -> Self {
+        Self {
+            producer,
+            stamp: None,
+        }
+    }
+
+    pub(crate) fn stamp_next_message(&mut self, target_frame: u64) {
+        self.stamp = Some((thread::current().id(), target_frame));
+    }
+
+    pub(crate) fn slots(&self) -> usize {
+        self.producer.slots()
+    }
+
+    /// Whether the callback has not taken every pushed message off the queue yet.
+    pub(crate) fn has_waiting_messages(&self) -> bool {
+        self.producer.slots() < self.producer.buffer().capacity()
+    }
+
+    /// Pushes `message` without a frame stamp; a stamp this thread set stays for the next one.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn push_unstamped(
+        &mut self,
+        message: ControlMessage,
+    ) -> Result<(), PushError<ControlMessage>> {
+        self.producer.push(message)
+    }
+
+    /// Pushes `message`, preceded by its frame stamp if this thread set one.
+    ///
+    /// A stamped message needs two free slots; the stamp is used up either way.
+    // Hands the message back like `Producer::push` instead of boxing it.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn push(
+        &mut self,
+        message: ControlMessage,
+    ) -> Result<(), PushError<ControlMessage>> {
+        let thread_id = thread::current().id();
+        let Some((_, target_frame)) = self.stamp.take_if(|(owner, _)| *owner == thread_id) else {
+            return self.producer.push(message);
+        };
+
+        if self.producer.slots() < 2 {
+            return Err(PushError::Full(message));
+        }
+        if let Err(PushError::Full(_)) = self.producer.push(ControlMessage::AtFrame(target_frame)) {
+            return Err(PushError::Full(message));
+        }
+        self.producer.push(message)
+    }
+}
+
+struct DeferredControlMessage {
+    target_frame: u64,
+    message: ControlMessage,
+}
+
+/// Stamped control messages ordered by target frame, then arrival.
+pub(crate) struct DeferredControlMessages<const CAPACITY: usize> {
+    messages: [Option<DeferredControlMessage>; CAPACITY],
+    len: usize,
+    /// Stamp read from the queue whose message has not arrived or found room yet.
+    pending_stamp: Option<u64>,
+}
+
+impl<const CAPACITY: usize> DeferredControlMessages<CAPACITY> {
+    pub(crate) fn new() -> Self {
+        Self {
+            messages: std::array::from_fn(|_| None),
+            len: 0,
+            pending_stamp: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.len == CAPACITY
+    }
+
+    pub(crate) fn set_pending_stamp(&mut self, target_frame: u64) {
+        self.pending_stamp = Some(target_frame);
+    }
+
+    pub(crate) fn has_pending_stamp(&self) -> bool {
+        self.pending_stamp.is_some()
+    }
+
+    /// Defers `message` to the pending stamp, or hands it back if there is no stamp or room.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn defer_stamped(&mut self, message: ControlMessage) -> Result<(), ControlMessage> {
+        let Some(target_frame) = self.pending_stamp else {
+            return Err(message);
+        };
+        if self.is_full() {
+            return Err(message);
+        }
+
+        let insert_at = self.messages[..self.len]
+            .iter()
+            .position(|deferred| {
+                deferred
+                    .as_ref()
+                    .is_some_and(|deferred| deferred.target_frame > target_frame)
+            })
+            .unwrap_or(self.len);
+        self.messages[insert_at..=self.len].rotate_right(1);
+        self.messages[insert_at] = Some(DeferredControlMessage {
+            target_frame,
+            message,
+        });
+        self.len += 1;
+        self.pending_stamp = None;
+        Ok(())
+    }
+
+    pub(crate) fn peek_next_target_frame(&self) -> Option<u64> {
+        self.messages[0]
+            .as_ref()
+            .map(|deferred| deferred.target_frame)
+    }
+
+    /// The next message if it is due at `frame`.
+    pub(crate) fn peek_due(&self, frame: u64) -> Option<&ControlMessage> {
+        self.messages[0]
+            .as_ref()
+            .filter(|deferred| deferred.target_frame <= frame)
+            .map(|deferred| &deferred.message)
+    }
+
+    pub(crate) fn pop_due(&mut self, frame: u64) -> Option<ControlMessage> {
+        self.peek_due(frame)?;
+
+        let deferred = self.messages[0].take()?;
+        self.messages[..self.len].rotate_left(1);
+        self.len -= 1;
+        Some(deferred.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rtrb::RingBuffer;
+
+    fn stop(id: usize) -> ControlMessage {
+        ControlMessage::StopSample { id }
+    }
+
+    fn stopped_id(message: Option<ControlMessage>) -> Option<usize> {
+        match message {
+            Some(ControlMessage::StopSample { id }) => Some(id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn deferred_messages_pop_in_target_frame_then_arrival_order() {
+        let mut queue = DeferredControlMessages::<4>::new();
+        assert!(queue.defer_stamped(stop(9)).is_err());
+
+        for (frame, id) in [(300, 0), (100, 1), (300, 2), (200, 3)] {
+            queue.set_pending_stamp(frame);
+            assert!(queue.defer_stamped(stop(id)).is_ok());
+        }
+        queue.set_pending_stamp(50);
+        assert!(queue.is_full());
+        assert!(queue.defer_stamped(stop(4)).is_err());
+        assert!(queue.has_pending_stamp());
+
+        assert_eq!(queue.peek_next_target_frame(), Some(100));
+        assert!(queue.peek_due(99).is_none());
+        assert_eq!(stopped_id(queue.pop_due(99)), None);
+        assert_eq!(stopped_id(queue.pop_due(100)), Some(1));
+        assert_eq!(stopped_id(queue.pop_due(1_000)), Some(3));
+        assert_eq!(stopped_id(queue.pop_due(1_000)), Some(0));
+        assert_eq!(stopped_id(queue.pop_due(1_000)), Some(2));
+        assert_eq!(queue.len(), 0);
+        assert_eq!(queue.peek_next_target_frame(), None);
+    }
+
+    #[test]
+    fn control_producer_stamps_only_the_next_message_of_the_stamping_thread() {
+        let (producer, mut consumer) = RingBuffer::new(8);
+        let mut producer = ControlProducer::new(producer);
+
+        producer.stamp_next_message(4_800);
+        let other_thread = thread::scope(|scope| {
+            scope
+                .spawn(|| producer.push(stop(7)).is_ok())
+                .join()
+                .unwrap()
+        });
+        assert!(other_thread);
+        producer.push(stop(1)).unwrap();
+        producer.push(stop(2)).unwrap();
+
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::StopSample { id: 7 })
+        ));
+        assert!(matches!(consumer.pop(), Ok(ControlMessage::AtFrame(4_800))));
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::StopSample { id: 1 })
+        ));
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::StopSample { id: 2 })
+        ));
+    }
+
+    #[test]
+    fn control_producer_rejects_stamped_message_without_room_for_both() {
+        let (producer, mut consumer) = RingBuffer::new(1);
+        let mut producer = ControlProducer::new(producer);
+
+        producer.stamp_next_message(100);
+        assert!(producer.push(stop(1)).is_err());
+        assert!(consumer.pop().is_err());
+
+        // The failed push used up the stamp.
+        producer.push(stop(2)).unwrap();
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::StopSample { id: 2 })
+        ));
+    }
+}
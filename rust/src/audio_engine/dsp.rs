@@ -17,7 +17,19 @@ const DSP_MAX_CHANNELS: usize = 8;
 const ISOLATOR_LOW_CROSSOVER_HZ: f32 = 250.0;
 const ISOLATOR_HIGH_CROSSOVER_HZ: f32 = 4_000.0;
 const ISOLATOR_BOOST_DB_MAX: f32 = 6.0;
+/// Frames to crossfade between the equal-gain bypass and the band sum, whose phase differs.
+const ISOLATOR_BYPASS_CROSSFADE_FRAMES: f32 = 256.0;
 const BUTTERWORTH_Q: f32 = 0.70710677;
+/// Lowest Butterworth corner as a fraction of the sample rate. Below this, `1 + a1 + a2` falls
+/// under f32 resolution and rounds the poles onto the unit circle.
+const BUTTERWORTH_MIN_NORMALIZED_FREQ: f32 = 5.0e-4;
+const MASTER_DC_BLOCKER_CUTOFF_HZ: f32 = 5.0;
+const MASTER_LIMITER_CEILING_DB: f32 = -0.3;
+const MASTER_LIMITER_RELEASE_MS: f32 = 80.0;
+/// Distance from unity below which a releasing limiter gain snaps back to exactly 1.0.
+const MASTER_LIMITER_GAIN_SNAP: f32 = 1.0e-4;
+/// Fall rate of the recent-peak gain reduction meter.
+const LIMITER_METER_PEAK_DECAY_DB_PER_S: f32 = 20.0;
 
 pub(crate) const DSP_PARAMETER_SLOTS: usize = 4;
 pub(crate) const NORMALIZED_PARAMETER_MIN: f32 = 0.0;
@@ -168,7 +180,7 @@ impl BiquadCoeffs {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 struct BiquadState {
     z1: f32,
     z2: f32,
@@ -183,7 +195,7 @@ impl BiquadState {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 struct IsolatorChannelState {
     low_split_lp: [BiquadState; 2],
     low_split_hp: [BiquadState; 2],
@@ -203,6 +215,8 @@ struct DjIsolatorNode {
     low_gain: f32,
     mid_gain: f32,
     high_gain: f32,
+    /// 1.0 renders the equal-gain bypass, 0.0 the band sum.
+    bypass_mix: f32,
 }
 
 impl DjIsolatorNode {
@@ -216,6 +230,7 @@ impl DjIsolatorNode {
             low_gain: 1.0,
             mid_gain: 1.0,
             high_gain: 1.0,
+            bypass_mix: 1.0,
         };
         node.prepare(sample_rate_hz);
         node
@@ -239,6 +254,17 @@ impl DjIsolatorNode {
         self.low_gain = normalized_isolator_gain(low);
         self.mid_gain = normalized_isolator_gain(mid);
         self.high_gain = normalized_isolator_gain(high);
+
+        let step = 1.0 / ISOLATOR_BYPASS_CROSSFADE_FRAMES;
+        self.bypass_mix = if self.gains_are_equal() {
+            (self.bypass_mix + step).min(1.0)
+        } else {
+            (self.bypass_mix - step).max(0.0)
+        };
+    }
+
+    fn gains_are_equal(&self) -> bool {
+        gains_are_equal(self.low_gain, self.mid_gain, self.high_gain)
     }
 
     fn process_sample(&mut self, channel: usize, x: f32) -> f32 {
@@ -260,10 +286,13 @@ impl DjIsolatorNode {
         let low = low_aligned_lp + low_aligned_hp;
 
         // Equal gains should behave as one scalar gain and keep neutral rendering sample-exact.
-        let y = if gains_are_equal(self.low_gain, self.mid_gain, self.high_gain) {
-            x * self.low_gain
+        // The band sum is phase-shifted against the input, so switching crossfades.
+        let bypass = x * self.mid_gain;
+        let y = if self.bypass_mix >= 1.0 {
+            bypass
         } else {
-            low * self.low_gain + mid * self.mid_gain + high * self.high_gain
+            let bands = low * self.low_gain + mid * self.mid_gain + high * self.high_gain;
+            bands + (bypass - bands) * self.bypass_mix
         };
 
         if y.is_finite() { y } else { 0.0 }
@@ -271,6 +300,7 @@ impl DjIsolatorNode {
 
     fn reset(&mut self) {
         self.states = [IsolatorChannelState::default(); DSP_MAX_CHANNELS];
+        self.bypass_mix = if self.gains_are_equal() { 1.0 } else { 0.0 };
     }
 }
 
@@ -391,6 +421,14 @@ impl PerPadDspChain {
         self.isolator_node.reset();
     }
 
+    #[cfg(test)]
+    pub(crate) fn filter_state_is_clear(&self) -> bool {
+        self.isolator_node
+            .states
+            .iter()
+            .all(|state| *state == IsolatorChannelState::default())
+    }
+
     #[cfg(test)]
     pub(crate) fn parameter(&self, slot: DspParameterSlot) -> SmoothedNormalizedValue {
         self.parameters[slot.index()]
@@ -402,6 +440,145 @@ impl PerPadDspChain {
     }
 }
 
+/// One-pole DC blocker applied to the interleaved master output.
+///
+/// Channels beyond `DSP_MAX_CHANNELS` pass through unfiltered.
+#[derive(Debug, Clone)]
+pub(crate) struct MasterDcBlocker {
+    coeffs: BiquadCoeffs,
+    states: [BiquadState; DSP_MAX_CHANNELS],
+}
+
+impl MasterDcBlocker {
+    pub(crate) fn new(sample_rate_hz: f32) -> Self {
+        Self {
+            coeffs: biquad_one_pole_high_pass(
+                sanitize_sample_rate(sample_rate_hz),
+                MASTER_DC_BLOCKER_CUTOFF_HZ,
+            ),
+            states: [BiquadState::default(); DSP_MAX_CHANNELS],
+        }
+    }
+
+    pub(crate) fn process_interleaved_block(&mut self, buffer: &mut [f32], channels: usize) {
+        if channels == 0 {
+            return;
+        }
+
+        for frame in buffer.chunks_exact_mut(channels) {
+            for (sample, state) in frame.iter_mut().zip(self.states.iter_mut()) {
+                *sample = state.process(self.coeffs, *sample);
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.states = [BiquadState::default(); DSP_MAX_CHANNELS];
+    }
+}
+
+/// Peak limiter on the interleaved master output.
+///
+/// The gain drops to the ceiling instantly and recovers with an exponential release, so the
+/// output never overshoots and the limiter adds no latency. Gain reduction is reported in
+/// positive dB and is exactly 0.0 while the limiter is idle.
+#[derive(Debug, Clone)]
+pub(crate) struct MasterLimiter {
+    ceiling: f32,
+    release_coeff: f32,
+    peak_decay_db_per_frame: f32,
+    gain: f32,
+    reduction_db: f32,
+    peak_reduction_db: f32,
+}
+
+impl MasterLimiter {
+    pub(crate) fn new(sample_rate_hz: f32) -> Self {
+        let sample_rate_hz = sanitize_sample_rate(sample_rate_hz);
+        let release_frames = MASTER_LIMITER_RELEASE_MS * sample_rate_hz / 1000.0;
+        Self {
+            ceiling: 10.0_f32.powf(MASTER_LIMITER_CEILING_DB / 20.0),
+            release_coeff: (-1.0 / release_frames).exp(),
+            peak_decay_db_per_frame: LIMITER_METER_PEAK_DECAY_DB_PER_S / sample_rate_hz,
+            gain: 1.0,
+            reduction_db: 0.0,
+            peak_reduction_db: 0.0,
+        }
+    }
+
+    pub(crate) fn process_interleaved_block(&mut self, buffer: &mut [f32], channels: usize) {
+        if channels == 0 {
+            return;
+        }
+
+        let mut min_gain = 1.0_f32;
+        let mut frames = 0;
+        for frame in buffer.chunks_exact_mut(channels) {
+            let peak = frame
+                .iter()
+                .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+            let target = if peak > self.ceiling {
+                self.ceiling / peak
+            } else {
+                1.0
+            };
+
+            if target < self.gain {
+                self.gain = target;
+            } else {
+                self.gain = target + (self.gain - target) * self.release_coeff;
+                if target == 1.0 && 1.0 - self.gain < MASTER_LIMITER_GAIN_SNAP {
+                    self.gain = 1.0;
+                }
+            }
+
+            if self.gain < 1.0 {
+                for sample in frame.iter_mut() {
+                    *sample *= self.gain;
+                }
+            }
+            min_gain = min_gain.min(self.gain);
+            frames += 1;
+        }
+
+        self.reduction_db = gain_reduction_db(self.gain);
+        let decayed = self.peak_reduction_db - self.peak_decay_db_per_frame * frames as f32;
+        self.peak_reduction_db = gain_reduction_db(min_gain).max(decayed);
+        if self.peak_reduction_db <= 0.0 {
+            self.peak_reduction_db = 0.0;
+        }
+    }
+
+    /// Gain reduction in dB at the end of the last block.
+    pub(crate) fn reduction_db(&self) -> f32 {
+        self.reduction_db
+    }
+
+    /// Highest recent gain reduction in dB, falling back to zero once the limiter is idle.
+    pub(crate) fn peak_reduction_db(&self) -> f32 {
+        self.peak_reduction_db
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.gain = 1.0;
+        self.reduction_db = 0.0;
+        self.peak_reduction_db = 0.0;
+    }
+}
+
+fn gain_reduction_db(gain: f32) -> f32 {
+    if gain >= 1.0 {
+        0.0
+    } else {
+        -20.0 * gain.log10()
+    }
+}
+
+/// Saturates `sample` smoothly towards `±ceiling`; near-linear well below the ceiling.
+pub(crate) fn soft_clip(sample: f32, ceiling: f32) -> f32 {
+    ceiling * (sample / ceiling).tanh()
+}
+
 fn normalized_isolator_gain(normalized: f32) -> f32 {
     let normalized = sanitize_normalized(normalized, DEFAULT_NORMALIZED_VALUE);
     if normalized <= NORMALIZED_PARAMETER_MIN {
@@ -417,7 +594,7 @@ fn normalized_isolator_gain(normalized: f32) -> f32 {
 }
 
 fn biquad_low_pass_butterworth(fs_hz: f32, freq_hz: f32) -> BiquadCoeffs {
-    let freq_hz = clamp_freq_hz(fs_hz, freq_hz);
+    let freq_hz = clamp_butterworth_freq_hz(fs_hz, freq_hz);
     let w0 = 2.0 * PI * freq_hz / fs_hz;
     let cos_w0 = w0.cos();
     let sin_w0 = w0.sin();
@@ -434,7 +611,7 @@ fn biquad_low_pass_butterworth(fs_hz: f32, freq_hz: f32) -> BiquadCoeffs {
 }
 
 fn biquad_high_pass_butterworth(fs_hz: f32, freq_hz: f32) -> BiquadCoeffs {
-    let freq_hz = clamp_freq_hz(fs_hz, freq_hz);
+    let freq_hz = clamp_butterworth_freq_hz(fs_hz, freq_hz);
     let w0 = 2.0 * PI * freq_hz / fs_hz;
     let cos_w0 = w0.cos();
     let sin_w0 = w0.sin();
@@ -450,14 +627,33 @@ fn biquad_high_pass_butterworth(fs_hz: f32, freq_hz: f32) -> BiquadCoeffs {
     normalize_biquad(b0, b1, b2, a0, a1, a2)
 }
 
+/// First-order high-pass with unity gain at Nyquist, expressed as biquad coefficients.
+fn biquad_one_pole_high_pass(fs_hz: f32, freq_hz: f32) -> BiquadCoeffs {
+    let freq_hz = clamp_freq_hz(fs_hz, freq_hz);
+    let pole = (-2.0 * PI * freq_hz / fs_hz).exp();
+    let gain = (1.0 + pole) * 0.5;
+
+    normalize_biquad(gain, -gain, 0.0, 1.0, -pole, 0.0)
+}
+
 fn clamp_freq_hz(fs_hz: f32, freq_hz: f32) -> f32 {
     if !fs_hz.is_finite() || fs_hz <= 0.0 {
         return freq_hz.max(1.0);
     }
 
-    let nyquist = fs_hz * 0.5;
-    let max_hz = (nyquist * 0.9).max(1.0);
-    freq_hz.clamp(1.0, max_hz)
+    // The upper bound must stay below Nyquist even at very low sample rates, otherwise the
+    // poles land on the unit circle.
+    let max_hz = fs_hz * 0.5 * 0.9;
+    let min_hz = max_hz.min(1.0);
+    if freq_hz.is_finite() {
+        freq_hz.clamp(min_hz, max_hz)
+    } else {
+        max_hz
+    }
+}
+
+fn clamp_butterworth_freq_hz(fs_hz: f32, freq_hz: f32) -> f32 {
+    clamp_freq_hz(fs_hz, freq_hz).max(fs_hz * BUTTERWORTH_MIN_NORMALIZED_FREQ)
 }
 
 fn normalize_biquad(b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) -> BiquadCoeffs {
@@ -609,6 +805,41 @@ mod tests {
             .collect()
     }
 
+    const SWEEP_SAMPLE_RATES_HZ: [f32; 8] = [
+        100.0, 8_000.0, 22_050.0, 44_100.0, 48_000.0, 96_000.0, 192_000.0, 384_000.0,
+    ];
+    const SWEEP_FREQUENCIES_HZ: [f32; 9] = [
+        0.0,
+        1.0,
+        5.0,
+        20.0,
+        250.0,
+        4_000.0,
+        20_000.0,
+        200_000.0,
+        f32::INFINITY,
+    ];
+
+    fn assert_biquad_is_stable(coeffs: BiquadCoeffs, context: &str) {
+        // A real-coefficient biquad has both poles inside the unit circle exactly when the
+        // denominator 1 + a1 z^-1 + a2 z^-2 lies inside the stability triangle.
+        let a1 = f64::from(coeffs.a1);
+        let a2 = f64::from(coeffs.a2);
+        assert!(a2.abs() < 1.0, "{context}: a2 = {a2}");
+        assert!(a1.abs() < 1.0 + a2, "{context}: a1 = {a1}, a2 = {a2}");
+
+        let mut state = BiquadState::default();
+        let mut tail_peak = 0.0_f32;
+        for frame in 0..16_384 {
+            let y = state.process(coeffs, if frame == 0 { 1.0 } else { 0.0 });
+            assert!(y.is_finite(), "{context}: non-finite impulse response");
+            if frame >= 8_192 {
+                tail_peak = tail_peak.max(y.abs());
+            }
+        }
+        assert!(tail_peak < 1.0, "{context}: impulse tail peak {tail_peak}");
+    }
+
     fn synthetic_music_like_peak_fixture() -> Vec<f32> {
         const SAMPLE_RATE_HZ: f32 = 48_000.0;
         const FRAMES: usize = 65_536;
@@ -696,6 +927,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn biquad_designs_are_stable_across_sample_rates_and_frequencies() {
+        for fs_hz in SWEEP_SAMPLE_RATES_HZ {
+            for freq_hz in SWEEP_FREQUENCIES_HZ {
+                let designs = [
+                    ("low-pass", biquad_low_pass_butterworth(fs_hz, freq_hz)),
+                    ("high-pass", biquad_high_pass_butterworth(fs_hz, freq_hz)),
+                    (
+                        "one-pole high-pass",
+                        biquad_one_pole_high_pass(fs_hz, freq_hz),
+                    ),
+                ];
+                for (name, coeffs) in designs {
+                    assert_biquad_is_stable(coeffs, &format!("{name} {freq_hz} Hz @ {fs_hz} Hz"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn isolator_impulse_response_is_finite_across_gains_and_sample_rates() {
+        let normalized_targets = [0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0];
+
+        for fs_hz in SWEEP_SAMPLE_RATES_HZ {
+            for low in normalized_targets {
+                for high in normalized_targets {
+                    let mid = 1.0 - low;
+                    let mut chain = PerPadDspChain::new(0, fs_hz, 1, 1);
+                    set_and_snap_parameter(&mut chain, DspParameterSlot::Slot0, low);
+                    set_and_snap_parameter(&mut chain, DspParameterSlot::Slot1, mid);
+                    set_and_snap_parameter(&mut chain, DspParameterSlot::Slot2, high);
+
+                    let mut tail_peak = 0.0_f32;
+                    for frame in 0..8_192 {
+                        chain.begin_frame();
+                        let y = chain.process_sample(0, if frame == 0 { 1.0 } else { 0.0 });
+                        assert!(y.is_finite());
+                        if frame >= 4_096 {
+                            tail_peak = tail_peak.max(y.abs());
+                        }
+                    }
+                    assert!(
+                        tail_peak < 1.0,
+                        "low {low}, mid {mid}, high {high} @ {fs_hz} Hz: tail peak {tail_peak}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn master_dc_blocker_removes_offset_and_passes_audio_band() {
+        let sample_rate_hz = 48_000.0;
+        let frames = 48_000;
+        let mut blocker = MasterDcBlocker::new(sample_rate_hz);
+        let mut buffer: Vec<f32> = (0..frames)
+            .flat_map(|frame| {
+                let tone = (frame as f32 * 1_000.0 * std::f32::consts::TAU / sample_rate_hz).sin();
+                [0.25 + 0.5 * tone, -0.25]
+            })
+            .collect();
+
+        blocker.process_interleaved_block(&mut buffer, 2);
+
+        let settled = &buffer[buffer.len() / 2..];
+        let left: Vec<f32> = settled.iter().step_by(2).copied().collect();
+        let right: Vec<f32> = settled.iter().skip(1).step_by(2).copied().collect();
+        let left_mean = left.iter().sum::<f32>() / left.len() as f32;
+
+        assert!(left_mean.abs() < 1e-3);
+        assert!(right.iter().all(|sample| sample.abs() < 1e-3));
+        assert!((rms(&left, 0) - 0.5 / 2.0_f32.sqrt()).abs() < 5e-3);
+    }
+
+    #[test]
+    fn soft_clip_stays_below_ceiling_and_is_odd_and_monotonic() {
+        let inputs: Vec<f32> = (-200..=200).map(|step| step as f32 * 0.01).collect();
+        let outputs: Vec<f32> = inputs.iter().map(|&x| soft_clip(x, 0.5)).collect();
+
+        assert!(outputs.iter().all(|y| y.abs() < 0.5));
+        assert!(outputs.windows(2).all(|pair| pair[1] > pair[0]));
+        assert_eq!(soft_clip(-0.3, 0.5), -soft_clip(0.3, 0.5));
+        assert!((soft_clip(0.01, 0.5) - 0.01).abs() < 1e-5);
+    }
+
     #[test]
     fn per_pad_chain_prepares_resets_and_rejects_wrong_parameter_identity() {
         let mut chain = PerPadDspChain::new(2, f32::NAN, 0, 0);
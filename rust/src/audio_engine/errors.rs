@@ -33,6 +33,13 @@ pub enum SampleLoadError {
     #[error("audio file is missing channel information")]
     MissingChannels,
 
+    /// Decoded audio exceeds the maximum sample length.
+    #[error("audio file exceeds the maximum length of {max_frames} frames")]
+    TooLarge {
+        /// Maximum number of source frames accepted per sample.
+        max_frames: u64,
+    },
+
     /// No decodable audio frames were found.
     #[error("audio file contains no decodable audio frames")]
     NoDecodedFrames,
@@ -66,3 +73,56 @@ pub enum SampleLoadError {
         output_channels: usize,
     },
 }
+
+/// Errors that can occur while selecting an output device configuration.
+#[derive(Debug, Error)]
+pub enum DeviceError {
+    /// Neither the default nor any supported configuration is usable.
+    #[error(
+        "output device offers no usable configuration (default: {default}; supported: {supported})"
+    )]
+    NoUsableConfig {
+        /// Description of the device default configuration.
+        default: String,
+        /// Description of all supported f32 configurations.
+        supported: String,
+    },
+
+    /// The requested output device index is not in `host.output_devices()`.
+    #[error("output device index {index} out of range ({count} output devices available)")]
+    DeviceIndexOutOfRange {
+        /// Requested device index.
+        index: usize,
+        /// Number of output devices the host reported.
+        count: usize,
+    },
+
+    /// No device in `host.output_devices()` has the requested name.
+    #[error("no output device named {name:?}; available: {available}")]
+    DeviceNameNotFound {
+        /// Requested device name.
+        name: String,
+        /// Comma-separated names of the output devices the host reported.
+        available: String,
+    },
+
+    /// The device rejected the buffer size requested for the stream.
+    #[error(
+        "output device rejected a buffer of {frames} frames (supported: {supported}): {reason}"
+    )]
+    BufferSizeRejected {
+        /// Requested callback buffer size in frames.
+        frames: u32,
+        /// Description of the buffer sizes the device reports.
+        supported: String,
+        /// Error the stream build failed with.
+        reason: String,
+    },
+
+    /// Every stream configuration in the negotiation ladder was rejected.
+    #[error("failed to build an output stream; tried: {attempts}")]
+    StreamBuildFailed {
+        /// Each attempted configuration with the error it failed with.
+        attempts: String,
+    },
+}
@@ -1,7 +1,7 @@
 use crate::audio_engine::constants::NUM_SAMPLES;
+use crate::audio_engine::deferred_control::ControlProducer;
 use crate::messages::ControlMessage;
 use midir::{Ignore, MidiInput, MidiInputConnection};
-use rtrb::Producer;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, RecvTimeoutError, SyncSender, sync_channel};
 use std::sync::{Arc, Mutex};
@@ -124,7 +124,7 @@ pub(crate) struct InputRuntime {
 }
 
 impl InputRuntime {
-    pub fn new(audio_producer: Arc<Mutex<Producer<ControlMessage>>>) -> Self {
+    pub fn new(audio_producer: Arc<Mutex<ControlProducer>>) -> Self {
         let origin = Instant::now();
         let enabled = Arc::new(AtomicBool::new(false));
         let learn_capture_active = Arc::new(AtomicBool::new(false));
@@ -322,7 +322,7 @@ fn input_dispatch_loop(
     event_tx: SyncSender<InputRuntimeEvent>,
     mappings: Arc<Mutex<Vec<InputMapping>>>,
     runtime_state: Arc<Mutex<RuntimeState>>,
-    audio_producer: Arc<Mutex<Producer<ControlMessage>>>,
+    audio_producer: Arc<Mutex<ControlProducer>>,
     enabled: Arc<AtomicBool>,
     learn_capture_active: Arc<AtomicBool>,
     running: Arc<AtomicBool>,
@@ -385,7 +385,7 @@ struct DispatchResult {
 fn dispatch_action(
     action: &InputAction,
     runtime_state: &Arc<Mutex<RuntimeState>>,
-    audio_producer: &Arc<Mutex<Producer<ControlMessage>>>,
+    audio_producer: &Arc<Mutex<ControlProducer>>,
 ) -> DispatchResult {
     match action {
         InputAction::TriggerPad { id } => dispatch_trigger_pad(*id, runtime_state, audio_producer),
@@ -408,7 +408,7 @@ fn dispatch_action(
 fn dispatch_trigger_pad(
     id: usize,
     runtime_state: &Arc<Mutex<RuntimeState>>,
-    audio_producer: &Arc<Mutex<Producer<ControlMessage>>>,
+    audio_producer: &Arc<Mutex<ControlProducer>>,
 ) -> DispatchResult {
     let Ok(state) = runtime_state.lock() else {
         return DispatchResult {
@@ -445,7 +445,7 @@ fn dispatch_trigger_pad(
 }
 
 fn dispatch_audio_messages<const N: usize>(
-    audio_producer: &Arc<Mutex<Producer<ControlMessage>>>,
+    audio_producer: &Arc<Mutex<ControlProducer>>,
     messages: [ControlMessage; N],
 ) -> DispatchResult {
     let Ok(mut producer) = audio_producer.try_lock() else {
@@ -814,7 +814,7 @@ mod tests {
     #[test]
     fn trigger_pad_dispatch_sends_loop_region_and_exclusive_play() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let state = Arc::new(Mutex::new(RuntimeState::default()));
         {
             let mut guard = state.lock().unwrap();
@@ -852,7 +852,7 @@ mod tests {
     #[test]
     fn trigger_pad_dispatch_uses_multiloop_play_when_enabled() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let state = Arc::new(Mutex::new(RuntimeState::default()));
         {
             let mut guard = state.lock().unwrap();
@@ -876,7 +876,7 @@ mod tests {
     #[test]
     fn trigger_pad_dispatch_rejects_partial_loop_and_play_sequence() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(1);
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let state = Arc::new(Mutex::new(RuntimeState::default()));
         {
             let mut guard = state.lock().unwrap();
@@ -899,7 +899,7 @@ mod tests {
     #[test]
     fn trigger_pad_dispatch_rejects_unloaded_pad_without_message() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let state = Arc::new(Mutex::new(RuntimeState::default()));
 
         let result = dispatch_trigger_pad(1, &state, &producer);
@@ -917,7 +917,7 @@ mod tests {
     #[test]
     fn learn_capture_suppresses_direct_dispatch_and_mapping_action() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let runtime = InputRuntime::new(Arc::new(Mutex::new(producer)));
+        let runtime = InputRuntime::new(Arc::new(Mutex::new(ControlProducer::new(producer))));
         runtime.set_enabled(true);
         runtime.replace_mappings(vec![(
             "midi:note:1:60".to_string(),
@@ -947,7 +947,7 @@ mod tests {
     #[test]
     fn failed_direct_dispatch_event_keeps_action_for_python_fallback() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let runtime = InputRuntime::new(Arc::new(Mutex::new(producer)));
+        let runtime = InputRuntime::new(Arc::new(Mutex::new(ControlProducer::new(producer))));
         runtime.set_enabled(true);
         runtime.replace_mappings(vec![(
             "midi:note:1:60".to_string(),
@@ -976,7 +976,7 @@ mod tests {
     #[test]
     fn future_dsp_parameter_actions_do_not_use_direct_dispatch() {
         let (producer, mut consumer) = RingBuffer::<ControlMessage>::new(8);
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let state = Arc::new(Mutex::new(RuntimeState::default()));
         let action = parse_input_action("dsp.pad.parameter.delta:0:filter.cutoff");
 
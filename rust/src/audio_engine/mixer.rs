@@ -10,22 +10,36 @@
 use crate::audio_engine::buffer_retirement::AudioBufferRetirement;
 #[cfg(test)]
 use crate::audio_engine::buffer_retirement::ImmediateAudioBufferRetirement;
+use crate::audio_engine::channels::mix_channels;
 use crate::audio_engine::constants::{
-    MAX_VOICES, NUM_SAMPLES, PAD_EQ_DB_MAX, PAD_EQ_DB_MIN, PAD_GAIN_DB_DEFAULT, PAD_GAIN_DB_MAX,
-    PAD_GAIN_DB_MIN, PAD_GAIN_SMOOTH_MS, SPEED_MAX, SPEED_MIN, VOLUME_MAX, VOLUME_MIN,
+    CUE_PROMOTE_MS, KEYBOARD_SEMITONES_MAX, MAX_OUTPUT_CHANNELS, MAX_VOICES, MIXER_SNAPSHOT_SLOTS,
+    NUM_BANKS, NUM_CHOKE_GROUPS, NUM_PADS, NUM_SAMPLES, OUTPUT_TRIM_MAX, PAD_CLIP_CEILING_MIN,
+    PAD_DELAY_CROSSFADE_MS, PAD_DELAY_MS_MAX, PAD_EQ_AUTOMATION_UPDATE_FRAMES, PAD_EQ_BANDS,
+    PAD_EQ_DB_MAX, PAD_EQ_DB_MIN, PAD_FADE_MS_MAX, PAD_FADE_MUTE_MS, PAD_GAIN_DB_DEFAULT,
+    PAD_GAIN_DB_MAX, PAD_GAIN_DB_MIN, PAD_GAIN_SMOOTH_MS, PAD_ONSETS_MAX, PAD_PRIORITY_DEFAULT,
+    PAD_RATE_RATIO_TERM_MAX, PAD_SLICES_MAX, SLOT_MASK_WORDS, SPEED_MAX, SPEED_MIN,
+    STOP_FADE_MS_DEFAULT, STOP_FADE_SAMPLES_MAX, TEMPO_GLIDE_MS_DEFAULT, TEMPO_GLIDE_MS_MAX,
+    TRANSIENT_SNAP_WINDOW_MS_MAX, VOICE_SLOTS, VOLUME_MAX, VOLUME_MIN,
 };
-use crate::audio_engine::dsp::{DspNodeSlot, DspParameterId, DspParameterSlot, PerPadDspChain};
-use crate::audio_engine::stretch_processor::DEFAULT_BLOCK_SAMPLES;
+use crate::audio_engine::dsp::{
+    DspNodeSlot, DspParameterId, DspParameterSlot, MasterDcBlocker, MasterLimiter, PerPadDspChain,
+    soft_clip,
+};
+use crate::audio_engine::spatial::SpeakerGains;
+use crate::audio_engine::stretch_processor::{DEFAULT_BLOCK_SAMPLES, KEY_LOCK_MIN_LOOP_FRAMES};
 use crate::audio_engine::voice_slot::{ExplicitSeekMode, PlaybackTimelineAnchor, VoiceSlot};
 use crate::messages::{
-    PadTimingMetadata, PreparedStemSet, STEM_BUFFER_COUNT, STEM_COMPONENT_MASK, SampleBuffer,
-    StemMixMode,
+    LoopMode, MonitorSource, OnsetTable, PadDelayLine, PadFades, PadTimingMetadata,
+    PreparedStemSet, STEM_BUFFER_COUNT, STEM_COMPONENT_MASK, SampleBuffer, SpeakerLayout,
+    StemMixMode, TriggerOptions, VelocityAttack, VoiceStealPolicy,
 };
 use cpal::Sample;
 
 const BEATS_PER_BAR_4_4: f64 = 4.0;
 const BAR_PHASE_EPSILON: f64 = 1.0e-9;
 const STEM_TRANSITION_RAMP_FRAMES: usize = 128;
+/// -3 dB compensation for the L+R mono sum.
+const MONO_SUM_GAIN: f32 = std::f32::consts::FRAC_1_SQRT_2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) struct RtRenderPadActivity {
@@ -78,10 +92,102 @@ impl RtRenderPadActivity {
     }
 }
 
+/// Linear ramp of one automated or morphed parameter, such as an EQ band's gain in dB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ParameterRamp {
+    start: f32,
+    target: f32,
+    duration_frames: u64,
+    elapsed_frames: u64,
+}
+
+impl ParameterRamp {
+    fn new(start: f32, target: f32, duration_frames: u64) -> Self {
+        Self {
+            start,
+            target,
+            duration_frames,
+            elapsed_frames: 0,
+        }
+    }
+
+    fn value_at(&self, elapsed_frames: u64) -> f32 {
+        if elapsed_frames >= self.duration_frames {
+            return self.target;
+        }
+        let progress = elapsed_frames as f64 / self.duration_frames as f64;
+        self.start + (self.target - self.start) * progress as f32
+    }
+
+    /// Moves the ramp `frames` on; returns its value there and whether it has finished.
+    fn advance(&mut self, frames: u64) -> (f32, bool) {
+        self.elapsed_frames = self.elapsed_frames.saturating_add(frames);
+        (
+            self.value_at(self.elapsed_frames),
+            self.elapsed_frames >= self.duration_frames,
+        )
+    }
+}
+
+/// Mixer parameters stored as an A/B snapshot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MixerSnapshot {
+    volume: f32,
+    pad_gain_db: [f32; NUM_SAMPLES],
+    pad_eq_db: [[f32; PAD_EQ_BANDS]; NUM_SAMPLES],
+    pad_muted: [bool; NUM_SAMPLES],
+    pad_cue: [bool; NUM_SAMPLES],
+    pad_delay_frames: [usize; NUM_SAMPLES],
+}
+
+/// Snapshot whose switch-off changes are due when the running morph ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PendingSnapshotSwitches {
+    snapshot: MixerSnapshot,
+    frames_remaining: u64,
+}
+
+/// Sample waiting to replace a slot's sample at the slot's next loop boundary.
+#[derive(Debug)]
+struct PendingSampleSwap {
+    entry: usize,
+    sample: SampleBuffer,
+    /// Pad BPM that comes with the sample.
+    bpm: Option<f32>,
+}
+
+/// Buffer a reload replaced while voices still fade out on it.
+#[derive(Debug)]
+struct FadingSample {
+    /// Slot generation the buffer was loaded as.
+    generation: u64,
+    sample: SampleBuffer,
+}
+
+/// Buffers a swap took out of a slot, kept until the callback can retire them.
+#[derive(Debug)]
+struct SwappedOutSample {
+    /// Playlist entry that was swapped in.
+    entry: usize,
+    sample: Option<SampleBuffer>,
+    stems: Option<PreparedStemSet>,
+    onsets: Option<OnsetTable>,
+}
+
+fn pad_eq_band_slot(band: usize) -> Option<DspParameterSlot> {
+    match band {
+        0 => Some(DspParameterSlot::Slot0),
+        1 => Some(DspParameterSlot::Slot1),
+        2 => Some(DspParameterSlot::Slot2),
+        _ => None,
+    }
+}
+
 fn pad_eq_db_to_normalized(db: f32) -> f32 {
     if !db.is_finite() {
         return 0.5;
     }
+    let db = db.clamp(PAD_EQ_DB_MIN, PAD_EQ_DB_MAX);
     if db <= PAD_EQ_DB_MIN {
         return 0.0;
     }
@@ -118,21 +224,48 @@ impl Default for SmoothedGain {
 
 impl SmoothedGain {
     fn set_target_db(&mut self, gain_db: f32, sample_rate_hz: f32, smooth: bool) {
-        let target = gain_db_to_linear(gain_db);
+        let ramp_ms = if smooth { PAD_GAIN_SMOOTH_MS } else { 0.0 };
+        self.set_target_linear(gain_db_to_linear(gain_db), sample_rate_hz, ramp_ms);
+    }
+
+    /// Ramp linearly to `target` over `ramp_ms`, or jump there when `ramp_ms` is zero.
+    fn set_target_linear(&mut self, target: f32, sample_rate_hz: f32, ramp_ms: f32) {
+        self.target = target;
+
+        if ramp_ms <= 0.0 || sample_rate_hz <= 0.0 {
+            self.current = target;
+            self.step = 0.0;
+            self.frames_remaining = 0;
+            return;
+        }
+
+        let smooth_frames = ((sample_rate_hz * ramp_ms) / 1000.0).round().max(1.0) as usize;
+        self.set_target_frames(target, smooth_frames);
+    }
+
+    /// Ramp linearly to `target` over `frames`, or jump there when `frames` is zero.
+    fn set_target_frames(&mut self, target: f32, frames: usize) {
         self.target = target;
 
-        if !smooth || sample_rate_hz <= 0.0 {
+        if frames == 0 {
             self.current = target;
             self.step = 0.0;
             self.frames_remaining = 0;
             return;
         }
 
-        let smooth_frames = ((sample_rate_hz * PAD_GAIN_SMOOTH_MS) / 1000.0)
-            .round()
-            .max(1.0) as usize;
-        self.step = (target - self.current) / smooth_frames as f32;
-        self.frames_remaining = smooth_frames;
+        self.step = (target - self.current) / frames as f32;
+        self.frames_remaining = frames;
+    }
+
+    /// Jump to `start`, then ramp to `target` over `ramp_ms`.
+    fn ramp_from(&mut self, start: f32, target: f32, sample_rate_hz: f32, ramp_ms: f32) {
+        self.current = start;
+        self.set_target_linear(target, sample_rate_hz, ramp_ms);
+    }
+
+    fn is_silent(&self) -> bool {
+        self.frames_remaining == 0 && self.target <= 0.0
     }
 
     fn next(&mut self) -> f32 {
@@ -155,6 +288,34 @@ impl SmoothedGain {
     }
 }
 
+/// Sampler-style mapping of a bank: every pad plays `source` transposed by its distance from
+/// `root_pad` in semitones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyboardMode {
+    source: usize,
+    root_pad: usize,
+}
+
+/// Whether `bank` exists and `mode` names a valid `(source slot, root pad)` pair.
+pub(crate) fn is_valid_keyboard_mode(bank: usize, mode: Option<(usize, usize)>) -> bool {
+    bank < NUM_BANKS
+        && mode.is_none_or(|(source, root_pad)| source < NUM_SAMPLES && root_pad < NUM_PADS)
+}
+
+/// Source slot and pitch ratio a pad plays with, following its bank's keyboard mode.
+///
+/// Pads further than `KEYBOARD_SEMITONES_MAX` from the root clamp to that interval, so the
+/// outer pads of a bank repeat the octave edge instead of leaving the usable pitch range.
+fn keyboard_source(keyboard_modes: &[Option<KeyboardMode>; NUM_BANKS], id: usize) -> (usize, f32) {
+    let Some(mode) = keyboard_modes.get(id / NUM_PADS).copied().flatten() else {
+        return (id, 1.0);
+    };
+
+    let semitones = (id % NUM_PADS) as i32 - mode.root_pad as i32;
+    let semitones = semitones.clamp(-KEYBOARD_SEMITONES_MAX, KEYBOARD_SEMITONES_MAX);
+    (mode.source, 2.0_f32.powf(semitones as f32 / 12.0))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct FrameRange {
     start: usize,
@@ -167,6 +328,24 @@ impl FrameRange {
     }
 }
 
+/// A voice taken from a lower-priority pad because the voice pool was full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct VoiceSteal {
+    pub id: usize,
+    pub priority: u8,
+    pub stolen_id: usize,
+    pub stolen_priority: u8,
+}
+
+/// Audition loop on the preview voice, independent of the pad's stored loop region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PreviewLoop {
+    sample_id: usize,
+    sample_generation: u64,
+    region: FrameRange,
+    frame_pos: usize,
+}
+
 fn explicit_seek_mode_for_frame(
     frame: usize,
     loop_region: FrameRange,
@@ -258,6 +437,20 @@ fn advance_playback_position(
     }
 }
 
+/// Source frames a one-shot voice plays from `frame_pos` before it ends.
+fn one_shot_remaining_frames(
+    frame_pos: usize,
+    sample_frames: usize,
+    loop_region: FrameRange,
+    seek_mode: ExplicitSeekMode,
+) -> usize {
+    let end = match seek_mode {
+        ExplicitSeekMode::AfterLoop => sample_frames,
+        ExplicitSeekMode::Normal | ExplicitSeekMode::BeforeLoop => loop_region.end,
+    };
+    end.saturating_sub(frame_pos)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct AnchoredPlaybackPosition {
     source_frame_pos: usize,
@@ -265,6 +458,118 @@ struct AnchoredPlaybackPosition {
     next_frame_pos: usize,
 }
 
+/// Whether both terms are within `1..=PAD_RATE_RATIO_TERM_MAX` and the quotient is a playable
+/// speed.
+pub(crate) fn is_valid_rate_ratio((numerator, denominator): (u16, u16)) -> bool {
+    let terms = 1..=PAD_RATE_RATIO_TERM_MAX;
+    terms.contains(&numerator)
+        && terms.contains(&denominator)
+        && (SPEED_MIN..=SPEED_MAX).contains(&rational_tempo_ratio((numerator, denominator)))
+}
+
+/// Whether both fade times are within `0..=PAD_FADE_MS_MAX` milliseconds.
+pub(crate) fn is_valid_pad_fades(fades: PadFades) -> bool {
+    [fades.fade_in_ms, fades.fade_out_ms]
+        .iter()
+        .all(|ms| ms.is_finite() && (0.0..=PAD_FADE_MS_MAX).contains(ms))
+}
+
+pub(crate) fn is_valid_velocity_attack(attack: VelocityAttack) -> bool {
+    [attack.soft_ms, attack.hard_ms]
+        .iter()
+        .all(|ms| ms.is_finite() && (0.0..=PAD_FADE_MS_MAX).contains(ms))
+}
+
+/// Fade-in length for a trigger at `velocity` (`VOLUME_MIN..=VOLUME_MAX`).
+fn velocity_attack_ms(attack: VelocityAttack, velocity: f32) -> f32 {
+    let position = velocity.clamp(VOLUME_MIN, VOLUME_MAX) / VOLUME_MAX;
+    attack.soft_ms + (attack.hard_ms - attack.soft_ms) * position
+}
+
+pub(crate) fn is_valid_pad_clip_ceiling(ceiling: f32) -> bool {
+    ceiling.is_finite() && (PAD_CLIP_CEILING_MIN..=1.0).contains(&ceiling)
+}
+
+pub(crate) fn is_valid_transient_snap_window(window_ms: f32) -> bool {
+    window_ms.is_finite() && window_ms > 0.0 && window_ms <= TRANSIENT_SNAP_WINDOW_MS_MAX
+}
+
+/// Adds `sample` to output `channel` of frame `frame`, through the pad's delay line unless it
+/// is bypassed.
+fn mix_into_output(
+    output: &mut [f32],
+    output_channels: usize,
+    pad_delay: &PadDelay,
+    delay_line: Option<&mut [f32]>,
+    frame: usize,
+    channel: usize,
+    sample: f32,
+) {
+    match delay_line {
+        Some(ring) if !pad_delay.is_bypassed() => {
+            pad_delay.write(ring, output_channels, frame, channel, sample);
+        }
+        _ => output[frame * output_channels + channel] += sample,
+    }
+}
+
+fn pad_delay_ms_to_frames(delay_ms: f32, sample_rate_hz: f32) -> usize {
+    (delay_ms * sample_rate_hz / 1000.0).round() as usize
+}
+
+/// Default stop fade in output frames at this rate.
+fn default_stop_fade_samples(sample_rate_hz: f32) -> u32 {
+    ((STOP_FADE_MS_DEFAULT * sample_rate_hz / 1000.0).round() as u32).min(STOP_FADE_SAMPLES_MAX)
+}
+
+/// Frames of a pad delay line: the longest delay plus one render chunk.
+fn pad_delay_line_frames(sample_rate_hz: f32) -> usize {
+    pad_delay_ms_to_frames(PAD_DELAY_MS_MAX, sample_rate_hz) + (DEFAULT_BLOCK_SAMPLES / 2).max(1)
+}
+
+/// A silent delay line for one pad of a mixer with these output channels and rate.
+///
+/// Allocates, so callers build it off the audio thread.
+pub(crate) fn new_pad_delay_line(output_channels: usize, sample_rate_hz: f32) -> PadDelayLine {
+    let sample_rate_hz = if sample_rate_hz.is_finite() && sample_rate_hz > 0.0 {
+        sample_rate_hz
+    } else {
+        44_100.0
+    };
+    vec![0.0; pad_delay_line_frames(sample_rate_hz) * output_channels].into_boxed_slice()
+}
+
+/// Left and right gains of an equal-power pan from -1.0 (left) to 1.0 (right).
+fn equal_power_pan_gains(pan: f32) -> [f32; 2] {
+    let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+    [angle.cos(), angle.sin()]
+}
+
+/// Nearest onset to `frame` no further than `window` frames away and inside `bounds`.
+///
+/// `onsets` must be sorted; ties go to the earlier onset.
+fn nearest_onset(
+    onsets: &[usize],
+    frame: usize,
+    window: usize,
+    bounds: FrameRange,
+) -> Option<usize> {
+    let index = onsets.partition_point(|&onset| onset < frame);
+    let before = index.checked_sub(1).map(|index| onsets[index]);
+    let after = onsets.get(index).copied();
+
+    [before, after]
+        .into_iter()
+        .flatten()
+        .filter(|&onset| onset.abs_diff(frame) <= window)
+        .filter(|&onset| onset >= bounds.start && onset < bounds.end)
+        .min_by_key(|&onset| onset.abs_diff(frame))
+}
+
+fn rational_tempo_ratio((numerator, denominator): (u16, u16)) -> f32 {
+    (f64::from(numerator) / f64::from(denominator)) as f32
+}
+
 fn rounded_source_offset(
     anchor: PlaybackTimelineAnchor,
     output_frame: u64,
@@ -416,6 +721,95 @@ impl Default for StemTransition {
     }
 }
 
+/// Per-slot output delay; the delayed signal lives in the slot's part of the mixer's ring.
+///
+/// Voices add their output ahead into the ring at the delay tap, and the mixer reads each
+/// frame back out when it is due. While the delay changes, voices write to the old and the
+/// new tap with complementary gains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct PadDelay {
+    delay_frames: usize,
+    previous_delay_frames: usize,
+    crossfade_elapsed_frames: usize,
+    crossfade_frames: usize,
+    /// Ring frame holding the next output frame.
+    read_frame: usize,
+    /// Frames from `read_frame` on that may still hold delayed signal.
+    pending_frames: usize,
+}
+
+impl PadDelay {
+    fn is_crossfading(&self) -> bool {
+        self.crossfade_elapsed_frames < self.crossfade_frames
+    }
+
+    /// Whether voices write straight to the output, leaving the ring alone.
+    fn is_bypassed(&self) -> bool {
+        self.delay_frames == 0 && self.pending_frames == 0 && !self.is_crossfading()
+    }
+
+    fn set_delay(&mut self, delay_frames: usize, crossfade_frames: usize) {
+        if delay_frames == self.delay_frames {
+            return;
+        }
+
+        self.previous_delay_frames = self.delay_frames;
+        self.delay_frames = delay_frames;
+        self.crossfade_elapsed_frames = 0;
+        self.crossfade_frames = crossfade_frames;
+    }
+
+    /// Adds `sample` to the taps of output frame `frame_offset` of the current chunk.
+    fn write(
+        &self,
+        ring: &mut [f32],
+        channels: usize,
+        frame_offset: usize,
+        channel: usize,
+        sample: f32,
+    ) {
+        let ring_frames = ring.len() / channels;
+        let mut add = |delay_frames: usize, gain: f32| {
+            let frame = (self.read_frame + frame_offset + delay_frames) % ring_frames;
+            ring[frame * channels + channel] += sample * gain;
+        };
+
+        if !self.is_crossfading() {
+            add(self.delay_frames, 1.0);
+            return;
+        }
+
+        let elapsed = (self.crossfade_elapsed_frames + frame_offset).min(self.crossfade_frames);
+        let gain = elapsed as f32 / self.crossfade_frames as f32;
+        add(self.previous_delay_frames, 1.0 - gain);
+        add(self.delay_frames, gain);
+    }
+
+    /// Moves the due frames of `ring` into `output` and advances by its length.
+    fn read_into(&mut self, ring: &mut [f32], output: &mut [f32], channels: usize) {
+        let ring_frames = ring.len() / channels;
+        let frames = output.len() / channels;
+        let written_frames = if self.is_crossfading() {
+            self.delay_frames.max(self.previous_delay_frames)
+        } else {
+            self.delay_frames
+        } + frames;
+
+        for (frame_offset, frame) in output.chunks_exact_mut(channels).enumerate() {
+            let start = (self.read_frame + frame_offset) % ring_frames * channels;
+            for (sample, delayed) in frame.iter_mut().zip(&mut ring[start..start + channels]) {
+                *sample += *delayed;
+                *delayed = 0.0;
+            }
+        }
+
+        self.read_frame = (self.read_frame + frames) % ring_frames;
+        self.pending_frames = self.pending_frames.max(written_frames) - frames;
+        self.crossfade_elapsed_frames =
+            (self.crossfade_elapsed_frames + frames).min(self.crossfade_frames);
+    }
+}
+
 fn phase_aligned_initial_frame(
     sample_rate_hz: f32,
     pad_bpm: Option<f32>,
@@ -592,9 +986,12 @@ fn render_source_selection_sample(
 /// multiple concurrent voices for playback. All operations are designed to be
 /// lock-free and real-time safe.
 pub struct RtMixer {
-    /// Number of output channels (1 for mono, 2 for stereo).
+    /// Number of channels of samples and voices (1 for mono, 2 for stereo and wider outputs).
     channels: usize,
 
+    /// Number of output channels.
+    output_channels: usize,
+
     /// Output sample rate in Hz.
     sample_rate_hz: f32,
 
@@ -607,9 +1004,45 @@ pub struct RtMixer {
     /// Enable BPM lock (tempo matching).
     bpm_lock_enabled: bool,
 
+    /// Time constant in milliseconds for voices gliding to a new tempo ratio.
+    tempo_glide_ms: f32,
+
+    /// Fade length in output frames for stopping voices of pads without a fade-out.
+    stop_fade_samples: u32,
+
     /// Per-pad Key Lock state (preserve pitch when tempo changes).
     pad_key_lock_enabled: [bool; NUM_SAMPLES],
 
+    /// Per-pad rational rate ratio `(numerator, denominator)` scaling the synced or speed rate.
+    pad_rate_ratio: [Option<(u16, u16)>; NUM_SAMPLES],
+
+    /// Per-bank keyboard mode; pads of a bank in keyboard mode play its source transposed.
+    keyboard_modes: [Option<KeyboardMode>; NUM_BANKS],
+
+    /// Per-pad voice priority; a full pool only steals voices of strictly lower priority.
+    pad_priority: [u8; NUM_SAMPLES],
+
+    /// Whether a full pool also steals voices of equal priority, and which.
+    voice_steal_policy: VoiceStealPolicy,
+
+    /// Loop mode of pads without their own.
+    default_loop_mode: LoopMode,
+
+    /// Per-pad loop mode overriding the default.
+    pad_loop_mode: [Option<LoopMode>; NUM_SAMPLES],
+
+    /// Per-pad flag: a one-shot voice reached its end since the caller last checked.
+    one_shot_ended: [bool; NUM_SAMPLES],
+
+    /// Steal made by the last trigger, until the caller reports it.
+    last_voice_steal: Option<VoiceSteal>,
+
+    /// Per-pad choke group membership.
+    pad_choke_group: [Option<u8>; NUM_SAMPLES],
+
+    /// Per-group flag: stopping one member stops all members.
+    choke_group_stop_all: [bool; NUM_CHOKE_GROUPS],
+
     /// Current master BPM when BPM lock is enabled.
     master_bpm: Option<f32>,
 
@@ -625,12 +1058,76 @@ pub struct RtMixer {
     /// Per-pad smoothed linear Gain/Trim multiplier used by the render path.
     pad_gain_smoothers: [SmoothedGain; NUM_SAMPLES],
 
+    /// Per-pad fade-mute gain; ramps to silence while voices keep advancing.
+    pad_fade_mutes: [SmoothedGain; NUM_SAMPLES],
+
+    /// Per-pad fade times, or None for the engine defaults.
+    pad_fades: [Option<PadFades>; NUM_SAMPLES],
+
+    /// Velocity-dependent trigger fade-in for pads without their own fades.
+    velocity_attack: Option<VelocityAttack>,
+
+    /// Per-pad output delay for time alignment.
+    pad_delays: [PadDelay; NUM_SAMPLES],
+
+    /// Delay line of each slot, sized by `new_pad_delay_line`.
+    ///
+    /// Handed over the first time a slot is delayed and kept from then on, so pads that are
+    /// never delayed cost no memory.
+    pad_delay_lines: Box<[Option<PadDelayLine>; NUM_SAMPLES]>,
+
+    /// Speaker azimuths of an output wider than stereo, or None to play every pad on the
+    /// first two channels.
+    speaker_layout: Option<SpeakerLayout>,
+
+    /// Per-pad azimuth in degrees on the speaker layout, or None for the first two channels.
+    pad_azimuth: [Option<f32>; NUM_SAMPLES],
+
+    /// Per-pad speaker gains for the layout and azimuth, or None while the pad is not panned.
+    pad_speaker_gains: Box<[Option<SpeakerGains>; NUM_SAMPLES]>,
+
+    /// Per-pad soft-clip ceiling, or None to leave the pad linear.
+    pad_clip_ceiling: [Option<f32>; NUM_SAMPLES],
+
     /// Per-pad DSP/FX chain with the live DJ isolator EQ node.
     pad_dsp_chains: Box<[PerPadDspChain]>,
 
+    /// Per-pad EQ band gains in dB, as last set or automated.
+    pad_eq_db: [[f32; PAD_EQ_BANDS]; NUM_SAMPLES],
+
+    /// Per-pad, per-band running EQ automation.
+    pad_eq_automations: [[Option<ParameterRamp>; PAD_EQ_BANDS]; NUM_SAMPLES],
+
+    /// Running morph of the global volume.
+    volume_ramp: Option<ParameterRamp>,
+
+    /// Per-pad running morph of the Gain/Trim in dB.
+    pad_gain_ramps: [Option<ParameterRamp>; NUM_SAMPLES],
+
+    /// Stored A/B parameter snapshots.
+    snapshots: [Option<MixerSnapshot>; MIXER_SNAPSHOT_SLOTS],
+
+    /// Mutes and cue switch-offs the running morph applies when it ends.
+    pending_snapshot_switches: Option<PendingSnapshotSwitches>,
+
+    /// Per-slot onset positions in frames, published from analysis.
+    pad_onsets: Box<[Option<OnsetTable>; NUM_SAMPLES]>,
+
+    /// Per-pad transient snap window in frames, or None when snapping is off.
+    pad_transient_snap_frames: [Option<usize>; NUM_SAMPLES],
+
+    /// Per-pad snap adjustment applied since the last drain, reported as a debug event.
+    pad_transient_snaps: [Option<i64>; NUM_SAMPLES],
+
     /// Per-pad loop region start frame.
     pad_loop_start_frame: [usize; NUM_SAMPLES],
 
+    /// Per-pad slice count; above 1, each trigger starts at the next slice of the loop region.
+    pad_slices: [u16; NUM_SAMPLES],
+
+    /// Per-pad index of the slice the next trigger starts at.
+    pad_next_slice: [u16; NUM_SAMPLES],
+
     /// Per-pad loop region end frame (exclusive), or None for full sample.
     pad_loop_end_frame: [Option<usize>; NUM_SAMPLES],
 
@@ -640,6 +1137,21 @@ pub struct RtMixer {
     /// Sample storage with NUM_SAMPLES slots.
     sample_bank: [Option<SampleBuffer>; NUM_SAMPLES],
 
+    /// Per-slot sample queued to take over at the slot's next loop boundary.
+    pending_sample_swaps: Box<[Option<PendingSampleSwap>; NUM_SAMPLES]>,
+
+    /// Per-slot buffers of the last swap, waiting for [`Self::drain_sample_swaps`].
+    swapped_out_samples: Box<[Option<SwappedOutSample>; NUM_SAMPLES]>,
+
+    /// Per-slot buffer replaced by a reload, kept until [`Self::retire_faded_samples`] finds
+    /// its voices finished.
+    fading_samples: Box<[Option<FadingSample>; NUM_SAMPLES]>,
+
+    /// Per-slot generation, bumped whenever a slot's sample is replaced or unloaded.
+    ///
+    /// Voices only render while their generation matches, so they never need their own handle.
+    sample_generations: [u64; NUM_SAMPLES],
+
     /// Prepared stem storage with NUM_SAMPLES slots.
     prepared_stems: Box<[Option<PreparedStemSet>; NUM_SAMPLES]>,
 
@@ -655,8 +1167,59 @@ pub struct RtMixer {
     /// Per-pad bounded transition state for accepted stem source-selection changes.
     stem_transitions: [StemTransition; NUM_SAMPLES],
 
-    /// Active voices with MAX_VOICES slots.
-    pub voices: [VoiceSlot; MAX_VOICES],
+    /// Voice slots; at most MAX_VOICES of them play, the rest only finish fade-outs.
+    pub voices: [VoiceSlot; VOICE_SLOTS],
+
+    /// Per-voice fade-in/fade-out gain, indexed like `voices`.
+    voice_fades: [SmoothedGain; VOICE_SLOTS],
+
+    /// Per-voice flag: the voice stops once its fade-out reaches silence.
+    voice_fading_out: [bool; VOICE_SLOTS],
+
+    /// Per-voice flag: a cued trigger that has not been promoted to the master mix.
+    voice_cue_only: [bool; VOICE_SLOTS],
+
+    /// Per-voice send level into the master mix; a promotion ramps it up from silence.
+    voice_master_sends: [SmoothedGain; VOICE_SLOTS],
+
+    /// Per-pad flag: the pad's pre-fader signal also feeds the cue bus.
+    pad_cue: [bool; NUM_SAMPLES],
+
+    /// Cue bus of the chunk being rendered, summed to mono.
+    cue_mix: Box<[f32]>,
+
+    /// Split cue: master summed to mono on the first output channel, the cue bus on the second.
+    cue_split: bool,
+
+    /// First of the two output channels the preview voice is routed to, or None for the mix.
+    preview_output: Option<usize>,
+
+    /// First of the two output channels the cue bus is routed to, or None.
+    cue_output: Option<usize>,
+
+    /// Dedicated preview voice outside the voice pool.
+    preview_loop: Option<PreviewLoop>,
+
+    /// Enable the DC blocker on the final master output.
+    master_dc_blocker_enabled: bool,
+
+    /// DC blocker state for the final master output.
+    master_dc_blocker: MasterDcBlocker,
+
+    /// Enable the peak limiter on the final master output.
+    master_limiter_enabled: bool,
+
+    /// Peak limiter state for the final master output.
+    master_limiter: MasterLimiter,
+
+    /// Publish periodic `LimiterMeter` events.
+    limiter_meter_events: bool,
+
+    /// Sum the first two output channels to mono and write the sum to both.
+    mono_output: bool,
+
+    /// Per-output-channel calibration gain applied last.
+    output_trims: [f32; MAX_OUTPUT_CHANNELS as usize],
 }
 
 impl RtMixer {
@@ -664,44 +1227,98 @@ impl RtMixer {
     ///
     /// # Parameters
     ///
-    /// - `channels`: Number of output channels (1 for mono, 2 for stereo)
+    /// - `output_channels`: Number of output channels; samples must have at most two
     ///
     /// # Returns
     ///
     /// A new `RtMixer` instance with empty sample bank and no active voices.
-    pub fn new(channels: usize, sample_rate_hz: f32) -> Self {
+    pub fn new(output_channels: usize, sample_rate_hz: f32) -> Self {
+        let channels = mix_channels(output_channels);
         let sample_rate_hz = if sample_rate_hz.is_finite() && sample_rate_hz > 0.0 {
             sample_rate_hz
         } else {
             44_100.0
         };
-
         Self {
             channels,
+            output_channels,
             sample_rate_hz,
             volume: VOLUME_MAX,
             speed: 1.0,
             bpm_lock_enabled: false,
+            tempo_glide_ms: TEMPO_GLIDE_MS_DEFAULT,
+            stop_fade_samples: default_stop_fade_samples(sample_rate_hz),
             pad_key_lock_enabled: std::array::from_fn(|_| false),
+            pad_rate_ratio: [None; NUM_SAMPLES],
+            keyboard_modes: [None; NUM_BANKS],
+            pad_priority: [PAD_PRIORITY_DEFAULT; NUM_SAMPLES],
+            voice_steal_policy: VoiceStealPolicy::default(),
+            default_loop_mode: LoopMode::Loop,
+            pad_loop_mode: [None; NUM_SAMPLES],
+            one_shot_ended: [false; NUM_SAMPLES],
+            last_voice_steal: None,
+            pad_choke_group: std::array::from_fn(|_| None),
+            choke_group_stop_all: std::array::from_fn(|_| false),
             master_bpm: None,
             pad_bpm: std::array::from_fn(|_| None),
             pad_phase_anchor_frame: std::array::from_fn(|_| 0),
             pad_gain_db: std::array::from_fn(|_| PAD_GAIN_DB_DEFAULT),
             pad_gain_smoothers: std::array::from_fn(|_| SmoothedGain::default()),
+            pad_fade_mutes: std::array::from_fn(|_| SmoothedGain::default()),
+            pad_fades: [None; NUM_SAMPLES],
+            velocity_attack: None,
+            pad_delays: [PadDelay::default(); NUM_SAMPLES],
+            pad_delay_lines: Box::new(std::array::from_fn(|_| None)),
+            speaker_layout: None,
+            pad_azimuth: [None; NUM_SAMPLES],
+            pad_speaker_gains: Box::new([None; NUM_SAMPLES]),
+            pad_clip_ceiling: [None; NUM_SAMPLES],
             pad_dsp_chains: (0..NUM_SAMPLES)
                 .map(|id| PerPadDspChain::new(id, sample_rate_hz, DEFAULT_BLOCK_SAMPLES, channels))
                 .collect::<Vec<_>>()
                 .into_boxed_slice(),
+            pad_eq_db: [[0.0; PAD_EQ_BANDS]; NUM_SAMPLES],
+            pad_eq_automations: [[None; PAD_EQ_BANDS]; NUM_SAMPLES],
+            volume_ramp: None,
+            pad_gain_ramps: [None; NUM_SAMPLES],
+            snapshots: [None; MIXER_SNAPSHOT_SLOTS],
+            pending_snapshot_switches: None,
+            pad_onsets: Box::new(std::array::from_fn(|_| None)),
+            pad_transient_snap_frames: [None; NUM_SAMPLES],
+            pad_transient_snaps: [None; NUM_SAMPLES],
             pad_loop_start_frame: std::array::from_fn(|_| 0),
+            pad_slices: [1; NUM_SAMPLES],
+            pad_next_slice: [0; NUM_SAMPLES],
             pad_loop_end_frame: std::array::from_fn(|_| None),
             pad_playhead_frame: std::array::from_fn(|_| None),
             sample_bank: std::array::from_fn(|_| None),
+            pending_sample_swaps: Box::new(std::array::from_fn(|_| None)),
+            swapped_out_samples: Box::new(std::array::from_fn(|_| None)),
+            fading_samples: Box::new(std::array::from_fn(|_| None)),
+            sample_generations: [0; NUM_SAMPLES],
             prepared_stems: Box::new(std::array::from_fn(|_| None)),
             stem_mix_mode: std::array::from_fn(|_| StemMixMode::FullMix),
             stem_mix_source_version_hash: std::array::from_fn(|_| 0),
             stem_enabled_mask: std::array::from_fn(|_| STEM_COMPONENT_MASK),
             stem_transitions: std::array::from_fn(|_| StemTransition::default()),
             voices: std::array::from_fn(|_| VoiceSlot::with_sample_rate(channels, sample_rate_hz)),
+            voice_fades: std::array::from_fn(|_| SmoothedGain::default()),
+            voice_fading_out: [false; VOICE_SLOTS],
+            voice_cue_only: [false; VOICE_SLOTS],
+            voice_master_sends: std::array::from_fn(|_| SmoothedGain::default()),
+            pad_cue: [false; NUM_SAMPLES],
+            cue_mix: vec![0.0; (DEFAULT_BLOCK_SAMPLES / 2).max(1)].into_boxed_slice(),
+            cue_split: false,
+            preview_output: None,
+            cue_output: None,
+            preview_loop: None,
+            master_dc_blocker_enabled: false,
+            master_dc_blocker: MasterDcBlocker::new(sample_rate_hz),
+            master_limiter_enabled: false,
+            master_limiter: MasterLimiter::new(sample_rate_hz),
+            mono_output: false,
+            limiter_meter_events: false,
+            output_trims: [1.0; MAX_OUTPUT_CHANNELS as usize],
         }
     }
 
@@ -738,54 +1355,342 @@ impl RtMixer {
             return false;
         }
 
+        // Voices of the previous generation fade out on its buffer, which waits for them.
+        self.retire_fading_sample(id, retirement);
+        let generation = self.sample_generations[id];
+        let mut fading = false;
+        for index in 0..self.voices.len() {
+            if self.voice_plays_slot_generation(index, id, generation) {
+                self.fade_out_voice(index);
+                fading |= self.voices[index].active;
+            }
+        }
+        if let Some(waiting) = self.pending_sample_swaps[id].take() {
+            retirement.retire_sample(waiting.sample);
+        }
         if let Some(old_sample) = self.sample_bank[id].take() {
-            retirement.retire_sample(old_sample);
+            if fading {
+                self.fading_samples[id] = Some(FadingSample {
+                    generation,
+                    sample: old_sample,
+                });
+            } else {
+                retirement.retire_sample(old_sample);
+            }
         }
         if let Some(old_stems) = self.prepared_stems[id].take() {
             retirement.retire_prepared_stems(old_stems);
         }
+        if let Some(old_onsets) = self.pad_onsets[id].take() {
+            retirement.retire_onsets(old_onsets);
+        }
 
         self.sample_bank[id] = Some(sample);
+        self.sample_generations[id] = self.sample_generations[id].wrapping_add(1);
+        self.pad_next_slice[id] = 0;
         self.stem_enabled_mask[id] = STEM_COMPONENT_MASK;
         self.stem_transitions[id].clear();
         true
     }
 
+    /// Whether voice `index` renders generation `generation` of slot `id`, directly or
+    /// through its bank's keyboard mode.
+    fn voice_plays_slot_generation(&self, index: usize, id: usize, generation: u64) -> bool {
+        let voice = &self.voices[index];
+        voice.plays_generation(generation)
+            && keyboard_source(&self.keyboard_modes, voice.sample_id).0 == id
+    }
+
+    /// Stops the voices still fading out on the slot's replaced buffer and retires it.
+    fn retire_fading_sample(&mut self, id: usize, retirement: &mut impl AudioBufferRetirement) {
+        let Some(fading) = self.fading_samples[id].take() else {
+            return;
+        };
+        for index in 0..self.voices.len() {
+            if self.voice_plays_slot_generation(index, id, fading.generation) {
+                self.voices[index].stop();
+                self.voice_fading_out[index] = false;
+            }
+        }
+        retirement.retire_sample(fading.sample);
+    }
+
+    /// Retires the replaced buffers whose voices have finished fading out.
+    ///
+    /// Slots wait for a later call while the retirement queue lacks room.
+    pub(crate) fn retire_faded_samples(&mut self, retirement: &mut impl AudioBufferRetirement) {
+        for id in 0..NUM_SAMPLES {
+            let Some(generation) = self.fading_samples[id].as_ref().map(|f| f.generation) else {
+                continue;
+            };
+            let playing = (0..self.voices.len())
+                .any(|index| self.voice_plays_slot_generation(index, id, generation));
+            if playing || retirement.available_retirement_slots() == 0 {
+                continue;
+            }
+            if let Some(fading) = self.fading_samples[id].take() {
+                retirement.retire_sample(fading.sample);
+            }
+        }
+    }
+
     #[cfg(test)]
-    pub(crate) fn publish_prepared_stems(&mut self, id: usize, stems: PreparedStemSet) -> bool {
+    pub(crate) fn queue_sample_swap(
+        &mut self,
+        id: usize,
+        entry: usize,
+        sample: SampleBuffer,
+        bpm: Option<f32>,
+    ) -> bool {
         let mut retirement = ImmediateAudioBufferRetirement;
-        self.publish_prepared_stems_rt(id, stems, &mut retirement)
+        self.queue_sample_swap_rt(id, entry, sample, bpm, &mut retirement)
     }
 
-    pub(crate) fn publish_prepared_stems_rt(
+    /// Queues `sample`, playlist entry `entry`, to replace the slot's sample without stopping
+    /// its voices, together with its pad BPM if it has one.
+    ///
+    /// An idle slot swaps right away. A playing slot swaps on the exact frame its voice wraps
+    /// back to the loop start, continuing at the same position in the new sample, or once it
+    /// stops. A newer sample replaces one still waiting.
+    pub(crate) fn queue_sample_swap_rt(
         &mut self,
         id: usize,
-        stems: PreparedStemSet,
+        entry: usize,
+        sample: SampleBuffer,
+        bpm: Option<f32>,
         retirement: &mut impl AudioBufferRetirement,
     ) -> bool {
-        if !self.can_accept_prepared_stems(id, &stems) {
-            retirement.retire_prepared_stems(stems);
+        if id >= NUM_SAMPLES || sample.channels != self.channels {
+            retirement.retire_sample(sample);
             return false;
         }
 
-        if let Some(old_stems) = self.prepared_stems[id].take() {
-            retirement.retire_prepared_stems(old_stems);
+        if let Some(waiting) =
+            self.pending_sample_swaps[id].replace(PendingSampleSwap { entry, sample, bpm })
+        {
+            retirement.retire_sample(waiting.sample);
+        }
+        if self.sample_swap_position(id).is_none() {
+            self.swap_in_pending_sample(id);
         }
-
-        self.prepared_stems[id] = Some(stems);
-        self.stem_transitions[id].clear();
         true
     }
 
-    pub(crate) fn set_stem_mix_mode(
-        &mut self,
-        id: usize,
-        mode: StemMixMode,
-        source_version_hash: u64,
-    ) -> bool {
-        if id >= NUM_SAMPLES {
-            return false;
-        }
+    /// Playhead of the slot's first active voice, or None while the slot is idle.
+    fn sample_swap_position(&self, id: usize) -> Option<usize> {
+        self.sample_swap_voice(id)
+            .map(|voice_index| self.voices[voice_index].frame_pos)
+    }
+
+    /// The slot's first active voice, whose loop wrap times a queued swap.
+    fn sample_swap_voice(&self, id: usize) -> Option<usize> {
+        self.voices
+            .iter()
+            .position(|voice| voice.active && voice.sample_id == id)
+    }
+
+    /// Moves the queued sample into the bank, keeping the replaced buffers for retirement.
+    ///
+    /// Waits while the buffers of the previous swap are still held.
+    fn swap_in_pending_sample(&mut self, id: usize) {
+        if self.swapped_out_samples[id].is_some() {
+            return;
+        }
+        let Some(PendingSampleSwap { entry, sample, bpm }) = self.pending_sample_swaps[id].take()
+        else {
+            return;
+        };
+        if bpm.is_some() {
+            self.set_pad_bpm(id, bpm);
+        }
+
+        self.swapped_out_samples[id] = Some(SwappedOutSample {
+            entry,
+            sample: self.sample_bank[id].replace(sample),
+            stems: self.prepared_stems[id].take(),
+            onsets: self.pad_onsets[id].take(),
+        });
+        self.pad_next_slice[id] = 0;
+        self.stem_enabled_mask[id] = STEM_COMPONENT_MASK;
+        self.stem_transitions[id].clear();
+        // The preview's frame range belongs to the swapped-out buffer.
+        if self
+            .preview_loop
+            .is_some_and(|preview| preview.sample_id == id)
+        {
+            self.preview_loop = None;
+        }
+    }
+
+    /// Playheads before a render chunk of the slots with a queued swap.
+    fn sample_swap_positions(&self) -> [Option<usize>; NUM_SAMPLES] {
+        let mut positions = [None; NUM_SAMPLES];
+        for (id, position) in positions.iter_mut().enumerate() {
+            if self.pending_sample_swaps[id].is_some() {
+                *position = self.sample_swap_position(id);
+            }
+        }
+        positions
+    }
+
+    /// Swaps in the queued samples of slots that wrapped or stopped during a render chunk.
+    fn swap_in_due_samples(&mut self, positions_before: &[Option<usize>; NUM_SAMPLES]) {
+        for (id, before) in positions_before.iter().enumerate() {
+            if self.pending_sample_swaps[id].is_none() {
+                continue;
+            }
+            let due = match (before, self.sample_swap_position(id)) {
+                (_, None) => true,
+                (Some(before), Some(after)) => after < *before,
+                (None, Some(_)) => false,
+            };
+            if due {
+                self.swap_in_pending_sample(id);
+            }
+        }
+    }
+
+    /// Retires the buffers of every swap since the last call and hands the slot and swapped-in
+    /// entry to `emit`.
+    ///
+    /// Slots wait for a later call while the retirement queue lacks room.
+    pub(crate) fn drain_sample_swaps(
+        &mut self,
+        retirement: &mut impl AudioBufferRetirement,
+        mut emit: impl FnMut(usize, usize),
+    ) {
+        for (id, slot) in self.swapped_out_samples.iter_mut().enumerate() {
+            if slot.is_none() || retirement.available_retirement_slots() < 3 {
+                continue;
+            }
+            let Some(swapped) = slot.take() else {
+                continue;
+            };
+            if let Some(sample) = swapped.sample {
+                retirement.retire_sample(sample);
+            }
+            if let Some(stems) = swapped.stems {
+                retirement.retire_prepared_stems(stems);
+            }
+            if let Some(onsets) = swapped.onsets {
+                retirement.retire_onsets(onsets);
+            }
+            emit(id, swapped.entry);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn publish_prepared_stems(&mut self, id: usize, stems: PreparedStemSet) -> bool {
+        let mut retirement = ImmediateAudioBufferRetirement;
+        self.publish_prepared_stems_rt(id, stems, &mut retirement)
+    }
+
+    pub(crate) fn publish_prepared_stems_rt(
+        &mut self,
+        id: usize,
+        stems: PreparedStemSet,
+        retirement: &mut impl AudioBufferRetirement,
+    ) -> bool {
+        if !self.can_accept_prepared_stems(id, &stems) {
+            retirement.retire_prepared_stems(stems);
+            return false;
+        }
+
+        if let Some(old_stems) = self.prepared_stems[id].take() {
+            retirement.retire_prepared_stems(old_stems);
+        }
+
+        self.prepared_stems[id] = Some(stems);
+        self.stem_transitions[id].clear();
+        true
+    }
+
+    #[cfg(test)]
+    pub(crate) fn publish_pad_onsets(&mut self, id: usize, onsets: OnsetTable) -> bool {
+        let mut retirement = ImmediateAudioBufferRetirement;
+        self.publish_pad_onsets_rt(id, onsets, &mut retirement)
+    }
+
+    /// Publishes a slot's onset table; tables arrive sorted from the control thread.
+    pub(crate) fn publish_pad_onsets_rt(
+        &mut self,
+        id: usize,
+        onsets: OnsetTable,
+        retirement: &mut impl AudioBufferRetirement,
+    ) -> bool {
+        if id >= NUM_SAMPLES || self.sample_bank[id].is_none() || onsets.len() > PAD_ONSETS_MAX {
+            retirement.retire_onsets(onsets);
+            return false;
+        }
+
+        if let Some(old_onsets) = self.pad_onsets[id].replace(onsets) {
+            retirement.retire_onsets(old_onsets);
+        }
+        true
+    }
+
+    /// Sets the window a pad's start and seek positions snap to the nearest onset within.
+    ///
+    /// `None` disables snapping.
+    pub fn set_pad_transient_snap(&mut self, id: usize, window_ms: Option<f32>) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        self.pad_transient_snap_frames[id] = match window_ms {
+            Some(window_ms) if is_valid_transient_snap_window(window_ms) => {
+                Some((window_ms * 0.001 * self.sample_rate_hz).round() as usize)
+            }
+            Some(_) => return,
+            None => None,
+        };
+    }
+
+    /// Moves `frame` onto the nearest onset of `source_id` within the pad's snap window.
+    ///
+    /// Only onsets inside `bounds` qualify. The applied adjustment is kept for the next
+    /// [`Self::drain_transient_snaps`].
+    fn snap_to_transient(
+        &mut self,
+        id: usize,
+        source_id: usize,
+        frame: usize,
+        bounds: FrameRange,
+    ) -> usize {
+        let Some(window) = self.pad_transient_snap_frames[id] else {
+            return frame;
+        };
+        let Some(onsets) = self.pad_onsets[source_id].as_deref() else {
+            return frame;
+        };
+        let Some(onset) = nearest_onset(onsets, frame, window, bounds) else {
+            return frame;
+        };
+
+        if onset != frame {
+            self.pad_transient_snaps[id] = Some(onset as i64 - frame as i64);
+        }
+        onset
+    }
+
+    /// Hands every snap adjustment applied since the last call to `emit`, once per pad.
+    pub(crate) fn drain_transient_snaps(&mut self, mut emit: impl FnMut(usize, i64)) {
+        for (id, snap) in self.pad_transient_snaps.iter_mut().enumerate() {
+            if let Some(offset_frames) = snap.take() {
+                emit(id, offset_frames);
+            }
+        }
+    }
+
+    pub(crate) fn set_stem_mix_mode(
+        &mut self,
+        id: usize,
+        mode: StemMixMode,
+        source_version_hash: u64,
+    ) -> bool {
+        if id >= NUM_SAMPLES {
+            return false;
+        }
 
         let previous = self.stem_render_selection(id);
         match mode {
@@ -895,17 +1800,127 @@ impl RtMixer {
         )
     }
 
-    fn sample_is_active(&self, id: usize) -> bool {
+    /// Whether each pad has a voice that is not fading out after a stop.
+    pub(crate) fn active_pads(&self) -> [bool; NUM_SAMPLES] {
+        let mut active = [false; NUM_SAMPLES];
+        for (voice, fading_out) in self.voices.iter().zip(&self.voice_fading_out) {
+            if voice.active && !fading_out {
+                active[voice.sample_id] = true;
+            }
+        }
+        active
+    }
+
+    /// Number of active voices, counting those still fading out after a stop.
+    pub(crate) fn active_voice_count(&self) -> usize {
+        self.voices.iter().filter(|voice| voice.active).count()
+    }
+
+    /// Bitmask of the sample slots holding a sample, bit `id % 64` of word `id / 64`.
+    pub(crate) fn loaded_slot_mask(&self) -> [u64; SLOT_MASK_WORDS] {
+        let mut mask = [0; SLOT_MASK_WORDS];
+        for (id, slot) in self.sample_bank.iter().enumerate() {
+            if slot.is_some() {
+                mask[id / 64] |= 1 << (id % 64);
+            }
+        }
+        mask
+    }
+
+    /// Whether the sample has a voice that is not fading out after a stop.
+    pub(crate) fn sample_is_active(&self, id: usize) -> bool {
         self.voices
             .iter()
-            .any(|voice| voice.active && voice.sample_id == id)
+            .zip(&self.voice_fading_out)
+            .any(|(voice, fading_out)| voice.active && voice.sample_id == id && !fading_out)
+    }
+
+    /// Current processing latency of a pad in output frames.
+    ///
+    /// Reports the slowest active voice of the pad plus the pad's delay; without a delay, idle
+    /// pads and dry varispeed voices report 0.
+    pub fn pad_latency_frames(&self, id: usize) -> usize {
+        let processing_frames = self
+            .voices
+            .iter()
+            .filter(|voice| voice.active && voice.sample_id == id)
+            .map(|voice| voice.stretch.latency_frames())
+            .max()
+            .unwrap_or(0);
+        let delay_frames = self
+            .pad_delays
+            .get(id)
+            .map_or(0, |delay| delay.delay_frames);
+        processing_frames + delay_frames
+    }
+
+    /// Output frames and seconds until a one-shot pad's voices end, or None while it loops.
+    ///
+    /// The source frames left are divided by each voice's current playback rate, so speed,
+    /// BPM sync and varispeed transposition count; the longest voice wins. Idle pads report
+    /// 0, and paused voices what they have left once resumed.
+    pub fn pad_remaining(&self, id: usize) -> Option<(usize, f32)> {
+        if id >= NUM_SAMPLES || self.channels == 0 {
+            return Some((0, 0.0));
+        }
+
+        let (source_id, pitch_ratio) = keyboard_source(&self.keyboard_modes, id);
+        let Some(sample) = self.sample_bank[source_id].as_ref() else {
+            return Some((0, 0.0));
+        };
+        let sample_frames = sample.samples.len() / self.channels;
+        let Some(loop_region) = self.effective_loop_region(source_id, sample_frames) else {
+            return Some((0, 0.0));
+        };
+        let key_lock =
+            self.pad_key_lock_enabled[id] && loop_region.len() >= KEY_LOCK_MIN_LOOP_FRAMES;
+
+        let mut voices = self
+            .voices
+            .iter()
+            .filter(|voice| {
+                voice.active
+                    && voice.sample_id == id
+                    && voice.plays_generation(self.sample_generations[source_id])
+            })
+            .peekable();
+        if voices.peek().is_none() {
+            return Some((0, 0.0));
+        }
+        let pad_loop_mode = self.pad_loop_mode[id].unwrap_or(self.default_loop_mode);
+        if voices
+            .clone()
+            .any(|voice| voice.loop_mode.unwrap_or(pad_loop_mode) != LoopMode::OneShot)
+        {
+            return None;
+        }
+
+        let frames = voices
+            .map(|voice| {
+                let tempo_ratio = voice.tempo_ratio_smoothed();
+                let source_rate = if key_lock {
+                    tempo_ratio
+                } else {
+                    (tempo_ratio * pitch_ratio).clamp(SPEED_MIN, SPEED_MAX)
+                };
+                let source_frames = one_shot_remaining_frames(
+                    voice.frame_pos,
+                    sample_frames,
+                    loop_region,
+                    voice.explicit_seek_mode,
+                );
+                (source_frames as f64 / f64::from(source_rate)).ceil() as usize
+            })
+            .max()
+            .unwrap_or(0);
+        Some((frames, frames as f32 / self.sample_rate_hz))
     }
 
     pub(crate) fn can_play_sample(&self, id: usize, velocity: f32) -> bool {
         id < NUM_SAMPLES
             && velocity.is_finite()
             && (VOLUME_MIN..=VOLUME_MAX).contains(&velocity)
-            && self.sample_bank[id].is_some()
+            && self.sample_bank[keyboard_source(&self.keyboard_modes, id).0].is_some()
     }
 
     /// Starts playback of a loaded sample.
@@ -918,39 +1933,16 @@ impl RtMixer {
     /// If no free voice slot is available, the playback request is silently dropped.
     #[cfg(test)]
     pub(crate) fn play_sample(&mut self, id: usize, velocity: f32) -> bool {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.play_sample_rt(id, velocity, &mut retirement)
-    }
-
-    #[cfg(test)]
-    pub(crate) fn play_sample_rt(
-        &mut self,
-        id: usize,
-        velocity: f32,
-        retirement: &mut impl AudioBufferRetirement,
-    ) -> bool {
-        self.play_sample_with_phase_rt(id, velocity, None, None, retirement)
-    }
-
-    pub(crate) fn play_sample_at_output_frame_rt(
-        &mut self,
-        id: usize,
-        velocity: f32,
-        output_frame: u64,
-        retirement: &mut impl AudioBufferRetirement,
-    ) -> bool {
-        self.play_sample_with_phase_rt(id, velocity, None, Some(output_frame), retirement)
+        self.play_sample_with_phase(id, velocity, None, None)
     }
 
-    #[cfg(test)]
     pub(crate) fn play_sample_at_output_frame(
         &mut self,
         id: usize,
         velocity: f32,
         output_frame: u64,
     ) -> bool {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.play_sample_at_output_frame_rt(id, velocity, output_frame, &mut retirement)
+        self.play_sample_with_phase(id, velocity, None, Some(output_frame))
     }
 
     #[cfg(test)]
@@ -960,140 +1952,685 @@ impl RtMixer {
         velocity: f32,
         target_bar_phase_beats: f64,
     ) -> bool {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.play_sample_with_phase_rt(
-            id,
-            velocity,
-            Some(target_bar_phase_beats),
-            None,
-            &mut retirement,
-        )
+        self.play_sample_with_phase(id, velocity, Some(target_bar_phase_beats), None)
     }
 
-    fn play_sample_with_phase_rt(
+    fn play_sample_with_phase(
         &mut self,
         id: usize,
         velocity: f32,
         target_bar_phase_beats: Option<f64>,
         start_output_frame: Option<u64>,
-        retirement: &mut impl AudioBufferRetirement,
     ) -> bool {
         if !self.can_play_sample(id, velocity) {
             return false;
         }
 
-        let Some(sample) = self.sample_bank[id].as_ref() else {
+        let (source_id, _) = keyboard_source(&self.keyboard_modes, id);
+        let Some(sample) = self.sample_bank[source_id].as_ref() else {
             return false;
         };
-        let sample = sample.clone();
+        let sample_generation = self.sample_generations[source_id];
 
-        let tempo_ratio = self.tempo_ratio_for_sample_id(id);
+        let tempo_ratio = self.tempo_ratio_for_sample_id(source_id);
 
         let sample_frames = sample.samples.len() / self.channels;
-        let initial_frame_pos = target_bar_phase_beats
-            .map(|phase| self.phase_aligned_initial_sample_frame(id, sample_frames, phase))
-            .unwrap_or_else(|| self.effective_loop_start_frame(id, sample_frames));
+        let initial_frame_pos = match target_bar_phase_beats {
+            // Phase-aligned starts stay on the grid; snapping them would break sync.
+            Some(phase) if source_id == id => {
+                self.phase_aligned_initial_sample_frame(id, sample_frames, phase)
+            }
+            _ => {
+                let frame = self.next_slice_start_frame(source_id, sample_frames);
+                match self.effective_loop_region(source_id, sample_frames) {
+                    Some(region) => self.snap_to_transient(id, source_id, frame, region),
+                    None => frame,
+                }
+            }
+        };
 
         // Sample is already playing? -> reset play position
-        for voice_slot in &mut self.voices {
+        for (index, voice_slot) in self.voices.iter_mut().enumerate() {
             if voice_slot.active && voice_slot.sample_id == id {
                 self.stem_transitions[id].clear();
                 voice_slot.restart(initial_frame_pos, velocity, tempo_ratio, start_output_frame);
+                self.start_voice_fade_in(index, id);
                 return true;
             }
         }
 
-        // Start new voice slot
-        for voice_slot in &mut self.voices {
-            if !voice_slot.active {
-                self.stem_transitions[id].clear();
-                self.pad_dsp_chains[id].reset();
-                voice_slot.start_rt(
-                    id,
-                    sample.clone(),
-                    initial_frame_pos,
-                    velocity,
-                    tempo_ratio,
-                    start_output_frame,
-                    retirement,
-                );
-                return true;
-            }
+        // Start new voice slot, stealing one from a lower-priority pad if the pool is full.
+        if self.playing_voice_count() >= MAX_VOICES {
+            // Nothing lower to steal: drop deterministically.
+            let Some(stolen) = self.steal_voice_index(id) else {
+                return false;
+            };
+            let stolen_id = self.voices[stolen].sample_id;
+            self.fade_out_voice(stolen);
+            self.last_voice_steal = Some(VoiceSteal {
+                id,
+                priority: self.pad_priority[id],
+                stolen_id,
+                stolen_priority: self.pad_priority[stolen_id],
+            });
         }
+        let index = self.claim_voice_slot();
 
-        // No free voice slot: drop deterministically.
-        false
+        self.stem_transitions[id].clear();
+        self.pad_dsp_chains[id].reset();
+        self.voices[index].start(
+            id,
+            sample_generation,
+            initial_frame_pos,
+            velocity,
+            tempo_ratio,
+            start_output_frame,
+        );
+        self.start_voice_fade_in(index, id);
+        true
     }
 
-    /// Sets the global volume multiplier.
-    ///
-    /// # Parameters
-    ///
-    /// - `volume`: Volume multiplier (0.0 to 1.0)
+    /// Voices that are playing rather than finishing a fade-out.
+    fn playing_voice_count(&self) -> usize {
+        self.voices
+            .iter()
+            .zip(&self.voice_fading_out)
+            .filter(|(voice, fading_out)| voice.active && !**fading_out)
+            .count()
+    }
+
+    /// A free voice slot, or with every slot busy the quietest fading voice, cut short.
     ///
-    /// Invalid values (NaN, infinite, or out of range) are silently ignored.
-    pub fn set_volume(&mut self, volume: f32) {
-        if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
-            return;
+    /// At most `MAX_VOICES` voices play, so a full bank always holds fading voices.
+    fn claim_voice_slot(&mut self) -> usize {
+        if let Some(index) = self.voices.iter().position(|voice| !voice.active) {
+            return index;
         }
 
-        self.volume = volume;
+        let index = (0..self.voices.len())
+            .filter(|&index| self.voice_fading_out[index])
+            .min_by(|&a, &b| {
+                self.voice_fades[a]
+                    .current
+                    .total_cmp(&self.voice_fades[b].current)
+            })
+            .unwrap_or(0);
+        self.voices[index].stop();
+        index
     }
 
-    /// Sets the global speed multiplier.
-    ///
-    /// # Parameters
+    /// Voice of the lowest-priority pad below `id`'s priority; the first slot wins ties.
     ///
-    /// - `speed`: Speed multiplier
-    ///
-    /// Invalid values (NaN, infinite, or out of range) are silently ignored.
-    pub fn set_speed(&mut self, speed: f32) {
-        if !speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed) {
-            return;
+    /// Without one, the steal policy picks among voices of pads with `id`'s priority.
+    fn steal_voice_index(&self, id: usize) -> Option<usize> {
+        let priority = self.pad_priority[id];
+        let lower = self
+            .voices
+            .iter()
+            .enumerate()
+            .filter(|&(index, voice)| {
+                voice.active
+                    && !self.voice_fading_out[index]
+                    && self.pad_priority[voice.sample_id] < priority
+            })
+            .min_by_key(|(_, voice)| self.pad_priority[voice.sample_id])
+            .map(|(index, _)| index);
+        if lower.is_some() {
+            return lower;
         }
 
-        self.speed = speed;
+        let equal = (0..self.voices.len()).filter(|&index| {
+            let voice = &self.voices[index];
+            voice.active
+                && !self.voice_fading_out[index]
+                && self.pad_priority[voice.sample_id] == priority
+        });
+        match self.voice_steal_policy {
+            VoiceStealPolicy::Silent => None,
+            VoiceStealPolicy::StealOldest => {
+                equal.min_by(|&a, &b| self.voice_progress(b).total_cmp(&self.voice_progress(a)))
+            }
+            VoiceStealPolicy::StealQuietest => {
+                equal.min_by(|&a, &b| self.voice_gain(a).total_cmp(&self.voice_gain(b)))
+            }
+        }
     }
 
-    pub fn set_bpm_lock(&mut self, enabled: bool) {
-        self.bpm_lock_enabled = enabled;
-        if !enabled {
-            self.master_bpm = None;
+    /// How far a voice is through its sample, from 0.0 to 1.0.
+    fn voice_progress(&self, index: usize) -> f32 {
+        let voice = &self.voices[index];
+        let (source_id, _) = keyboard_source(&self.keyboard_modes, voice.sample_id);
+        let sample_frames = self.sample_bank[source_id]
+            .as_ref()
+            .map_or(0, |sample| sample.samples.len() / self.channels.max(1));
+        if sample_frames == 0 {
+            return 1.0;
         }
+        voice.frame_pos as f32 / sample_frames as f32
     }
 
-    pub fn set_key_lock(&mut self, enabled: bool) {
-        self.pad_key_lock_enabled.fill(enabled);
+    /// Current linear gain of a voice from its velocity, pad gain and fade.
+    fn voice_gain(&self, index: usize) -> f32 {
+        let voice = &self.voices[index];
+        voice.volume
+            * self.pad_gain_smoothers[voice.sample_id].current
+            * self.voice_fades[index].current
     }
 
-    pub fn set_pad_key_lock(&mut self, id: usize, enabled: bool) {
+    /// Takes the steal made by the last trigger, if it needed one.
+    pub(crate) fn take_voice_steal(&mut self) -> Option<VoiceSteal> {
+        self.last_voice_steal.take()
+    }
+
+    pub fn set_pad_priority(&mut self, id: usize, priority: u8) {
         if id >= NUM_SAMPLES {
             return;
         }
 
-        self.pad_key_lock_enabled[id] = enabled;
+        self.pad_priority[id] = priority;
     }
 
-    pub fn set_master_bpm(&mut self, bpm: f32) {
-        if !bpm.is_finite() || bpm <= 0.0 {
-            return;
-        }
+    pub fn set_voice_steal_policy(&mut self, policy: VoiceStealPolicy) {
+        self.voice_steal_policy = policy;
+    }
 
-        self.master_bpm = Some(bpm);
+    pub fn set_default_loop_mode(&mut self, mode: LoopMode) {
+        self.default_loop_mode = mode;
     }
 
-    pub fn set_pad_bpm(&mut self, id: usize, bpm: Option<f32>) {
+    pub fn set_pad_loop_mode(&mut self, id: usize, mode: Option<LoopMode>) {
         if id >= NUM_SAMPLES {
             return;
         }
 
-        let bpm = bpm.and_then(|value| {
-            if !value.is_finite() || value <= 0.0 {
-                None
-            } else {
-                Some(value)
-            }
-        });
+        self.pad_loop_mode[id] = mode;
+    }
+
+    /// Takes a pad whose one-shot voice reached its end since the last call.
+    pub(crate) fn take_ended_one_shot(&mut self) -> Option<usize> {
+        let id = self.one_shot_ended.iter().position(|ended| *ended)?;
+        self.one_shot_ended[id] = false;
+        Some(id)
+    }
+
+    fn start_voice_fade_in(&mut self, index: usize, id: usize) {
+        let fade_in_ms = match (self.pad_fades[id], self.velocity_attack) {
+            (Some(fades), _) => fades.fade_in_ms,
+            (None, Some(attack)) => velocity_attack_ms(attack, self.voices[index].volume),
+            (None, None) => 0.0,
+        };
+        self.voice_fades[index].ramp_from(0.0, 1.0, self.sample_rate_hz, fade_in_ms);
+        self.voice_fading_out[index] = false;
+        self.voice_cue_only[index] = false;
+        self.voice_master_sends[index].set_target_linear(1.0, self.sample_rate_hz, 0.0);
+    }
+
+    /// Starts a voice that only feeds the cue bus until [`Self::promote_to_master`].
+    pub(crate) fn play_sample_cued_at_output_frame(
+        &mut self,
+        id: usize,
+        velocity: f32,
+        output_frame: u64,
+    ) -> bool {
+        if !self.play_sample_at_output_frame(id, velocity, output_frame) {
+            return false;
+        }
+
+        for (index, voice_slot) in self.voices.iter().enumerate() {
+            if voice_slot.is_playing_sample(id) && !self.voice_fading_out[index] {
+                self.voice_cue_only[index] = true;
+                self.voice_master_sends[index].set_target_linear(0.0, self.sample_rate_hz, 0.0);
+            }
+        }
+        true
+    }
+
+    /// Starts a voice whose settings in `options` override the pad's.
+    ///
+    /// A speed override is clamped to the speed range together with the pad's rate.
+    pub(crate) fn play_sample_with_at_output_frame(
+        &mut self,
+        id: usize,
+        velocity: f32,
+        options: TriggerOptions,
+        output_frame: u64,
+    ) -> bool {
+        if options.speed.is_some_and(|speed| !speed.is_finite())
+            || options
+                .start_s
+                .is_some_and(|start_s| !start_s.is_finite() || start_s < 0.0)
+            || options.pan.is_some_and(|pan| !(-1.0..=1.0).contains(&pan))
+            || !self.play_sample_at_output_frame(id, velocity, output_frame)
+        {
+            return false;
+        }
+
+        let start = options
+            .start_s
+            .and_then(|start_s| self.trigger_start_frame(id, start_s, options.loop_mode));
+        for (index, voice_slot) in self.voices.iter_mut().enumerate() {
+            if voice_slot.is_playing_sample(id) && !self.voice_fading_out[index] {
+                if let Some((frame, seek_mode)) = start {
+                    voice_slot.seek(frame, seek_mode, Some(output_frame));
+                }
+                if let Some(speed) = options.speed {
+                    voice_slot.set_trigger_speed(speed.clamp(SPEED_MIN, SPEED_MAX));
+                }
+                voice_slot.loop_mode = options.loop_mode;
+                voice_slot.pan = options.pan;
+            }
+        }
+        if let Some((frame, _)) = start {
+            self.pad_playhead_frame[id] = Some(frame);
+        }
+        true
+    }
+
+    /// Source frame and seek mode of a trigger starting `start_s` seconds into pad `id`.
+    ///
+    /// Offsets past the end wrap around the sample for looping voices and clamp to its last
+    /// frame for one-shots.
+    fn trigger_start_frame(
+        &self,
+        id: usize,
+        start_s: f32,
+        loop_mode: Option<LoopMode>,
+    ) -> Option<(usize, ExplicitSeekMode)> {
+        let (source_id, _) = keyboard_source(&self.keyboard_modes, id);
+        let sample_frames = self.sample_bank[source_id].as_ref()?.samples.len() / self.channels;
+        if sample_frames == 0 {
+            return None;
+        }
+        let loop_region = self.effective_loop_region(source_id, sample_frames)?;
+        let loop_mode = loop_mode
+            .or(self.pad_loop_mode[id])
+            .unwrap_or(self.default_loop_mode);
+
+        let frame = (f64::from(start_s) * f64::from(self.sample_rate_hz)).round() as usize;
+        let frame = match loop_mode {
+            LoopMode::Loop => frame % sample_frames,
+            LoopMode::OneShot => frame.min(sample_frames - 1),
+        };
+        Some((
+            frame,
+            explicit_seek_mode_for_frame(frame, loop_region, sample_frames),
+        ))
+    }
+
+    /// Fades the cue-only voices of a sample into the master mix, keeping their playhead.
+    ///
+    /// Returns whether any voice was promoted.
+    pub fn promote_to_master(&mut self, id: usize) -> bool {
+        let mut promoted = false;
+        for (index, voice_slot) in self.voices.iter().enumerate() {
+            if voice_slot.is_playing_sample(id) && self.voice_cue_only[index] {
+                self.voice_cue_only[index] = false;
+                self.voice_master_sends[index].set_target_linear(
+                    1.0,
+                    self.sample_rate_hz,
+                    CUE_PROMOTE_MS,
+                );
+                promoted = true;
+            }
+        }
+
+        promoted
+    }
+
+    pub fn set_pad_cue(&mut self, id: usize, enabled: bool) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        self.pad_cue[id] = enabled;
+    }
+
+    pub fn set_cue_split(&mut self, enabled: bool) {
+        self.cue_split = enabled;
+    }
+
+    /// Route a monitor signal to output channels `first_channel` and `first_channel + 1`.
+    ///
+    /// The pair then carries only monitor signals, never the main mix. While the device has
+    /// too few channels for the pair, the preview falls back to the main mix and the cue bus
+    /// to split cue, or is muted without it.
+    pub fn set_monitor_output(&mut self, source: MonitorSource, first_channel: Option<usize>) {
+        match source {
+            MonitorSource::Preview => self.preview_output = first_channel,
+            MonitorSource::Cue => self.cue_output = first_channel,
+        }
+    }
+
+    /// The routed pair's first channel if the device has both channels and the pair stays
+    /// clear of the main mix on channels 0 and 1.
+    fn monitor_pair(&self, first_channel: Option<usize>) -> Option<usize> {
+        first_channel.filter(|first| *first >= 2 && first.saturating_add(1) < self.output_channels)
+    }
+
+    /// Sets the global volume multiplier.
+    ///
+    /// # Parameters
+    ///
+    /// - `volume`: Volume multiplier (0.0 to 1.0)
+    ///
+    /// Invalid values (NaN, infinite, or out of range) are silently ignored.
+    pub fn set_volume(&mut self, volume: f32) {
+        if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
+            return;
+        }
+
+        self.volume_ramp = None;
+        self.volume = volume;
+    }
+
+    /// Sets the global speed multiplier.
+    ///
+    /// # Parameters
+    ///
+    /// - `speed`: Speed multiplier
+    ///
+    /// Invalid values (NaN, infinite, or out of range) are silently ignored.
+    pub fn set_speed(&mut self, speed: f32) {
+        if !speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed) {
+            return;
+        }
+
+        self.speed = speed;
+    }
+
+    /// Enables or disables the DC blocker on the final master output.
+    pub fn set_master_dc_blocker(&mut self, enabled: bool) {
+        if enabled && !self.master_dc_blocker_enabled {
+            self.master_dc_blocker.reset();
+        }
+
+        self.master_dc_blocker_enabled = enabled;
+    }
+
+    /// Enables or disables the peak limiter on the final master output.
+    pub fn set_master_limiter(&mut self, enabled: bool) {
+        if enabled != self.master_limiter_enabled {
+            self.master_limiter.reset();
+        }
+
+        self.master_limiter_enabled = enabled;
+    }
+
+    /// Current and recent-peak gain reduction of the master limiter in dB.
+    ///
+    /// Both are exactly 0.0 while the limiter is idle or disabled.
+    pub fn limiter_reduction_db(&self) -> (f32, f32) {
+        (
+            self.master_limiter.reduction_db(),
+            self.master_limiter.peak_reduction_db(),
+        )
+    }
+
+    pub fn set_limiter_meter_events(&mut self, enabled: bool) {
+        self.limiter_meter_events = enabled;
+    }
+
+    pub(crate) fn limiter_meter_events(&self) -> bool {
+        self.limiter_meter_events
+    }
+
+    /// Enables or disables the mono sum on stereo outputs.
+    ///
+    /// Unlike width, this is a hard sum of both channels, so mono compatibility does not depend
+    /// on any panning or width setting. It has no effect on mono devices or while split cue uses
+    /// the second channel. The output trims of both channels apply before the sum.
+    pub fn set_mono_output(&mut self, enabled: bool) {
+        self.mono_output = enabled;
+    }
+
+    /// Sets the tempo glide time constant in milliseconds (0 to TEMPO_GLIDE_MS_MAX).
+    pub fn set_tempo_glide_ms(&mut self, glide_ms: f32) -> bool {
+        if !glide_ms.is_finite() || !(0.0..=TEMPO_GLIDE_MS_MAX).contains(&glide_ms) {
+            return false;
+        }
+
+        self.tempo_glide_ms = glide_ms;
+        true
+    }
+
+    /// Sets the stop fade length in output frames (0 to STOP_FADE_SAMPLES_MAX).
+    ///
+    /// Overrides the default of `STOP_FADE_MS_DEFAULT` at the output rate.
+    pub fn set_stop_fade_samples(&mut self, samples: u32) -> bool {
+        if samples > STOP_FADE_SAMPLES_MAX {
+            return false;
+        }
+
+        self.stop_fade_samples = samples;
+        true
+    }
+
+    #[cfg(test)]
+    pub(crate) fn stop_fade_samples(&self) -> u32 {
+        self.stop_fade_samples
+    }
+
+    /// Sets the calibration trim of one physical output channel (0.0 to OUTPUT_TRIM_MAX).
+    pub fn set_output_trim(&mut self, channel: usize, gain: f32) -> bool {
+        if channel >= self.output_channels.min(self.output_trims.len())
+            || !gain.is_finite()
+            || !(0.0..=OUTPUT_TRIM_MAX).contains(&gain)
+        {
+            return false;
+        }
+
+        self.output_trims[channel] = gain;
+        true
+    }
+
+    pub fn set_bpm_lock(&mut self, enabled: bool) {
+        self.bpm_lock_enabled = enabled;
+        if !enabled {
+            self.master_bpm = None;
+        }
+    }
+
+    pub fn set_key_lock(&mut self, enabled: bool) {
+        self.pad_key_lock_enabled.fill(enabled);
+    }
+
+    pub fn set_pad_key_lock(&mut self, id: usize, enabled: bool) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        self.pad_key_lock_enabled[id] = enabled;
+    }
+
+    /// Scale a pad's synced or speed-scaled rate by exactly `numerator / denominator`, or
+    /// return it to plain speed and BPM sync with `None`. Terms must be within
+    /// `1..=PAD_RATE_RATIO_TERM_MAX`.
+    pub fn set_pad_rate_ratio(&mut self, id: usize, ratio: Option<(u16, u16)>) -> bool {
+        if id >= NUM_SAMPLES || ratio.is_some_and(|ratio| !is_valid_rate_ratio(ratio)) {
+            return false;
+        }
+
+        self.pad_rate_ratio[id] = ratio;
+        true
+    }
+
+    /// Map every pad of `bank` onto `source` transposed by its semitone distance from
+    /// `root_pad`, or restore the bank's own slots with `None`.
+    ///
+    /// Voices of the bank are stopped, since they play the previous mapping. Returns whether
+    /// the mode was applied.
+    pub fn set_keyboard_mode(&mut self, bank: usize, mode: Option<(usize, usize)>) -> bool {
+        if !is_valid_keyboard_mode(bank, mode) {
+            return false;
+        }
+
+        for voice_slot in &mut self.voices {
+            if voice_slot.active && voice_slot.sample_id / NUM_PADS == bank {
+                voice_slot.stop();
+            }
+        }
+        self.keyboard_modes[bank] =
+            mode.map(|(source, root_pad)| KeyboardMode { source, root_pad });
+        true
+    }
+
+    /// Override a pad's trigger, stop and mute fade times, or restore the defaults with `None`.
+    pub fn set_pad_fades(&mut self, id: usize, fades: Option<PadFades>) -> bool {
+        if id >= NUM_SAMPLES || fades.is_some_and(|fades| !is_valid_pad_fades(fades)) {
+            return false;
+        }
+
+        self.pad_fades[id] = fades;
+        true
+    }
+
+    /// Map the trigger fade-in of pads without their own fades from velocity, or restore the
+    /// instant attack with `None`.
+    ///
+    /// The length is interpolated linearly between `soft_ms` at velocity 0 and `hard_ms` at
+    /// full velocity, and fixed when the voice starts.
+    pub fn set_velocity_attack(&mut self, attack: Option<VelocityAttack>) -> bool {
+        if attack.is_some_and(|attack| !is_valid_velocity_attack(attack)) {
+            return false;
+        }
+
+        self.velocity_attack = attack;
+        true
+    }
+
+    /// Delays a pad's output by `delay_ms` for time alignment; 0 bypasses the delay line.
+    ///
+    /// Changes crossfade between the old and the new delay over `PAD_DELAY_CROSSFADE_MS`. A
+    /// nonzero delay needs the pad's delay line from `set_pad_delay_line` first.
+    pub fn set_pad_delay(&mut self, id: usize, delay_ms: f32) -> bool {
+        if id >= NUM_SAMPLES || !(0.0..=PAD_DELAY_MS_MAX).contains(&delay_ms) {
+            return false;
+        }
+
+        self.apply_pad_delay_frames(id, pad_delay_ms_to_frames(delay_ms, self.sample_rate_hz))
+    }
+
+    /// Hands a pad the delay line built by `new_pad_delay_line`.
+    ///
+    /// A pad keeps its first line; callers send each pad's line once, as a rejected line is
+    /// dropped on this thread.
+    pub(crate) fn set_pad_delay_line(&mut self, id: usize, line: PadDelayLine) -> bool {
+        let expected_len = pad_delay_line_frames(self.sample_rate_hz) * self.output_channels;
+        if id >= NUM_SAMPLES || line.len() != expected_len || self.pad_delay_lines[id].is_some() {
+            return false;
+        }
+
+        self.pad_delay_lines[id] = Some(line);
+        true
+    }
+
+    /// Pan pads across the speakers of an output wider than stereo, or play every pad on the
+    /// first two channels again with `None`.
+    ///
+    /// The layout needs one azimuth per output channel.
+    pub(crate) fn set_speaker_layout(&mut self, layout: Option<SpeakerLayout>) -> bool {
+        if layout.is_some_and(|layout| layout.speakers != self.output_channels) {
+            return false;
+        }
+
+        self.speaker_layout = layout;
+        for id in 0..NUM_SAMPLES {
+            self.update_pad_speaker_gains(id);
+        }
+        true
+    }
+
+    /// Place a pad at `azimuth_degrees` on the speaker layout, or back on the first two
+    /// channels with `None`.
+    ///
+    /// A placed pad plays the mono sum of its channels. Without a speaker layout, as on
+    /// stereo outputs, the position is kept but has no effect.
+    pub fn set_pad_position(&mut self, id: usize, azimuth_degrees: Option<f32>) -> bool {
+        if id >= NUM_SAMPLES || azimuth_degrees.is_some_and(|azimuth| !azimuth.is_finite()) {
+            return false;
+        }
+
+        self.pad_azimuth[id] = azimuth_degrees;
+        self.update_pad_speaker_gains(id);
+        true
+    }
+
+    fn update_pad_speaker_gains(&mut self, id: usize) {
+        self.pad_speaker_gains[id] = self
+            .speaker_layout
+            .zip(self.pad_azimuth[id])
+            .map(|(layout, azimuth)| layout.gains(azimuth));
+    }
+
+    fn apply_pad_delay_frames(&mut self, id: usize, delay_frames: usize) -> bool {
+        if delay_frames > 0 && self.pad_delay_lines[id].is_none() {
+            return false;
+        }
+
+        let crossfade_frames =
+            pad_delay_ms_to_frames(PAD_DELAY_CROSSFADE_MS, self.sample_rate_hz).max(1);
+        self.pad_delays[id].set_delay(delay_frames, crossfade_frames);
+        true
+    }
+
+    /// Enables or disables the soft clip on a pad's voices.
+    ///
+    /// The ceiling must be within `PAD_CLIP_CEILING_MIN..=1.0`, even when disabling.
+    pub fn set_pad_clip(&mut self, id: usize, enabled: bool, ceiling: f32) -> bool {
+        if id >= NUM_SAMPLES || !is_valid_pad_clip_ceiling(ceiling) {
+            return false;
+        }
+
+        self.pad_clip_ceiling[id] = enabled.then_some(ceiling);
+        true
+    }
+
+    pub fn set_pad_choke_group(&mut self, id: usize, group: Option<u8>) {
+        if id >= NUM_SAMPLES || group.is_some_and(|group| group as usize >= NUM_CHOKE_GROUPS) {
+            return;
+        }
+
+        self.pad_choke_group[id] = group;
+    }
+
+    pub fn set_choke_group_stop_all(&mut self, group: u8, enabled: bool) {
+        let Some(stop_all) = self.choke_group_stop_all.get_mut(group as usize) else {
+            return;
+        };
+
+        *stop_all = enabled;
+    }
+
+    /// Returns the choke group of `id` when stopping it should stop the whole group.
+    pub(crate) fn choke_group_stopping_with(&self, id: usize) -> Option<u8> {
+        let group = (*self.pad_choke_group.get(id)?)?;
+        self.choke_group_stop_all[group as usize].then_some(group)
+    }
+
+    pub(crate) fn pad_choke_group(&self, id: usize) -> Option<u8> {
+        self.pad_choke_group.get(id).copied().flatten()
+    }
+
+    pub fn set_master_bpm(&mut self, bpm: f32) {
+        if !bpm.is_finite() || bpm <= 0.0 {
+            return;
+        }
+
+        self.master_bpm = Some(bpm);
+    }
+
+    pub fn set_pad_bpm(&mut self, id: usize, bpm: Option<f32>) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        let bpm = bpm.and_then(|value| {
+            if !value.is_finite() || value <= 0.0 {
+                None
+            } else {
+                Some(value)
+            }
+        });
 
         self.pad_bpm[id] = bpm;
     }
@@ -1176,6 +2713,23 @@ impl RtMixer {
         frame.round() as usize
     }
 
+    /// Start frame for a plain trigger, advancing the pad's slice cursor when slicing is on.
+    fn next_slice_start_frame(&mut self, id: usize, sample_frames: usize) -> usize {
+        let slices = self.pad_slices[id];
+        let Some(region) = self
+            .effective_loop_region(id, sample_frames)
+            .filter(|_| slices > 1)
+        else {
+            return self.effective_loop_start_frame(id, sample_frames);
+        };
+
+        let slice_index = self.pad_next_slice[id] % slices;
+        self.pad_next_slice[id] = (slice_index + 1) % slices;
+
+        let region_frames = region.end - region.start;
+        region.start + usize::from(slice_index) * region_frames / usize::from(slices)
+    }
+
     fn effective_loop_start_frame(&self, id: usize, sample_frames: usize) -> usize {
         self.effective_loop_region(id, sample_frames)
             .map(|region| region.start)
@@ -1202,64 +2756,276 @@ impl RtMixer {
         }
     }
 
-    fn phase_alignment_loop_region(&self, id: usize, sample_frames: usize) -> Option<FrameRange> {
-        if id >= NUM_SAMPLES || sample_frames == 0 {
-            return None;
-        }
+    fn phase_alignment_loop_region(&self, id: usize, sample_frames: usize) -> Option<FrameRange> {
+        if id >= NUM_SAMPLES || sample_frames == 0 {
+            return None;
+        }
+
+        let start = self.pad_loop_start_frame[id];
+        if start >= sample_frames {
+            return None;
+        }
+
+        let end = self.pad_loop_end_frame[id]
+            .unwrap_or(sample_frames)
+            .min(sample_frames);
+        if end <= start {
+            return None;
+        }
+
+        Some(FrameRange { start, end })
+    }
+
+    pub fn set_pad_gain(&mut self, id: usize, gain_db: f32) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        if !gain_db.is_finite() || !(PAD_GAIN_DB_MIN..=PAD_GAIN_DB_MAX).contains(&gain_db) {
+            return;
+        }
+
+        self.pad_gain_ramps[id] = None;
+        self.apply_pad_gain_db(id, gain_db);
+    }
+
+    fn apply_pad_gain_db(&mut self, id: usize, gain_db: f32) {
+        self.pad_gain_db[id] = gain_db;
+        let smooth = self.sample_is_active(id);
+        self.pad_gain_smoothers[id].set_target_db(gain_db, self.sample_rate_hz, smooth);
+    }
+
+    /// Fade a pad to silence or back in without stopping its voices.
+    ///
+    /// The ramp takes `PAD_FADE_MUTE_MS`, or applies at once when the pad is idle.
+    pub fn set_pad_fade_mute(&mut self, id: usize, muted: bool) -> bool {
+        if id >= NUM_SAMPLES {
+            return false;
+        }
+
+        let target = if muted { 0.0 } else { 1.0 };
+        let ramp_ms = match self.pad_fades[id] {
+            _ if !self.sample_is_active(id) => 0.0,
+            Some(fades) if muted => fades.fade_out_ms,
+            Some(fades) => fades.fade_in_ms,
+            None => PAD_FADE_MUTE_MS,
+        };
+        self.pad_fade_mutes[id].set_target_linear(target, self.sample_rate_hz, ramp_ms);
+        true
+    }
+
+    /// Chop a pad into `slices` equal parts of its loop region.
+    ///
+    /// With more than one slice, every trigger starts at the next slice and wraps after the
+    /// last one; `1` restores plain triggering from the loop start. Resets the slice cursor.
+    pub fn set_pad_slices(&mut self, id: usize, slices: u16) -> bool {
+        if id >= NUM_SAMPLES || !(1..=PAD_SLICES_MAX).contains(&slices) {
+            return false;
+        }
+
+        self.pad_slices[id] = slices;
+        self.pad_next_slice[id] = 0;
+        true
+    }
+
+    /// Set the per-pad EQ band gains. Finite gains are clamped to
+    /// `PAD_EQ_DB_MIN..=PAD_EQ_DB_MAX`; non-finite gains leave the EQ untouched.
+    ///
+    /// Cancels any running EQ automation of the pad.
+    pub fn set_pad_eq(&mut self, id: usize, low_db: f32, mid_db: f32, high_db: f32) {
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        if [low_db, mid_db, high_db].iter().any(|v| !v.is_finite()) {
+            return;
+        }
+
+        self.pad_eq_db[id] =
+            [low_db, mid_db, high_db].map(|db| db.clamp(PAD_EQ_DB_MIN, PAD_EQ_DB_MAX));
+        self.pad_eq_automations[id] = [None; PAD_EQ_BANDS];
+
+        let low = pad_eq_db_to_normalized(low_db);
+        let mid = pad_eq_db_to_normalized(mid_db);
+        let high = pad_eq_db_to_normalized(high_db);
+
+        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot0, low);
+        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot1, mid);
+        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot2, high);
+
+        if !self.sample_is_active(id) {
+            self.pad_dsp_chains[id].reset();
+        }
+    }
+
+    /// Ramp one EQ band (0 low, 1 mid, 2 high) of a pad from its current gain to `target_db`.
+    ///
+    /// The target is clamped like `set_pad_eq`. The band gain is recomputed at the start of
+    /// every render chunk, and chunks are capped at `PAD_EQ_AUTOMATION_UPDATE_FRAMES` while any
+    /// automation runs. A zero duration jumps to the target through the usual smoothing.
+    pub(crate) fn automate_pad_eq(
+        &mut self,
+        id: usize,
+        band: usize,
+        target_db: f32,
+        duration_frames: u64,
+    ) -> bool {
+        if id >= NUM_SAMPLES || band >= PAD_EQ_BANDS || !target_db.is_finite() {
+            return false;
+        }
+
+        self.pad_eq_automations[id][band] = Some(ParameterRamp::new(
+            self.pad_eq_db[id][band],
+            target_db.clamp(PAD_EQ_DB_MIN, PAD_EQ_DB_MAX),
+            duration_frames,
+        ));
+        if duration_frames == 0 {
+            self.advance_parameter_ramps(0);
+        }
+        true
+    }
+
+    /// Stores the volume, pad gains, pad EQ, pad mutes, pad cue routing and pad delays in
+    /// `slot`.
+    pub(crate) fn store_snapshot(&mut self, slot: usize) -> bool {
+        if slot >= MIXER_SNAPSHOT_SLOTS {
+            return false;
+        }
+
+        self.snapshots[slot] = Some(MixerSnapshot {
+            volume: self.volume,
+            pad_gain_db: self.pad_gain_db,
+            pad_eq_db: self.pad_eq_db,
+            pad_muted: std::array::from_fn(|id| self.pad_fade_mutes[id].target <= 0.0),
+            pad_cue: self.pad_cue,
+            pad_delay_frames: std::array::from_fn(|id| self.pad_delays[id].delay_frames),
+        });
+        true
+    }
+
+    /// Morph from the current parameters to the snapshot in `slot` over `duration_frames`.
+    ///
+    /// Volume, pad gains and pad EQ ramp linearly like EQ automation; a later direct set of a
+    /// parameter cancels its ramp. Pads the snapshot unmutes or cues switch at the start of
+    /// the morph, pads it mutes or uncues at the end, so nothing drops out while fading. Pad
+    /// delays crossfade to the snapshot's at the start.
+    /// Returns false if the slot is empty.
+    pub(crate) fn morph_to_snapshot(&mut self, slot: usize, duration_frames: u64) -> bool {
+        let Some(snapshot) = self.snapshots.get(slot).copied().flatten() else {
+            return false;
+        };
+
+        self.volume_ramp = Some(ParameterRamp::new(
+            self.volume,
+            snapshot.volume,
+            duration_frames,
+        ));
+        for id in 0..NUM_SAMPLES {
+            self.pad_gain_ramps[id] = Some(ParameterRamp::new(
+                self.pad_gain_db[id],
+                snapshot.pad_gain_db[id],
+                duration_frames,
+            ));
+            for band in 0..PAD_EQ_BANDS {
+                self.pad_eq_automations[id][band] = Some(ParameterRamp::new(
+                    self.pad_eq_db[id][band],
+                    snapshot.pad_eq_db[id][band],
+                    duration_frames,
+                ));
+            }
+
+            if !snapshot.pad_muted[id] {
+                self.set_pad_fade_mute(id, false);
+            }
+            if snapshot.pad_cue[id] {
+                self.pad_cue[id] = true;
+            }
+            self.apply_pad_delay_frames(id, snapshot.pad_delay_frames[id]);
+        }
+
+        self.pending_snapshot_switches = Some(PendingSnapshotSwitches {
+            snapshot,
+            frames_remaining: duration_frames,
+        });
+        self.advance_parameter_ramps(0);
+        true
+    }
+
+    fn parameter_ramps_running(&self) -> bool {
+        self.volume_ramp.is_some()
+            || self.pending_snapshot_switches.is_some()
+            || self.pad_gain_ramps.iter().any(Option::is_some)
+            || self
+                .pad_eq_automations
+                .iter()
+                .flatten()
+                .any(Option::is_some)
+    }
 
-        let start = self.pad_loop_start_frame[id];
-        if start >= sample_frames {
-            return None;
+    /// Moves every running EQ automation and snapshot morph to where it is `frames` from now.
+    fn advance_parameter_ramps(&mut self, frames: u64) {
+        if let Some(ramp) = self.volume_ramp.as_mut() {
+            let (volume, finished) = ramp.advance(frames);
+            if finished {
+                self.volume_ramp = None;
+            }
+            self.volume = volume;
         }
 
-        let end = self.pad_loop_end_frame[id]
-            .unwrap_or(sample_frames)
-            .min(sample_frames);
-        if end <= start {
-            return None;
-        }
+        for id in 0..NUM_SAMPLES {
+            if let Some(ramp) = self.pad_gain_ramps[id].as_mut() {
+                let (gain_db, finished) = ramp.advance(frames);
+                if finished {
+                    self.pad_gain_ramps[id] = None;
+                }
+                self.apply_pad_gain_db(id, gain_db);
+            }
 
-        Some(FrameRange { start, end })
-    }
+            for band in 0..PAD_EQ_BANDS {
+                let Some(automation) = self.pad_eq_automations[id][band].as_mut() else {
+                    continue;
+                };
 
-    pub fn set_pad_gain(&mut self, id: usize, gain_db: f32) {
-        if id >= NUM_SAMPLES {
-            return;
-        }
+                let (db, finished) = automation.advance(frames);
+                if finished {
+                    self.pad_eq_automations[id][band] = None;
+                }
 
-        if !gain_db.is_finite() || !(PAD_GAIN_DB_MIN..=PAD_GAIN_DB_MAX).contains(&gain_db) {
-            return;
+                self.pad_eq_db[id][band] = db;
+                if let Some(slot) = pad_eq_band_slot(band) {
+                    self.set_pad_dsp_parameter(id, slot, pad_eq_db_to_normalized(db));
+                }
+            }
         }
 
-        self.pad_gain_db[id] = gain_db;
-        let smooth = self.sample_is_active(id);
-        self.pad_gain_smoothers[id].set_target_db(gain_db, self.sample_rate_hz, smooth);
+        if let Some(pending) = self.pending_snapshot_switches.as_mut() {
+            pending.frames_remaining = pending.frames_remaining.saturating_sub(frames);
+            if pending.frames_remaining == 0 {
+                let snapshot = pending.snapshot;
+                self.pending_snapshot_switches = None;
+                for id in 0..NUM_SAMPLES {
+                    if snapshot.pad_muted[id] {
+                        self.set_pad_fade_mute(id, true);
+                    }
+                    if !snapshot.pad_cue[id] {
+                        self.pad_cue[id] = false;
+                    }
+                }
+            }
+        }
     }
 
-    pub fn set_pad_eq(&mut self, id: usize, low_db: f32, mid_db: f32, high_db: f32) {
+    /// Clear the filter and time-stretch state of a pad without stopping its voices.
+    ///
+    /// Parameter smoothing snaps to the current targets. Playback positions are kept.
+    pub(crate) fn reset_pad_dsp(&mut self, id: usize) -> bool {
         if id >= NUM_SAMPLES {
-            return;
-        }
-
-        let all = [low_db, mid_db, high_db];
-        if all
-            .iter()
-            .any(|v| !v.is_finite() || !(PAD_EQ_DB_MIN..=PAD_EQ_DB_MAX).contains(v))
-        {
-            return;
+            return false;
         }
 
-        let low = pad_eq_db_to_normalized(low_db);
-        let mid = pad_eq_db_to_normalized(mid_db);
-        let high = pad_eq_db_to_normalized(high_db);
-
-        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot0, low);
-        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot1, mid);
-        self.set_pad_dsp_parameter(id, DspParameterSlot::Slot2, high);
-
-        if !self.sample_is_active(id) {
-            self.pad_dsp_chains[id].reset();
-        }
+        self.pad_dsp_chains[id].reset();
+        self.reset_voice_stretch_for_sample(id);
+        true
     }
 
     fn set_pad_dsp_parameter(
@@ -1322,6 +3088,37 @@ impl RtMixer {
         }
     }
 
+    /// Loop `start_s..end_s` of a loaded sample on the preview voice at its original rate.
+    ///
+    /// Replaces a running preview; the pad's own loop region and voices are left untouched.
+    pub fn preview_loop_region(&mut self, id: usize, start_s: f32, end_s: f32) -> bool {
+        if id >= NUM_SAMPLES || self.channels == 0 {
+            return false;
+        }
+        let Some(sample) = self.sample_bank[id].as_ref() else {
+            return false;
+        };
+
+        let sample_frames = sample.samples.len() / self.channels;
+        let start = self.source_frame_from_seconds(start_s, sample_frames);
+        let end = self.source_frame_from_seconds(end_s, sample_frames);
+        if end <= start {
+            return false;
+        }
+
+        self.preview_loop = Some(PreviewLoop {
+            sample_id: id,
+            sample_generation: self.sample_generations[id],
+            region: FrameRange { start, end },
+            frame_pos: start,
+        });
+        true
+    }
+
+    pub fn stop_preview_loop(&mut self) {
+        self.preview_loop = None;
+    }
+
     #[cfg(test)]
     pub fn seek_sample(&mut self, id: usize, position_s: f32) -> bool {
         self.seek_sample_with_output_frame(id, position_s, None)
@@ -1346,7 +3143,8 @@ impl RtMixer {
             return false;
         }
 
-        let Some(sample) = self.sample_bank[id].as_ref() else {
+        let (source_id, _) = keyboard_source(&self.keyboard_modes, id);
+        let Some(sample) = self.sample_bank[source_id].as_ref() else {
             return false;
         };
         let sample_frames = sample.samples.len() / self.channels;
@@ -1354,10 +3152,15 @@ impl RtMixer {
             return false;
         }
 
-        let Some(loop_region) = self.effective_loop_region(id, sample_frames) else {
+        let Some(loop_region) = self.effective_loop_region(source_id, sample_frames) else {
             return false;
         };
         let target_frame = self.source_frame_from_seconds(position_s, sample_frames);
+        let bounds = FrameRange {
+            start: 0,
+            end: sample_frames,
+        };
+        let target_frame = self.snap_to_transient(id, source_id, target_frame, bounds);
         let seek_mode = explicit_seek_mode_for_frame(target_frame, loop_region, sample_frames);
 
         let mut did_seek = false;
@@ -1392,7 +3195,9 @@ impl RtMixer {
             .voices
             .iter()
             .find(|voice| voice.active && !voice.paused && voice.sample_id == id)?;
-        let sample = voice.sample.as_ref()?;
+        let sample = self.sample_bank[id]
+            .as_ref()
+            .filter(|_| voice.plays_generation(self.sample_generations[id]))?;
         let sample_frames = sample.samples.len() / self.channels;
 
         self.pad_bar_phase_beats_at_frame(id, sample_frames, voice.frame_pos)
@@ -1449,6 +3254,9 @@ impl RtMixer {
         {
             ratio = master_bpm / pad_bpm;
         }
+        if let Some(rate_ratio) = self.pad_rate_ratio[sample_id] {
+            ratio *= rational_tempo_ratio(rate_ratio);
+        }
 
         if !ratio.is_finite() {
             ratio = 1.0;
@@ -1462,26 +3270,150 @@ impl RtMixer {
     /// # Parameters
     ///
     /// - `id`: Sample slot ID to stop
-    #[cfg(test)]
     pub(crate) fn stop_sample(&mut self, id: usize) {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.stop_sample_rt(id, &mut retirement);
+        if id >= NUM_SAMPLES {
+            return;
+        }
+
+        for index in 0..self.voices.len() {
+            if self.voices[index].is_playing_sample(id) {
+                self.fade_out_voice(index);
+            }
+        }
     }
 
-    pub(crate) fn stop_sample_rt(
-        &mut self,
-        id: usize,
-        retirement: &mut impl AudioBufferRetirement,
-    ) {
-        if id >= NUM_SAMPLES {
+    /// Stops the voices of a sample without a fade, before its buffer goes away.
+    fn stop_sample_now(&mut self, id: usize) {
+        for (index, voice_slot) in self.voices.iter_mut().enumerate() {
+            if voice_slot.is_playing_sample(id) {
+                voice_slot.stop();
+                self.voice_fading_out[index] = false;
+            }
+        }
+    }
+
+    /// Stops every voice, each fading out like in `stop_sample`.
+    pub(crate) fn stop_all(&mut self) {
+        for index in 0..self.voices.len() {
+            if self.voices[index].active {
+                self.fade_out_voice(index);
+            }
+        }
+    }
+
+    /// Fades a voice out over its pad's fade-out time, or the stop fade if the pad has none,
+    /// and frees it once silent. Paused voices stop right away.
+    fn fade_out_voice(&mut self, index: usize) {
+        if self.voice_fading_out[index] {
             return;
         }
 
+        let voice_slot = &mut self.voices[index];
+        let fade_out_ms =
+            self.pad_fades[voice_slot.sample_id].map_or(0.0, |fades| fades.fade_out_ms);
+        let voice_fade = &mut self.voice_fades[index];
+        if voice_slot.paused {
+            voice_slot.stop();
+            return;
+        }
+
+        // Fade from the current gain, so an unfinished fade-in does not jump up first.
+        if fade_out_ms > 0.0 {
+            voice_fade.set_target_linear(0.0, self.sample_rate_hz, fade_out_ms);
+        } else {
+            voice_fade.set_target_frames(0.0, self.stop_fade_samples as usize);
+        }
+
+        if voice_fade.is_silent() {
+            voice_slot.stop();
+        } else {
+            self.voice_fading_out[index] = true;
+        }
+    }
+
+    /// Mark the active voices of a sample to stop at `stop_frame`.
+    pub(crate) fn set_auto_stop_frame(&mut self, id: usize, stop_frame: u64) {
+        for voice_slot in &mut self.voices {
+            if voice_slot.is_playing_sample(id) {
+                voice_slot.auto_stop_frame = Some(stop_frame);
+            }
+        }
+    }
+
+    /// Tags the voice just started for `id` with the handle of its trigger.
+    pub(crate) fn set_voice_handle(&mut self, id: usize, handle: u64) {
+        for (index, voice_slot) in self.voices.iter_mut().enumerate() {
+            if voice_slot.is_playing_sample(id) && !self.voice_fading_out[index] {
+                voice_slot.handle = Some(handle);
+            }
+        }
+    }
+
+    /// Fades out the voice tagged with `handle` like `stop_sample`.
+    ///
+    /// Returns the voice's sample id, or `None` if no voice carries the handle.
+    pub(crate) fn stop_voice(&mut self, handle: u64) -> Option<usize> {
+        let index = self
+            .voices
+            .iter()
+            .enumerate()
+            .position(|(index, voice_slot)| {
+                voice_slot.active
+                    && voice_slot.handle == Some(handle)
+                    && !self.voice_fading_out[index]
+            })?;
+        let id = self.voices[index].sample_id;
+        self.fade_out_voice(index);
+        Some(id)
+    }
+
+    /// Fade out the voices of a sample still marked to stop at `stop_frame`, like
+    /// `stop_sample`.
+    ///
+    /// Returns whether any voice was stopped.
+    pub(crate) fn auto_stop_sample(&mut self, id: usize, stop_frame: u64) -> bool {
+        let mut stopped = false;
+        for index in 0..self.voices.len() {
+            let voice_slot = &self.voices[index];
+            if voice_slot.is_playing_sample(id)
+                && voice_slot.auto_stop_frame == Some(stop_frame)
+                && !self.voice_fading_out[index]
+            {
+                self.fade_out_voice(index);
+                stopped = true;
+            }
+        }
+
+        stopped
+    }
+
+    /// Start or stop the stutter effect on the voices of a sample.
+    ///
+    /// While on, each voice repeatedly replays the `slice_frames` preceding its position at
+    /// capture time. The regular playhead keeps advancing, so turning it off resumes where
+    /// playback would have been. Returns whether any voice was affected.
+    pub(crate) fn set_stutter(&mut self, id: usize, slice_frames: usize, on: bool) -> bool {
+        if id >= NUM_SAMPLES || self.channels == 0 || (on && slice_frames == 0) {
+            return false;
+        }
+
+        let sample_frames = self.sample_bank[keyboard_source(&self.keyboard_modes, id).0]
+            .as_ref()
+            .map_or(0, |sample| sample.samples.len() / self.channels);
+
+        let mut affected = false;
         for voice_slot in &mut self.voices {
             if voice_slot.is_playing_sample(id) {
-                voice_slot.stop_rt(retirement);
+                if on {
+                    voice_slot.start_stutter(slice_frames, sample_frames);
+                } else {
+                    voice_slot.stop_stutter();
+                }
+                affected = true;
             }
         }
+
+        affected
     }
 
     /// Pause playback of a specific sample without resetting position.
@@ -1562,19 +3494,35 @@ impl RtMixer {
             return false;
         }
 
-        self.stop_sample_rt(id, retirement);
+        self.stop_sample_now(id);
+        self.retire_fading_sample(id, retirement);
+        self.pad_gain_ramps[id] = None;
+        self.apply_pad_gain_db(id, PAD_GAIN_DB_DEFAULT);
+        if let Some(waiting) = self.pending_sample_swaps[id].take() {
+            retirement.retire_sample(waiting.sample);
+        }
         if let Some(sample) = self.sample_bank[id].take() {
             retirement.retire_sample(sample);
         }
+        self.sample_generations[id] = self.sample_generations[id].wrapping_add(1);
         if let Some(stems) = self.prepared_stems[id].take() {
             retirement.retire_prepared_stems(stems);
         }
+        if let Some(onsets) = self.pad_onsets[id].take() {
+            retirement.retire_onsets(onsets);
+        }
         self.stem_enabled_mask[id] = STEM_COMPONENT_MASK;
         self.stem_transitions[id].clear();
         self.pad_phase_anchor_frame[id] = 0;
+        self.pad_next_slice[id] = 0;
         true
     }
 
+    /// Largest block handed to the per-voice DSP in one go.
+    ///
+    /// Backends may ignore the requested fixed buffer size, so every render call is sliced
+    /// into chunks of at most this many frames and the stretch processors always see a
+    /// bounded block regardless of the callback length.
     pub(crate) fn max_realtime_render_frames(&self) -> usize {
         (DEFAULT_BLOCK_SAMPLES / 2).max(1)
     }
@@ -1590,8 +3538,8 @@ impl RtMixer {
     /// - `peaks`: Pad peaks
     #[cfg(test)]
     pub(crate) fn render(&mut self, output: &mut [f32], pad_peaks: &mut [f32; NUM_SAMPLES]) {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.render_rt(output, pad_peaks, &mut retirement);
+        let mut pad_activity = RtRenderPadActivity::default();
+        self.render_rt_with_output_frame(output, pad_peaks, None, &mut pad_activity);
     }
 
     #[cfg(test)]
@@ -1600,29 +3548,6 @@ impl RtMixer {
         output_start_frame: u64,
         output: &mut [f32],
         pad_peaks: &mut [f32; NUM_SAMPLES],
-    ) {
-        let mut retirement = ImmediateAudioBufferRetirement;
-        self.render_rt_at_output_frame(output, pad_peaks, output_start_frame, &mut retirement);
-    }
-
-    #[cfg(test)]
-    pub(crate) fn render_rt(
-        &mut self,
-        output: &mut [f32],
-        pad_peaks: &mut [f32; NUM_SAMPLES],
-        retirement: &mut impl AudioBufferRetirement,
-    ) {
-        let mut pad_activity = RtRenderPadActivity::default();
-        self.render_rt_with_output_frame(output, pad_peaks, None, &mut pad_activity, retirement);
-    }
-
-    #[cfg(test)]
-    pub(crate) fn render_rt_at_output_frame(
-        &mut self,
-        output: &mut [f32],
-        pad_peaks: &mut [f32; NUM_SAMPLES],
-        output_start_frame: u64,
-        retirement: &mut impl AudioBufferRetirement,
     ) {
         let mut pad_activity = RtRenderPadActivity::default();
         self.render_rt_at_output_frame_tracking_pads(
@@ -1630,7 +3555,6 @@ impl RtMixer {
             pad_peaks,
             output_start_frame,
             &mut pad_activity,
-            retirement,
         );
     }
 
@@ -1640,15 +3564,8 @@ impl RtMixer {
         pad_peaks: &mut [f32; NUM_SAMPLES],
         output_start_frame: u64,
         pad_activity: &mut RtRenderPadActivity,
-        retirement: &mut impl AudioBufferRetirement,
     ) {
-        self.render_rt_with_output_frame(
-            output,
-            pad_peaks,
-            Some(output_start_frame),
-            pad_activity,
-            retirement,
-        );
+        self.render_rt_with_output_frame(output, pad_peaks, Some(output_start_frame), pad_activity);
     }
 
     fn render_rt_with_output_frame(
@@ -1657,7 +3574,6 @@ impl RtMixer {
         pad_peaks: &mut [f32; NUM_SAMPLES],
         output_start_frame: Option<u64>,
         pad_activity: &mut RtRenderPadActivity,
-        retirement: &mut impl AudioBufferRetirement,
     ) {
         pad_peaks.fill(f32::EQUILIBRIUM);
         output.fill(Sample::EQUILIBRIUM);
@@ -1667,20 +3583,28 @@ impl RtMixer {
             return;
         }
 
-        let frames = output.len() / self.channels;
+        // A trailing partial frame stays silent: it was zeroed above and is excluded from
+        // rendering. It is not logged because the callback must not log.
+        let frames = output.len() / self.output_channels;
+        let output = &mut output[..frames * self.output_channels];
         if frames == 0 {
             return;
         }
 
-        let max_frames = self.max_realtime_render_frames();
+        let max_frames = if self.parameter_ramps_running() {
+            self.max_realtime_render_frames()
+                .min(PAD_EQ_AUTOMATION_UPDATE_FRAMES)
+        } else {
+            self.max_realtime_render_frames()
+        };
         if frames > max_frames {
             let mut rendered_frames = 0;
             let mut chunk_peaks = [f32::EQUILIBRIUM; NUM_SAMPLES];
 
             while rendered_frames < frames {
                 let chunk_frames = (frames - rendered_frames).min(max_frames);
-                let start = rendered_frames * self.channels;
-                let end = start + chunk_frames * self.channels;
+                let start = rendered_frames * self.output_channels;
+                let end = start + chunk_frames * self.output_channels;
                 let chunk_output_start_frame =
                     output_start_frame.map(|frame| frame.saturating_add(rendered_frames as u64));
 
@@ -1689,7 +3613,6 @@ impl RtMixer {
                     &mut chunk_peaks,
                     chunk_output_start_frame,
                     pad_activity,
-                    retirement,
                 );
                 for id in pad_activity.iter() {
                     pad_peaks[id] = pad_peaks[id].max(chunk_peaks[id]);
@@ -1697,17 +3620,39 @@ impl RtMixer {
 
                 rendered_frames += chunk_frames;
             }
+        } else {
+            self.render_rt_chunk(output, pad_peaks, output_start_frame, pad_activity);
+        }
 
-            return;
+        // Summed ahead of the DC blocker and limiter so the limiter also catches the up to
+        // +3 dB a correlated signal gains in the sum; both keep identical channels identical.
+        // The two summed channels take their trims before the sum, so they stay identical.
+        let mono_sum = self.mono_output && !self.cue_split && self.output_channels >= 2;
+        if mono_sum {
+            let [left_trim, right_trim, ..] = self.output_trims;
+            for frame in output.chunks_exact_mut(self.output_channels) {
+                let mono = (frame[0] * left_trim + frame[1] * right_trim) * MONO_SUM_GAIN;
+                frame[0] = mono;
+                frame[1] = mono;
+            }
         }
 
-        self.render_rt_chunk(
-            output,
-            pad_peaks,
-            output_start_frame,
-            pad_activity,
-            retirement,
-        );
+        if self.master_dc_blocker_enabled {
+            self.master_dc_blocker
+                .process_interleaved_block(output, self.output_channels);
+        }
+
+        if self.master_limiter_enabled {
+            self.master_limiter
+                .process_interleaved_block(output, self.output_channels);
+        }
+
+        let trimmed_from = if mono_sum { 2 } else { 0 };
+        for frame in output.chunks_exact_mut(self.output_channels) {
+            for (sample, trim) in frame.iter_mut().zip(&self.output_trims).skip(trimmed_from) {
+                *sample *= trim;
+            }
+        }
     }
 
     fn render_rt_chunk(
@@ -1716,7 +3661,6 @@ impl RtMixer {
         pad_peaks: &mut [f32; NUM_SAMPLES],
         output_start_frame: Option<u64>,
         pad_activity: &mut RtRenderPadActivity,
-        retirement: &mut impl AudioBufferRetirement,
     ) {
         pad_peaks.fill(f32::EQUILIBRIUM);
 
@@ -1724,20 +3668,44 @@ impl RtMixer {
             return;
         }
 
-        let frames = output.len() / self.channels;
+        debug_assert_eq!(output.len() % self.output_channels, 0);
+        let frames = output.len() / self.output_channels;
         if frames == 0 {
             return;
         }
 
+        // Aim each automated band at its value at the end of this chunk; the parameter
+        // smoothing slews towards it over the chunk. The master volume is interpolated
+        // per frame from where it was before.
+        let volume_before = self.volume;
+        if self.parameter_ramps_running() {
+            self.advance_parameter_ramps(frames as u64);
+        }
+        let swap_positions = self.sample_swap_positions();
+        // Voices that switch to their slot's queued sample on the frame they wrap.
+        let swap_voices: [Option<usize>; NUM_SAMPLES] = std::array::from_fn(|id| {
+            self.swapped_out_samples[id]
+                .is_none()
+                .then(|| swap_positions[id].and(self.sample_swap_voice(id)))
+                .flatten()
+        });
+        let pending_sample_swaps = &self.pending_sample_swaps;
+
         let channels = self.channels;
+        let output_channels = self.output_channels;
         let sample_rate_hz = self.sample_rate_hz;
         let speed = self.speed;
         let volume = self.volume;
+        let volume_step = (volume - volume_before) / frames as f32;
         let bpm_lock_enabled = self.bpm_lock_enabled;
+        let tempo_glide_ms = self.tempo_glide_ms;
         let pad_key_lock_enabled = &self.pad_key_lock_enabled;
         let master_bpm = self.master_bpm;
         let pad_bpm = &self.pad_bpm;
+        let pad_rate_ratio = &self.pad_rate_ratio;
         let pad_gain_smoothers = &mut self.pad_gain_smoothers;
+        let pad_fade_mutes = &mut self.pad_fade_mutes;
+        let pad_clip_ceiling = &self.pad_clip_ceiling;
         let pad_dsp_chains = &mut self.pad_dsp_chains;
         let pad_loop_start_frame = &self.pad_loop_start_frame;
         let pad_loop_end_frame = &self.pad_loop_end_frame;
@@ -1747,42 +3715,74 @@ impl RtMixer {
         let stem_mix_source_version_hash = &self.stem_mix_source_version_hash;
         let stem_enabled_mask = &self.stem_enabled_mask;
         let stem_transitions = &mut self.stem_transitions;
-
-        for voice in &mut self.voices {
+        let sample_bank = &self.sample_bank;
+        let fading_samples = &self.fading_samples;
+        let sample_generations = &self.sample_generations;
+        let voice_fades = &mut self.voice_fades;
+        let voice_fading_out = &mut self.voice_fading_out;
+        let keyboard_modes = &self.keyboard_modes;
+        let default_loop_mode = self.default_loop_mode;
+        let pad_loop_mode = &self.pad_loop_mode;
+        let one_shot_ended = &mut self.one_shot_ended;
+        let voice_cue_only = &self.voice_cue_only;
+        let voice_master_sends = &mut self.voice_master_sends;
+        let pad_cue = &self.pad_cue;
+        let pad_delays = &self.pad_delays;
+        let pad_speaker_gains = &self.pad_speaker_gains;
+        let pad_delay_lines = &mut self.pad_delay_lines;
+        let cue_mix = &mut self.cue_mix[..frames];
+        cue_mix.fill(0.0);
+
+        for (voice_index, voice) in self.voices.iter_mut().enumerate() {
             if !voice.active {
                 continue;
             }
             pad_activity.record(voice.sample_id);
+            let mut one_shot_finished = false;
 
             let is_paused = voice.paused;
+            let (source_id, pitch_ratio) = keyboard_source(keyboard_modes, voice.sample_id);
 
-            let Some(sample) = voice.sample.clone() else {
-                voice.stop_rt(retirement);
+            let Some(sample) = sample_bank[source_id]
+                .as_ref()
+                .filter(|_| voice.plays_generation(sample_generations[source_id]))
+                .or_else(|| {
+                    fading_samples[source_id]
+                        .as_ref()
+                        .filter(|fading| voice.plays_generation(fading.generation))
+                        .map(|fading| &fading.sample)
+                })
+            else {
+                voice.stop();
                 continue;
             };
 
             if !is_paused {
                 let sample_frames = sample.samples.len() / channels;
                 if sample_frames == 0 {
-                    voice.stop_rt(retirement);
+                    voice.stop();
                     continue;
                 }
                 let prepared_stem_set = prepared_stem_set_for_render(
-                    prepared_stem_slots[voice.sample_id].as_ref(),
-                    &sample,
+                    prepared_stem_slots[source_id].as_ref(),
+                    sample,
                     channels,
                     sample_rate_hz,
                     sample_frames,
                 );
                 let current_selection = StemRenderSelection::from_state(
-                    stem_mix_mode[voice.sample_id],
-                    stem_mix_source_version_hash[voice.sample_id],
-                    stem_enabled_mask[voice.sample_id],
+                    stem_mix_mode[source_id],
+                    stem_mix_source_version_hash[source_id],
+                    stem_enabled_mask[source_id],
                 );
-                let stem_transition = stem_transitions[voice.sample_id];
+                let stem_transition = stem_transitions[source_id];
 
-                let pad_bpm_for_voice = pad_bpm[voice.sample_id];
+                let pad_bpm_for_voice = pad_bpm[source_id];
+                let rate_ratio = pad_rate_ratio[source_id];
                 let bpm_locked_phase = bpm_lock_enabled
+                    && rate_ratio.is_none()
+                    && voice.speed == 1.0
+                    && source_id == voice.sample_id
                     && master_bpm.is_some_and(|bpm| bpm.is_finite() && bpm > 0.0)
                     && pad_bpm_for_voice.is_some_and(|bpm| bpm.is_finite() && bpm > 0.0);
 
@@ -1792,18 +3792,26 @@ impl RtMixer {
                 {
                     target_tempo_ratio = master_bpm / pad_bpm;
                 }
+                if let Some(ratio) = rate_ratio {
+                    target_tempo_ratio *= rational_tempo_ratio(ratio);
+                }
 
                 if !target_tempo_ratio.is_finite() {
                     target_tempo_ratio = 1.0;
                 }
                 target_tempo_ratio = target_tempo_ratio.clamp(SPEED_MIN, SPEED_MAX);
+                if voice.speed != 1.0 {
+                    target_tempo_ratio =
+                        (target_tempo_ratio * voice.speed).clamp(SPEED_MIN, SPEED_MAX);
+                }
 
                 let previous_tempo_ratio = voice.tempo_ratio_smoothed();
-                let tempo_ratio = voice.smooth_tempo_ratio(target_tempo_ratio);
+                let tempo_ratio =
+                    voice.smooth_tempo_ratio(target_tempo_ratio, frames, tempo_glide_ms);
                 let tempo_ratio_changed = (tempo_ratio - previous_tempo_ratio).abs() > f32::EPSILON;
 
-                let mut loop_start = pad_loop_start_frame[voice.sample_id].min(sample_frames);
-                let mut loop_end = pad_loop_end_frame[voice.sample_id].unwrap_or(sample_frames);
+                let mut loop_start = pad_loop_start_frame[source_id].min(sample_frames);
+                let mut loop_end = pad_loop_end_frame[source_id].unwrap_or(sample_frames);
                 loop_end = loop_end.min(sample_frames);
                 if loop_end <= loop_start {
                     loop_start = 0;
@@ -1811,9 +3819,17 @@ impl RtMixer {
                 }
                 let loop_len = loop_end - loop_start;
                 if loop_len == 0 {
-                    voice.stop_rt(retirement);
+                    voice.stop();
                     continue;
                 }
+                let key_lock =
+                    pad_key_lock_enabled[voice.sample_id] && loop_len >= KEY_LOCK_MIN_LOOP_FRAMES;
+                // Without key lock, keyboard transposition is varispeed, bounded like speed.
+                let source_rate = if key_lock {
+                    tempo_ratio
+                } else {
+                    (tempo_ratio * pitch_ratio).clamp(SPEED_MIN, SPEED_MAX)
+                };
 
                 let loop_region = FrameRange {
                     start: loop_start,
@@ -1821,6 +3837,7 @@ impl RtMixer {
                 };
                 let mut seek_mode = voice.explicit_seek_mode;
                 let mut timeline_anchor_needs_reset = tempo_ratio_changed;
+                // Only the whole frame position moves; the carried fractional phase is kept.
                 if seek_mode == ExplicitSeekMode::Normal
                     && (voice.frame_pos < loop_start || voice.frame_pos >= loop_end)
                 {
@@ -1874,24 +3891,71 @@ impl RtMixer {
                             Some(position.next_frame_pos),
                         )
                     } else {
-                        let mut input_frames = ((frames as f32) * tempo_ratio).round() as usize;
-                        input_frames = input_frames.clamp(1, DEFAULT_BLOCK_SAMPLES);
+                        let input_frames = voice.advance_source_phase(frames, source_rate);
                         (voice.frame_pos, input_frames, None)
                     };
 
-                let input_buffers = voice.stretch.input_buffers_mut(input_frames);
-                for (channel, buf) in input_buffers.iter_mut().enumerate().take(channels) {
-                    for (i, sample_ref) in buf.iter_mut().enumerate().take(input_frames) {
-                        let frame = source_frame_for_playback(
+                let one_shot_remaining = (voice
+                    .loop_mode
+                    .or(pad_loop_mode[voice.sample_id])
+                    .unwrap_or(default_loop_mode)
+                    == LoopMode::OneShot)
+                    .then(|| {
+                        one_shot_remaining_frames(
                             source_frame_pos,
-                            i,
                             sample_frames,
                             loop_region,
                             seek_mode,
-                        );
+                        )
+                    });
+
+                let stutter = voice.stutter;
+                // Source frames from the loop wrap on come from the queued sample, so the swap
+                // lands on the wrap frame whatever the chunk size.
+                let swap_sample = pending_sample_swaps[source_id]
+                    .as_ref()
+                    .filter(|_| {
+                        swap_voices[source_id] == Some(voice_index)
+                            && seek_mode == ExplicitSeekMode::Normal
+                            && stutter.is_none()
+                    })
+                    .map(|swap| &swap.sample);
+                let swap_offset = loop_end.saturating_sub(source_frame_pos);
+                let input_buffers = voice.stretch.input_buffers_mut(input_frames);
+                for (channel, buf) in input_buffers.iter_mut().enumerate().take(channels) {
+                    for (i, sample_ref) in buf.iter_mut().enumerate().take(input_frames) {
+                        if one_shot_remaining.is_some_and(|remaining| i >= remaining) {
+                            *sample_ref = 0.0;
+                            continue;
+                        }
+                        if let Some(swap_sample) = swap_sample.filter(|_| i >= swap_offset) {
+                            let frame = source_frame_for_playback(
+                                source_frame_pos,
+                                i,
+                                sample_frames,
+                                loop_region,
+                                seek_mode,
+                            );
+                            *sample_ref = swap_sample
+                                .samples
+                                .get(frame * channels + channel)
+                                .copied()
+                                .unwrap_or(0.0);
+                            continue;
+                        }
+                        let frame = match stutter {
+                            Some(stutter) => stutter.frame_at(i),
+                            None => source_frame_for_playback(
+                                source_frame_pos,
+                                i,
+                                sample_frames,
+                                loop_region,
+                                seek_mode,
+                            ),
+                        };
                         *sample_ref = if stem_transition.is_active() {
                             let from_sample = render_source_selection_sample(
-                                &sample,
+                                sample,
                                 prepared_stem_set,
                                 stem_transition.from,
                                 frame,
@@ -1899,7 +3963,7 @@ impl RtMixer {
                                 channel,
                             );
                             let to_sample = render_source_selection_sample(
-                                &sample,
+                                sample,
                                 prepared_stem_set,
                                 current_selection,
                                 frame,
@@ -1910,7 +3974,7 @@ impl RtMixer {
                             from_sample * from_gain + to_sample * to_gain
                         } else {
                             render_source_selection_sample(
-                                &sample,
+                                sample,
                                 prepared_stem_set,
                                 current_selection,
                                 frame,
@@ -1920,29 +3984,82 @@ impl RtMixer {
                         };
                     }
                 }
-                stem_transitions[voice.sample_id].advance(input_frames);
+                stem_transitions[source_id].advance(input_frames);
+                if let Some(stutter) = voice.stutter.as_mut() {
+                    stutter.advance(input_frames);
+                }
 
-                voice.stretch.process(
+                voice.stretch.process_transposed(
                     input_frames,
                     frames,
-                    tempo_ratio,
-                    pad_key_lock_enabled[voice.sample_id],
+                    source_rate,
+                    key_lock,
+                    pitch_ratio,
                 );
 
                 let pad_dsp_chain = &mut pad_dsp_chains[voice.sample_id];
                 let pad_gain_smoother = &mut pad_gain_smoothers[voice.sample_id];
+                let pad_fade_mute = &mut pad_fade_mutes[voice.sample_id];
+                let clip_ceiling = pad_clip_ceiling[voice.sample_id];
+                let voice_fade = &mut voice_fades[voice_index];
+                let master_send = &mut voice_master_sends[voice_index];
+                let cue_send = pad_cue[voice.sample_id] || voice_cue_only[voice_index];
+                let speaker_gains = pad_speaker_gains[voice.sample_id].as_ref();
+                let pan_gains = voice
+                    .pan
+                    .filter(|_| channels == 2)
+                    .map(equal_power_pan_gains);
+                let pad_delay = &pad_delays[voice.sample_id];
+                let mut delay_line = pad_delay_lines[voice.sample_id].as_deref_mut();
 
                 let output_buffers = voice.stretch.output_buffers();
                 for frame in 0..frames {
-                    let out_base = frame * channels;
-                    let trim_gain = pad_gain_smoother.next();
+                    // The cue taps the voice after its Gain/Trim, before mute and master send.
+                    let pre_fader_gain = pad_gain_smoother.next() * voice_fade.next();
+                    let trim_gain = pre_fader_gain * pad_fade_mute.next();
+                    let frame_volume = volume - volume_step * (frames - 1 - frame) as f32;
+                    let master_gain = frame_volume * master_send.next();
                     pad_dsp_chain.begin_frame();
                     for (channel, buffer) in output_buffers.iter().enumerate().take(channels) {
+                        if cue_send {
+                            cue_mix[frame] +=
+                                buffer[frame] * pre_fader_gain * voice.volume / channels as f32;
+                        }
                         let sample = buffer[frame] * trim_gain;
                         let sample = pad_dsp_chain.process_sample(channel, sample);
-                        let contribution = sample * voice.volume;
-                        let mixed = contribution * volume;
-                        output[out_base + channel] += mixed;
+                        let mut contribution = sample * voice.volume;
+                        if let Some(ceiling) = clip_ceiling {
+                            contribution = soft_clip(contribution, ceiling);
+                        }
+                        let mixed = contribution * master_gain;
+                        if let Some(gains) = speaker_gains {
+                            // A placed pad plays the mono sum of its channels.
+                            let mono = mixed / channels as f32;
+                            for (speaker, gain) in gains.iter().enumerate().take(output_channels) {
+                                if *gain > 0.0 {
+                                    mix_into_output(
+                                        output,
+                                        output_channels,
+                                        pad_delay,
+                                        delay_line.as_deref_mut(),
+                                        frame,
+                                        speaker,
+                                        mono * gain,
+                                    );
+                                }
+                            }
+                        } else {
+                            let panned = pan_gains.map_or(mixed, |gains| mixed * gains[channel]);
+                            mix_into_output(
+                                output,
+                                output_channels,
+                                pad_delay,
+                                delay_line.as_deref_mut(),
+                                frame,
+                                channel,
+                                panned,
+                            );
+                        }
 
                         let peak = contribution.abs();
                         if peak > pad_peaks[voice.sample_id] {
@@ -1965,6 +4082,8 @@ impl RtMixer {
                     };
                 voice.frame_pos = next_frame_pos;
                 voice.explicit_seek_mode = next_seek_mode;
+                one_shot_finished =
+                    one_shot_remaining.is_some_and(|remaining| input_frames >= remaining);
                 if anchored_next_frame_pos.is_none()
                     && let Some(segment_start_frame) = output_start_frame
                 {
@@ -1975,9 +4094,9 @@ impl RtMixer {
                 }
             } else {
                 let sample_frames = sample.samples.len() / channels;
-                let loop_start = pad_loop_start_frame[voice.sample_id].min(sample_frames);
+                let loop_start = pad_loop_start_frame[source_id].min(sample_frames);
 
-                let mut loop_end = pad_loop_end_frame[voice.sample_id].unwrap_or(sample_frames);
+                let mut loop_end = pad_loop_end_frame[source_id].unwrap_or(sample_frames);
                 loop_end = loop_end.min(sample_frames);
                 if loop_end <= loop_start {
                     // Invalid loop; but voice is paused; skip.
@@ -1995,8 +4114,93 @@ impl RtMixer {
                     });
                 }
             }
-            pad_playhead_frame[voice.sample_id] = Some(voice.frame_pos);
+            pad_playhead_frame[voice.sample_id] = Some(voice.frame_pos);
+            if one_shot_finished {
+                one_shot_ended[voice.sample_id] |= !voice_fading_out[voice_index];
+                voice.stop();
+                voice_fading_out[voice_index] = false;
+            } else if voice_fading_out[voice_index] && voice_fades[voice_index].is_silent() {
+                voice.stop();
+                voice_fading_out[voice_index] = false;
+            }
+        }
+
+        for (pad_delay, line) in self
+            .pad_delays
+            .iter_mut()
+            .zip(self.pad_delay_lines.iter_mut())
+        {
+            if let Some(ring) = line
+                && !pad_delay.is_bypassed()
+            {
+                pad_delay.read_into(ring, output, output_channels);
+            }
+        }
+
+        let preview_pair = self.monitor_pair(self.preview_output);
+        let cue_pair = self.monitor_pair(self.cue_output);
+        for first in [preview_pair, cue_pair].into_iter().flatten() {
+            for frame in output.chunks_exact_mut(self.output_channels) {
+                frame[first..first + 2].fill(0.0);
+            }
+        }
+
+        self.render_preview_loop(output, preview_pair);
+
+        if let Some(first) = cue_pair {
+            for (frame, cue) in output
+                .chunks_exact_mut(self.output_channels)
+                .zip(&self.cue_mix[..])
+            {
+                frame[first] += *cue;
+                frame[first + 1] += *cue;
+            }
+        } else if self.cue_split && self.output_channels >= 2 {
+            for (frame, cue) in output
+                .chunks_exact_mut(self.output_channels)
+                .zip(&self.cue_mix[..])
+            {
+                let master = frame.iter().sum::<f32>() / self.output_channels as f32;
+                frame.fill(0.0);
+                frame[0] = master;
+                frame[1] = *cue;
+            }
+        }
+
+        self.swap_in_due_samples(&swap_positions);
+    }
+
+    /// Adds the preview voice to the mix, or only to the pair starting at `pair`.
+    fn render_preview_loop(&mut self, output: &mut [f32], pair: Option<usize>) {
+        let Some(mut preview) = self.preview_loop else {
+            return;
+        };
+        let Some(sample) = self.sample_bank[preview.sample_id]
+            .as_ref()
+            .filter(|_| self.sample_generations[preview.sample_id] == preview.sample_generation)
+        else {
+            self.preview_loop = None;
+            return;
+        };
+
+        let channels = self.channels;
+        for frame in output.chunks_exact_mut(self.output_channels) {
+            let source_base = preview.frame_pos * channels;
+            if let Some(first) = pair {
+                frame[first] += sample.samples[source_base] * self.volume;
+                frame[first + 1] += sample.samples[source_base + 1] * self.volume;
+            } else {
+                for (channel, out) in frame.iter_mut().enumerate().take(channels) {
+                    *out += sample.samples[source_base + channel] * self.volume;
+                }
+            }
+
+            preview.frame_pos += 1;
+            if preview.frame_pos >= preview.region.end {
+                preview.frame_pos = preview.region.start;
+            }
         }
+        self.preview_loop = Some(preview);
     }
 }
 
@@ -2121,135 +4325,657 @@ mod tests {
         let mut pattern_index = 0;
         let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
 
-        while remaining > 0 {
-            let frames = pattern[pattern_index % pattern.len()].min(remaining);
-            pattern_index += 1;
-            if frames == 0 {
-                continue;
-            }
+        while remaining > 0 {
+            let frames = pattern[pattern_index % pattern.len()].min(remaining);
+            pattern_index += 1;
+            if frames == 0 {
+                continue;
+            }
+
+            let mut output = vec![0.0; frames];
+            mixer.render_at_output_frame(*output_frame, &mut output, &mut pad_peaks);
+            *output_frame = (*output_frame).saturating_add(frames as u64);
+            remaining -= frames;
+        }
+    }
+
+    /// Flags sample-to-sample jumps the local signal does not explain.
+    ///
+    /// A step is a click when it exceeds `ratio` times the largest step of the preceding
+    /// `window_frames` frames, with `floor` as the smallest reference step.
+    #[derive(Debug, Clone, Copy)]
+    struct ClickDetector {
+        ratio: f32,
+        window_frames: usize,
+        floor: f32,
+    }
+
+    impl Default for ClickDetector {
+        fn default() -> Self {
+            Self {
+                ratio: 4.0,
+                window_frames: 256,
+                floor: 0.02,
+            }
+        }
+    }
+
+    impl ClickDetector {
+        /// Frames of interleaved `output` where any channel clicks.
+        fn clicks(&self, output: &[f32], channels: usize) -> Vec<usize> {
+            let step = |frame: usize, channel: usize| {
+                (output[frame * channels + channel] - output[(frame - 1) * channels + channel])
+                    .abs()
+            };
+
+            (1..output.len() / channels)
+                .filter(|&frame| {
+                    let window_start = frame.saturating_sub(self.window_frames).max(1);
+                    (0..channels).any(|channel| {
+                        let reference = (window_start..frame)
+                            .map(|previous| step(previous, channel))
+                            .fold(self.floor, f32::max);
+                        step(frame, channel) > self.ratio * reference
+                    })
+                })
+                .collect()
+        }
+    }
+
+    fn active_voice_frame(mixer: &RtMixer, id: usize) -> Option<usize> {
+        mixer
+            .voices
+            .iter()
+            .find(|voice| voice.active && voice.sample_id == id)
+            .map(|voice| voice.frame_pos)
+    }
+
+    fn four_bar_loop_frames(sample_rate_hz: f32, bpm: f32) -> usize {
+        ((sample_rate_hz as f64 * 60.0 / bpm as f64) * 16.0).round() as usize
+    }
+
+    fn circular_phase_error_frames(left_phase: f64, right_phase: f64, cycle_frames: f64) -> f64 {
+        let difference = (left_phase - right_phase).abs() % cycle_frames;
+        difference.min(cycle_frames - difference)
+    }
+
+    fn bpm_locked_phase_error_frames(
+        mixer: &RtMixer,
+        left_id: usize,
+        left_bpm: f32,
+        right_id: usize,
+        right_bpm: f32,
+        master_bpm: f32,
+        sample_rate_hz: f32,
+    ) -> f64 {
+        let left_loop_frames = four_bar_loop_frames(sample_rate_hz, left_bpm);
+        let right_loop_frames = four_bar_loop_frames(sample_rate_hz, right_bpm);
+        let output_cycle_frames = four_bar_loop_frames(sample_rate_hz, master_bpm) as f64;
+        let left_ratio = f64::from((master_bpm / left_bpm).clamp(SPEED_MIN, SPEED_MAX));
+        let right_ratio = f64::from((master_bpm / right_bpm).clamp(SPEED_MIN, SPEED_MAX));
+        let left_frame = active_voice_frame(mixer, left_id).expect("left voice active");
+        let right_frame = active_voice_frame(mixer, right_id).expect("right voice active");
+        let left_phase = (left_frame % left_loop_frames) as f64 / left_ratio;
+        let right_phase = (right_frame % right_loop_frames) as f64 / right_ratio;
+
+        circular_phase_error_frames(left_phase, right_phase, output_cycle_frames)
+    }
+
+    fn active_voice_rubberband_block_size(mixer: &RtMixer, id: usize) -> usize {
+        mixer
+            .voices
+            .iter()
+            .find(|voice| voice.active && voice.sample_id == id)
+            .map(|voice| voice.stretch.rubberband_block_size())
+            .unwrap_or(1)
+    }
+
+    #[derive(Default)]
+    struct CollectingRetirement {
+        samples: Vec<SampleBuffer>,
+        stems: Vec<PreparedStemSet>,
+    }
+
+    impl AudioBufferRetirement for CollectingRetirement {
+        fn retire_sample(&mut self, sample: SampleBuffer) {
+            self.samples.push(sample);
+        }
+
+        fn retire_prepared_stems(&mut self, stems: PreparedStemSet) {
+            self.stems.push(stems);
+        }
+
+        fn retire_onsets(&mut self, _onsets: OnsetTable) {}
+
+        fn available_retirement_slots(&mut self) -> usize {
+            usize::MAX
+        }
+    }
+
+    fn create_test_prepared_stems(
+        channels: usize,
+        sample_rate_hz: u32,
+        frames: usize,
+    ) -> PreparedStemSet {
+        let buffer = create_test_sample(channels, frames, 0.25);
+        PreparedStemSet {
+            source_version_hash: 42,
+            sample_rate_hz,
+            channels,
+            frame_count: frames,
+            available_mask: full_stem_available_mask(),
+            stems: std::array::from_fn(|_| buffer.clone()),
+        }
+    }
+
+    fn create_test_prepared_stems_with_values(
+        channels: usize,
+        sample_rate_hz: u32,
+        frames: usize,
+        values: [f32; STEM_BUFFER_COUNT],
+    ) -> PreparedStemSet {
+        PreparedStemSet {
+            source_version_hash: 42,
+            sample_rate_hz,
+            channels,
+            frame_count: frames,
+            available_mask: full_stem_available_mask(),
+            stems: values.map(|value| create_test_sample(channels, frames, value)),
+        }
+    }
+
+    #[test]
+    fn test_render_splits_oversized_blocks_to_preserve_stretch_capacity() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_speed(2.0);
+        mixer.load_sample(0, create_test_sample(1, 5_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let frames = mixer.max_realtime_render_frames() * 2 + 37;
+        let mut output = vec![0.0; frames];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(frames * 2));
+    }
+
+    #[test]
+    fn test_keyboard_mode_plays_source_transposed_per_pad() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        assert!(mixer.set_stop_fade_samples(0));
+        mixer.load_sample(0, create_frame_number_sample(1_000));
+        let root = NUM_PADS;
+        assert!(mixer.set_keyboard_mode(1, Some((0, 0))));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        assert!(mixer.play_sample(root, 1.0));
+        let mut unison = vec![0.0; 64];
+        mixer.render(&mut unison, &mut pad_peaks);
+        assert!(mixer.play_sample(root + 12, 1.0));
+        mixer.stop_sample(root);
+        let mut octave_up = vec![0.0; 64];
+        mixer.render(&mut octave_up, &mut pad_peaks);
+
+        assert_eq!(active_voice_frame(&mixer, root + 12), Some(128));
+        assert!((unison[32] - 32.0).abs() < 1e-3);
+        assert!((octave_up[32] - 64.0).abs() < 1.0);
+        assert!(pad_peaks[root + 12] > 100.0);
+        assert_eq!(keyboard_source(&mixer.keyboard_modes, root + 30), (0, 2.0));
+        assert_eq!(keyboard_source(&mixer.keyboard_modes, 5), (5, 1.0));
+
+        assert!(mixer.set_keyboard_mode(1, None));
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+        assert!(!mixer.play_sample(root + 12, 1.0));
+        assert!(!mixer.set_keyboard_mode(NUM_BANKS, Some((0, 0))));
+        assert!(!mixer.set_keyboard_mode(1, Some((0, NUM_PADS))));
+    }
+
+    #[test]
+    fn test_full_voice_pool_steals_only_from_lower_priority_pads() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        for id in 0..=MAX_VOICES + 1 {
+            mixer.load_sample(id, create_test_sample(1, 64, 0.5));
+        }
+        mixer.set_pad_priority(3, 10);
+        mixer.set_pad_priority(5, 20);
+        for id in 0..MAX_VOICES {
+            assert!(mixer.play_sample(id, 1.0));
+        }
+        assert_eq!(mixer.take_voice_steal(), None);
+
+        assert!(mixer.play_sample(MAX_VOICES, 1.0));
+        assert_eq!(
+            mixer.take_voice_steal(),
+            Some(VoiceSteal {
+                id: MAX_VOICES,
+                priority: PAD_PRIORITY_DEFAULT,
+                stolen_id: 3,
+                stolen_priority: 10,
+            })
+        );
+        assert!(!mixer.sample_is_active(3));
+
+        mixer.set_pad_priority(MAX_VOICES + 1, 20);
+        assert!(!mixer.play_sample(MAX_VOICES + 1, 1.0));
+        assert_eq!(mixer.take_voice_steal(), None);
+        assert!(mixer.sample_is_active(5));
+    }
+
+    #[test]
+    fn test_pad_eq_automation_ramps_band_target_in_update_steps() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.automate_pad_eq(0, 1, PAD_EQ_DB_MIN, 4_800));
+        assert!(!mixer.automate_pad_eq(0, PAD_EQ_BANDS, 0.0, 4_800));
+        assert!(!mixer.automate_pad_eq(0, 1, f32::NAN, 4_800));
+
+        // One 1024-frame callback is rendered in update-sized chunks.
+        render_chunks(&mut mixer, 1, 1_024);
+        let mid = mixer.pad_dsp_chains[0].parameter(DspParameterSlot::Slot1);
+        let expected_db = PAD_EQ_DB_MIN * 1_024.0 / 4_800.0;
+        assert!((mid.target() - pad_eq_db_to_normalized(expected_db)).abs() < 1e-6);
+        assert_eq!(
+            mixer.pad_dsp_chains[0]
+                .parameter(DspParameterSlot::Slot0)
+                .target(),
+            0.5
+        );
+
+        render_chunks(&mut mixer, 4, 1_024);
+        assert!(!mixer.parameter_ramps_running());
+        assert_eq!(mixer.pad_eq_db[0], [0.0, PAD_EQ_DB_MIN, 0.0]);
+    }
+
+    #[test]
+    fn test_snapshot_morph_completes_on_schedule_and_switches_discrete_parameters() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.store_snapshot(0));
+        assert!(!mixer.store_snapshot(MIXER_SNAPSHOT_SLOTS));
+        assert!(!mixer.morph_to_snapshot(1, 4_800));
+
+        mixer.set_volume(0.2);
+        mixer.set_pad_gain(0, -12.0);
+        mixer.set_pad_eq(0, -6.0, 0.0, 3.0);
+        assert!(mixer.set_pad_fade_mute(1, true));
+        mixer.set_pad_cue(2, true);
+        assert!(mixer.store_snapshot(1));
+
+        // A zero-length morph restores A at once.
+        assert!(mixer.morph_to_snapshot(0, 0));
+        assert!(!mixer.parameter_ramps_running());
+        assert_eq!(mixer.volume, 1.0);
+        assert_eq!(mixer.pad_gain_db[0], 0.0);
+        assert_eq!(mixer.pad_eq_db[0], [0.0; PAD_EQ_BANDS]);
+        assert_eq!(mixer.pad_fade_mutes[1].target, 1.0);
+        assert!(!mixer.pad_cue[2]);
+
+        assert!(mixer.morph_to_snapshot(1, 4_800));
+        assert!(mixer.pad_cue[2]);
+        render_chunks(&mut mixer, 2, 1_200);
+        assert!((mixer.volume - 0.6).abs() < 1e-6);
+        assert!((mixer.pad_gain_db[0] + 6.0).abs() < 1e-5);
+        assert_eq!(mixer.pad_fade_mutes[1].target, 1.0);
+
+        render_chunks(&mut mixer, 2, 1_200);
+        assert!(!mixer.parameter_ramps_running());
+        assert_eq!(mixer.volume, 0.2);
+        assert_eq!(mixer.pad_gain_db[0], -12.0);
+        assert_eq!(mixer.pad_eq_db[0], [-6.0, 0.0, 3.0]);
+        assert_eq!(mixer.pad_fade_mutes[1].target, 0.0);
+        assert!(mixer.pad_cue[2]);
+    }
+
+    #[test]
+    fn test_snapshot_morph_ramps_the_volume_per_frame() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.set_volume(0.0);
+        assert!(mixer.store_snapshot(0));
+        mixer.set_volume(1.0);
+        mixer.load_sample(0, create_test_sample(1, 48_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        assert!(mixer.morph_to_snapshot(0, 1_000));
+        let output = render_chunks(&mut mixer, 1, 1_000);
+        for (frame, sample) in output.iter().enumerate() {
+            let expected = 0.5 * (1.0 - (frame + 1) as f32 / 1_000.0);
+            assert!((sample - expected).abs() < 1e-4, "frame {frame}: {sample}");
+        }
+    }
+
+    #[test]
+    fn test_pad_eq_automation_renders_without_clicks_and_set_pad_eq_cancels_it() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 4, 1_024);
+        assert!(mixer.automate_pad_eq(0, 1, PAD_EQ_DB_MIN, 9_600));
+        output.extend(render_chunks(&mut mixer, 12, 1_024));
+
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+        let peak = |samples: &[f32]| samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        assert!(peak(&output[output.len() - 1_024..]) < 0.15 * peak(&output[..1_024]));
+
+        assert!(mixer.automate_pad_eq(0, 1, 0.0, 48_000));
+        mixer.set_pad_eq(0, 0.0, -6.0, 0.0);
+        assert!(!mixer.parameter_ramps_running());
+        assert_eq!(mixer.pad_eq_db[0], [0.0, -6.0, 0.0]);
+    }
+
+    #[test]
+    fn test_pad_clip_soft_clips_only_its_own_pad() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 16, 0.9));
+        mixer.load_sample(1, create_test_sample(1, 16, 0.9));
+        assert!(mixer.set_pad_clip(0, true, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.play_sample(1, 1.0));
+
+        let mut output = vec![0.0; 8];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        let clipped = 0.5 * 1.8_f32.tanh();
+        assert!((pad_peaks[0] - clipped).abs() < 1e-6);
+        assert!((pad_peaks[1] - 0.9).abs() < 1e-6);
+        assert!(
+            output
+                .iter()
+                .all(|sample| (sample - (clipped + 0.9)).abs() < 1e-6)
+        );
+
+        assert!(mixer.set_pad_clip(0, false, 0.5));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (sample - 1.8).abs() < 1e-6));
+        assert!(!mixer.set_pad_clip(0, true, 0.0));
+        assert!(!mixer.set_pad_clip(0, true, 1.5));
+        assert!(!mixer.set_pad_clip(NUM_SAMPLES, true, 0.5));
+    }
+
+    #[test]
+    fn test_output_trims_scale_each_channel_independently() {
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        mixer.load_sample(0, create_test_sample(2, 16, 0.5));
+        assert!(mixer.set_output_trim(0, 0.5));
+        assert!(mixer.set_output_trim(1, 2.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![0.0; 16];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        for frame in output.chunks_exact(2) {
+            assert!((frame[0] - 0.25).abs() < 1e-6);
+            assert!((frame[1] - 1.0).abs() < 1e-6);
+        }
+        assert!((pad_peaks[0] - 0.5).abs() < 1e-6);
+        assert!(!mixer.set_output_trim(2, 1.0));
+        assert!(!mixer.set_output_trim(0, OUTPUT_TRIM_MAX + 0.1));
+    }
+
+    #[test]
+    fn test_render_mono_odd_length_buffer_renders_every_frame() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 64, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![1.0; 7];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(7));
+    }
+
+    #[test]
+    fn test_render_stereo_odd_length_buffer_zeroes_trailing_partial_frame() {
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        mixer.load_sample(0, create_test_sample(2, 64, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![1.0; 7];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(
+            output[..6]
+                .iter()
+                .all(|sample| (*sample - 0.5).abs() < 1e-5)
+        );
+        assert_eq!(output[6], 0.0);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(3));
+    }
+
+    #[test]
+    fn test_render_stereo_oversized_odd_length_buffer_zeroes_trailing_partial_frame() {
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        mixer.load_sample(0, create_test_sample(2, 5_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let frames = mixer.max_realtime_render_frames() + 3;
+        let mut output = vec![1.0; frames * 2 + 1];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        mixer.render(&mut output, &mut pad_peaks);
+
+        let (rendered, remainder) = output.split_at(frames * 2);
+        assert!(rendered.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
+        assert_eq!(remainder, &[0.0]);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(frames));
+    }
+
+    #[test]
+    fn test_render_variable_callback_sizes_matches_fixed_block_output() {
+        const CALLBACK_FRAMES: [usize; 10] = [1, 7, 64, 511, 512, 513, 1024, 3, 2049, 200];
+
+        fn configured_mixer() -> RtMixer {
+            let mut mixer = RtMixer::new(1, 44_100.0);
+            mixer.set_master_dc_blocker(true);
+            mixer.load_sample(0, create_sine_sample(44_100.0, 10_000, 220.0));
+            mixer.set_pad_eq(0, 6.0, -3.0, 4.0);
+            mixer.set_pad_gain(0, -2.0);
+            assert!(mixer.play_sample(0, 1.0));
+            mixer
+        }
+
+        let total_frames: usize = CALLBACK_FRAMES.iter().sum();
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        let mut reference_mixer = configured_mixer();
+        let mut reference = vec![0.0; total_frames];
+        for block in reference.chunks_mut(reference_mixer.max_realtime_render_frames()) {
+            reference_mixer.render(block, &mut pad_peaks);
+        }
 
-            let mut output = vec![0.0; frames];
-            mixer.render_at_output_frame(*output_frame, &mut output, &mut pad_peaks);
-            *output_frame = (*output_frame).saturating_add(frames as u64);
-            remaining -= frames;
+        let mut mixer = configured_mixer();
+        let mut output = vec![0.0; total_frames];
+        let mut start = 0;
+        for frames in CALLBACK_FRAMES {
+            mixer.render(&mut output[start..start + frames], &mut pad_peaks);
+            start += frames;
         }
-    }
 
-    fn active_voice_frame(mixer: &RtMixer, id: usize) -> Option<usize> {
-        mixer
-            .voices
-            .iter()
-            .find(|voice| voice.active && voice.sample_id == id)
-            .map(|voice| voice.frame_pos)
+        for (frame, (actual, expected)) in output.iter().zip(&reference).enumerate() {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "frame {frame}: {actual} != {expected}"
+            );
+        }
+        assert_eq!(active_voice_frame(&mixer, 0), Some(total_frames));
     }
 
-    fn four_bar_loop_frames(sample_rate_hz: f32, bpm: f32) -> usize {
-        ((sample_rate_hz as f64 * 60.0 / bpm as f64) * 16.0).round() as usize
-    }
+    #[test]
+    fn test_source_phase_does_not_drift_over_hours_at_fractional_ratio() {
+        let tempo_ratio = 0.97_f32;
+        let block_frames = 441;
+        let blocks = 3 * 60 * 60 * 100;
+        let mut voice = VoiceSlot::with_sample_rate(1, 44_100.0);
+
+        let mut consumed = 0_u64;
+        for _ in 0..blocks {
+            consumed += voice.advance_source_phase(block_frames, tempo_ratio) as u64;
+        }
 
-    fn circular_phase_error_frames(left_phase: f64, right_phase: f64, cycle_frames: f64) -> f64 {
-        let difference = (left_phase - right_phase).abs() % cycle_frames;
-        difference.min(cycle_frames - difference)
+        let expected = (blocks * block_frames) as f64 * f64::from(tempo_ratio);
+        assert!((consumed as f64 - expected).abs() <= 1.0);
     }
 
-    fn bpm_locked_phase_error_frames(
-        mixer: &RtMixer,
-        left_id: usize,
-        left_bpm: f32,
-        right_id: usize,
-        right_bpm: f32,
-        master_bpm: f32,
-        sample_rate_hz: f32,
-    ) -> f64 {
-        let left_loop_frames = four_bar_loop_frames(sample_rate_hz, left_bpm);
-        let right_loop_frames = four_bar_loop_frames(sample_rate_hz, right_bpm);
-        let output_cycle_frames = four_bar_loop_frames(sample_rate_hz, master_bpm) as f64;
-        let left_ratio = f64::from((master_bpm / left_bpm).clamp(SPEED_MIN, SPEED_MAX));
-        let right_ratio = f64::from((master_bpm / right_bpm).clamp(SPEED_MIN, SPEED_MAX));
-        let left_frame = active_voice_frame(mixer, left_id).expect("left voice active");
-        let right_frame = active_voice_frame(mixer, right_id).expect("right voice active");
-        let left_phase = (left_frame % left_loop_frames) as f64 / left_ratio;
-        let right_phase = (right_frame % right_loop_frames) as f64 / right_ratio;
+    #[test]
+    fn test_source_phase_pays_back_frames_of_clamped_blocks() {
+        let tempo_ratio = 0.3_f32;
+        let mut voice = VoiceSlot::with_sample_rate(1, 44_100.0);
+
+        // One-frame blocks still consume one source frame each, ahead of the exact phase.
+        let mut consumed = 0;
+        for _ in 0..10 {
+            consumed += voice.advance_source_phase(1, tempo_ratio);
+        }
+        assert_eq!(consumed, 10);
 
-        circular_phase_error_frames(left_phase, right_phase, output_cycle_frames)
+        consumed += voice.advance_source_phase(100, tempo_ratio);
+        assert_eq!(consumed, 33);
     }
 
-    fn active_voice_rubberband_block_size(mixer: &RtMixer, id: usize) -> usize {
-        mixer
-            .voices
-            .iter()
-            .find(|voice| voice.active && voice.sample_id == id)
-            .map(|voice| voice.stretch.rubberband_block_size())
-            .unwrap_or(1)
-    }
+    #[test]
+    fn test_varispeed_loop_position_matches_analytic_phase() {
+        let sample_frames = 10_007;
+        let tempo_ratio = 0.97_f32;
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_speed(tempo_ratio);
+        mixer.load_sample(0, create_test_sample(1, sample_frames, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
 
-    #[derive(Default)]
-    struct CollectingRetirement {
-        samples: Vec<SampleBuffer>,
-        stems: Vec<PreparedStemSet>,
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut output = vec![0.0; 441];
+        let callbacks = 2_000;
+        for _ in 0..callbacks {
+            mixer.render(&mut output, &mut pad_peaks);
+        }
+
+        let total_source = (callbacks * output.len()) as f64 * f64::from(tempo_ratio);
+        let expected = total_source.round() as usize % sample_frames;
+        let actual = active_voice_frame(&mixer, 0).expect("voice keeps looping");
+        let distance = actual.abs_diff(expected);
+        assert!(
+            distance.min(sample_frames - distance) <= 1,
+            "position {actual} drifted from {expected}"
+        );
     }
 
-    impl AudioBufferRetirement for CollectingRetirement {
-        fn retire_sample(&mut self, sample: SampleBuffer) {
-            self.samples.push(sample);
-        }
+    #[test]
+    fn test_render_variable_callback_sizes_keeps_stretched_voice_position() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_speed(2.0);
+        mixer.load_sample(0, create_test_sample(1, 20_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
 
-        fn retire_prepared_stems(&mut self, stems: PreparedStemSet) {
-            self.stems.push(stems);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut total_frames = 0;
+        for frames in [3, 512, 1_500, 1, 700, 64] {
+            let mut output = vec![0.0; frames];
+            mixer.render(&mut output, &mut pad_peaks);
+            assert!(output.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
+            total_frames += frames;
         }
 
-        fn available_retirement_slots(&mut self) -> usize {
-            usize::MAX
-        }
+        assert_eq!(active_voice_frame(&mixer, 0), Some(total_frames * 2));
     }
 
-    fn create_test_prepared_stems(
-        channels: usize,
-        sample_rate_hz: u32,
-        frames: usize,
-    ) -> PreparedStemSet {
-        let buffer = create_test_sample(channels, frames, 0.25);
-        PreparedStemSet {
-            source_version_hash: 42,
-            sample_rate_hz,
-            channels,
-            frame_count: frames,
-            available_mask: full_stem_available_mask(),
-            stems: std::array::from_fn(|_| buffer.clone()),
+    #[test]
+    fn test_mono_output_writes_identical_channels() {
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        let frames = 32;
+        let data = (0..frames)
+            .flat_map(|frame| [0.5, if frame % 2 == 0 { -0.25 } else { 0.1 }])
+            .collect::<Vec<f32>>();
+        mixer.load_sample(
+            0,
+            SampleBuffer {
+                channels: 2,
+                samples: Arc::from(data.clone().into_boxed_slice()),
+            },
+        );
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![0.0; frames * 2];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.chunks_exact(2).any(|frame| frame[0] != frame[1]));
+
+        mixer.set_mono_output(true);
+        mixer.render(&mut output, &mut pad_peaks);
+        for (frame, source) in output.chunks_exact(2).zip(data.chunks_exact(2)) {
+            assert_eq!(frame[0].to_bits(), frame[1].to_bits());
+            assert!((frame[0] - (source[0] + source[1]) * MONO_SUM_GAIN).abs() < 1e-6);
+        }
+
+        // Trims weigh the channels going into the sum instead of unbalancing the mono result.
+        assert!(mixer.set_output_trim(0, 0.5));
+        assert!(mixer.set_output_trim(1, 1.5));
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.render(&mut output, &mut pad_peaks);
+        for (frame, source) in output.chunks_exact(2).zip(data.chunks_exact(2)) {
+            assert_eq!(frame[0].to_bits(), frame[1].to_bits());
+            let expected = (source[0] * 0.5 + source[1] * 1.5) * MONO_SUM_GAIN;
+            assert!((frame[0] - expected).abs() < 1e-6);
         }
     }
 
-    fn create_test_prepared_stems_with_values(
-        channels: usize,
-        sample_rate_hz: u32,
-        frames: usize,
-        values: [f32; STEM_BUFFER_COUNT],
-    ) -> PreparedStemSet {
-        PreparedStemSet {
-            source_version_hash: 42,
-            sample_rate_hz,
-            channels,
-            frame_count: frames,
-            available_mask: full_stem_available_mask(),
-            stems: values.map(|value| create_test_sample(channels, frames, value)),
+    #[test]
+    fn test_master_limiter_reports_reduction_while_hot_and_exactly_zero_after() {
+        let mut mixer = RtMixer::new(2, 1_000.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(2, 1_000, 3.0));
+        mixer.set_master_limiter(true);
+        assert_eq!(mixer.limiter_reduction_db(), (0.0, 0.0));
+        mixer.play_sample(0, 1.0);
+
+        let mut output = vec![0.0; 2 * 200];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| sample.abs() < 1.0));
+        let (hot_reduction_db, hot_peak_reduction_db) = mixer.limiter_reduction_db();
+        assert!(hot_reduction_db > 9.0, "{hot_reduction_db}");
+        assert!(hot_peak_reduction_db >= hot_reduction_db);
+
+        // The release recovers faster than the recent-peak meter falls.
+        mixer.stop_sample(0);
+        let mut output = vec![0.0; 2 * 100];
+        mixer.render(&mut output, &mut pad_peaks);
+        let (reduction_db, peak_reduction_db) = mixer.limiter_reduction_db();
+        assert!(reduction_db > 0.0 && reduction_db < hot_reduction_db);
+        assert!(peak_reduction_db > reduction_db);
+
+        for _ in 0..10 {
+            mixer.render(&mut output, &mut pad_peaks);
         }
+        let (reduction_db, peak_reduction_db) = mixer.limiter_reduction_db();
+        assert_eq!(reduction_db.to_bits(), 0.0_f32.to_bits());
+        assert_eq!(peak_reduction_db.to_bits(), 0.0_f32.to_bits());
     }
 
     #[test]
-    fn test_render_splits_oversized_blocks_to_preserve_stretch_capacity() {
-        let mut mixer = RtMixer::new(1, 44_100.0);
-        mixer.set_speed(2.0);
-        mixer.load_sample(0, create_test_sample(1, 5_000, 0.5));
+    fn test_master_dc_blocker_settles_offset_output_near_zero_mean() {
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        mixer.set_master_dc_blocker(true);
+        mixer.load_sample(0, create_test_sample(2, 44_100, 0.5));
         assert!(mixer.play_sample(0, 1.0));
 
-        let frames = mixer.max_realtime_render_frames() * 2 + 37;
-        let mut output = vec![0.0; frames];
+        let mut output = vec![0.0; 1024];
         let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        for _ in 0..40 {
+            mixer.render(&mut output, &mut pad_peaks);
+        }
 
-        mixer.render(&mut output, &mut pad_peaks);
-
-        assert!(output.iter().all(|sample| (*sample - 0.5).abs() < 1e-5));
-        assert_eq!(active_voice_frame(&mixer, 0), Some(frames * 2));
+        let mean = output.iter().sum::<f32>() / output.len() as f32;
+        assert!(mean.abs() < 0.01);
+        assert!((pad_peaks[0] - 0.5).abs() < 1e-5);
     }
 
     #[test]
@@ -2277,6 +5003,120 @@ mod tests {
         assert!(weak.upgrade().is_none());
     }
 
+    #[test]
+    fn test_unload_while_playing_retires_only_bank_handle_and_stops_voice() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let samples: Arc<[f32]> = Arc::from(vec![0.5_f32; 4096].into_boxed_slice());
+        let weak = Arc::downgrade(&samples);
+        mixer.load_sample(
+            0,
+            SampleBuffer {
+                channels: 1,
+                samples,
+            },
+        );
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![0.0; 64];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(weak.strong_count(), 1);
+
+        let mut retirement = CollectingRetirement::default();
+        assert!(mixer.unload_sample_rt(0, &mut retirement));
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+        assert!(output.iter().all(|sample| *sample == 0.0));
+        assert_eq!(retirement.samples.len(), 1);
+        assert_eq!(weak.strong_count(), 1);
+
+        drop(retirement);
+
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_reload_while_playing_fades_voices_of_previous_generation_out() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let samples: Arc<[f32]> = Arc::from(vec![0.5_f32; 4096].into_boxed_slice());
+        let weak = Arc::downgrade(&samples);
+        mixer.load_sample(
+            0,
+            SampleBuffer {
+                channels: 1,
+                samples,
+            },
+        );
+        assert!(mixer.play_sample(0, 1.0));
+        render_chunks(&mut mixer, 1, 64);
+
+        let mut retirement = CollectingRetirement::default();
+        assert!(mixer.load_sample_rt(0, create_test_sample(1, 4096, 0.25), &mut retirement));
+        assert!(retirement.samples.is_empty());
+        assert!(!mixer.sample_is_active(0));
+
+        // The old voice fades out on the old buffer instead of cutting off.
+        let stop_fade = mixer.stop_fade_samples as usize;
+        let fade = render_chunks(&mut mixer, 1, stop_fade + 16);
+        assert!(fade[0] > 0.45);
+        assert!(fade.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+        assert!(fade[stop_fade..].iter().all(|sample| *sample == 0.0));
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+
+        mixer.retire_faded_samples(&mut retirement);
+        assert_eq!(retirement.samples.len(), 1);
+        drop(retirement);
+        assert!(weak.upgrade().is_none());
+
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![0.0; 8];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (*sample - 0.25).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_queued_sample_swaps_at_the_loop_boundary_and_right_away_when_idle() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_frame_number_sample(256));
+        mixer.load_sample(1, create_test_sample(1, 256, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![0.0; 100];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        let mut swaps = Vec::new();
+        let mut retirement = CollectingRetirement::default();
+        assert!(mixer.queue_sample_swap(0, 3, create_test_sample(1, 256, 0.25), None));
+        mixer.render(&mut output, &mut pad_peaks);
+        mixer.drain_sample_swaps(&mut retirement, |id, entry| swaps.push((id, entry)));
+        assert!(output.iter().all(|sample| *sample > 1.0));
+        assert!(swaps.is_empty());
+
+        // The voice wraps 56 frames into this block; the new sample takes over on that frame.
+        mixer.render(&mut output, &mut pad_peaks);
+        mixer.drain_sample_swaps(&mut retirement, |id, entry| swaps.push((id, entry)));
+        assert!(output[..56].iter().all(|sample| *sample > 1.0));
+        assert!(
+            output[56..]
+                .iter()
+                .all(|sample| (*sample - 0.25).abs() < 1e-6)
+        );
+        assert_eq!(swaps, vec![(0, 3)]);
+        assert_eq!(retirement.samples.len(), 1);
+        assert!(mixer.sample_is_active(0));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (*sample - 0.25).abs() < 1e-6));
+
+        assert!(mixer.queue_sample_swap(1, 5, create_test_sample(1, 256, 0.75), Some(128.0)));
+        mixer.drain_sample_swaps(&mut retirement, |id, entry| swaps.push((id, entry)));
+        assert_eq!(swaps, vec![(0, 3), (1, 5)]);
+        assert_eq!(mixer.pad_bpm[1], Some(128.0));
+        assert!(mixer.play_sample(1, 1.0));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (*sample - 1.0).abs() < 1e-6));
+    }
+
     #[test]
     fn test_rejected_prepared_stems_are_retired() {
         let mut mixer = RtMixer::new(1, 44_100.0);
@@ -2334,6 +5174,56 @@ mod tests {
         assert!((ratio - 1.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_invalid_master_and_pad_bpm_are_ignored() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_master_bpm(128.0);
+        mixer.set_pad_bpm(0, Some(90.0));
+        for invalid in [f32::NAN, f32::INFINITY, 0.0, -120.0] {
+            mixer.set_master_bpm(invalid);
+        }
+        mixer.set_pad_bpm(NUM_SAMPLES, Some(100.0));
+        assert_eq!(mixer.master_bpm, Some(128.0));
+        assert_eq!(mixer.pad_bpm[0], Some(90.0));
+
+        // An unusable pad BPM leaves the pad unsynced rather than keeping a stale tempo.
+        mixer.set_pad_bpm(0, Some(f32::NAN));
+        assert_eq!(mixer.pad_bpm[0], None);
+    }
+
+    #[test]
+    fn test_pad_rate_ratio_scales_the_synced_rate_exactly() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 100_000, 0.5));
+        mixer.set_bpm_lock(true);
+        mixer.set_master_bpm(90.0);
+        mixer.set_pad_bpm(0, Some(90.0));
+        assert!(mixer.set_pad_rate_ratio(0, Some((3, 2))));
+        assert!((mixer.tempo_ratio_for_sample_id(0) - 1.5).abs() < 1e-6);
+
+        assert!(mixer.play_sample(0, 1.0));
+        render_chunks(&mut mixer, 40, 512);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(40 * 512 * 3 / 2));
+
+        // The ratio follows the synced rate and, without sync, the global speed.
+        mixer.set_master_bpm(120.0);
+        assert!((mixer.tempo_ratio_for_sample_id(0) - 2.0).abs() < 1e-6);
+        mixer.set_bpm_lock(false);
+        mixer.set_speed(0.5);
+        assert!((mixer.tempo_ratio_for_sample_id(0) - 0.75).abs() < 1e-6);
+        mixer.set_speed(SPEED_MAX);
+        assert!((mixer.tempo_ratio_for_sample_id(0) - SPEED_MAX).abs() < 1e-6);
+
+        mixer.set_bpm_lock(true);
+        mixer.set_master_bpm(120.0);
+        mixer.set_pad_bpm(0, Some(90.0));
+        assert!(mixer.set_pad_rate_ratio(0, None));
+        assert!((mixer.tempo_ratio_for_sample_id(0) - (120.0 / 90.0)).abs() < 1e-6);
+        assert!(!mixer.set_pad_rate_ratio(0, Some((0, 1))));
+        assert!(!mixer.set_pad_rate_ratio(0, Some((PAD_RATE_RATIO_TERM_MAX + 1, 16))));
+        assert!(!mixer.set_pad_rate_ratio(0, Some((3, 1))));
+    }
+
     #[test]
     fn key_lock_reduces_varispeed_pitch_shift_in_mixer_path() {
         let sample_rate_hz = 48_000.0;
@@ -2365,6 +5255,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn key_lock_short_loops_match_varispeed_onset_and_energy() {
+        fn onset_and_energy(output: &[f32]) -> (Option<usize>, f32) {
+            let onset = output.iter().position(|sample| sample.abs() > 0.1);
+            let energy = output.iter().map(|sample| sample * sample).sum();
+            (onset, energy)
+        }
+
+        let sample_rate_hz = 48_000.0;
+        for frames in [64, 300, 1_000] {
+            let samples: Vec<f32> = (0..frames)
+                .map(|frame| if frame < 8 { 1.0 } else { 0.0 })
+                .collect();
+            let source = SampleBuffer {
+                channels: 1,
+                samples: Arc::from(samples.into_boxed_slice()),
+            };
+
+            for speed in [1.0, 1.5] {
+                let render = |key_lock: bool| {
+                    let mut mixer = RtMixer::new(1, sample_rate_hz);
+                    mixer.set_master_dc_blocker(false);
+                    mixer.load_sample(0, source.clone());
+                    mixer.set_speed(speed);
+                    mixer.set_key_lock(key_lock);
+                    assert!(mixer.play_sample(0, 1.0));
+                    render_chunks(&mut mixer, 8, 512)
+                };
+
+                let (varispeed_onset, varispeed_energy) = onset_and_energy(&render(false));
+                let (key_lock_onset, key_lock_energy) = onset_and_energy(&render(true));
+
+                assert_eq!(varispeed_onset, Some(0), "frames={frames} speed={speed}");
+                assert_eq!(
+                    key_lock_onset, varispeed_onset,
+                    "frames={frames} speed={speed}"
+                );
+                assert!(
+                    (key_lock_energy - varispeed_energy).abs() <= varispeed_energy * 1e-4,
+                    "frames={frames} speed={speed} varispeed={varispeed_energy} \
+                     key_lock={key_lock_energy}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn key_lock_ratio_change_while_active_advances_existing_voice() {
         let sample_rate_hz = 48_000.0;
@@ -2490,6 +5426,7 @@ mod tests {
     #[test]
     fn key_lock_retrigger_stop_and_unload_clear_pending_shifted_output() {
         let mut mixer = RtMixer::new(1, 48_000.0);
+        assert!(mixer.set_stop_fade_samples(0));
         let source = create_sine_sample(48_000.0, 96_000, 440.0);
         mixer.load_sample(0, source.clone());
         mixer.set_speed(2.0);
@@ -3590,12 +6527,93 @@ mod tests {
         assert_eq!(mixer.voices.iter().filter(|v| v.active).count(), 2);
 
         mixer.stop_sample(0);
+        assert!(!mixer.sample_is_active(0));
 
-        // Only sample 1 should be stopped, sample 2 should still play
+        // Sample 0 frees its voice after the stop fade, sample 1 should still play
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut output = vec![0.0; 2 * mixer.stop_fade_samples() as usize];
+        mixer.render(&mut output, &mut pad_peaks);
         assert!(mixer.voices.iter().any(|v| v.active && v.sample_id == 1));
         assert!(mixer.voices.iter().all(|v| !v.active || v.sample_id != 0));
     }
 
+    #[test]
+    fn test_stop_sample_and_stop_all_fade_out_linearly_over_the_stop_fade() {
+        // 5 ms at each rate.
+        for (sample_rate_hz, fade) in [(48_000.0, 240), (96_000.0, 480)] {
+            for stop in ["sample", "all", "auto"] {
+                let mut mixer = RtMixer::new(1, sample_rate_hz);
+                assert_eq!(mixer.stop_fade_samples() as usize, fade);
+                mixer.load_sample(0, create_test_sample(1, 48_000, 0.5));
+                assert!(mixer.play_sample(0, 1.0));
+                mixer.set_auto_stop_frame(0, 256);
+                let before = render_chunks(&mut mixer, 1, 256);
+                let level = before[255].abs();
+                assert!(level > 0.0);
+
+                match stop {
+                    "sample" => mixer.stop_sample(0),
+                    "all" => mixer.stop_all(),
+                    _ => {
+                        assert!(mixer.auto_stop_sample(0, 256));
+                        assert!(!mixer.auto_stop_sample(0, 256));
+                    }
+                }
+                let after = render_chunks(&mut mixer, 1, 512);
+
+                assert!(after.iter().all(|sample| sample.abs() <= level));
+                assert!(
+                    after[..fade]
+                        .windows(2)
+                        .all(|pair| pair[1].abs() < pair[0].abs())
+                );
+                assert!(after[fade..].iter().all(|sample| *sample == 0.0));
+                assert!(mixer.voices.iter().all(|voice| !voice.active));
+            }
+        }
+
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        assert!(!mixer.set_stop_fade_samples(STOP_FADE_SAMPLES_MAX + 1));
+        assert!(mixer.set_stop_fade_samples(64));
+        mixer.load_sample(0, create_test_sample(1, 48_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.stop_sample(0);
+        render_chunks(&mut mixer, 1, 64);
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+    }
+
+    #[test]
+    fn test_pad_gain_multiplies_velocity_and_volume_and_resets_on_unload() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_volume(0.5);
+        mixer.load_sample(0, create_test_sample(1, 44_100, 0.8));
+        mixer.set_pad_gain(0, 20.0 * 0.5_f32.log10());
+        assert!(mixer.play_sample(0, 0.5));
+        let output = render_chunks(&mut mixer, 1, 64);
+        assert!(
+            (output[63] - 0.8 * 0.5 * 0.5 * 0.5).abs() < 1e-4,
+            "{}",
+            output[63]
+        );
+
+        // A playing voice follows a gain change after the smoothing ramp.
+        mixer.set_pad_gain(0, 0.0);
+        let output = render_chunks(&mut mixer, 1, 1_024);
+        assert!(
+            (output[1_023] - 0.8 * 0.5 * 0.5).abs() < 1e-4,
+            "{}",
+            output[1_023]
+        );
+
+        mixer.set_pad_gain(0, PAD_GAIN_DB_MIN);
+        mixer.unload_sample(0);
+        assert_eq!(mixer.pad_gain_db[0], PAD_GAIN_DB_DEFAULT);
+        mixer.load_sample(0, create_test_sample(1, 44_100, 0.8));
+        assert!(mixer.play_sample(0, 1.0));
+        let output = render_chunks(&mut mixer, 1, 64);
+        assert!((output[63] - 0.8 * 0.5).abs() < 1e-4, "{}", output[63]);
+    }
+
     #[test]
     fn test_unload_sample() {
         let mut mixer = RtMixer::new(2, 44_100.0);
@@ -3682,6 +6700,108 @@ mod tests {
         assert!(pad_peaks[0] < 1e-6);
     }
 
+    #[test]
+    fn test_pad_eq_clamps_extreme_gains_to_documented_range() {
+        let mut clamped = RtMixer::new(1, 44_100.0);
+        let mut bounded = RtMixer::new(1, 44_100.0);
+        clamped.set_pad_eq(0, -200.0, 200.0, 1.0e30);
+        bounded.set_pad_eq(0, PAD_EQ_DB_MIN, PAD_EQ_DB_MAX, PAD_EQ_DB_MAX);
+
+        for slot in [
+            DspParameterSlot::Slot0,
+            DspParameterSlot::Slot1,
+            DspParameterSlot::Slot2,
+        ] {
+            assert_eq!(
+                clamped.pad_dsp_chains[0].parameter(slot).target(),
+                bounded.pad_dsp_chains[0].parameter(slot).target()
+            );
+        }
+
+        let before = clamped.pad_dsp_chains[0]
+            .parameter(DspParameterSlot::Slot0)
+            .target();
+        clamped.set_pad_eq(0, f32::NAN, 0.0, 0.0);
+        assert_eq!(
+            clamped.pad_dsp_chains[0]
+                .parameter(DspParameterSlot::Slot0)
+                .target(),
+            before
+        );
+    }
+
+    #[test]
+    fn test_pad_eq_low_cut_removes_energy_below_the_250_hz_crossover() {
+        let frames = 8_192;
+        let energy = |low_db: f32, frequency_hz: f32| {
+            let mut mixer = RtMixer::new(1, 44_100.0);
+            mixer.load_sample(0, create_sine_sample(44_100.0, frames, frequency_hz));
+            mixer.set_pad_eq(0, low_db, 0.0, 0.0);
+            assert!(mixer.play_sample(0, 1.0));
+            let output = render_chunks(&mut mixer, 16, frames / 16);
+            // Skip the gain smoothing and filter settling at the start.
+            output[frames / 2..]
+                .iter()
+                .map(|sample| sample * sample)
+                .sum::<f32>()
+        };
+
+        let bass_bypass = energy(0.0, 100.0);
+        let bass_cut = energy(PAD_EQ_DB_MIN, 100.0);
+        assert!(bass_cut < bass_bypass * 0.1, "{bass_cut} vs {bass_bypass}");
+
+        let mid_bypass = energy(0.0, 1_000.0);
+        let mid_cut = energy(PAD_EQ_DB_MIN, 1_000.0);
+        assert!(mid_cut > mid_bypass * 0.8, "{mid_cut} vs {mid_bypass}");
+    }
+
+    #[test]
+    fn test_pad_eq_change_reaches_a_playing_voice_at_the_output_rate() {
+        let frames = 16_384;
+        let energy = |output: &[f32]| {
+            output[frames / 2..]
+                .iter()
+                .map(|sample| sample * sample)
+                .sum::<f32>()
+        };
+        let cut_ratio = |frequency_hz: f32| {
+            let mut mixer = RtMixer::new(1, 96_000.0);
+            mixer.load_sample(0, create_sine_sample(96_000.0, 4 * frames, frequency_hz));
+            assert!(mixer.play_sample(0, 1.0));
+            let flat = energy(&render_chunks(&mut mixer, 16, frames / 16));
+            mixer.set_pad_eq(0, PAD_EQ_DB_MIN, 0.0, 0.0);
+            let cut = energy(&render_chunks(&mut mixer, 16, frames / 16));
+            assert!(mixer.sample_is_active(0));
+            cut / flat
+        };
+
+        assert!(cut_ratio(100.0) < 0.1);
+        // A crossover scaled for 44.1 kHz would sit near 540 Hz and cut this tone as well.
+        assert!(cut_ratio(400.0) > 0.5);
+    }
+
+    #[test]
+    fn test_reset_pad_dsp_clears_filter_state_and_keeps_position() {
+        let frames = 1024;
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_sine_sample(44_100.0, 4 * frames, 1_000.0));
+        mixer.set_pad_eq(0, PAD_EQ_DB_MIN, 0.0, PAD_EQ_DB_MAX);
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![0.0; frames];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(!mixer.pad_dsp_chains[0].filter_state_is_clear());
+        let frame_pos = active_voice_frame(&mixer, 0);
+
+        assert!(mixer.reset_pad_dsp(0));
+
+        assert!(mixer.pad_dsp_chains[0].filter_state_is_clear());
+        assert_eq!(active_voice_frame(&mixer, 0), frame_pos);
+        assert!(mixer.sample_is_active(0));
+        assert!(!mixer.reset_pad_dsp(NUM_SAMPLES));
+    }
+
     #[test]
     fn test_pad_isolator_boost_is_not_double_processed_by_old_eq_path() {
         let frames = 4096;
@@ -3743,6 +6863,123 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_speed_change_glides_running_voices_without_a_jump() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_frame_number_sample(80_000));
+        assert!(mixer.play_sample(0, 1.0));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut block = vec![0.0; 256];
+        mixer.render(&mut block, &mut pad_peaks);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(256));
+
+        mixer.set_speed(2.0);
+        let mut output = vec![block[255]];
+        for _ in 0..100 {
+            mixer.render(&mut block, &mut pad_peaks);
+            output.extend_from_slice(&block);
+        }
+        for pair in output.windows(2) {
+            let step = pair[1] - pair[0];
+            assert!(step > 0.0 && step <= 2.0 + 1e-2, "step {step}");
+        }
+
+        // Once the glide settled, a block consumes twice its length.
+        let settled = active_voice_frame(&mixer, 0).unwrap();
+        mixer.render(&mut block, &mut pad_peaks);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(settled + 512));
+    }
+
+    #[test]
+    fn test_trigger_speed_applies_to_its_own_voice_only_and_stacks_with_the_global_speed() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_frame_number_sample(80_000));
+        mixer.load_sample(1, create_frame_number_sample(80_000));
+        let half_speed = TriggerOptions {
+            speed: Some(0.5),
+            loop_mode: None,
+            start_s: None,
+            pan: None,
+        };
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, half_speed, 0));
+        assert!(mixer.play_sample(1, 1.0));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut block = vec![0.0; 256];
+        mixer.render(&mut block, &mut pad_peaks);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(128));
+        assert_eq!(active_voice_frame(&mixer, 1), Some(256));
+
+        // The trigger speed multiplies the global speed, within the same limits.
+        mixer.set_speed(SPEED_MAX);
+        for _ in 0..100 {
+            mixer.render(&mut block, &mut pad_peaks);
+        }
+        let slow = active_voice_frame(&mixer, 0).unwrap();
+        mixer.render(&mut block, &mut pad_peaks);
+        assert_eq!(
+            active_voice_frame(&mixer, 0),
+            Some(slow + (256.0 * SPEED_MAX * 0.5) as usize)
+        );
+
+        let nan_speed = TriggerOptions {
+            speed: Some(f32::NAN),
+            loop_mode: None,
+            start_s: None,
+            pan: None,
+        };
+        assert!(!mixer.play_sample_with_at_output_frame(0, 1.0, nan_speed, 0));
+    }
+
+    #[test]
+    fn test_unit_speed_is_bit_exact_and_double_speed_doubles_the_pitch() {
+        let sine = create_sine_sample(44_100.0, 8_192, 441.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, sine.clone());
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![0.0; 2_048];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output[..], sine.samples[..2_048]);
+
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.set_speed(2.0);
+        mixer.load_sample(0, sine.clone());
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.render(&mut output, &mut pad_peaks);
+        let subsampled: Vec<f32> = sine.samples.iter().step_by(2).copied().collect();
+        let rising_zero_crossings = |signal: &[f32]| {
+            signal
+                .windows(2)
+                .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+                .count() as f32
+        };
+        let played = rising_zero_crossings(&output);
+        let expected = rising_zero_crossings(&subsampled[..output.len()]);
+        assert!(
+            (played / expected - 1.0).abs() < 0.03,
+            "{played} vs {expected} cycles"
+        );
+    }
+
+    #[test]
+    fn test_double_speed_ramp_produces_every_other_frame() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.set_speed(2.0);
+        mixer.load_sample(0, create_frame_number_sample(4_096));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![0.0; 512];
+        mixer.render(&mut output, &mut pad_peaks);
+        // The voice reads frames 0, 2, 4, ... within the varispeed block interpolation.
+        assert_eq!(output[0], 0.0);
+        for pair in output.windows(2) {
+            assert!((pair[1] - pair[0] - 2.0).abs() < 0.01, "{pair:?}");
+        }
+        assert_eq!(active_voice_frame(&mixer, 0), Some(1_024));
+    }
+
     #[test]
     fn test_render_loop_sample() {
         let mut mixer = RtMixer::new(1, 44_100.0);
@@ -3760,6 +6997,303 @@ mod tests {
         assert!(output.iter().all(|&s| (s - 0.5).abs() < 1e-5));
     }
 
+    #[test]
+    fn test_one_shot_default_stops_at_sample_end_unless_pad_loops() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(1, 5, 0.5));
+        mixer.load_sample(1, create_test_sample(1, 5, 0.5));
+        mixer.set_default_loop_mode(LoopMode::OneShot);
+        mixer.set_pad_loop_mode(1, Some(LoopMode::Loop));
+        mixer.play_sample(0, 1.0);
+
+        let mut output = vec![0.0; 20];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output[..5].iter().all(|&s| (s - 0.5).abs() < 1e-5));
+        assert!(output[5..].iter().all(|&s| s == 0.0));
+        assert!(!mixer.sample_is_active(0));
+        assert_eq!(mixer.take_ended_one_shot(), Some(0));
+        assert_eq!(mixer.take_ended_one_shot(), None);
+
+        mixer.play_sample(1, 1.0);
+        let mut output = vec![0.0; 20];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output.iter().all(|&s| (s - 0.5).abs() < 1e-5));
+        assert!(mixer.sample_is_active(1));
+        assert_eq!(mixer.take_ended_one_shot(), None);
+    }
+
+    #[test]
+    fn test_one_shot_pad_releases_its_voice_after_exactly_one_playthrough_and_replays() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(1, 64, 0.5));
+        mixer.set_pad_loop_mode(0, Some(LoopMode::OneShot));
+
+        for _ in 0..2 {
+            assert!(mixer.play_sample(0, 1.0));
+            let mut output = vec![0.0; 64];
+            mixer.render(&mut output, &mut pad_peaks);
+            assert!(output.iter().all(|&s| (s - 0.5).abs() < 1e-5));
+            assert!(!mixer.voices.iter().any(|voice| voice.active));
+
+            mixer.render(&mut output, &mut pad_peaks);
+            assert!(output.iter().all(|&s| s == 0.0));
+            assert_eq!(mixer.take_ended_one_shot(), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_trigger_loop_mode_overrides_the_pad_for_that_voice_only() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(1, 64, 0.5));
+        let once = TriggerOptions {
+            speed: None,
+            loop_mode: Some(LoopMode::OneShot),
+            start_s: None,
+            pan: None,
+        };
+
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, once, 0));
+        assert_eq!(mixer.pad_remaining(0), Some((64, 64.0 / 44_100.0)));
+        let mut output = vec![0.0; 128];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output[64..].iter().all(|&s| s == 0.0));
+        assert_eq!(mixer.take_ended_one_shot(), Some(0));
+
+        // A plain trigger loops again, and a looping trigger outlasts a one-shot pad.
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(mixer.sample_is_active(0));
+        assert_eq!(mixer.pad_remaining(0), None);
+
+        mixer.set_pad_loop_mode(0, Some(LoopMode::OneShot));
+        let looped = TriggerOptions {
+            speed: None,
+            loop_mode: Some(LoopMode::Loop),
+            start_s: None,
+            pan: None,
+        };
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, looped, 0));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|&s| (s - 0.5).abs() < 1e-5));
+        assert!(mixer.sample_is_active(0));
+        assert_eq!(mixer.take_ended_one_shot(), None);
+    }
+
+    #[test]
+    fn test_trigger_pan_uses_equal_power_gains_on_stereo_only() {
+        let render_panned = |channels: usize, pan: f32| {
+            let mut mixer = RtMixer::new(channels, 44_100.0);
+            mixer.load_sample(0, create_test_sample(channels, 1_000, 0.5));
+            let options = TriggerOptions {
+                pan: Some(pan),
+                ..TriggerOptions::default()
+            };
+            assert!(mixer.play_sample_with_at_output_frame(0, 1.0, options, 0));
+            render_chunks(&mut mixer, 1, 64)
+        };
+
+        let left = render_panned(2, -1.0);
+        assert!(
+            left.chunks_exact(2)
+                .all(|frame| (frame[0] - 0.5).abs() < 1e-5)
+        );
+        assert!(left.chunks_exact(2).all(|frame| frame[1].abs() < 1e-5));
+        let right = render_panned(2, 1.0);
+        assert!(right.chunks_exact(2).all(|frame| frame[0].abs() < 1e-5));
+        assert!(
+            right
+                .chunks_exact(2)
+                .all(|frame| (frame[1] - 0.5).abs() < 1e-5)
+        );
+        let center = render_panned(2, 0.0);
+        let half_power = 0.5 * std::f32::consts::FRAC_1_SQRT_2;
+        assert!(
+            center
+                .iter()
+                .all(|sample| (sample - half_power).abs() < 1e-5)
+        );
+
+        let mono = render_panned(1, -1.0);
+        assert!(mono.iter().all(|sample| (sample - 0.5).abs() < 1e-5));
+
+        let mut mixer = RtMixer::new(2, 44_100.0);
+        mixer.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        let too_far = TriggerOptions {
+            pan: Some(1.5),
+            ..TriggerOptions::default()
+        };
+        assert!(!mixer.play_sample_with_at_output_frame(0, 1.0, too_far, 0));
+    }
+
+    #[test]
+    fn test_trigger_start_offset_renders_from_that_source_region() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_frame_number_sample(1_000));
+        let from_quarter = TriggerOptions {
+            start_s: Some(0.25),
+            ..TriggerOptions::default()
+        };
+
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, from_quarter, 0));
+        let mut output = vec![0.0; 16];
+        mixer.render(&mut output, &mut pad_peaks);
+        let expected: Vec<f32> = (250..266).map(|frame| frame as f32).collect();
+        assert_eq!(output, expected);
+
+        // Past the end, a looping voice wraps and a one-shot stops at the end.
+        let wrapped = TriggerOptions {
+            start_s: Some(2.1),
+            ..TriggerOptions::default()
+        };
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, wrapped, 0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(100));
+
+        let clamped = TriggerOptions {
+            start_s: Some(2.1),
+            loop_mode: Some(LoopMode::OneShot),
+            ..TriggerOptions::default()
+        };
+        assert!(mixer.play_sample_with_at_output_frame(0, 1.0, clamped, 0));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output[0], 999.0);
+        assert!(output[1..].iter().all(|&sample| sample == 0.0));
+        assert_eq!(mixer.take_ended_one_shot(), Some(0));
+
+        let negative = TriggerOptions {
+            start_s: Some(-1.0),
+            ..TriggerOptions::default()
+        };
+        assert!(!mixer.play_sample_with_at_output_frame(0, 1.0, negative, 0));
+    }
+
+    #[test]
+    fn test_cued_voice_stays_off_master_until_promoted_without_a_jump() {
+        let mut mixer = RtMixer::new(2, 1_000.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        mixer.set_cue_split(true);
+        assert!(mixer.play_sample_cued_at_output_frame(0, 1.0, 0));
+
+        let mut output = vec![0.0; 2 * 20];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(
+            output
+                .chunks_exact(2)
+                .all(|frame| frame[0] == 0.0 && (frame[1] - 0.5).abs() < 1e-5)
+        );
+
+        assert!(mixer.promote_to_master(0));
+        assert!(!mixer.promote_to_master(0));
+        let mut output = vec![0.0; 2 * 40];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        // 10 ms at 1 kHz: the master send ramps over 10 frames.
+        let master: Vec<f32> = output.chunks_exact(2).map(|frame| frame[0]).collect();
+        assert!(
+            master
+                .windows(2)
+                .all(|pair| pair[1] >= pair[0] && pair[1] - pair[0] <= 0.05 + 1e-5)
+        );
+        assert!((master[39] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_monitor_outputs_use_their_own_pair_and_fall_back_on_stereo() {
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let stereo_sample = SampleBuffer {
+            channels: 2,
+            samples: Arc::from([0.1, 0.2].repeat(1_000).into_boxed_slice()),
+        };
+        let mut mixer = RtMixer::new(4, 1_000.0);
+        mixer.load_sample(0, stereo_sample.clone());
+        mixer.load_sample(1, stereo_sample);
+        mixer.set_monitor_output(MonitorSource::Cue, Some(2));
+        mixer.set_monitor_output(MonitorSource::Preview, Some(2));
+        mixer.set_pad_cue(0, true);
+        mixer.play_sample(0, 1.0);
+        assert!(mixer.preview_loop_region(1, 0.0, 0.5));
+
+        let mut output = vec![0.0; 4 * 20];
+        mixer.render(&mut output, &mut pad_peaks);
+        for frame in output.chunks_exact(4) {
+            assert!((frame[0] - 0.1).abs() < 1e-5 && (frame[1] - 0.2).abs() < 1e-5);
+            // Cue (mono sum 0.15) plus the preview.
+            assert!((frame[2] - 0.25).abs() < 1e-5 && (frame[3] - 0.35).abs() < 1e-5);
+        }
+
+        let mut stereo = RtMixer::new(2, 1_000.0);
+        stereo.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        stereo.load_sample(1, create_test_sample(2, 1_000, 0.25));
+        stereo.set_monitor_output(MonitorSource::Cue, Some(2));
+        stereo.set_monitor_output(MonitorSource::Preview, Some(2));
+        stereo.set_pad_cue(0, true);
+        stereo.play_sample(0, 1.0);
+        assert!(stereo.preview_loop_region(1, 0.0, 0.5));
+
+        let mut output = vec![0.0; 2 * 20];
+        stereo.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (sample - 0.75).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_quad_output_pans_placed_pads_and_keeps_others_on_the_front_pair() {
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        let mut mixer = RtMixer::new(4, 1_000.0);
+        mixer.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        mixer.load_sample(1, create_test_sample(2, 1_000, 0.25));
+        assert!(!mixer.set_speaker_layout(SpeakerLayout::new(&[0.0, 120.0, 240.0])));
+        assert!(mixer.set_speaker_layout(SpeakerLayout::new(&[-45.0, 45.0, -135.0, 135.0])));
+        assert!(mixer.set_pad_position(0, Some(90.0)));
+        assert!(!mixer.set_pad_position(1, Some(f32::INFINITY)));
+        mixer.play_sample(0, 1.0);
+        mixer.play_sample(1, 1.0);
+
+        let mut output = vec![0.0; 4 * 20];
+        mixer.render(&mut output, &mut pad_peaks);
+        let side = 0.5 * std::f32::consts::FRAC_1_SQRT_2;
+        for frame in output.chunks_exact(4) {
+            assert!((frame[0] - 0.25).abs() < 1e-5);
+            assert!((frame[1] - (0.25 + side)).abs() < 1e-5);
+            assert!(frame[2].abs() < 1e-5);
+            assert!((frame[3] - side).abs() < 1e-5);
+        }
+
+        // Stereo outputs take no layout, so positions change nothing.
+        let mut stereo = RtMixer::new(2, 1_000.0);
+        stereo.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        assert!(!stereo.set_speaker_layout(SpeakerLayout::new(&[-45.0, 45.0, 180.0])));
+        assert!(stereo.set_pad_position(0, Some(90.0)));
+        stereo.play_sample(0, 1.0);
+        let mut output = vec![0.0; 2 * 20];
+        stereo.render(&mut output, &mut pad_peaks);
+        assert!(output.iter().all(|sample| (sample - 0.5).abs() < 1e-5));
+    }
+
+    #[test]
+    fn test_pad_cue_hears_pad_with_master_volume_down() {
+        let mut mixer = RtMixer::new(2, 1_000.0);
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_test_sample(2, 1_000, 0.5));
+        mixer.set_cue_split(true);
+        mixer.set_volume(0.0);
+        mixer.set_pad_cue(0, true);
+        mixer.play_sample(0, 1.0);
+
+        let mut output = vec![0.0; 2 * 20];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(
+            output
+                .chunks_exact(2)
+                .all(|frame| frame[0] == 0.0 && (frame[1] - 0.5).abs() < 1e-5)
+        );
+    }
+
     #[test]
     fn test_render_respects_custom_loop_region_frames() {
         let mut mixer = RtMixer::new(1, 10.0);
@@ -3780,6 +7314,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_preview_loop_region_auditions_without_touching_pad() {
+        let mut mixer = RtMixer::new(1, 10.0);
+        mixer.load_sample(0, create_frame_number_sample(20));
+        mixer.set_pad_loop_region(0, 0.0, Some(1.0));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        assert!(mixer.preview_loop_region(0, 0.2, 0.5));
+        let mut output = vec![0.0; 7];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert_eq!(output, vec![2.0, 3.0, 4.0, 2.0, 3.0, 4.0, 2.0]);
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+        assert_eq!(mixer.pad_loop_start_frame[0], 0);
+        assert_eq!(mixer.pad_loop_end_frame[0], Some(10));
+
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.stop_preview_loop();
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        assert!(!mixer.preview_loop_region(0, 0.5, 0.5));
+        assert!(!mixer.preview_loop_region(1, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_sample_swap_ends_a_preview_of_the_swapped_out_buffer() {
+        let mut mixer = RtMixer::new(1, 10.0);
+        mixer.load_sample(0, create_frame_number_sample(20));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        assert!(mixer.preview_loop_region(0, 1.0, 1.9));
+        let mut output = vec![0.0; 3];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output, vec![10.0, 11.0, 12.0]);
+
+        // The idle slot swaps at once; rendering must not read the old range from the new buffer.
+        assert!(mixer.queue_sample_swap(0, 1, create_frame_number_sample(5), None));
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output, vec![0.0; 3]);
+        assert!(mixer.preview_loop.is_none());
+    }
+
     #[test]
     fn test_render_clamps_frame_pos_to_loop_start_after_update() {
         let mut mixer = RtMixer::new(1, 10.0);
@@ -3825,6 +7401,53 @@ mod tests {
         assert_eq!(active_voice_frame(&mixer, 0), Some(7));
     }
 
+    #[test]
+    fn test_stutter_replays_slice_and_resumes_at_regular_position() {
+        let mut mixer = RtMixer::new(1, 10.0);
+        mixer.load_sample(0, create_frame_number_sample(30));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = vec![0.0; 6];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(mixer.set_stutter(0, 3, true));
+        let mut output = vec![0.0; 8];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output, vec![3.0, 4.0, 5.0, 3.0, 4.0, 5.0, 3.0, 4.0]);
+        assert_eq!(active_voice_frame(&mixer, 0), Some(14));
+
+        assert!(mixer.set_stutter(0, 3, false));
+        let mut output = vec![0.0; 3];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert_eq!(output, vec![14.0, 15.0, 16.0]);
+    }
+
+    #[test]
+    fn test_stutter_near_sample_start_extends_slice_forward() {
+        let mut mixer = RtMixer::new(1, 10.0);
+        mixer.load_sample(0, create_frame_number_sample(30));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.set_stutter(0, 2, true));
+
+        let mut output = vec![0.0; 5];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert_eq!(output, vec![0.0, 1.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_stutter_requires_playing_voice_and_nonzero_slice() {
+        let mut mixer = RtMixer::new(1, 10.0);
+        mixer.load_sample(0, create_frame_number_sample(30));
+
+        assert!(!mixer.set_stutter(0, 3, true));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(!mixer.set_stutter(0, 0, true));
+        assert!(!mixer.set_stutter(NUM_SAMPLES, 3, true));
+    }
+
     #[test]
     fn test_seek_before_loop_plays_into_loop_then_wraps() {
         let mut mixer = RtMixer::new(1, 10.0);
@@ -3988,6 +7611,34 @@ mod tests {
         assert!(output.iter().all(|&s| (s - expected).abs() < 1e-6));
     }
 
+    #[test]
+    fn test_pad_gain_matches_trigger_velocity_and_persists_across_triggers() {
+        let half_gain_db = -20.0 * 2.0_f32.log10();
+        let render_pad = |gain_db: f32, velocity: f32| {
+            let mut mixer = RtMixer::new(1, 44_100.0);
+            mixer.load_sample(0, create_sine_sample(44_100.0, 1_000, 441.0));
+            mixer.set_pad_gain(0, gain_db);
+            let mut output = Vec::new();
+            for _ in 0..2 {
+                assert!(mixer.play_sample(0, velocity));
+                output.extend(render_chunks(&mut mixer, 1, 256));
+                mixer.stop_sample(0);
+                render_chunks(&mut mixer, 1, 512);
+            }
+            output
+        };
+
+        let trimmed = render_pad(half_gain_db, 1.0);
+        let reference = render_pad(0.0, 0.5);
+        assert!(trimmed.iter().any(|sample| sample.abs() > 0.1));
+        for (trimmed, reference) in trimmed.iter().zip(&reference) {
+            assert!(
+                (trimmed - reference).abs() < 1e-5,
+                "{trimmed} vs {reference}"
+            );
+        }
+    }
+
     #[test]
     fn test_pad_gain_db_to_linear_reference_values() {
         assert!((gain_db_to_linear(0.0) - 1.0).abs() < 1e-6);
@@ -4030,6 +7681,423 @@ mod tests {
         assert!(first_smoothed_value < target);
     }
 
+    #[test]
+    fn test_pad_fade_mute_ramps_to_silence_while_voice_keeps_advancing() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 20_000, 0.5));
+        assert!(mixer.play_sample(0, 1.0));
+        let ramp_frames = (44_100.0 * PAD_FADE_MUTE_MS / 1000.0).round() as usize;
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        assert!(mixer.set_pad_fade_mute(0, true));
+        let mut output = vec![0.0; ramp_frames + 64];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output[0] > 0.49);
+        assert!(output.windows(2).all(|pair| pair[1] <= pair[0] + 1e-6));
+        assert!(output[ramp_frames..].iter().all(|sample| *sample == 0.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(ramp_frames + 64));
+
+        assert!(mixer.set_pad_fade_mute(0, false));
+        let mut output = vec![0.0; ramp_frames + 64];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output[0] < 0.01);
+        assert!(output.windows(2).all(|pair| pair[1] + 1e-6 >= pair[0]));
+        assert!(
+            output[ramp_frames..]
+                .iter()
+                .all(|sample| (*sample - 0.5).abs() < 1e-5)
+        );
+        assert_eq!(active_voice_frame(&mixer, 0), Some(2 * (ramp_frames + 64)));
+    }
+
+    #[test]
+    fn test_pad_fades_ramp_over_many_buffers_and_stop_from_current_gain() {
+        let mut mixer = RtMixer::new(1, 100.0);
+        mixer.load_sample(0, create_test_sample(1, 1_000, 1.0));
+        let fades = PadFades {
+            fade_in_ms: 1_000.0,
+            fade_out_ms: 2_000.0,
+        };
+        assert!(mixer.set_pad_fades(0, Some(fades)));
+        assert!(mixer.play_sample(0, 1.0));
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+
+        let fade_in = render_chunks(&mut mixer, 5, 10);
+        assert!(fade_in.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!((fade_in[49] - 0.5).abs() < 1e-4);
+
+        mixer.stop_sample(0);
+        assert!(!mixer.sample_is_active(0));
+        let mut output = vec![0.0; 100];
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output[0] < 0.5 && output[0] > 0.49);
+        assert!((output[99] - 0.25).abs() < 1e-4);
+        assert!(mixer.voices.iter().any(|voice| voice.active));
+
+        mixer.render(&mut output, &mut pad_peaks);
+        assert!(output[99].abs() < 1e-4);
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+
+        let too_long = PadFades {
+            fade_in_ms: PAD_FADE_MS_MAX + 1.0,
+            fade_out_ms: 0.0,
+        };
+        assert!(!mixer.set_pad_fades(0, Some(too_long)));
+        assert!(mixer.set_pad_fades(0, None));
+        assert!(mixer.set_stop_fade_samples(0));
+        assert!(mixer.play_sample(0, 1.0));
+        mixer.stop_sample(0);
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+    }
+
+    #[test]
+    fn test_pad_fade_mute_applies_immediately_to_idle_pad() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        mixer.load_sample(0, create_test_sample(1, 64, 0.5));
+
+        assert!(mixer.set_pad_fade_mute(0, true));
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = vec![1.0; 16];
+        let mut pad_peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.render(&mut output, &mut pad_peaks);
+
+        assert!(output.iter().all(|sample| *sample == 0.0));
+        assert!(!mixer.set_pad_fade_mute(NUM_SAMPLES, true));
+    }
+
+    #[test]
+    fn test_pad_latency_reports_stretcher_delay_only_for_key_locked_pads() {
+        let sample_rate_hz = 48_000.0;
+        let source = create_sine_sample(sample_rate_hz, 96_000, 440.0);
+        let mut mixer = RtMixer::new(1, sample_rate_hz);
+        mixer.load_sample(0, source.clone());
+        mixer.load_sample(1, source);
+        mixer.set_speed(2.0);
+        mixer.set_pad_key_lock(0, true);
+
+        assert_eq!(mixer.pad_latency_frames(0), 0);
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.play_sample(1, 1.0));
+        render_chunks(&mut mixer, 4, 512);
+
+        let key_lock_stretch = mixer
+            .voices
+            .iter()
+            .find(|voice| voice.active && voice.sample_id == 0)
+            .map(|voice| &voice.stretch)
+            .unwrap();
+        assert_eq!(
+            mixer.pad_latency_frames(0),
+            key_lock_stretch.rubberband_block_size() + key_lock_stretch.rubberband_start_delay()
+        );
+        assert_eq!(mixer.pad_latency_frames(1), 0);
+        assert_eq!(mixer.pad_latency_frames(2), 0);
+    }
+
+    #[test]
+    fn test_pad_remaining_scales_with_speed_and_reports_loops_as_indefinite() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 1_000, 0.5));
+        mixer.load_sample(1, create_test_sample(1, 1_000, 0.5));
+        mixer.set_pad_loop_mode(0, Some(LoopMode::OneShot));
+        assert!(mixer.set_tempo_glide_ms(0.0));
+        mixer.set_speed(2.0);
+
+        assert_eq!(mixer.pad_remaining(0), Some((0, 0.0)));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.play_sample(1, 1.0));
+        render_chunks(&mut mixer, 1, 100);
+
+        // 800 source frames left at double speed.
+        assert_eq!(mixer.pad_remaining(0), Some((400, 0.4)));
+        assert_eq!(mixer.pad_remaining(1), None);
+        assert_eq!(mixer.pad_remaining(2), Some((0, 0.0)));
+    }
+
+    #[test]
+    fn test_pad_slices_advance_start_frame_per_trigger_and_wrap() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 2_000, 0.5));
+        mixer.set_pad_loop_region(0, 0.4, Some(2.0));
+        assert!(mixer.set_pad_slices(0, 4));
+
+        let starts: Vec<usize> = (0..5)
+            .map(|_| {
+                assert!(mixer.play_sample(0, 1.0));
+                active_voice_frame(&mixer, 0).unwrap()
+            })
+            .collect();
+
+        assert_eq!(starts, vec![400, 800, 1_200, 1_600, 400]);
+    }
+
+    #[test]
+    fn test_transient_snap_moves_starts_and_seeks_onto_onsets_within_window() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 2_000, 0.5));
+        assert!(mixer.set_pad_slices(0, 4));
+        assert!(mixer.publish_pad_onsets(0, Arc::from([10_usize, 530, 1_400].as_slice())));
+        mixer.set_pad_transient_snap(0, Some(20.0));
+        let mut snaps = Vec::new();
+
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(10));
+        mixer.drain_transient_snaps(|id, offset| snaps.push((id, offset)));
+        assert_eq!(snaps, vec![(0, 10)]);
+
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(500));
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(1_000));
+
+        assert!(mixer.seek_sample(0, 1.39));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(1_400));
+
+        mixer.set_pad_transient_snap(0, None);
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(1_500));
+
+        mixer.load_sample(0, create_test_sample(1, 2_000, 0.5));
+        mixer.set_pad_transient_snap(0, Some(20.0));
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(0));
+        assert!(!mixer.publish_pad_onsets(1, Arc::from([0_usize].as_slice())));
+    }
+
+    #[test]
+    fn test_click_detector_flags_hard_stop_but_not_a_running_sine() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        let default_fade = mixer.stop_fade_samples();
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 1, 1_025);
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+
+        assert!(mixer.set_stop_fade_samples(0));
+        mixer.stop_sample(0);
+        output.extend(render_chunks(&mut mixer, 1, 100));
+        assert_eq!(ClickDetector::default().clicks(&output, 1), vec![1_025]);
+
+        assert!(mixer.set_stop_fade_samples(default_fade));
+        assert!(mixer.play_sample(0, 1.0));
+        let mut output = render_chunks(&mut mixer, 1, 1_025);
+        mixer.stop_sample(0);
+        output.extend(render_chunks(&mut mixer, 1, 512));
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_velocity_attack_shortens_fade_in_for_harder_triggers() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 1_000, 1.0));
+        mixer.load_sample(1, create_test_sample(1, 1_000, 1.0));
+        assert!(!mixer.set_velocity_attack(Some(VelocityAttack {
+            soft_ms: -1.0,
+            hard_ms: 1.0,
+        })));
+
+        // The default attack is instant regardless of velocity.
+        assert!(mixer.play_sample(0, 0.25));
+        assert_eq!(mixer.voice_fades[0].frames_remaining, 0);
+
+        assert!(mixer.set_velocity_attack(Some(VelocityAttack {
+            soft_ms: 20.0,
+            hard_ms: 4.0,
+        })));
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(mixer.voice_fades[0].frames_remaining, 4);
+        assert!(mixer.play_sample(0, 0.25));
+        assert_eq!(mixer.voice_fades[0].frames_remaining, 16);
+
+        // A pad's own fade-in wins over the mapping.
+        assert!(mixer.set_pad_fades(
+            1,
+            Some(PadFades {
+                fade_in_ms: 8.0,
+                fade_out_ms: 0.0,
+            }),
+        ));
+        assert!(mixer.play_sample(1, 1.0));
+        assert_eq!(mixer.voice_fades[1].frames_remaining, 8);
+
+        assert!(mixer.set_velocity_attack(None));
+        assert!(mixer.play_sample(0, 0.25));
+        assert_eq!(mixer.voice_fades[0].frames_remaining, 0);
+    }
+
+    #[test]
+    fn test_pad_delay_shifts_the_pad_and_crossfades_changes() {
+        fn render_impulse(delay_ms: f32) -> Vec<f32> {
+            let mut impulse = vec![0.0; 1_000];
+            impulse[0] = 1.0;
+            let mut mixer = RtMixer::new(1, 1_000.0);
+            mixer.load_sample(
+                0,
+                SampleBuffer {
+                    channels: 1,
+                    samples: Arc::from(impulse.into_boxed_slice()),
+                },
+            );
+            assert!(mixer.set_pad_delay_line(0, new_pad_delay_line(1, 1_000.0)));
+            assert!(mixer.set_pad_delay(0, delay_ms));
+            // Let the crossfade from the initial bypass finish first.
+            render_chunks(&mut mixer, 1, 32);
+            assert!(mixer.play_sample(0, 1.0));
+            render_chunks(&mut mixer, 4, 64)
+        }
+
+        let dry = render_impulse(0.0);
+        let delayed = render_impulse(25.0);
+        let correlation_at = |lag: usize| -> f32 {
+            dry.iter()
+                .zip(&delayed[lag..])
+                .map(|(dry, delayed)| dry * delayed)
+                .sum()
+        };
+        let best_lag = (0..=100)
+            .max_by(|&a, &b| correlation_at(a).total_cmp(&correlation_at(b)))
+            .unwrap();
+        assert_eq!(best_lag, 25);
+
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        assert!(!mixer.set_pad_delay(0, PAD_DELAY_MS_MAX + 1.0));
+        assert!(!mixer.set_pad_delay(0, f32::NAN));
+        // A pad has no delay line until it is handed one.
+        assert!(!mixer.set_pad_delay(0, 3.0));
+        assert!(!mixer.set_pad_delay_line(0, new_pad_delay_line(2, 48_000.0)));
+        assert!(mixer.set_pad_delay_line(0, new_pad_delay_line(1, 48_000.0)));
+        assert!(!mixer.set_pad_delay_line(0, new_pad_delay_line(1, 48_000.0)));
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 4, 256);
+        assert!(mixer.set_pad_delay(0, 3.0));
+        assert_eq!(mixer.pad_latency_frames(0), 144);
+        output.extend(render_chunks(&mut mixer, 4, 256));
+        assert!(mixer.set_pad_delay(0, 0.0));
+        output.extend(render_chunks(&mut mixer, 4, 256));
+
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+        assert!(mixer.pad_delays[0].is_bypassed());
+    }
+
+    #[test]
+    fn test_pad_fades_render_without_clicks() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        let fades = PadFades {
+            fade_in_ms: 10.0,
+            fade_out_ms: 10.0,
+        };
+        assert!(mixer.set_pad_fades(0, Some(fades)));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 4, 256);
+        mixer.stop_sample(0);
+        output.extend(render_chunks(&mut mixer, 4, 256));
+
+        assert!(mixer.voices.iter().all(|voice| !voice.active));
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_pad_fade_mute_renders_without_clicks() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 2, 256);
+        assert!(mixer.set_pad_fade_mute(0, true));
+        output.extend(render_chunks(&mut mixer, 8, 256));
+        assert!(mixer.set_pad_fade_mute(0, false));
+        output.extend(render_chunks(&mut mixer, 8, 256));
+
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_loop_wrap_on_whole_periods_renders_without_clicks() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        mixer.set_pad_loop_region(0, 0.0, Some(0.1));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let output = render_chunks(&mut mixer, 48, 256);
+
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_seek_to_matching_phase_renders_without_clicks() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        mixer.load_sample(0, create_sine_sample(48_000.0, 48_000, 480.0));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 1, 1_000);
+        assert!(mixer.seek_sample(0, 0.5));
+        output.extend(render_chunks(&mut mixer, 4, 256));
+
+        assert_eq!(active_voice_frame(&mixer, 0), Some(24_000 + 1_024));
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_stem_mask_crossfade_renders_without_clicks() {
+        let mut mixer = RtMixer::new(1, 48_000.0);
+        let sine = create_sine_sample(48_000.0, 48_000, 480.0);
+        let inverted = SampleBuffer {
+            channels: 1,
+            samples: sine.samples.iter().map(|sample| -sample).collect(),
+        };
+        let silence = create_test_sample(1, 48_000, 0.0);
+        let stems = PreparedStemSet {
+            source_version_hash: 42,
+            sample_rate_hz: 48_000,
+            channels: 1,
+            frame_count: 48_000,
+            available_mask: full_stem_available_mask(),
+            stems: [
+                sine.clone(),
+                silence.clone(),
+                silence.clone(),
+                inverted,
+                silence,
+            ],
+        };
+        mixer.load_sample(0, sine);
+        assert!(mixer.publish_prepared_stems(0, stems));
+        assert!(mixer.set_stem_mix_mode(0, StemMixMode::AllStems, 42));
+        assert!(mixer.set_stem_enabled_mask(0, STEM_MASK_VOCALS, 42));
+        assert!(mixer.play_sample(0, 1.0));
+
+        let mut output = render_chunks(&mut mixer, 2, 256);
+        assert!(mixer.set_stem_enabled_mask(0, STEM_MASK_DRUMS, 42));
+        output.extend(render_chunks(&mut mixer, 2, 256));
+
+        assert!(ClickDetector::default().clicks(&output, 1).is_empty());
+    }
+
+    #[test]
+    fn test_pad_slices_of_one_trigger_from_loop_start() {
+        let mut mixer = RtMixer::new(1, 1_000.0);
+        mixer.load_sample(0, create_test_sample(1, 2_000, 0.5));
+        assert!(mixer.set_pad_slices(0, 4));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.set_pad_slices(0, 1));
+
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(0));
+        assert!(mixer.play_sample(0, 1.0));
+        assert_eq!(active_voice_frame(&mixer, 0), Some(0));
+        assert!(!mixer.set_pad_slices(0, 0));
+        assert!(!mixer.set_pad_slices(0, PAD_SLICES_MAX + 1));
+        assert!(!mixer.set_pad_slices(NUM_SAMPLES, 4));
+    }
+
     #[test]
     fn test_voice_limit() {
         let mut mixer = RtMixer::new(1, 44_100.0);
@@ -4048,6 +8116,55 @@ mod tests {
         assert_eq!(success_count, MAX_VOICES);
         // Only MAX_VOICES voices should be active
         assert_eq!(mixer.voices.iter().filter(|v| v.active).count(), MAX_VOICES);
+
+        // Over-triggering under each steal policy keeps the pool at MAX_VOICES.
+        let fill = |policy| {
+            let mut mixer = RtMixer::new(1, 1_000.0);
+            mixer.set_voice_steal_policy(policy);
+            for id in 0..=MAX_VOICES {
+                mixer.load_sample(id, create_test_sample(1, 1_000, 0.5));
+            }
+            for id in 0..MAX_VOICES {
+                assert!(mixer.play_sample(id, 0.5 + id as f32 / 100.0));
+                render_chunks(&mut mixer, 1, 4);
+            }
+            mixer.set_pad_gain(7, PAD_GAIN_DB_MIN);
+            render_chunks(&mut mixer, 1, 64);
+            mixer
+        };
+        let active_voices = |mixer: &RtMixer| mixer.playing_voice_count();
+
+        let mut silent = fill(VoiceStealPolicy::Silent);
+        assert!(!silent.play_sample(MAX_VOICES, 1.0));
+        assert_eq!(active_voices(&silent), MAX_VOICES);
+
+        // Pad 0 started first and is furthest through its sample.
+        let mut oldest = fill(VoiceStealPolicy::StealOldest);
+        assert!(oldest.play_sample(MAX_VOICES, 1.0));
+        assert!(!oldest.sample_is_active(0));
+        assert!(oldest.sample_is_active(MAX_VOICES));
+        assert_eq!(active_voices(&oldest), MAX_VOICES);
+        assert_eq!(
+            oldest.take_voice_steal().map(|steal| steal.stolen_id),
+            Some(0)
+        );
+        // The stolen voice fades out in a spare slot instead of cutting off.
+        let sounding = |mixer: &RtMixer| mixer.voices.iter().filter(|voice| voice.active).count();
+        assert_eq!(sounding(&oldest), MAX_VOICES + 1);
+        let fade = oldest.stop_fade_samples() as usize;
+        render_chunks(&mut oldest, 1, fade + 1);
+        assert_eq!(sounding(&oldest), MAX_VOICES);
+
+        let mut quietest = fill(VoiceStealPolicy::StealQuietest);
+        assert!(quietest.play_sample(MAX_VOICES, 1.0));
+        assert!(!quietest.sample_is_active(7));
+        assert_eq!(active_voices(&quietest), MAX_VOICES);
+
+        // Higher-priority voices stay protected under every policy.
+        let mut protected = fill(VoiceStealPolicy::StealOldest);
+        protected.set_pad_priority(MAX_VOICES, 0);
+        assert!(!protected.play_sample(MAX_VOICES, 1.0));
+        assert_eq!(active_voices(&protected), MAX_VOICES);
     }
 
     #[test]
@@ -4156,4 +8273,25 @@ mod tests {
             .frame_pos;
         assert_eq!(frame_after_resume, frame_after_pause + 20);
     }
+
+    #[test]
+    fn test_pausing_an_idle_pad_is_a_no_op_and_resume_continues_at_the_exact_frame() {
+        let mut mixer = RtMixer::new(1, 44_100.0);
+        let mut peaks = [0.0_f32; NUM_SAMPLES];
+        mixer.load_sample(0, create_frame_number_sample(1_000));
+        mixer.pause_sample(0);
+        mixer.pause_sample(NUM_SAMPLES);
+        assert!(mixer.play_sample(0, 1.0));
+        assert!(mixer.voices.iter().all(|voice| !voice.paused));
+
+        let mut output = vec![0.0; 100];
+        mixer.render(&mut output, &mut peaks);
+        mixer.pause_sample(0);
+        mixer.render(&mut output, &mut peaks);
+        mixer.resume_sample(0);
+        mixer.render(&mut output, &mut peaks);
+
+        let expected: Vec<f32> = (100..200).map(|frame| frame as f32).collect();
+        assert_eq!(output, expected);
+    }
 }
@@ -1,51 +1,85 @@
 use crate::audio_engine::analysis::analyze_sample;
-use crate::audio_engine::audio_stream::{AudioStreamHandle, create_audio_stream, start_stream};
+pub(crate) use crate::audio_engine::audio_stream::setup_logger;
+use crate::audio_engine::audio_stream::{
+    AudioStreamHandle, OutputDeviceChoice, create_audio_stream, output_device_names, start_stream,
+};
+use crate::audio_engine::automation::{
+    AutomationEvent, AutomationPlayer, AutomationRecorder, AutomationSchedule,
+};
+use crate::audio_engine::channels::mix_channels;
 use crate::audio_engine::constants::{
-    NUM_SAMPLES, PAD_EQ_DB_MAX, PAD_EQ_DB_MIN, PAD_GAIN_DB_MAX, PAD_GAIN_DB_MIN, SPEED_MAX,
-    SPEED_MIN, VOLUME_MAX, VOLUME_MIN,
+    AUTOMATION_LOOKAHEAD_MS, BUILD_FEATURES, CONTROL_BUFFER_CAPACITY_DEFAULT,
+    CONTROL_BUFFER_CAPACITY_MAX, MAX_OUTPUT_CHANNELS, MONO_WIDENING_MS_MAX, NUM_BANKS,
+    NUM_CHOKE_GROUPS, NUM_PADS, NUM_SAMPLES, OUTPUT_TRIM_MAX, PAD_CLIP_CEILING_MIN,
+    PAD_DELAY_MS_MAX, PAD_EQ_DB_MAX, PAD_EQ_DB_MIN, PAD_FADE_MS_MAX, PAD_GAIN_DB_MAX,
+    PAD_GAIN_DB_MIN, PAD_ONSETS_MAX, PAD_RATE_RATIO_TERM_MAX, PAD_SLICES_MAX, PRODUCER_LOCK_SPINS,
+    PRODUCER_LOCK_YIELDS, SEQUENCER_STEPS_MAX, SEQUENCER_STEPS_PER_BAR, SEQUENCER_TRACKS,
+    SILENCE_TRIM_DB_DEFAULT, SILENCE_TRIM_DB_MIN, SPEED_MAX, SPEED_MIN, STOP_FADE_SAMPLES_MAX,
+    SWING_PERCENT_MAX, TEMPO_GLIDE_MS_MAX, VOLUME_MAX, VOLUME_MIN,
 };
-use crate::audio_engine::errors::SampleLoadError;
+use crate::audio_engine::deferred_control::ControlProducer;
+use crate::audio_engine::errors::{DeviceError, SampleLoadError};
 use crate::audio_engine::input_mapping::InputRuntime;
+use crate::audio_engine::mixer::{
+    is_valid_keyboard_mode, is_valid_pad_clip_ceiling, is_valid_pad_fades, is_valid_rate_ratio,
+    is_valid_transient_snap_window, is_valid_velocity_attack, new_pad_delay_line,
+};
+use crate::audio_engine::playlist::{PadPlaylist, PadPlaylists, PlaylistLoader};
 use crate::audio_engine::progress::{LoadProgressStage, ProgressReporter};
 use crate::audio_engine::sample_loader::{
-    SampleLoadProgress, SampleLoadSubtask, cache_audio_file_for_project,
+    ResamplerQuality, SUPPORTED_EXTENSIONS, SampleDecodeOptions, SampleLoadProgress,
+    SampleLoadSubtask, cache_audio_file_for_project, decode_audio_bytes_to_sample_buffer,
     decode_audio_file_to_sample_buffer,
 };
+use crate::audio_engine::sequencer::is_valid_sequence_len;
+use crate::audio_engine::silence_trim::SilenceTrim;
 use crate::audio_engine::stem_cache::{
     prepare_stem_buffers_from_cache, project_stem_cache_dir, source_version_hash,
     write_deterministic_stem_artifacts,
 };
 use crate::messages::{
     AudioMessage, BackgroundTaskKind, ControlMessage, ControlParameterMessage, LoaderEvent,
-    PadTimingMetadata, STEM_COMPONENT_MASK, SampleBuffer, StemMixMode, TriggerQuantization,
-    task_to_str,
+    LoopMode, MonitorSource, OnsetTable, PadFades, PadParams, PadTimingMetadata,
+    STEM_COMPONENT_MASK, SampleAnalysis, SampleBuffer, SequencerStep, SpeakerLayout, StemMixMode,
+    TriggerOptions, TriggerQuantization, VelocityAttack, VoiceStealPolicy, task_to_str,
 };
 use numpy::{PyArray1, ToPyArray};
+use pyo3::create_exception;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyBytes, PyDict};
 use rtrb::Producer;
 use std::collections::HashSet;
 use std::path::Path;
 use std::sync::{
-    Arc, Mutex,
+    Arc, Mutex, MutexGuard, TryLockError,
+    atomic::Ordering,
     mpsc::{Receiver, Sender, TryRecvError},
 };
 use std::thread;
 
+#[cfg(test)]
+mod alloc_tracking;
 mod analysis;
 mod audio_stream;
+mod automation;
 mod buffer_retirement;
 mod channels;
-mod constants;
+mod clock;
+pub(crate) mod constants;
+mod deferred_control;
 mod dsp;
 mod errors;
 mod input_mapping;
 mod mixer;
+mod playlist;
 mod progress;
 mod rubberband_backend;
 mod sample_loader;
 mod scheduler;
+mod sequencer;
+mod silence_trim;
+mod spatial;
 mod stem_cache;
 mod stretch_processor;
 mod transport;
@@ -94,8 +128,77 @@ fn parse_stem_mix_mode(mode: &str) -> Option<StemMixMode> {
     }
 }
 
+fn parse_default_loop_mode(mode: &str) -> PyResult<LoopMode> {
+    match mode {
+        "looper" => Ok(LoopMode::Loop),
+        "sampler" => Ok(LoopMode::OneShot),
+        _ => Err(PyValueError::new_err(
+            "loop mode must be one of looper, sampler",
+        )),
+    }
+}
+
+fn parse_voice_steal_policy(policy: &str) -> PyResult<VoiceStealPolicy> {
+    match policy {
+        "silent" => Ok(VoiceStealPolicy::Silent),
+        "oldest" => Ok(VoiceStealPolicy::StealOldest),
+        "quietest" => Ok(VoiceStealPolicy::StealQuietest),
+        _ => Err(PyValueError::new_err(
+            "voice steal policy must be one of silent, oldest, quietest",
+        )),
+    }
+}
+
+fn parse_pad_loop_mode(mode: &str) -> PyResult<LoopMode> {
+    match mode {
+        "loop" => Ok(LoopMode::Loop),
+        "one_shot" => Ok(LoopMode::OneShot),
+        _ => Err(PyValueError::new_err(
+            "pad loop mode must be one of loop, one_shot",
+        )),
+    }
+}
+
+create_exception!(
+    flitzis_looper_audio,
+    QueueBusy,
+    PyRuntimeError,
+    "Raised when another thread holds the engine's command queue for too long."
+);
+
+/// Acquires a producer without blocking behind a descheduled lock holder.
+///
+/// Spins briefly, then yields a bounded number of times, and fails with `QueueBusy` instead of
+/// parking the calling thread. Messages pushed from one thread still arrive in call order.
+fn lock_producer<T>(producer: &Mutex<T>) -> PyResult<MutexGuard<'_, T>> {
+    for attempt in 0..PRODUCER_LOCK_SPINS + PRODUCER_LOCK_YIELDS {
+        match producer.try_lock() {
+            Ok(guard) => return Ok(guard),
+            Err(TryLockError::WouldBlock) if attempt < PRODUCER_LOCK_SPINS => {
+                std::hint::spin_loop();
+            }
+            Err(TryLockError::WouldBlock) => thread::yield_now(),
+            Err(TryLockError::Poisoned(_)) => {
+                return Err(PyRuntimeError::new_err("Failed to acquire producer lock"));
+            }
+        }
+    }
+
+    Err(QueueBusy::new_err(
+        "Command queue is busy in another thread; retry the call",
+    ))
+}
+
+fn lock_automation_recorder(
+    recorder: &Mutex<AutomationRecorder>,
+) -> PyResult<MutexGuard<'_, AutomationRecorder>> {
+    recorder
+        .lock()
+        .map_err(|_| PyRuntimeError::new_err("Failed to acquire automation recorder lock"))
+}
+
 fn push_control_message(
-    producer: &mut Producer<ControlMessage>,
+    producer: &mut ControlProducer,
     message: ControlMessage,
     label: &str,
 ) -> PyResult<()> {
@@ -114,11 +217,195 @@ fn push_parameter_message(
     })
 }
 
+/// Sends parameter updates so they apply after every control message sent before them.
+///
+/// The updates take the coalescing parameter queue, which the callback drains first, unless
+/// control messages still wait; then they queue behind those as `ApplyParameter`. Either way
+/// all of them are sent, or none when the queue lacks room.
+fn send_parameter_messages(
+    producer: &Mutex<ControlProducer>,
+    parameter_producer: &Mutex<Producer<ControlParameterMessage>>,
+    messages: &[ControlParameterMessage],
+    label: &str,
+) -> PyResult<()> {
+    let full = || PyRuntimeError::new_err(format!("Failed to send {label} - buffer may be full"));
+
+    let mut control_guard = lock_producer(producer)?;
+    if control_guard.has_waiting_messages() {
+        if control_guard.slots() < messages.len() {
+            return Err(full());
+        }
+        for message in messages {
+            control_guard
+                .push_unstamped(ControlMessage::ApplyParameter(*message))
+                .map_err(|_| full())?;
+        }
+        return Ok(());
+    }
+
+    let mut parameter_guard = lock_producer(parameter_producer)?;
+    if parameter_guard.slots() < messages.len() {
+        return Err(full());
+    }
+    for message in messages {
+        push_parameter_message(&mut parameter_guard, *message, label)?;
+    }
+    Ok(())
+}
+
+fn validate_pad_gain_db(gain_db: f32) -> PyResult<()> {
+    if !gain_db.is_finite() || !(PAD_GAIN_DB_MIN..=PAD_GAIN_DB_MAX).contains(&gain_db) {
+        return Err(PyValueError::new_err("gain out of range"));
+    }
+    Ok(())
+}
+
+/// Pad gain in dB that brings a sample with the given linear peak to `target_dbfs`.
+///
+/// Returns `None` for silent samples, which no gain can bring to the target.
+fn auto_trim_gain_db(peak: f32, target_dbfs: f32) -> Option<f32> {
+    if !peak.is_finite() || peak <= 0.0 {
+        return None;
+    }
+
+    let gain_db = target_dbfs - 20.0 * peak.log10();
+    Some(gain_db.clamp(PAD_GAIN_DB_MIN, PAD_GAIN_DB_MAX))
+}
+
+fn validate_pad_eq_db(eq_db: [f32; 3]) -> PyResult<()> {
+    if eq_db
+        .iter()
+        .any(|v| !v.is_finite() || !(PAD_EQ_DB_MIN..=PAD_EQ_DB_MAX).contains(v))
+    {
+        return Err(PyValueError::new_err(format!(
+            "eq gain must be finite and within {PAD_EQ_DB_MIN}..={PAD_EQ_DB_MAX} dB"
+        )));
+    }
+    Ok(())
+}
+
+/// Recorded automation event as `(frame, parameter, pad id, values)`.
+type AutomationEventTuple = (u64, String, Option<usize>, Vec<f32>);
+
+fn automation_event_tuple(event: &AutomationEvent) -> Option<AutomationEventTuple> {
+    let (parameter, id, values) = match event.parameter {
+        ControlParameterMessage::SetVolume(volume) => ("volume", None, vec![volume]),
+        ControlParameterMessage::SetSpeed(speed) => ("speed", None, vec![speed]),
+        ControlParameterMessage::SetMasterBpm(bpm) => ("master_bpm", None, vec![bpm]),
+        ControlParameterMessage::SetPadGain { id, gain_db } => {
+            ("pad_gain", Some(id), vec![gain_db])
+        }
+        ControlParameterMessage::SetPadEq {
+            id,
+            low_db,
+            mid_db,
+            high_db,
+        } => ("pad_eq", Some(id), vec![low_db, mid_db, high_db]),
+        ControlParameterMessage::SetPadBpm { .. }
+        | ControlParameterMessage::SetPadParams { .. } => {
+            return None;
+        }
+    };
+    Some((event.frame, parameter.to_string(), id, values))
+}
+
+fn parse_automation_event(
+    (frame, parameter, id, values): AutomationEventTuple,
+) -> PyResult<AutomationEvent> {
+    let pad_id = || match id {
+        Some(id) if id < NUM_SAMPLES => Ok(id),
+        Some(_) => Err(PyValueError::new_err("id out of range")),
+        None => Err(PyValueError::new_err(format!("{parameter} needs a pad id"))),
+    };
+    let parameter = match (parameter.as_str(), values.as_slice()) {
+        ("volume", &[volume]) => {
+            if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
+                return Err(PyValueError::new_err("volume out of range"));
+            }
+            ControlParameterMessage::SetVolume(volume)
+        }
+        ("speed", &[speed]) => {
+            if !speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed) {
+                return Err(PyValueError::new_err("speed out of range"));
+            }
+            ControlParameterMessage::SetSpeed(speed)
+        }
+        ("master_bpm", &[bpm]) => {
+            if !bpm.is_finite() || bpm <= 0.0 {
+                return Err(PyValueError::new_err("bpm out of range"));
+            }
+            ControlParameterMessage::SetMasterBpm(bpm)
+        }
+        ("pad_gain", &[gain_db]) => {
+            validate_pad_gain_db(gain_db)?;
+            ControlParameterMessage::SetPadGain {
+                id: pad_id()?,
+                gain_db,
+            }
+        }
+        ("pad_eq", &[low_db, mid_db, high_db]) => {
+            validate_pad_eq_db([low_db, mid_db, high_db])?;
+            ControlParameterMessage::SetPadEq {
+                id: pad_id()?,
+                low_db,
+                mid_db,
+                high_db,
+            }
+        }
+        ("volume" | "speed" | "master_bpm" | "pad_gain" | "pad_eq", _) => {
+            return Err(PyValueError::new_err(format!(
+                "wrong number of values for {parameter}"
+            )));
+        }
+        _ => {
+            return Err(PyValueError::new_err(
+                "parameter must be one of volume, speed, master_bpm, pad_gain, pad_eq",
+            ));
+        }
+    };
+    Ok(AutomationEvent { frame, parameter })
+}
+
+fn parse_snapshot_slot(snapshot: &str) -> PyResult<usize> {
+    match snapshot {
+        "a" => Ok(0),
+        "b" => Ok(1),
+        _ => Err(PyValueError::new_err("snapshot must be one of a, b")),
+    }
+}
+
+fn parse_pad_eq_band(band: &str) -> PyResult<usize> {
+    match band {
+        "low" => Ok(0),
+        "mid" => Ok(1),
+        "high" => Ok(2),
+        _ => Err(PyValueError::new_err(
+            "eq band must be one of low, mid, high",
+        )),
+    }
+}
+
 struct PadLoadingGuard {
     id: usize,
     loading_sample_ids: Arc<Mutex<HashSet<usize>>>,
 }
 
+impl PadLoadingGuard {
+    /// Marks `id` as loading until the guard drops; fails if a load already holds it.
+    fn acquire(loading_sample_ids: &Arc<Mutex<HashSet<usize>>>, id: usize) -> PyResult<Self> {
+        let mut set = loading_sample_ids
+            .lock()
+            .map_err(|_| PyRuntimeError::new_err("Failed to acquire loading ids lock"))?;
+        if !set.insert(id) {
+            return Err(PyValueError::new_err("sample is already loading"));
+        }
+        Ok(Self {
+            id,
+            loading_sample_ids: loading_sample_ids.clone(),
+        })
+    }
+}
+
 impl Drop for PadLoadingGuard {
     fn drop(&mut self) {
         if let Ok(mut set) = self.loading_sample_ids.lock() {
@@ -174,11 +461,42 @@ fn pad_request_matches(pad_request_ids: &Arc<Mutex<Vec<u64>>>, id: usize, reques
     current_pad_request_id(pad_request_ids, id).is_ok_and(|current| current == request_id)
 }
 
+/// Whether an analysis result describes a sample that is no longer in its slot.
+///
+/// Analysis jobs carry the slot's request id from when they started; every load or unload
+/// bumps it, so a slow analysis of a replaced sample can be recognized when it arrives.
+fn is_stale_analysis_result(event: &LoaderEvent, pad_request_ids: &Arc<Mutex<Vec<u64>>>) -> bool {
+    match event {
+        LoaderEvent::TaskSuccess {
+            id,
+            request_id,
+            analysis: Some(_),
+            ..
+        } => !pad_request_matches(pad_request_ids, *id, *request_id),
+        _ => false,
+    }
+}
+
+/// Converts onset times in seconds into the sorted, deduplicated frame table the mixer snaps to.
+fn onset_table(onsets_s: &[f32], sample_rate_hz: u32) -> OnsetTable {
+    let sample_rate_hz = sample_rate_hz as f32;
+    let mut onsets: Vec<usize> = onsets_s
+        .iter()
+        .filter(|onset_s| onset_s.is_finite() && **onset_s >= 0.0)
+        .map(|onset_s| (onset_s * sample_rate_hz).round() as usize)
+        .collect();
+    onsets.sort_unstable();
+    onsets.dedup();
+    onsets.truncate(PAD_ONSETS_MAX);
+    Arc::from(onsets)
+}
+
 fn publish_loaded_sample(
-    producer: &Arc<Mutex<Producer<ControlMessage>>>,
+    producer: &Arc<Mutex<ControlProducer>>,
     sample_cache: &Arc<Mutex<Vec<Option<SampleBuffer>>>>,
     id: usize,
     sample: SampleBuffer,
+    onsets: Option<OnsetTable>,
 ) -> Result<(), String> {
     let mut producer_guard = producer
         .lock()
@@ -188,6 +506,7 @@ fn publish_loaded_sample(
         .push(ControlMessage::LoadSample {
             id,
             sample: sample.clone(),
+            onsets,
         })
         .map_err(|_| "Failed to send LoadSample - buffer may be full".to_string())?;
 
@@ -200,6 +519,25 @@ fn publish_loaded_sample(
     Ok(())
 }
 
+fn analysis_to_py_dict(py: Python<'_>, analysis: SampleAnalysis) -> PyResult<Bound<'_, PyDict>> {
+    let analysis_dict = PyDict::new(py);
+    analysis_dict.set_item("bpm", analysis.bpm)?;
+    analysis_dict.set_item("key", analysis.key)?;
+
+    let beat_grid_dict = PyDict::new(py);
+    beat_grid_dict.set_item("beats", &analysis.beat_grid.beats)?;
+    beat_grid_dict.set_item("downbeats", &analysis.beat_grid.downbeats)?;
+    beat_grid_dict.set_item("bars", &analysis.beat_grid.bars)?;
+    analysis_dict.set_item("beat_grid", beat_grid_dict)?;
+
+    analysis_dict.set_item("bpm_confidence", analysis.bpm_confidence)?;
+    analysis_dict.set_item("key_confidence", analysis.key_confidence)?;
+    analysis_dict.set_item("grid_stability", analysis.grid_stability)?;
+    analysis_dict.set_item("onsets", analysis.onsets_s)?;
+
+    Ok(analysis_dict)
+}
+
 /// AudioEngine provides minimal audio output capabilities using cpal
 #[pyclass]
 pub struct AudioEngine {
@@ -211,7 +549,11 @@ pub struct AudioEngine {
     loading_sample_ids: Arc<Mutex<HashSet<usize>>>,
     active_tasks: Arc<Mutex<HashSet<(usize, BackgroundTaskKind)>>>,
     pad_request_ids: Arc<Mutex<Vec<u64>>>,
+    pad_playlists: PadPlaylists,
     input_runtime: Option<InputRuntime>,
+    automation_player: Option<AutomationPlayer>,
+    decode_options: SampleDecodeOptions,
+    next_voice_handle: u64,
 }
 
 #[pymethods]
@@ -230,30 +572,184 @@ impl AudioEngine {
             loading_sample_ids: Arc::new(Mutex::new(HashSet::new())),
             active_tasks: Arc::new(Mutex::new(HashSet::new())),
             pad_request_ids: Arc::new(Mutex::new(vec![0; NUM_SAMPLES])),
+            pad_playlists: Arc::new(Mutex::new((0..NUM_SAMPLES).map(|_| None).collect())),
             input_runtime: None,
+            automation_player: None,
+            decode_options: SampleDecodeOptions::default(),
+            next_voice_handle: 0,
         })
     }
 
-    /// Initialize and run the audio engine.
-    pub fn run(&mut self) -> PyResult<()> {
-        if self.stream_handle.is_some() {
-            return Err(PyRuntimeError::new_err("AudioEngine already running"));
+    /// Widen mono files upmixed to stereo with a complementary comb of `delay_ms`, or keep
+    /// them dead center with `None` (the default).
+    ///
+    /// The mono sum of a widened sample is the original, 3.5 dB down so the widened sides
+    /// cannot clip; each side on its own is comb filtered. Applies to samples loaded after the
+    /// call.
+    #[pyo3(signature = (delay_ms = None))]
+    pub fn set_mono_widening(&mut self, delay_ms: Option<f32>) -> PyResult<()> {
+        if let Some(delay_ms) = delay_ms
+            && (!delay_ms.is_finite() || delay_ms <= 0.0 || delay_ms > MONO_WIDENING_MS_MAX)
+        {
+            return Err(PyValueError::new_err(format!(
+                "delay_ms must be within (0, {MONO_WIDENING_MS_MAX}]"
+            )));
         }
 
-        match create_audio_stream() {
-            Ok(handle) => {
-                start_stream(&handle.stream).map_err(|e| {
-                    PyRuntimeError::new_err(format!("Failed to start audio stream: {e}"))
-                })?;
-                self.input_runtime = Some(InputRuntime::new(handle.producer.clone()));
-                self.stream_handle = Some(handle);
-                self.is_playing = true;
-                Ok(())
+        self.decode_options.mono_widening_ms = delay_ms;
+        Ok(())
+    }
+
+    /// Select the load-time resampler preset: `"fast"`, `"balanced"` (default) or `"high"`.
+    ///
+    /// `fast` suits quick session restores, `high` final-quality loads at several times the
+    /// CPU cost. Applies to samples loaded after the call.
+    pub fn set_resampler_quality(&mut self, quality: &str) -> PyResult<()> {
+        self.decode_options.resampler_quality = ResamplerQuality::parse(quality)
+            .ok_or_else(|| PyValueError::new_err("quality must be fast, balanced or high"))?;
+        Ok(())
+    }
+
+    /// Trim leading and trailing silence from samples loaded after the call.
+    ///
+    /// `mode` is `"fixed"` to treat audio below `threshold_db` dBFS as silence, `"auto"` to
+    /// derive the threshold from each file's noise floor, or `None` (the default) to keep
+    /// files whole. Auto mode keeps quiet intros of clean files and still trims the hiss of
+    /// noisy ones.
+    #[pyo3(signature = (mode = None, threshold_db = SILENCE_TRIM_DB_DEFAULT))]
+    pub fn set_silence_trim(&mut self, mode: Option<&str>, threshold_db: f32) -> PyResult<()> {
+        self.decode_options.silence_trim = match mode {
+            None => None,
+            Some("auto") => Some(SilenceTrim::Auto),
+            Some("fixed") => {
+                if !threshold_db.is_finite() || !(SILENCE_TRIM_DB_MIN..=0.0).contains(&threshold_db)
+                {
+                    return Err(PyValueError::new_err(format!(
+                        "threshold_db must be within [{SILENCE_TRIM_DB_MIN}, 0]"
+                    )));
+                }
+                Some(SilenceTrim::FixedDbfs(threshold_db))
             }
-            Err(e) => Err(PyRuntimeError::new_err(format!(
-                "Failed to create audio stream: {e}"
-            ))),
-        }
+            Some(_) => return Err(PyValueError::new_err("mode must be fixed, auto or None")),
+        };
+        Ok(())
+    }
+
+    /// File extensions (without leading dot) that this build can decode.
+    #[staticmethod]
+    pub fn supported_extensions() -> Vec<String> {
+        SUPPORTED_EXTENSIONS
+            .iter()
+            .map(|ext| (*ext).to_string())
+            .collect()
+    }
+
+    /// Crate version and the capabilities compiled into this build.
+    ///
+    /// Returns a dict with `version` (the crate version string) and `features` (a list of
+    /// capability names such as `"key_lock"`).
+    #[staticmethod]
+    pub fn build_info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("version", env!("CARGO_PKG_VERSION"))?;
+        dict.set_item("features", BUILD_FEATURES.to_vec())?;
+        Ok(dict)
+    }
+
+    /// Initialize and run the audio engine.
+    ///
+    /// If `sample_rate` is given but unsupported by the output device, the nearest
+    /// supported rate is used; query `output_sample_rate()` for the negotiated rate.
+    /// `loop_mode` sets the default loop mode, see `set_loop_mode()`. `buffer_capacity` is
+    /// the number of control and parameter messages that can wait for the audio thread, at
+    /// most 65536; raise it when bursts such as loading a full set at startup fill the queue.
+    ///
+    /// `frames` requests a fixed callback buffer size in frames. Without it, 512 frames are
+    /// tried first, then the device's bounds and default. A requested size that the device
+    /// rejects fails with a `RuntimeError` naming it, so a supported size can be retried.
+    #[pyo3(signature = (
+        sample_rate = None,
+        loop_mode = "looper",
+        buffer_capacity = CONTROL_BUFFER_CAPACITY_DEFAULT,
+        frames = None,
+    ))]
+    pub fn run(
+        &mut self,
+        sample_rate: Option<u32>,
+        loop_mode: &str,
+        buffer_capacity: usize,
+        frames: Option<u32>,
+    ) -> PyResult<()> {
+        let loop_mode = parse_default_loop_mode(loop_mode)?;
+        self.start(sample_rate, None, loop_mode, buffer_capacity, frames)
+    }
+
+    /// Names of the available output devices; a name's position is its device index.
+    ///
+    /// Needs no running engine. Pass a name to `run_with_device()`; identical interfaces may
+    /// share a name, so use the index with `run_with_device_index()` for a stable selection.
+    #[staticmethod]
+    pub fn list_output_devices() -> PyResult<Vec<String>> {
+        output_device_names().map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to enumerate output devices: {e}"))
+        })
+    }
+
+    /// Initialize and run the audio engine on the output device at `index` in
+    /// `list_output_devices()` order.
+    #[pyo3(signature = (
+        index,
+        sample_rate = None,
+        loop_mode = "looper",
+        buffer_capacity = CONTROL_BUFFER_CAPACITY_DEFAULT,
+        frames = None,
+    ))]
+    pub fn run_with_device_index(
+        &mut self,
+        index: usize,
+        sample_rate: Option<u32>,
+        loop_mode: &str,
+        buffer_capacity: usize,
+        frames: Option<u32>,
+    ) -> PyResult<()> {
+        let loop_mode = parse_default_loop_mode(loop_mode)?;
+        let device = OutputDeviceChoice::Index(index);
+        self.start(
+            sample_rate,
+            Some(device),
+            loop_mode,
+            buffer_capacity,
+            frames,
+        )
+    }
+
+    /// Initialize and run the audio engine on the first output device named `name`.
+    ///
+    /// Fails with a `ValueError` listing the available names if no device matches.
+    #[pyo3(signature = (
+        name,
+        sample_rate = None,
+        loop_mode = "looper",
+        buffer_capacity = CONTROL_BUFFER_CAPACITY_DEFAULT,
+        frames = None,
+    ))]
+    pub fn run_with_device(
+        &mut self,
+        name: &str,
+        sample_rate: Option<u32>,
+        loop_mode: &str,
+        buffer_capacity: usize,
+        frames: Option<u32>,
+    ) -> PyResult<()> {
+        let loop_mode = parse_default_loop_mode(loop_mode)?;
+        let device = OutputDeviceChoice::Name(name.to_string());
+        self.start(
+            sample_rate,
+            Some(device),
+            loop_mode,
+            buffer_capacity,
+            frames,
+        )
     }
 
     pub fn output_sample_rate(&self) -> PyResult<u32> {
@@ -264,92 +760,318 @@ impl AudioEngine {
         Ok(handle.output_sample_rate)
     }
 
-    pub fn loaded_sample_shape(&self, id: usize) -> PyResult<(u32, usize, usize)> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err(format!(
-                "id out of range (expected 0..{}, got {id})",
-                NUM_SAMPLES - 1
-            )));
-        }
-
+    /// Number of output channels the stream was opened with.
+    pub fn output_channels(&self) -> PyResult<usize> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-
-        let sample = {
-            let cache = self
-                .sample_cache
-                .lock()
-                .map_err(|_| PyRuntimeError::new_err("Failed to acquire sample cache lock"))?;
-            cache
-                .get(id)
-                .and_then(|slot| slot.clone())
-                .ok_or_else(|| PyValueError::new_err("sample is not loaded"))?
-        };
-
-        let frames = sample.samples.len() / sample.channels;
-        Ok((handle.output_sample_rate, sample.channels, frames))
+        Ok(handle.output_channels)
     }
 
-    /// Shut down the audio engine.
-    pub fn shut_down(&mut self) -> PyResult<()> {
-        self.input_runtime = None;
-        self.stream_handle = None;
-        self.is_playing = false;
-        Ok(())
+    /// Describe the negotiated output stream.
+    ///
+    /// Returns a dict with `channels`, `sample_rate`, `sample_format` (`"f32"`, `"i16"` or
+    /// `"u16"`), `buffer_frames` (`None` when the backend default buffer size is used) and
+    /// `buffer_capacity`, the configured control message capacity.
+    pub fn get_output_config<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("channels", handle.output_channels)?;
+        dict.set_item("sample_rate", handle.output_sample_rate)?;
+        dict.set_item("sample_format", handle.output_sample_format.as_str())?;
+        dict.set_item("buffer_frames", handle.output_buffer_frames)?;
+        dict.set_item("buffer_capacity", handle.control_buffer_capacity)?;
+        Ok(dict)
     }
 
-    pub fn set_input_mapping_enabled(&self, enabled: bool) -> PyResult<()> {
-        let runtime = self
-            .input_runtime
+    /// Number of output samples the final safety clamp limited to [-1.0, 1.0] since `run()`.
+    ///
+    /// A rising count means the mix overloads; lower pad gains or the master volume.
+    pub fn get_clip_count(&self) -> PyResult<u64> {
+        let handle = self
+            .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-        runtime.set_enabled(enabled);
-        Ok(())
+
+        Ok(handle.clip_count())
     }
 
-    pub fn set_input_learn_active(&self, active: bool) -> PyResult<()> {
-        let runtime = self
-            .input_runtime
+    /// Master limiter gain reduction in dB as `(current, recent_peak)`.
+    ///
+    /// Both are exactly 0.0 while the limiter is idle or disabled, so a UI can hide the meter.
+    pub fn get_limiter_reduction(&self) -> PyResult<(f32, f32)> {
+        let handle = self
+            .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-        runtime.set_learn_capture_active(active);
-        Ok(())
+
+        Ok(handle.limiter_reduction_db())
     }
 
-    pub fn set_input_mapping_snapshot(&self, mappings: Vec<(String, String)>) -> PyResult<()> {
-        let runtime = self
-            .input_runtime
+    /// Latest `(output_frame, monotonic_s)` pair published by the audio callback.
+    ///
+    /// `monotonic_s` is the time the callback starting at `output_frame` ran, on a clock all
+    /// engines in the process share. Comparing two engines' pairs over time measures how
+    /// their devices drift. Returns None until the first callback ran.
+    pub fn audio_clock(&self) -> PyResult<Option<(u64, f64)>> {
+        let handle = self
+            .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-        runtime.replace_mappings(mappings);
-        Ok(())
+
+        Ok(handle.audio_clock().map(|timestamp| {
+            (
+                timestamp.output_frame,
+                timestamp.monotonic_ns as f64 / 1_000_000_000.0,
+            )
+        }))
     }
 
-    pub fn set_input_runtime_state(
-        &self,
-        multi_loop: bool,
-        loaded: Vec<bool>,
-        loop_starts: Vec<f32>,
-        loop_ends: Vec<Option<f32>>,
-    ) -> PyResult<()> {
-        let runtime = self
-            .input_runtime
+    /// Latest frames-to-wallclock mapping published by the audio callback.
+    ///
+    /// Keys: `output_frame`, `monotonic_s` (as in `audio_clock()`), `output_latency_s`,
+    /// `playback_s` (filtered time `output_frame` is heard) and `sample_rate` (measured device
+    /// rate). The last three are None on backends without usable timestamps. Returns None
+    /// until the first callback ran.
+    pub fn get_clock_info<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let handle = self
+            .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-        runtime
-            .set_runtime_state(multi_loop, loaded, loop_starts, loop_ends)
-            .map_err(PyValueError::new_err)
+
+        let Some(timestamp) = handle.audio_clock() else {
+            return Ok(None);
+        };
+        let seconds = |ns: u64| ns as f64 / 1_000_000_000.0;
+        let dict = PyDict::new(py);
+        dict.set_item("output_frame", timestamp.output_frame)?;
+        dict.set_item("monotonic_s", seconds(timestamp.monotonic_ns))?;
+        dict.set_item("output_latency_s", timestamp.output_latency_ns.map(seconds))?;
+        dict.set_item("playback_s", timestamp.playback_ns.map(seconds))?;
+        dict.set_item("sample_rate", timestamp.sample_rate_hz)?;
+        Ok(Some(dict))
     }
 
-    pub fn start_midi_input(&self) -> PyResult<usize> {
-        let runtime = self
-            .input_runtime
+    /// Apply the next control message this thread sends at output frame `frame`.
+    ///
+    /// The engine renders up to that frame before applying it, so e.g. a stop lands exactly
+    /// on a beat instead of at the next buffer boundary. Frames already passed apply at the
+    /// start of the next buffer. Parameter updates (volume, speed, BPM, pad gain and EQ) do
+    /// not use the stamp.
+    pub fn schedule_next_message(&mut self, frame: u64) -> PyResult<()> {
+        let handle = self
+            .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
-        runtime.start_midi_input().map_err(PyRuntimeError::new_err)
-    }
+
+        lock_producer(&handle.producer)?.stamp_next_message(frame);
+        Ok(())
+    }
+
+    /// Start recording parameter automation, discarding any unfinished recording.
+    ///
+    /// Every volume, speed, master BPM, pad gain and pad EQ update is recorded with the
+    /// output frame it took effect at, counted from the start of the recording. Replayed
+    /// automation is not recorded again.
+    pub fn automation_record_start(&mut self) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let origin_frame = handle
+            .audio_clock()
+            .map_or(0, |timestamp| timestamp.output_frame);
+        lock_automation_recorder(&handle.automation_recorder)?.start(origin_frame);
+        Ok(())
+    }
+
+    /// Stop recording and return the events as `(frame, parameter, id, values)` tuples.
+    ///
+    /// `parameter` is `"volume"`, `"speed"`, `"master_bpm"`, `"pad_gain"` or `"pad_eq"`; `id`
+    /// is the pad for the pad parameters and None otherwise; `values` holds the value, or the
+    /// low, mid and high gains for `"pad_eq"`.
+    pub fn automation_record_stop(&mut self) -> PyResult<Vec<AutomationEventTuple>> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let (events, dropped) = lock_automation_recorder(&handle.automation_recorder)?.stop();
+        if dropped > 0 {
+            log::warn!("Automation recording was full; dropped {dropped} parameter update(s)");
+        }
+        Ok(events.iter().filter_map(automation_event_tuple).collect())
+    }
+
+    /// Replay recorded automation, each event at its frame plus `offset_frames`, counted from
+    /// a playback start just ahead of the current output frame.
+    ///
+    /// With `loop_frames`, the events repeat every `loop_frames` frames until
+    /// `automation_stop()`. Replaces any automation already playing. Events are sent as
+    /// frame-stamped messages shortly before they are due, so they apply on their exact frame.
+    #[pyo3(signature = (events, offset_frames = 0, loop_frames = None))]
+    pub fn automation_play(
+        &mut self,
+        events: Vec<AutomationEventTuple>,
+        offset_frames: u64,
+        loop_frames: Option<u64>,
+    ) -> PyResult<()> {
+        if loop_frames == Some(0) {
+            return Err(PyValueError::new_err("loop_frames must be positive"));
+        }
+        let events = events
+            .into_iter()
+            .map(parse_automation_event)
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let lookahead_frames =
+            (f64::from(handle.output_sample_rate) * AUTOMATION_LOOKAHEAD_MS / 1000.0) as u64;
+        // Start one lookahead out, so the first events still arrive before they are due.
+        let start_frame = handle
+            .audio_clock()
+            .map_or(0, |timestamp| timestamp.output_frame)
+            .saturating_add(lookahead_frames);
+        self.automation_player = None;
+        self.automation_player = Some(AutomationPlayer::spawn(
+            AutomationSchedule::new(
+                events,
+                start_frame.saturating_add(offset_frames),
+                loop_frames,
+            ),
+            handle.producer.clone(),
+            handle.shared_audio_clock(),
+            lookahead_frames,
+        ));
+        Ok(())
+    }
+
+    /// Stop replaying automation. Events already sent still apply.
+    pub fn automation_stop(&mut self) {
+        self.automation_player = None;
+    }
+
+    /// Whether replayed automation still has events to send.
+    pub fn is_automation_playing(&self) -> bool {
+        self.automation_player
+            .as_ref()
+            .is_some_and(AutomationPlayer::is_playing)
+    }
+
+    pub fn loaded_sample_shape(&self, id: usize) -> PyResult<(u32, usize, usize)> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err(format!(
+                "id out of range (expected 0..{}, got {id})",
+                NUM_SAMPLES - 1
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let sample = {
+            let cache = self
+                .sample_cache
+                .lock()
+                .map_err(|_| PyRuntimeError::new_err("Failed to acquire sample cache lock"))?;
+            cache
+                .get(id)
+                .and_then(|slot| slot.clone())
+                .ok_or_else(|| PyValueError::new_err("sample is not loaded"))?
+        };
+
+        let frames = sample.samples.len() / sample.channels;
+        Ok((handle.output_sample_rate, sample.channels, frames))
+    }
+
+    /// Whether the engine was started and its audio callback has not faulted.
+    pub fn is_running(&self) -> bool {
+        self.stream_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_faulted())
+    }
+
+    /// Engine health: `"stopped"`, `"running"`, or `"faulted"` after the audio callback
+    /// panicked. A faulted engine outputs silence until it is shut down and run again.
+    pub fn health(&self) -> &'static str {
+        match &self.stream_handle {
+            None => "stopped",
+            Some(handle) if handle.is_faulted() => "faulted",
+            Some(_) => "running",
+        }
+    }
+
+    /// Shut down the audio engine.
+    pub fn shut_down(&mut self) -> PyResult<()> {
+        self.automation_player = None;
+        self.input_runtime = None;
+        self.stream_handle = None;
+        self.is_playing = false;
+        Ok(())
+    }
+
+    pub fn set_input_mapping_enabled(&self, enabled: bool) -> PyResult<()> {
+        let runtime = self
+            .input_runtime
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        runtime.set_enabled(enabled);
+        Ok(())
+    }
+
+    pub fn set_input_learn_active(&self, active: bool) -> PyResult<()> {
+        let runtime = self
+            .input_runtime
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        runtime.set_learn_capture_active(active);
+        Ok(())
+    }
+
+    pub fn set_input_mapping_snapshot(&self, mappings: Vec<(String, String)>) -> PyResult<()> {
+        let runtime = self
+            .input_runtime
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        runtime.replace_mappings(mappings);
+        Ok(())
+    }
+
+    pub fn set_input_runtime_state(
+        &self,
+        multi_loop: bool,
+        loaded: Vec<bool>,
+        loop_starts: Vec<f32>,
+        loop_ends: Vec<Option<f32>>,
+    ) -> PyResult<()> {
+        let runtime = self
+            .input_runtime
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        runtime
+            .set_runtime_state(multi_loop, loaded, loop_starts, loop_ends)
+            .map_err(PyValueError::new_err)
+    }
+
+    pub fn start_midi_input(&self) -> PyResult<usize> {
+        let runtime = self
+            .input_runtime
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        runtime.start_midi_input().map_err(PyRuntimeError::new_err)
+    }
 
     pub fn stop_midi_input(&self) -> PyResult<()> {
         let runtime = self
@@ -394,24 +1116,18 @@ impl AudioEngine {
 
         let loader_tx = self.loader_tx.clone();
         let producer = handle.producer.clone();
-        let output_channels = handle.output_channels;
+        let output_channels = mix_channels(handle.output_channels);
         let output_sample_rate = handle.output_sample_rate;
         let sample_cache = self.sample_cache.clone();
-        let loading_sample_ids = self.loading_sample_ids.clone();
         let pad_request_ids = self.pad_request_ids.clone();
         let run_analysis = run_analysis.unwrap_or(true);
+        let decode_options = self.decode_options;
 
-        {
-            let mut set = loading_sample_ids
-                .lock()
-                .map_err(|_| PyRuntimeError::new_err("Failed to acquire loading ids lock"))?;
-            if !set.insert(id) {
-                return Err(PyValueError::new_err("sample is already loading"));
-            }
-        }
+        let loading_guard = PadLoadingGuard::acquire(&self.loading_sample_ids, id)?;
 
         let request_id =
             next_pad_request_id(&pad_request_ids, id).map_err(PyRuntimeError::new_err)?;
+        self.drop_playlist(id);
 
         {
             let mut cache = sample_cache
@@ -423,10 +1139,7 @@ impl AudioEngine {
         }
 
         thread::spawn(move || {
-            let _loading_guard = PadLoadingGuard {
-                id,
-                loading_sample_ids,
-            };
+            let _loading_guard = loading_guard;
 
             let _ = loader_tx.send(LoaderEvent::Started { id, request_id });
 
@@ -436,6 +1149,7 @@ impl AudioEngine {
                 Path::new(&path),
                 output_channels,
                 output_sample_rate,
+                decode_options,
                 |update: SampleLoadProgress| {
                     let stage = match update.subtask {
                         SampleLoadSubtask::Decoding => LoadProgressStage::Decoding,
@@ -520,7 +1234,11 @@ impl AudioEngine {
                 return;
             }
 
-            if let Err(error) = publish_loaded_sample(&producer, &sample_cache, id, sample) {
+            let onsets = analysis
+                .as_ref()
+                .map(|analysis| onset_table(&analysis.onsets_s, output_sample_rate));
+            if let Err(error) = publish_loaded_sample(&producer, &sample_cache, id, sample, onsets)
+            {
                 let _ = loader_tx.send(LoaderEvent::Error {
                     id,
                     request_id,
@@ -547,6 +1265,62 @@ impl AudioEngine {
         Ok(request_id)
     }
 
+    /// Decode in-memory audio, such as a network response, into a sample slot.
+    ///
+    /// Decodes on the calling thread with the GIL released and skips analysis and the
+    /// project sample cache. `hint_extension` (e.g. `"wav"`) helps pick the container
+    /// format. Returns the sample duration in seconds.
+    #[pyo3(signature = (id, data, hint_extension = None))]
+    pub fn load_sample_from_bytes(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        data: &Bound<'_, PyBytes>,
+        hint_extension: Option<&str>,
+    ) -> PyResult<f32> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err(format!(
+                "id out of range (expected 0..{}, got {id})",
+                NUM_SAMPLES - 1
+            )));
+        }
+
+        // Hold the pad while decoding with the GIL released, so a `load_sample_async` for it
+        // is refused instead of being superseded by this load.
+        let _loading_guard = PadLoadingGuard::acquire(&self.loading_sample_ids, id)?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        let output_channels = mix_channels(handle.output_channels);
+        let output_sample_rate = handle.output_sample_rate;
+
+        let data = data.as_bytes();
+        let decode_options = self.decode_options;
+        let sample = py
+            .detach(|| {
+                decode_audio_bytes_to_sample_buffer(
+                    data,
+                    hint_extension,
+                    output_channels,
+                    output_sample_rate,
+                    decode_options,
+                    |_| {},
+                )
+            })
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+        // Supersede analysis results still on their way for the previous sample.
+        next_pad_request_id(&self.pad_request_ids, id).map_err(PyRuntimeError::new_err)?;
+        self.drop_playlist(id);
+
+        let frames = sample.samples.len() / sample.channels;
+        publish_loaded_sample(&handle.producer, &self.sample_cache, id, sample, None)
+            .map_err(PyRuntimeError::new_err)?;
+        Ok(frames as f32 / output_sample_rate as f32)
+    }
+
     /// Analyze a previously loaded sample on a background thread.
     pub fn analyze_sample_async(&self, id: usize) -> PyResult<u64> {
         if id >= NUM_SAMPLES {
@@ -829,10 +1603,7 @@ impl AudioEngine {
         )
         .map_err(PyValueError::new_err)?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::PublishPreparedStems { id, stems })
@@ -874,10 +1645,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::SetStemMixMode {
@@ -917,10 +1685,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::SetStemEnabledMask {
@@ -935,17 +1700,23 @@ impl AudioEngine {
 
     /// Poll for pending background loader events.
     ///
-    /// Returns `None` when no events are available.
+    /// Returns `None` when no events are available. Analysis results for a sample that has
+    /// since been replaced or unloaded are dropped here, so they never reach the new sample.
     pub fn poll_loader_events(&self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
         let loader_rx = self
             .loader_rx
             .lock()
             .map_err(|_| PyRuntimeError::new_err("Failed to acquire loader receiver lock"))?;
 
-        let event = match loader_rx.try_recv() {
-            Ok(event) => event,
-            Err(TryRecvError::Empty) => return Ok(None),
-            Err(TryRecvError::Disconnected) => return Ok(None),
+        let event = loop {
+            let event = match loader_rx.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => return Ok(None),
+                Err(TryRecvError::Disconnected) => return Ok(None),
+            };
+            if !is_stale_analysis_result(&event, &self.pad_request_ids) {
+                break event;
+            }
         };
 
         let dict = PyDict::new(py);
@@ -981,17 +1752,7 @@ impl AudioEngine {
                 dict.set_item("cached_path", cached_path)?;
 
                 if let Some(analysis) = analysis {
-                    let analysis_dict = PyDict::new(py);
-                    analysis_dict.set_item("bpm", analysis.bpm)?;
-                    analysis_dict.set_item("key", analysis.key)?;
-
-                    let beat_grid_dict = PyDict::new(py);
-                    beat_grid_dict.set_item("beats", &analysis.beat_grid.beats)?;
-                    beat_grid_dict.set_item("downbeats", &analysis.beat_grid.downbeats)?;
-                    beat_grid_dict.set_item("bars", &analysis.beat_grid.bars)?;
-                    analysis_dict.set_item("beat_grid", beat_grid_dict)?;
-
-                    dict.set_item("analysis", analysis_dict)?;
+                    dict.set_item("analysis", analysis_to_py_dict(py, analysis)?)?;
                 }
             }
             LoaderEvent::Error {
@@ -1004,6 +1765,21 @@ impl AudioEngine {
                 dict.set_item("request_id", request_id)?;
                 dict.set_item("msg", error)?;
             }
+            LoaderEvent::PlaylistEntryLoaded {
+                id,
+                entry,
+                duration_s,
+                analysis,
+            } => {
+                dict.set_item("type", "playlist_entry")?;
+                dict.set_item("id", id)?;
+                dict.set_item("entry", entry)?;
+                dict.set_item("duration_s", duration_s)?;
+
+                if let Some(analysis) = analysis {
+                    dict.set_item("analysis", analysis_to_py_dict(py, analysis)?)?;
+                }
+            }
             LoaderEvent::TaskStarted {
                 id,
                 request_id,
@@ -1040,17 +1816,7 @@ impl AudioEngine {
                 dict.set_item("task", task_to_str(task))?;
 
                 if let Some(analysis) = analysis {
-                    let analysis_dict = PyDict::new(py);
-                    analysis_dict.set_item("bpm", analysis.bpm)?;
-                    analysis_dict.set_item("key", analysis.key)?;
-
-                    let beat_grid_dict = PyDict::new(py);
-                    beat_grid_dict.set_item("beats", &analysis.beat_grid.beats)?;
-                    beat_grid_dict.set_item("downbeats", &analysis.beat_grid.downbeats)?;
-                    beat_grid_dict.set_item("bars", &analysis.beat_grid.bars)?;
-                    analysis_dict.set_item("beat_grid", beat_grid_dict)?;
-
-                    dict.set_item("analysis", analysis_dict)?;
+                    dict.set_item("analysis", analysis_to_py_dict(py, analysis)?)?;
                 }
             }
             LoaderEvent::TaskError {
@@ -1098,32 +1864,106 @@ impl AudioEngine {
     }
 
     /// Trigger playback of a previously loaded sample.
-    pub fn play_sample(&mut self, id: usize, volume: f32) -> PyResult<()> {
+    ///
+    /// `speed` plays this trigger at its own speed, multiplied with the global speed, and
+    /// leaves other pads alone. The voice then skips BPM lock phase alignment. `loop`
+    /// overrides the pad's loop mode for this trigger: `False` plays the sample once and
+    /// reports `VoiceFinished` and `SampleStopped` when it ends. The keyword-only `loop_mode`
+    /// is the same override; passing both with different values raises `ValueError`.
+    /// `start_seconds` starts playback that far into the sample; offsets past its end wrap
+    /// for looping pads and end one-shots at once.
+    /// `pan` places the voice from -1.0 (left) to 1.0 (right) with equal-power gains on
+    /// stereo outputs and is ignored on mono ones.
+    ///
+    /// Returns a handle for `stop_voice()`. Handles increase with every trigger; if the
+    /// trigger does not start a voice, `receive_msg()` reports its handle in
+    /// `AudioMessage.TriggerDropped`. A pad plays a single voice, so retriggering it restarts
+    /// that voice under the new handle and the older handle then addresses nothing.
+    #[pyo3(signature = (
+        id, volume, speed = None, r#loop = None, start_seconds = None, pan = None, *,
+        loop_mode = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn play_sample(
+        &mut self,
+        id: usize,
+        volume: f32,
+        speed: Option<f32>,
+        r#loop: Option<bool>,
+        start_seconds: Option<f32>,
+        pan: Option<f32>,
+        loop_mode: Option<bool>,
+    ) -> PyResult<u64> {
         if id >= NUM_SAMPLES {
             return Err(PyValueError::new_err("id out of range"));
         }
 
+        if r#loop
+            .zip(loop_mode)
+            .is_some_and(|(looped, mode)| looped != mode)
+        {
+            return Err(PyValueError::new_err("loop and loop_mode disagree"));
+        }
+        let r#loop = r#loop.or(loop_mode);
+
         if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
             return Err(PyValueError::new_err("volume out of range"));
         }
 
+        if let Some(speed) = speed
+            && (!speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed))
+        {
+            return Err(PyValueError::new_err("speed out of range"));
+        }
+
+        if let Some(start_seconds) = start_seconds
+            && (!start_seconds.is_finite() || start_seconds < 0.0)
+        {
+            return Err(PyValueError::new_err("start_seconds out of range"));
+        }
+
+        if pan.is_some_and(|pan| !(-1.0..=1.0).contains(&pan)) {
+            return Err(PyValueError::new_err("pan out of range"));
+        }
+
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
+        let options = TriggerOptions {
+            speed,
+            loop_mode: r#loop.map(|looped| {
+                if looped {
+                    LoopMode::Loop
+                } else {
+                    LoopMode::OneShot
+                }
+            }),
+            start_s: start_seconds,
+            pan,
+        };
+        let voice_handle = self.next_voice_handle;
+        self.next_voice_handle += 1;
         producer_guard
-            .push(ControlMessage::PlaySample { id, volume })
-            .map_err(|_| PyRuntimeError::new_err("Failed to send PlaySample - buffer may be full"))
+            .push(ControlMessage::PlaySampleWith {
+                id,
+                volume,
+                options,
+                handle: voice_handle,
+            })
+            .map_err(|_| {
+                PyRuntimeError::new_err("Failed to send PlaySample - buffer may be full")
+            })?;
+        Ok(voice_handle)
     }
 
-    /// Stop all active voices and play a sample as one audio-thread command.
-    pub fn play_sample_exclusive(&mut self, id: usize, volume: f32) -> PyResult<()> {
+    /// Play a sample and stop it automatically after `frames` output frames.
+    ///
+    /// The automatic stop fades out over the stop fade, like `stop_sample`.
+    pub fn play_sample_for(&mut self, id: usize, volume: f32, frames: u64) -> PyResult<()> {
         if id >= NUM_SAMPLES {
             return Err(PyValueError::new_err("id out of range"));
         }
@@ -1132,42 +1972,58 @@ impl AudioEngine {
             return Err(PyValueError::new_err("volume out of range"));
         }
 
+        if frames == 0 {
+            return Err(PyValueError::new_err("frames must be greater than 0"));
+        }
+
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        producer_guard
-            .push(ControlMessage::PlaySampleExclusive { id, volume })
-            .map_err(|_| {
-                PyRuntimeError::new_err("Failed to send PlaySampleExclusive - buffer may be full")
-            })
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::PlaySampleFor { id, volume, frames },
+            "PlaySampleFor",
+        )
     }
 
-    /// Stop playback of all active voices.
-    pub fn stop_all(&mut self) -> PyResult<()> {
+    /// Stop all active voices and play a sample as one audio-thread command.
+    pub fn play_sample_exclusive(&mut self, id: usize, volume: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
+            return Err(PyValueError::new_err("volume out of range"));
+        }
+
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
-            .push(ControlMessage::StopAll())
-            .map_err(|_| PyRuntimeError::new_err("Failed to send Stop - buffer may be full"))
+            .push(ControlMessage::PlaySampleExclusive { id, volume })
+            .map_err(|_| {
+                PyRuntimeError::new_err("Failed to send PlaySampleExclusive - buffer may be full")
+            })
     }
 
-    /// Set the global volume multiplier.
-    pub fn set_volume(&mut self, volume: f32) -> PyResult<()> {
+    /// Play a sample on the cue bus only, for previewing it before it enters the mix.
+    ///
+    /// The voice starts immediately and stays out of the master mix until
+    /// `promote_to_master()`.
+    #[pyo3(signature = (id, volume = 1.0))]
+    pub fn play_sample_cued(&mut self, id: usize, volume: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
         if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
             return Err(PyValueError::new_err("volume out of range"));
         }
@@ -1177,22 +2033,19 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_parameter_message(
+        push_control_message(
             &mut producer_guard,
-            ControlParameterMessage::SetVolume(volume),
-            "SetVolume",
+            ControlMessage::PlaySampleCued { id, volume },
+            "PlaySampleCued",
         )
     }
 
-    /// Set the global speed multiplier.
-    pub fn set_speed(&mut self, speed: f32) -> PyResult<()> {
-        if !speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed) {
-            return Err(PyValueError::new_err("speed out of range"));
+    /// Fade a pad's cue-only voices into the master mix, keeping their phase.
+    pub fn promote_to_master(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
         }
 
         let handle = self
@@ -1200,14 +2053,63 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_parameter_message(
+        push_control_message(
             &mut producer_guard,
-            ControlParameterMessage::SetSpeed(speed),
+            ControlMessage::PromoteToMaster { id },
+            "PromoteToMaster",
+        )
+    }
+
+    /// Stop playback of all active voices.
+    pub fn stop_all(&mut self) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        producer_guard
+            .push(ControlMessage::StopAll())
+            .map_err(|_| PyRuntimeError::new_err("Failed to send Stop - buffer may be full"))
+    }
+
+    /// Set the global volume multiplier.
+    pub fn set_volume(&mut self, volume: f32) -> PyResult<()> {
+        if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
+            return Err(PyValueError::new_err("volume out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetVolume(volume)],
+            "SetVolume",
+        )
+    }
+
+    /// Set the global speed multiplier.
+    pub fn set_speed(&mut self, speed: f32) -> PyResult<()> {
+        if !speed.is_finite() || !(SPEED_MIN..=SPEED_MAX).contains(&speed) {
+            return Err(PyValueError::new_err("speed out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetSpeed(speed)],
             "SetSpeed",
         )
     }
@@ -1218,10 +2120,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         push_control_message(
             &mut producer_guard,
@@ -1230,16 +2129,289 @@ impl AudioEngine {
         )
     }
 
+    /// Enable or disable the DC blocker on the final master output (enabled by default).
+    pub fn set_master_dc_blocker(&mut self, enabled: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetMasterDcBlocker(enabled),
+            "SetMasterDcBlocker",
+        )
+    }
+
+    /// Enable or disable the peak limiter on the final master output (disabled by default).
+    ///
+    /// The limiter holds the master just below full scale without adding latency.
+    pub fn set_master_limiter(&mut self, enabled: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetMasterLimiter(enabled),
+            "SetMasterLimiter",
+        )
+    }
+
+    /// Enable or disable the mono sum on stereo outputs (disabled by default).
+    ///
+    /// Both output channels carry (L + R) at -3 dB, regardless of panning or width. The
+    /// output trims of both channels apply before the sum.
+    pub fn set_mono_output(&mut self, enabled: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetMonoOutput(enabled),
+            "SetMonoOutput",
+        )
+    }
+
+    /// Route the `"preview"` voice or the `"cue"` bus to output channels `first_channel` and
+    /// `first_channel + 1` (0-based, so 2 for outputs 3–4), or back to the default with None.
+    ///
+    /// Channels 0 and 1 carry the main mix, so the pair must start at 2 or above. The pair
+    /// then carries only monitor signals, never the main mix. On a device with too
+    /// few channels the preview falls back to the main mix and the cue bus to split cue, or is
+    /// muted without it.
+    #[pyo3(signature = (source, first_channel = None))]
+    pub fn set_monitor_output(
+        &mut self,
+        source: &str,
+        first_channel: Option<usize>,
+    ) -> PyResult<()> {
+        let source = match source {
+            "preview" => MonitorSource::Preview,
+            "cue" => MonitorSource::Cue,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "monitor source must be one of preview, cue",
+                ));
+            }
+        };
+        if first_channel.is_some_and(|first| {
+            first < 2 || first.saturating_add(1) >= usize::from(MAX_OUTPUT_CHANNELS)
+        }) {
+            return Err(PyValueError::new_err(format!(
+                "first_channel must be within 2..={}",
+                MAX_OUTPUT_CHANNELS - 2
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetMonitorOutput {
+                source,
+                first_channel,
+            },
+            "SetMonitorOutput",
+        )
+    }
+
+    /// Pan placed pads across a ring of speakers, given as one azimuth in degrees per output
+    /// channel.
+    ///
+    /// 0 is straight ahead and angles turn clockwise, so a quad rig in FL, FR, RL, RR order
+    /// is `[-45, 45, -135, 135]`. Only outputs wider than stereo take a layout; pads without
+    /// a position stay on the first two channels.
+    pub fn set_speaker_layout(&mut self, angles: Vec<f32>) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        if angles.len() != handle.output_channels {
+            return Err(PyValueError::new_err(format!(
+                "expected one angle per output channel ({}), got {}",
+                handle.output_channels,
+                angles.len()
+            )));
+        }
+        let layout = SpeakerLayout::new(&angles).ok_or_else(|| {
+            PyValueError::new_err(
+                "speaker layouts need at least 3 outputs and finite, distinct angles",
+            )
+        })?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetSpeakerLayout(Some(layout)),
+            "SetSpeakerLayout",
+        )
+    }
+
+    /// Play every pad on the first two output channels again.
+    pub fn clear_speaker_layout(&mut self) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetSpeakerLayout(None),
+            "SetSpeakerLayout",
+        )
+    }
+
+    /// Place a pad at `azimuth_degrees` on the speaker layout.
+    ///
+    /// The pad plays the mono sum of its channels on the two speakers around that angle.
+    /// Without a speaker layout, as on stereo devices, the position has no effect.
+    pub fn set_pad_position(&mut self, id: usize, azimuth_degrees: f32) -> PyResult<()> {
+        if !azimuth_degrees.is_finite() {
+            return Err(PyValueError::new_err("azimuth_degrees must be finite"));
+        }
+
+        self.send_pad_position(id, Some(azimuth_degrees))
+    }
+
+    /// Put a placed pad back on the first two output channels.
+    pub fn clear_pad_position(&mut self, id: usize) -> PyResult<()> {
+        self.send_pad_position(id, None)
+    }
+
+    /// Enable or disable periodic `AudioMessage.LimiterMeter` events (disabled by default).
+    pub fn set_limiter_meter_events(&mut self, enabled: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetLimiterMeterEvents(enabled),
+            "SetLimiterMeterEvents",
+        )
+    }
+
+    /// Set how long tempo changes glide, as an exponential time constant in milliseconds.
+    ///
+    /// `0` applies tempo changes immediately; the maximum is `TEMPO_GLIDE_MS_MAX`.
+    pub fn set_tempo_glide(&mut self, glide_ms: f32) -> PyResult<()> {
+        if !glide_ms.is_finite() || !(0.0..=TEMPO_GLIDE_MS_MAX).contains(&glide_ms) {
+            return Err(PyValueError::new_err(format!(
+                "glide_ms must be within 0.0..={TEMPO_GLIDE_MS_MAX}"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetTempoGlide(glide_ms),
+            "SetTempoGlide",
+        )
+    }
+
+    /// Set how many output frames a stopped voice fades out over, to avoid a click.
+    ///
+    /// Applies to `stop_sample()` and `stop_all()` on pads without a fade-out time of their
+    /// own. Defaults to 5 ms at the output rate. `0` stops voices instantly; the maximum is
+    /// `STOP_FADE_SAMPLES_MAX`.
+    pub fn set_stop_fade_samples(&mut self, samples: u32) -> PyResult<()> {
+        if samples > STOP_FADE_SAMPLES_MAX {
+            return Err(PyValueError::new_err(format!(
+                "samples must be within 0..={STOP_FADE_SAMPLES_MAX}"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetFadeSamples(samples),
+            "SetFadeSamples",
+        )
+    }
+
+    /// Set a calibration gain per physical output channel, applied as the final stage.
+    ///
+    /// `trims` must have one entry per output channel, each within `0..=OUTPUT_TRIM_MAX`.
+    pub fn set_output_trim(&mut self, trims: Vec<f32>) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        if trims.len() != handle.output_channels {
+            return Err(PyValueError::new_err(format!(
+                "trims must have {} entries, got {}",
+                handle.output_channels,
+                trims.len()
+            )));
+        }
+        if trims
+            .iter()
+            .any(|gain| !gain.is_finite() || !(0.0..=OUTPUT_TRIM_MAX).contains(gain))
+        {
+            return Err(PyValueError::new_err(format!(
+                "trims must be within 0.0..={OUTPUT_TRIM_MAX}"
+            )));
+        }
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+        if producer_guard.slots() < trims.len() {
+            return Err(PyRuntimeError::new_err(
+                "Failed to send output trims - buffer may be full",
+            ));
+        }
+
+        for (channel, gain) in trims.into_iter().enumerate() {
+            push_control_message(
+                &mut producer_guard,
+                ControlMessage::SetOutputTrim { channel, gain },
+                "SetOutputTrim",
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn set_key_lock(&mut self, enabled: bool) -> PyResult<()> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         push_control_message(
             &mut producer_guard,
@@ -1258,10 +2430,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         push_control_message(
             &mut producer_guard,
@@ -1270,9 +2439,903 @@ impl AudioEngine {
         )
     }
 
-    pub fn set_master_bpm(&mut self, bpm: f32) -> PyResult<()> {
-        if !bpm.is_finite() || bpm <= 0.0 {
-            return Err(PyValueError::new_err("bpm out of range"));
+    /// Set a pad's voice priority (0–255, default 128).
+    ///
+    /// When every voice is busy, a trigger steals from a pad of strictly lower priority,
+    /// whose voice fades out like a stop. Otherwise `set_voice_steal_policy()` decides
+    /// whether it drops.
+    pub fn set_pad_priority(&mut self, id: usize, priority: u8) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadPriority { id, priority },
+            "SetPadPriority",
+        )
+    }
+
+    /// Choose what a trigger does when every voice is busy and no lower-priority pad plays:
+    /// `"silent"` (the default) drops it, `"oldest"` takes the equal-priority voice furthest
+    /// through its sample and `"quietest"` the one with the lowest gain.
+    pub fn set_voice_steal_policy(&mut self, policy: &str) -> PyResult<()> {
+        let policy = parse_voice_steal_policy(policy)?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetVoiceStealPolicy(policy),
+            "SetVoiceStealPolicy",
+        )
+    }
+
+    /// Set the loop mode of pads without their own: `"looper"` (the default) loops them,
+    /// `"sampler"` plays them once to their loop end.
+    pub fn set_loop_mode(&mut self, mode: &str) -> PyResult<()> {
+        let mode = parse_default_loop_mode(mode)?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetDefaultLoopMode(mode),
+            "SetDefaultLoopMode",
+        )
+    }
+
+    /// Make a pad `"loop"` or play `"one_shot"` regardless of the default loop mode, or
+    /// follow the default again with `None`.
+    ///
+    /// A one-shot pad stops at its loop end and reports `VoiceFinished` and `SampleStopped`.
+    #[pyo3(signature = (id, mode = None))]
+    pub fn set_pad_loop_mode(&mut self, id: usize, mode: Option<&str>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+        let mode = mode.map(parse_pad_loop_mode).transpose()?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadLoopMode { id, mode },
+            "SetPadLoopMode",
+        )
+    }
+
+    /// Send a pad's pre-fader signal to the cue bus, whether or not it is audible in the
+    /// master mix.
+    ///
+    /// The cue taps the pad after its Gain/Trim, before mute, EQ and master volume.
+    pub fn set_pad_cue(&mut self, id: usize, enabled: bool) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadCue { id, enabled },
+            "SetPadCue",
+        )
+    }
+
+    /// Route the cue bus to the output as split cue: the master mix summed to mono on the
+    /// first channel, the cue bus on the second. Off by default, which keeps the cue silent.
+    pub fn set_cue_split(&mut self, enabled: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetCueSplit(enabled),
+            "SetCueSplit",
+        )
+    }
+
+    /// Assign a pad to a choke group, or remove it from its group with `None`.
+    #[pyo3(signature = (id, group = None))]
+    pub fn set_pad_choke_group(&mut self, id: usize, group: Option<u8>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if group.is_some_and(|group| group as usize >= NUM_CHOKE_GROUPS) {
+            return Err(PyValueError::new_err("choke group out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadChokeGroup { id, group },
+            "SetPadChokeGroup",
+        )
+    }
+
+    /// Set whether stopping one pad of a choke group stops all pads in that group.
+    pub fn set_choke_group_stop_all(&mut self, group: u8, enabled: bool) -> PyResult<()> {
+        if group as usize >= NUM_CHOKE_GROUPS {
+            return Err(PyValueError::new_err("choke group out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetChokeGroupStopAll { group, enabled },
+            "SetChokeGroupStopAll",
+        )
+    }
+
+    pub fn set_master_bpm(&mut self, bpm: f32) -> PyResult<()> {
+        if !bpm.is_finite() || bpm <= 0.0 {
+            return Err(PyValueError::new_err("bpm out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetMasterBpm(bpm)],
+            "SetMasterBpm",
+        )
+    }
+
+    pub fn set_pad_bpm(&mut self, id: usize, bpm: Option<f32>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if bpm.is_some_and(|value| !value.is_finite() || value <= 0.0) {
+            return Err(PyValueError::new_err("bpm out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetPadBpm { id, bpm }],
+            "SetPadBpm",
+        )
+    }
+
+    pub fn set_pad_timing_metadata(&mut self, id: usize, phase_anchor_s: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !phase_anchor_s.is_finite() || phase_anchor_s < 0.0 {
+            return Err(PyValueError::new_err("phase_anchor_s out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadTimingMetadata {
+                id,
+                metadata: PadTimingMetadata { phase_anchor_s },
+            },
+            "SetPadTimingMetadata",
+        )
+    }
+
+    /// Publish a slot's onset times (seconds from the sample start) for transient snapping.
+    ///
+    /// Loads that run analysis publish the analyzed onsets with the sample; this replaces them.
+    pub fn set_pad_onsets(&mut self, id: usize, onsets_s: Vec<f32>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if onsets_s.len() > PAD_ONSETS_MAX {
+            return Err(PyValueError::new_err("too many onsets"));
+        }
+
+        if onsets_s
+            .iter()
+            .any(|onset_s| !onset_s.is_finite() || *onset_s < 0.0)
+        {
+            return Err(PyValueError::new_err("onsets_s out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let onsets = onset_table(&onsets_s, handle.output_sample_rate);
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::PublishPadOnsets { id, onsets },
+            "PublishPadOnsets",
+        )
+    }
+
+    /// Snap a pad's start and seek positions to the nearest onset within `window_ms`.
+    pub fn set_snap_to_transient(
+        &mut self,
+        id: usize,
+        enabled: bool,
+        window_ms: f32,
+    ) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !is_valid_transient_snap_window(window_ms) {
+            return Err(PyValueError::new_err("window_ms out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadTransientSnap {
+                id,
+                window_ms: enabled.then_some(window_ms),
+            },
+            "SetPadTransientSnap",
+        )
+    }
+
+    pub fn anchor_transport_phase_from_pad(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::AnchorTransportPhaseFromPad { id },
+            "AnchorTransportPhaseFromPad",
+        )
+    }
+
+    pub fn set_pad_gain(&mut self, id: usize, gain_db: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        validate_pad_gain_db(gain_db)?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetPadGain { id, gain_db }],
+            "SetPadGain",
+        )
+    }
+
+    /// Trim every loaded pad so its sample peak lands on `target_dbfs`.
+    ///
+    /// A crude kit-level loudness match: each pad gets a `SetPadGain` computed from the peak of
+    /// its loaded sample, clamped to the pad gain range. Silent pads are left alone. Returns
+    /// the applied gains in dB keyed by pad id.
+    pub fn auto_trim_pads<'py>(
+        &mut self,
+        py: Python<'py>,
+        target_dbfs: f32,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if !target_dbfs.is_finite() || !(PAD_GAIN_DB_MIN..=0.0).contains(&target_dbfs) {
+            return Err(PyValueError::new_err(format!(
+                "target_dbfs must be within {PAD_GAIN_DB_MIN}..=0.0"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let gains: Vec<(usize, f32)> = {
+            let cache = self
+                .sample_cache
+                .lock()
+                .map_err(|_| PyRuntimeError::new_err("Failed to acquire sample cache lock"))?;
+            cache
+                .iter()
+                .enumerate()
+                .filter_map(|(id, slot)| {
+                    let peak = slot
+                        .as_ref()?
+                        .samples
+                        .iter()
+                        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+                    auto_trim_gain_db(peak, target_dbfs).map(|gain_db| (id, gain_db))
+                })
+                .collect()
+        };
+
+        let messages: Vec<ControlParameterMessage> = gains
+            .iter()
+            .map(|&(id, gain_db)| ControlParameterMessage::SetPadGain { id, gain_db })
+            .collect();
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &messages,
+            "SetPadGain",
+        )?;
+
+        let applied = PyDict::new(py);
+        for (id, gain_db) in gains {
+            applied.set_item(id, gain_db)?;
+        }
+        Ok(applied)
+    }
+
+    pub fn set_pad_eq(
+        &mut self,
+        id: usize,
+        low_db: f32,
+        mid_db: f32,
+        high_db: f32,
+    ) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        validate_pad_eq_db([low_db, mid_db, high_db])?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetPadEq {
+                id,
+                low_db,
+                mid_db,
+                high_db,
+            }],
+            "SetPadEq",
+        )
+    }
+
+    /// Set several per-pad parameters in one message so they change in the same callback.
+    ///
+    /// Parameters left as `None` keep their current value. `eq_db` is `(low, mid, high)`;
+    /// `mute` ramps like `set_pad_fade_mute()`.
+    #[pyo3(signature = (id, gain_db = None, eq_db = None, mute = None))]
+    pub fn set_pad_params(
+        &mut self,
+        id: usize,
+        gain_db: Option<f32>,
+        eq_db: Option<(f32, f32, f32)>,
+        mute: Option<bool>,
+    ) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let params = PadParams {
+            gain_db,
+            eq_db: eq_db.map(|(low_db, mid_db, high_db)| [low_db, mid_db, high_db]),
+            mute,
+        };
+        if let Some(gain_db) = params.gain_db {
+            validate_pad_gain_db(gain_db)?;
+        }
+        if let Some(eq_db) = params.eq_db {
+            validate_pad_eq_db(eq_db)?;
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        if params == PadParams::default() {
+            return Ok(());
+        }
+
+        send_parameter_messages(
+            &handle.producer,
+            &handle.parameter_producer,
+            &[ControlParameterMessage::SetPadParams { id, params }],
+            "SetPadParams",
+        )
+    }
+
+    pub fn set_pad_loop_region(
+        &mut self,
+        id: usize,
+        start_s: f32,
+        end_s: Option<f32>,
+    ) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !start_s.is_finite() || start_s < 0.0 {
+            return Err(PyValueError::new_err("start_s out of range"));
+        }
+
+        if end_s.is_some_and(|end_s| !end_s.is_finite() || end_s < 0.0) {
+            return Err(PyValueError::new_err("end_s out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadLoopRegion { id, start_s, end_s },
+            "SetPadLoopRegion",
+        )
+    }
+
+    /// Audition `start_s..end_s` of a pad on the preview voice, looping, without storing it.
+    ///
+    /// The preview does not use a regular voice; commit the region with
+    /// `set_pad_loop_region`.
+    pub fn preview_loop_region(&mut self, id: usize, start_s: f32, end_s: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !start_s.is_finite() || start_s < 0.0 {
+            return Err(PyValueError::new_err("start_s out of range"));
+        }
+
+        if !end_s.is_finite() || end_s <= start_s {
+            return Err(PyValueError::new_err("end_s must be after start_s"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::PreviewLoopRegion { id, start_s, end_s },
+            "PreviewLoopRegion",
+        )
+    }
+
+    pub fn stop_preview_loop(&mut self) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::StopPreviewLoop(),
+            "StopPreviewLoop",
+        )
+    }
+
+    /// Fade a pad out to silence (`muted=True`) or back in while its voices keep playing.
+    pub fn set_pad_fade_mute(&mut self, id: usize, muted: bool) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadFadeMute { id, muted },
+            "SetPadFadeMute",
+        )
+    }
+
+    /// Chop a pad's loop region into `slices` equal parts for finger-drumming.
+    ///
+    /// With more than one slice, each trigger plays from the next slice start and wraps
+    /// around after the last one. `slices=1` (the default) triggers from the loop start.
+    pub fn set_pad_slices(&mut self, id: usize, slices: u16) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !(1..=PAD_SLICES_MAX).contains(&slices) {
+            return Err(PyValueError::new_err(format!(
+                "slices must be within 1..={PAD_SLICES_MAX}"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadSlices { id, slices },
+            "SetPadSlices",
+        )
+    }
+
+    /// Play a pad at exactly `numerator / denominator` of its synced rate, or of the global
+    /// speed while BPM lock is off.
+    ///
+    /// Both terms must be within `1..=PAD_RATE_RATIO_TERM_MAX` and the ratio within the speed
+    /// range; the combined rate is clamped to it. Use `clear_pad_rate_ratio` to return the pad
+    /// to normal sync.
+    pub fn set_pad_rate_ratio(
+        &mut self,
+        id: usize,
+        numerator: u16,
+        denominator: u16,
+    ) -> PyResult<()> {
+        if !is_valid_rate_ratio((numerator, denominator)) {
+            return Err(PyValueError::new_err(format!(
+                "rate ratio must use terms within 1..={PAD_RATE_RATIO_TERM_MAX} and a rate \
+                 within {SPEED_MIN}..={SPEED_MAX}"
+            )));
+        }
+
+        self.send_pad_rate_ratio(id, Some((numerator, denominator)))
+    }
+
+    /// Return a pad to normal speed and BPM sync after `set_pad_rate_ratio`.
+    pub fn clear_pad_rate_ratio(&mut self, id: usize) -> PyResult<()> {
+        self.send_pad_rate_ratio(id, None)
+    }
+
+    /// Play `source_slot` across all pads of `bank`, transposed by each pad's distance from
+    /// `root_pad` in semitones (clamped to +/-KEYBOARD_SEMITONES_MAX).
+    ///
+    /// Transposition uses the Key Lock path for key-locked pads and varispeed otherwise. Gain,
+    /// fades, choke groups and velocity stay per pad. Voices of the bank are stopped.
+    pub fn set_keyboard_mode(
+        &mut self,
+        bank: usize,
+        source_slot: usize,
+        root_pad: usize,
+    ) -> PyResult<()> {
+        self.send_keyboard_mode(bank, Some((source_slot, root_pad)))
+    }
+
+    /// Leave keyboard mode and restore the bank's normal slot mapping.
+    pub fn clear_keyboard_mode(&mut self, bank: usize) -> PyResult<()> {
+        self.send_keyboard_mode(bank, None)
+    }
+
+    /// Fade a pad in over `fade_in_ms` on triggers and unmutes, and out over `fade_out_ms`
+    /// on stops and mutes, overriding the engine defaults.
+    ///
+    /// Both times must be within `0..=PAD_FADE_MS_MAX`. Use `clear_pad_fades` to restore the
+    /// defaults.
+    pub fn set_pad_fades(&mut self, id: usize, fade_in_ms: f32, fade_out_ms: f32) -> PyResult<()> {
+        let fades = PadFades {
+            fade_in_ms,
+            fade_out_ms,
+        };
+        if !is_valid_pad_fades(fades) {
+            return Err(PyValueError::new_err(format!(
+                "fade times must be within 0..={PAD_FADE_MS_MAX} ms"
+            )));
+        }
+
+        self.send_pad_fades(id, Some(fades))
+    }
+
+    /// Restore the engine's default fades for a pad after `set_pad_fades`.
+    pub fn clear_pad_fades(&mut self, id: usize) -> PyResult<()> {
+        self.send_pad_fades(id, None)
+    }
+
+    /// Fade triggers of pads without their own fades in over a velocity-dependent time.
+    ///
+    /// The fade-in length is interpolated linearly from `soft_ms` at velocity 0 to `hard_ms`
+    /// at velocity 1.0 and fixed when the voice starts; e.g. 20 and 2 ms give 11 ms at 0.5.
+    /// Both times must be within `0..=PAD_FADE_MS_MAX`. `set_pad_fades` takes precedence, and
+    /// `clear_velocity_attack` restores the instant attack.
+    pub fn set_velocity_attack(&mut self, soft_ms: f32, hard_ms: f32) -> PyResult<()> {
+        let attack = VelocityAttack { soft_ms, hard_ms };
+        if !is_valid_velocity_attack(attack) {
+            return Err(PyValueError::new_err(format!(
+                "attack times must be within 0..={PAD_FADE_MS_MAX} ms"
+            )));
+        }
+
+        self.send_velocity_attack(Some(attack))
+    }
+
+    /// Restore the velocity-independent instant attack after `set_velocity_attack`.
+    pub fn clear_velocity_attack(&mut self) -> PyResult<()> {
+        self.send_velocity_attack(None)
+    }
+
+    /// Ramp one EQ band (`"low"`, `"mid"` or `"high"`) of a pad to `target_db` over
+    /// `duration_s` seconds.
+    ///
+    /// The ramp is linear in dB and starts from the band's current gain. The engine updates
+    /// the band gain at least every 128 frames and smooths between updates. `set_pad_eq`
+    /// cancels a running ramp.
+    pub fn automate_pad_eq(
+        &mut self,
+        id: usize,
+        band: &str,
+        target_db: f32,
+        duration_s: f64,
+    ) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let band = parse_pad_eq_band(band)?;
+        validate_pad_eq_db([target_db; 3])?;
+        if !duration_s.is_finite() || duration_s < 0.0 {
+            return Err(PyValueError::new_err(
+                "duration_s must be finite and non-negative",
+            ));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        let duration_frames = (duration_s * f64::from(handle.output_sample_rate)).round() as u64;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::AutomatePadEq {
+                id,
+                band,
+                target_db,
+                duration_frames,
+            },
+            "AutomatePadEq",
+        )
+    }
+
+    /// Store the current volume, pad gains, pad EQ, pad mutes and pad cue routing as
+    /// snapshot `"a"` or `"b"`.
+    pub fn store_snapshot(&mut self, snapshot: &str) -> PyResult<()> {
+        let slot = parse_snapshot_slot(snapshot)?;
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::StoreSnapshot { slot },
+            "StoreSnapshot",
+        )
+    }
+
+    /// Morph from the current parameters to snapshot `"a"` or `"b"` over `seconds`.
+    ///
+    /// Volume, pad gains and pad EQ ramp linearly, updated at least every 128 frames and
+    /// smoothed in between; setting one of them directly cancels its ramp. Pads the snapshot
+    /// unmutes or cues switch at the start, pads it mutes or uncues at the end. Does nothing if
+    /// the snapshot was never stored.
+    pub fn morph_to(&mut self, snapshot: &str, seconds: f64) -> PyResult<()> {
+        let slot = parse_snapshot_slot(snapshot)?;
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err(PyValueError::new_err(
+                "seconds must be finite and non-negative",
+            ));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+        let duration_frames = (seconds * f64::from(handle.output_sample_rate)).round() as u64;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::MorphToSnapshot {
+                slot,
+                duration_frames,
+            },
+            "MorphToSnapshot",
+        )
+    }
+
+    /// Soft-clip a pad's voices to `ceiling` (linear gain) before the master mix.
+    ///
+    /// Disabled by default; a disabled pad stays linear.
+    #[pyo3(signature = (id, enabled, ceiling = 1.0))]
+    pub fn set_pad_clip(&mut self, id: usize, enabled: bool, ceiling: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !is_valid_pad_clip_ceiling(ceiling) {
+            return Err(PyValueError::new_err(format!(
+                "ceiling must be within {PAD_CLIP_CEILING_MIN}..=1.0"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadClip {
+                id,
+                enabled,
+                ceiling,
+            },
+            "SetPadClip",
+        )
+    }
+
+    /// Delay a pad's output by up to `PAD_DELAY_MS_MAX` milliseconds to line it up with the
+    /// other pads; 0 bypasses the delay.
+    ///
+    /// Changes crossfade over a few milliseconds, so they do not click. The delay is part of
+    /// the A/B snapshots.
+    pub fn set_pad_delay_ms(&mut self, id: usize, ms: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !(0.0..=PAD_DELAY_MS_MAX).contains(&ms) {
+            return Err(PyValueError::new_err(format!(
+                "ms must be within 0.0..={PAD_DELAY_MS_MAX}"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        // Pads only get a delay line once they are delayed, allocated here.
+        let sends_line = ms > 0.0 && !handle.pad_delay_line_sent[id].swap(true, Ordering::AcqRel);
+        let line = sends_line
+            .then(|| new_pad_delay_line(handle.output_channels, handle.output_sample_rate as f32));
+        let result = push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadDelay {
+                id,
+                delay_ms: ms,
+                line,
+            },
+            "SetPadDelay",
+        );
+        if result.is_err() && sends_line {
+            handle.pad_delay_line_sent[id].store(false, Ordering::Release);
+        }
+        result
+    }
+
+    /// Seek an active or paused sample voice to a source position in seconds.
+    ///
+    /// `receive_msg()` reports `AudioMessage.SeekAcknowledged` once the voice moved; seeking
+    /// an idle pad does nothing.
+    pub fn seek_sample(&mut self, id: usize, position_s: f32) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        if !position_s.is_finite() || position_s < 0.0 {
+            return Err(PyValueError::new_err("position_s out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        producer_guard
+            .push(ControlMessage::SeekSample { id, position_s })
+            .map_err(|_| PyRuntimeError::new_err("Failed to send SeekSample - buffer may be full"))
+    }
+
+    /// Clear a pad's filter and time-stretch state without stopping playback.
+    pub fn reset_pad_dsp(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
         }
 
         let handle = self
@@ -1280,25 +3343,23 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_parameter_message(
+        push_control_message(
             &mut producer_guard,
-            ControlParameterMessage::SetMasterBpm(bpm),
-            "SetMasterBpm",
+            ControlMessage::ResetPadDsp { id },
+            "ResetPadDsp",
         )
     }
 
-    pub fn set_pad_bpm(&mut self, id: usize, bpm: Option<f32>) -> PyResult<()> {
+    /// Start or stop replaying the last `slice_frames` of a playing sample.
+    pub fn stutter(&mut self, id: usize, slice_frames: usize, on: bool) -> PyResult<()> {
         if id >= NUM_SAMPLES {
             return Err(PyValueError::new_err("id out of range"));
         }
 
-        if bpm.is_some_and(|value| !value.is_finite() || value <= 0.0) {
-            return Err(PyValueError::new_err("bpm out of range"));
+        if on && slice_frames == 0 {
+            return Err(PyValueError::new_err("slice_frames must be greater than 0"));
         }
 
         let handle = self
@@ -1306,50 +3367,51 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_parameter_message(
+        push_control_message(
             &mut producer_guard,
-            ControlParameterMessage::SetPadBpm { id, bpm },
-            "SetPadBpm",
+            ControlMessage::Stutter {
+                id,
+                slice_frames,
+                on,
+            },
+            "Stutter",
         )
     }
 
-    pub fn set_pad_timing_metadata(&mut self, id: usize, phase_anchor_s: f32) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
-        }
-
-        if !phase_anchor_s.is_finite() || phase_anchor_s < 0.0 {
-            return Err(PyValueError::new_err("phase_anchor_s out of range"));
-        }
+    pub fn set_trigger_quantization(&mut self, mode: &str) -> PyResult<()> {
+        let mode = parse_trigger_quantization(mode).ok_or_else(|| {
+            PyValueError::new_err(
+                "trigger quantization mode must be immediate or one of 1/16, 1/32, 1/64",
+            )
+        })?;
 
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_control_message(
-            &mut producer_guard,
-            ControlMessage::SetPadTimingMetadata {
-                id,
-                metadata: PadTimingMetadata { phase_anchor_s },
-            },
-            "SetPadTimingMetadata",
-        )
+        producer_guard
+            .push(ControlMessage::SetTriggerQuantization(mode))
+            .map_err(|_| {
+                PyRuntimeError::new_err(
+                    "Failed to send SetTriggerQuantization - buffer may be full",
+                )
+            })
     }
 
-    pub fn anchor_transport_phase_from_pad(&mut self, id: usize) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
+    /// Set MPC-style swing from `0` (straight) to `100` percent.
+    ///
+    /// Delays every off-beat position of the 1/16, 1/32 and 1/64 trigger quantization grids by
+    /// up to half a step. Triggers that are already scheduled keep their target frame.
+    pub fn set_swing(&mut self, amount: f32) -> PyResult<()> {
+        if !amount.is_finite() || !(0.0..=SWING_PERCENT_MAX).contains(&amount) {
+            return Err(PyValueError::new_err(format!(
+                "swing must be within 0..={SWING_PERCENT_MAX}"
+            )));
         }
 
         let handle = self
@@ -1357,101 +3419,95 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         push_control_message(
             &mut producer_guard,
-            ControlMessage::AnchorTransportPhaseFromPad { id },
-            "AnchorTransportPhaseFromPad",
+            ControlMessage::SetSwing(amount),
+            "SetSwing",
         )
     }
 
-    pub fn set_pad_gain(&mut self, id: usize, gain_db: f32) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
-        }
-
-        if !gain_db.is_finite() || !(PAD_GAIN_DB_MIN..=PAD_GAIN_DB_MAX).contains(&gain_db) {
-            return Err(PyValueError::new_err("gain out of range"));
-        }
-
+    /// Schedule quantized triggers and scene launches early by the measured output latency.
+    ///
+    /// Targets then land on the grid when they are heard rather than when they are rendered.
+    /// Backends that report no callback timestamps keep the uncompensated targets.
+    pub fn set_latency_compensation(&mut self, enabled: bool) -> PyResult<()> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        push_parameter_message(
+        push_control_message(
             &mut producer_guard,
-            ControlParameterMessage::SetPadGain { id, gain_db },
-            "SetPadGain",
+            ControlMessage::SetLatencyCompensation(enabled),
+            "SetLatencyCompensation",
         )
     }
 
-    pub fn set_pad_eq(
-        &mut self,
-        id: usize,
-        low_db: f32,
-        mid_db: f32,
-        high_db: f32,
-    ) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
+    /// Replace the pattern of sequencer `track` with one entry per 1/16 note.
+    ///
+    /// Each step is `(sample_id, volume)` or `None` for a rest, and the pattern must be one to
+    /// four whole bars long. A running sequencer switches to the new pattern at the next bar.
+    pub fn set_sequence(&mut self, track: usize, steps: Vec<Option<(usize, f32)>>) -> PyResult<()> {
+        if track >= SEQUENCER_TRACKS {
+            return Err(PyValueError::new_err("track out of range"));
         }
 
-        let all = [low_db, mid_db, high_db];
-        if all
-            .iter()
-            .any(|v| !v.is_finite() || !(PAD_EQ_DB_MIN..=PAD_EQ_DB_MAX).contains(v))
-        {
-            return Err(PyValueError::new_err("eq gain out of range"));
+        if !is_valid_sequence_len(steps.len()) {
+            return Err(PyValueError::new_err(format!(
+                "steps must hold a multiple of {SEQUENCER_STEPS_PER_BAR} entries, at most \
+                 {SEQUENCER_STEPS_MAX}"
+            )));
+        }
+
+        let mut messages = Vec::with_capacity(steps.len() + 2);
+        messages.push(ControlMessage::BeginSequence {
+            track,
+            steps: steps.len(),
+        });
+        for (step, entry) in steps.into_iter().enumerate() {
+            let Some((id, volume)) = entry else {
+                continue;
+            };
+            if id >= NUM_SAMPLES {
+                return Err(PyValueError::new_err("id out of range"));
+            }
+            if !volume.is_finite() || !(VOLUME_MIN..=VOLUME_MAX).contains(&volume) {
+                return Err(PyValueError::new_err("volume out of range"));
+            }
+            messages.push(ControlMessage::SetSequenceStep {
+                track,
+                step,
+                event: Some(SequencerStep { id, volume }),
+            });
         }
+        messages.push(ControlMessage::CommitSequence { track });
 
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .parameter_producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
-
-        push_parameter_message(
-            &mut producer_guard,
-            ControlParameterMessage::SetPadEq {
-                id,
-                low_db,
-                mid_db,
-                high_db,
-            },
-            "SetPadEq",
-        )
-    }
-
-    pub fn set_pad_loop_region(
-        &mut self,
-        id: usize,
-        start_s: f32,
-        end_s: Option<f32>,
-    ) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
+        let mut producer_guard = lock_producer(&handle.producer)?;
+        if producer_guard.slots() < messages.len() {
+            return Err(PyRuntimeError::new_err(
+                "Failed to send sequence - buffer may be full",
+            ));
         }
 
-        if !start_s.is_finite() || start_s < 0.0 {
-            return Err(PyValueError::new_err("start_s out of range"));
+        for message in messages {
+            push_control_message(&mut producer_guard, message, "SetSequenceStep")?;
         }
+        Ok(())
+    }
 
-        if end_s.is_some_and(|end_s| !end_s.is_finite() || end_s < 0.0) {
-            return Err(PyValueError::new_err("end_s out of range"));
+    /// Mute or unmute sequencer `track`; its steps keep advancing.
+    pub fn set_sequencer_track_mute(&mut self, track: usize, muted: bool) -> PyResult<()> {
+        if track >= SEQUENCER_TRACKS {
+            return Err(PyValueError::new_err("track out of range"));
         }
 
         let handle = self
@@ -1459,67 +3515,79 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         push_control_message(
             &mut producer_guard,
-            ControlMessage::SetPadLoopRegion { id, start_s, end_s },
-            "SetPadLoopRegion",
+            ControlMessage::SetSequencerTrackMute { track, muted },
+            "SetSequencerTrackMute",
         )
     }
 
-    /// Seek an active or paused sample voice to a source position in seconds.
-    pub fn seek_sample(&mut self, id: usize, position_s: f32) -> PyResult<()> {
-        if id >= NUM_SAMPLES {
-            return Err(PyValueError::new_err("id out of range"));
-        }
+    /// Start the step sequencer on the next 1/16 grid position of the transport.
+    pub fn sequencer_start(&mut self) -> PyResult<()> {
+        self.send_sequencer_running(true)
+    }
 
-        if !position_s.is_finite() || position_s < 0.0 {
-            return Err(PyValueError::new_err("position_s out of range"));
+    /// Stop the step sequencer. Voices it triggered keep playing.
+    pub fn sequencer_stop(&mut self) -> PyResult<()> {
+        self.send_sequencer_running(false)
+    }
+
+    /// Switch `bank` to a scene on the next bar.
+    ///
+    /// `slot_states[i]` marks whether pad `i` of the bank plays after the switch. Pads that
+    /// keep playing are not retriggered. A new launch replaces one that has not fired yet;
+    /// `AudioMessage.SceneLaunched` reports when the switch happened, and
+    /// `AudioMessage.SceneDropped` a launch the full scheduler had no room for.
+    pub fn launch_scene(&mut self, bank: usize, slot_states: Vec<bool>) -> PyResult<()> {
+        if bank >= NUM_BANKS {
+            return Err(PyValueError::new_err(format!(
+                "bank out of range (expected 0..{}, got {bank})",
+                NUM_BANKS - 1
+            )));
         }
+        if slot_states.len() != NUM_PADS {
+            return Err(PyValueError::new_err(format!(
+                "slot_states must have {NUM_PADS} entries, got {}",
+                slot_states.len()
+            )));
+        }
+
+        let pads = slot_states
+            .iter()
+            .enumerate()
+            .filter(|(_, playing)| **playing)
+            .fold(0_u64, |pads, (pad, _)| pads | (1 << pad));
 
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        producer_guard
-            .push(ControlMessage::SeekSample { id, position_s })
-            .map_err(|_| PyRuntimeError::new_err("Failed to send SeekSample - buffer may be full"))
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::LaunchScene { bank, pads },
+            "LaunchScene",
+        )
     }
 
-    pub fn set_trigger_quantization(&mut self, mode: &str) -> PyResult<()> {
-        let mode = parse_trigger_quantization(mode).ok_or_else(|| {
-            PyValueError::new_err(
-                "trigger quantization mode must be immediate or one of 1/16, 1/32, 1/64",
-            )
-        })?;
-
+    /// Cancel a scene launch that has not fired yet.
+    pub fn cancel_scene(&mut self) -> PyResult<()> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        producer_guard
-            .push(ControlMessage::SetTriggerQuantization(mode))
-            .map_err(|_| {
-                PyRuntimeError::new_err(
-                    "Failed to send SetTriggerQuantization - buffer may be full",
-                )
-            })
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::CancelScene(),
+            "CancelScene",
+        )
     }
 
     /// Stop playback of a previously triggered sample.
@@ -1536,16 +3604,32 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::StopSample { id })
             .map_err(|_| PyRuntimeError::new_err("Failed to send StopSample - buffer may be full"))
     }
 
+    /// Stop the voice started by the `play_sample()` call that returned `handle`.
+    ///
+    /// Other voices of the same sample keep playing. Handles of voices that already ended
+    /// or were retriggered are ignored.
+    pub fn stop_voice(&mut self, handle: u64) -> PyResult<()> {
+        let stream = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&stream.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::StopVoice { handle },
+            "StopVoice",
+        )
+    }
+
     /// Pause playback of a sample without resetting its position.
     ///
     /// If the sample is playing, it becomes silent but retains its current
@@ -1563,10 +3647,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::PauseSample { id })
@@ -1590,10 +3671,7 @@ impl AudioEngine {
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::ResumeSample { id })
@@ -1617,11 +3695,9 @@ impl AudioEngine {
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
         let _ = next_pad_request_id(&self.pad_request_ids, id).map_err(PyRuntimeError::new_err)?;
+        self.drop_playlist(id);
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
         producer_guard
             .push(ControlMessage::UnloadSample { id })
@@ -1635,50 +3711,186 @@ impl AudioEngine {
             *slot = None;
         }
 
-        if let Ok(mut set) = self.loading_sample_ids.lock() {
-            set.remove(&id);
-        }
+        if let Ok(mut set) = self.loading_sample_ids.lock() {
+            set.remove(&id);
+        }
+
+        if let Ok(mut set) = self.active_tasks.lock() {
+            set.retain(|(task_id, _)| *task_id != id);
+        }
+
+        Ok(())
+    }
+
+    /// Queue several files on one sample slot and activate entry `position` (default 0).
+    ///
+    /// Entries load and are analyzed on a background thread, one ahead of the active entry;
+    /// entries that fail to load are skipped with a warning. Each queued entry is reported by
+    /// `poll_loader_events()` as a `playlist_entry` event with its duration and analysis, and
+    /// the analyzed BPM becomes the pad BPM when the entry takes over. Each activated entry
+    /// is reported with a `PlaylistEntryActive` message. Loading or unloading the slot drops
+    /// its playlist.
+    #[pyo3(signature = (id, paths, position = 0))]
+    pub fn set_playlist(&mut self, id: usize, paths: Vec<String>, position: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+        let playlist = PadPlaylist::new(paths, position)
+            .ok_or_else(|| PyValueError::new_err("position must index a non-empty playlist"))?;
+        let loader = self.playlist_loader()?;
+
+        {
+            let loading = self
+                .loading_sample_ids
+                .lock()
+                .map_err(|_| PyRuntimeError::new_err("Failed to acquire loading ids lock"))?;
+            if loading.contains(&id) {
+                return Err(PyValueError::new_err("sample is currently loading"));
+            }
+        }
+        let _ = next_pad_request_id(&self.pad_request_ids, id).map_err(PyRuntimeError::new_err)?;
+
+        self.pad_playlists
+            .lock()
+            .map_err(|_| PyRuntimeError::new_err("Failed to acquire playlist lock"))?[id] =
+            Some(playlist);
+        loader.start(id);
+        Ok(())
+    }
+
+    /// Drop the slot's playlist; the active entry stays loaded.
+    pub fn clear_playlist(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+        self.drop_playlist(id);
+        Ok(())
+    }
+
+    /// Advance the slot's playlist to the next entry, wrapping after the last.
+    ///
+    /// A playing slot switches at its next loop boundary, an idle one right away.
+    pub fn playlist_next(&mut self, id: usize) -> PyResult<()> {
+        self.step_playlist(id, true)
+    }
+
+    /// Step the slot's playlist back one entry, wrapping before the first.
+    pub fn playlist_prev(&mut self, id: usize) -> PyResult<()> {
+        self.step_playlist(id, false)
+    }
+
+    /// Entry last activated on the slot's playlist, or None without a playlist.
+    ///
+    /// Pass it back to `set_playlist` to resume a saved session at the same entry.
+    pub fn playlist_position(&self, id: usize) -> PyResult<Option<usize>> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+        let playlists = self
+            .pad_playlists
+            .lock()
+            .map_err(|_| PyRuntimeError::new_err("Failed to acquire playlist lock"))?;
+        Ok(playlists[id].as_ref().map(PadPlaylist::position))
+    }
+
+    /// Send a ping message to the audio thread.
+    pub fn ping(&mut self) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        producer_guard
+            .push(ControlMessage::Ping())
+            .map_err(|_| PyRuntimeError::new_err("Failed to send Ping - buffer may be full"))
+    }
+
+    /// Ask the audio thread for a pad's processing latency in output frames.
+    ///
+    /// The answer arrives as an `AudioMessage.PadLatency` through `receive_msg()`.
+    pub fn query_pad_latency(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::QueryPadLatency { id },
+            "QueryPadLatency",
+        )
+    }
+
+    /// Ask the audio thread how long a one-shot pad has left to play.
+    ///
+    /// The answer arrives as an `AudioMessage.PadRemaining` through `receive_msg()`.
+    pub fn query_remaining(&mut self, id: usize) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        if let Ok(mut set) = self.active_tasks.lock() {
-            set.retain(|(task_id, _)| *task_id != id);
-        }
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        Ok(())
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::QueryRemaining { id },
+            "QueryRemaining",
+        )
     }
 
-    /// Send a ping message to the audio thread.
-    pub fn ping(&mut self) -> PyResult<()> {
+    /// Ask the audio thread how many voices run and which sample slots hold a sample.
+    ///
+    /// The answer arrives as an `AudioMessage.StateSnapshot` through `receive_msg()`; its
+    /// `loaded_slots()` expands the slot bitmask to one bool per slot.
+    pub fn query_state(&mut self) -> PyResult<()> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
-        let mut producer_guard = handle
-            .producer
-            .lock()
-            .map_err(|_| PyRuntimeError::new_err("Failed to acquire producer lock"))?;
+        let mut producer_guard = lock_producer(&handle.producer)?;
 
-        producer_guard
-            .push(ControlMessage::Ping())
-            .map_err(|_| PyRuntimeError::new_err("Failed to send Ping - buffer may be full"))
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::QueryState(),
+            "QueryState",
+        )
     }
 
     /// Receive a message from the audio thread.
+    ///
+    /// One-shot messages such as acks, faults and query answers are returned before queued
+    /// meter and playhead updates, which the audio thread drops first when Python falls behind.
     pub fn receive_msg(&mut self) -> PyResult<Option<AudioMessage>> {
         let handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
 
+        let stream_errors = handle.take_stream_error_count();
+        if stream_errors > 0 {
+            log::error!("Audio stream reported {stream_errors} error(s)");
+        }
+
         let mut consumer_guard = handle
             .consumer
             .lock()
             .map_err(|_| PyRuntimeError::new_err("Failed to acquire consumer lock"))?;
 
-        match consumer_guard.pop() {
-            Ok(msg) => Ok(Some(msg)),
-            Err(_) => Ok(None),
-        }
+        Ok(consumer_guard.pop())
     }
 
     /// Get the waveform data for a loaded sample slot.
@@ -1853,6 +4065,210 @@ impl AudioEngine {
     }
 }
 
+impl AudioEngine {
+    fn playlist_loader(&self) -> PyResult<PlaylistLoader> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        Ok(PlaylistLoader {
+            playlists: self.pad_playlists.clone(),
+            producer: handle.producer.clone(),
+            sample_cache: self.sample_cache.clone(),
+            channels: mix_channels(handle.output_channels),
+            sample_rate: handle.output_sample_rate,
+            decode_options: self.decode_options,
+            loader_tx: self.loader_tx.clone(),
+        })
+    }
+
+    fn step_playlist(&mut self, id: usize, forward: bool) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+        let loader = self.playlist_loader()?;
+
+        if loader.step(id, forward).map_err(PyRuntimeError::new_err)? {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err("slot has no playlist"))
+        }
+    }
+
+    fn drop_playlist(&self, id: usize) {
+        if let Ok(mut playlists) = self.pad_playlists.lock()
+            && let Some(playlist) = playlists.get_mut(id)
+        {
+            *playlist = None;
+        }
+    }
+
+    fn send_sequencer_running(&mut self, running: bool) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetSequencerRunning(running),
+            "SetSequencerRunning",
+        )
+    }
+
+    fn send_pad_rate_ratio(&mut self, id: usize, ratio: Option<(u16, u16)>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadRateRatio { id, ratio },
+            "SetPadRateRatio",
+        )
+    }
+
+    fn send_keyboard_mode(&mut self, bank: usize, mode: Option<(usize, usize)>) -> PyResult<()> {
+        if !is_valid_keyboard_mode(bank, mode) {
+            return Err(PyValueError::new_err(format!(
+                "keyboard mode needs bank < {NUM_BANKS}, source_slot < {NUM_SAMPLES} and \
+                 root_pad < {NUM_PADS}"
+            )));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetKeyboardMode { bank, mode },
+            "SetKeyboardMode",
+        )
+    }
+
+    fn send_pad_position(&mut self, id: usize, azimuth_degrees: Option<f32>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadPosition {
+                id,
+                azimuth_degrees,
+            },
+            "SetPadPosition",
+        )
+    }
+
+    fn send_velocity_attack(&mut self, attack: Option<VelocityAttack>) -> PyResult<()> {
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetVelocityAttack(attack),
+            "SetVelocityAttack",
+        )
+    }
+
+    fn send_pad_fades(&mut self, id: usize, fades: Option<PadFades>) -> PyResult<()> {
+        if id >= NUM_SAMPLES {
+            return Err(PyValueError::new_err("id out of range"));
+        }
+
+        let handle = self
+            .stream_handle
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("Audio engine not initialized"))?;
+
+        let mut producer_guard = lock_producer(&handle.producer)?;
+
+        push_control_message(
+            &mut producer_guard,
+            ControlMessage::SetPadFades { id, fades },
+            "SetPadFades",
+        )
+    }
+
+    fn start(
+        &mut self,
+        sample_rate: Option<u32>,
+        device: Option<OutputDeviceChoice>,
+        loop_mode: LoopMode,
+        buffer_capacity: usize,
+        frames: Option<u32>,
+    ) -> PyResult<()> {
+        if !(1..=CONTROL_BUFFER_CAPACITY_MAX).contains(&buffer_capacity) {
+            return Err(PyValueError::new_err(format!(
+                "buffer_capacity must be within 1..={CONTROL_BUFFER_CAPACITY_MAX}"
+            )));
+        }
+
+        if frames == Some(0) {
+            return Err(PyValueError::new_err("frames must be at least 1"));
+        }
+
+        if self.stream_handle.is_some() {
+            return Err(PyRuntimeError::new_err("AudioEngine already running"));
+        }
+
+        match create_audio_stream(sample_rate, device.as_ref(), buffer_capacity, frames) {
+            Ok(handle) => {
+                if loop_mode != LoopMode::Loop {
+                    // The queue is empty before the stream starts, so this cannot fail.
+                    let mut producer_guard = lock_producer(&handle.producer)?;
+                    push_control_message(
+                        &mut producer_guard,
+                        ControlMessage::SetDefaultLoopMode(loop_mode),
+                        "SetDefaultLoopMode",
+                    )?;
+                }
+                start_stream(&handle.stream).map_err(|e| {
+                    PyRuntimeError::new_err(format!("Failed to start audio stream: {e}"))
+                })?;
+                self.input_runtime = Some(InputRuntime::new(handle.producer.clone()));
+                self.stream_handle = Some(handle);
+                self.is_playing = true;
+                Ok(())
+            }
+            Err(e) => match e.downcast_ref::<DeviceError>() {
+                Some(DeviceError::DeviceNameNotFound { .. }) => {
+                    Err(PyValueError::new_err(e.to_string()))
+                }
+                _ => Err(PyRuntimeError::new_err(format!(
+                    "Failed to create audio stream: {e}"
+                ))),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1862,7 +4278,8 @@ mod tests {
     fn push_control_message_reports_full_queue() {
         Python::initialize();
 
-        let (mut producer, _consumer) = RingBuffer::new(1);
+        let (producer, _consumer) = RingBuffer::new(1);
+        let mut producer = ControlProducer::new(producer);
         producer.push(ControlMessage::Ping()).unwrap();
 
         let error = push_control_message(&mut producer, ControlMessage::StopAll(), "StopAll")
@@ -1890,18 +4307,160 @@ mod tests {
         assert!(error.to_string().contains("Failed to send SetSpeed"));
     }
 
+    #[test]
+    fn parameter_messages_queue_behind_waiting_control_messages() {
+        Python::initialize();
+
+        let (producer, mut consumer) = RingBuffer::new(4);
+        let producer = Mutex::new(ControlProducer::new(producer));
+        let (parameter_producer, mut parameter_consumer) = RingBuffer::new(4);
+        let parameter_producer = Mutex::new(parameter_producer);
+        let gain = ControlParameterMessage::SetPadGain {
+            id: 0,
+            gain_db: -6.0,
+        };
+
+        send_parameter_messages(&producer, &parameter_producer, &[gain], "SetPadGain").unwrap();
+        assert_eq!(parameter_consumer.pop(), Ok(gain));
+
+        // Behind a waiting trigger the gain takes the control queue, leaving a stamp set for
+        // the next control message alone.
+        {
+            let mut producer = producer.lock().unwrap();
+            producer
+                .push(ControlMessage::PlaySample { id: 0, volume: 1.0 })
+                .unwrap();
+            producer.stamp_next_message(480);
+        }
+        send_parameter_messages(&producer, &parameter_producer, &[gain], "SetPadGain").unwrap();
+        producer
+            .lock()
+            .unwrap()
+            .push(ControlMessage::StopSample { id: 0 })
+            .unwrap();
+        assert!(parameter_consumer.is_empty());
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::PlaySample { id: 0, .. })
+        ));
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::ApplyParameter(parameter)) if parameter == gain
+        ));
+        assert!(matches!(consumer.pop(), Ok(ControlMessage::AtFrame(480))));
+        assert!(matches!(
+            consumer.pop(),
+            Ok(ControlMessage::StopSample { id: 0 })
+        ));
+
+        let error =
+            send_parameter_messages(&producer, &parameter_producer, &[gain; 5], "SetPadGain")
+                .expect_err("more updates than free slots should fail");
+        assert!(error.to_string().contains("Failed to send SetPadGain"));
+        assert!(parameter_consumer.is_empty());
+    }
+
+    #[test]
+    fn lock_producer_preserves_single_thread_message_order() {
+        Python::initialize();
+
+        let (producer, mut consumer) = RingBuffer::new(NUM_SAMPLES);
+        let producer = Mutex::new(ControlProducer::new(producer));
+
+        for id in 0..NUM_SAMPLES {
+            let mut guard = lock_producer(&producer).unwrap();
+            push_control_message(&mut guard, ControlMessage::StopSample { id }, "StopSample")
+                .unwrap();
+        }
+
+        for expected in 0..NUM_SAMPLES {
+            match consumer.pop() {
+                Ok(ControlMessage::StopSample { id }) => assert_eq!(id, expected),
+                other => panic!("unexpected message {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn lock_producer_reports_queue_busy_instead_of_blocking() {
+        Python::initialize();
+
+        let (producer, _consumer) = RingBuffer::<ControlMessage>::new(1);
+        let producer = Arc::new(Mutex::new(producer));
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        let holder = {
+            let producer = producer.clone();
+            thread::spawn(move || {
+                let _guard = producer.lock().unwrap();
+                locked_tx.send(()).unwrap();
+                let _ = release_rx.recv();
+            })
+        };
+        locked_rx.recv().unwrap();
+
+        let error = lock_producer(&producer).expect_err("held lock should report busy");
+        Python::attach(|py| assert!(error.is_instance_of::<QueueBusy>(py)));
+
+        release_tx.send(()).unwrap();
+        holder.join().unwrap();
+        assert!(lock_producer(&producer).is_ok());
+    }
+
+    #[test]
+    fn auto_trim_gain_brings_peak_to_target_within_gain_range() {
+        let gain_db = auto_trim_gain_db(0.5, -6.0).unwrap();
+        assert!(
+            (gain_db - (-6.0 + 6.0206)).abs() < 1e-3,
+            "gain_db={gain_db}"
+        );
+
+        assert_eq!(auto_trim_gain_db(1.0e-6, -1.0), Some(PAD_GAIN_DB_MAX));
+        assert_eq!(
+            auto_trim_gain_db(1.0, PAD_GAIN_DB_MIN),
+            Some(PAD_GAIN_DB_MIN)
+        );
+        assert_eq!(auto_trim_gain_db(0.0, -6.0), None);
+        assert_eq!(auto_trim_gain_db(f32::NAN, -6.0), None);
+    }
+
+    #[test]
+    fn build_info_reports_crate_version_and_features() {
+        Python::initialize();
+
+        Python::attach(|py| {
+            let info = AudioEngine::build_info(py).unwrap();
+            let version: String = info
+                .get_item("version")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+            let features: Vec<String> = info
+                .get_item("features")
+                .unwrap()
+                .unwrap()
+                .extract()
+                .unwrap();
+
+            assert_eq!(version, env!("CARGO_PKG_VERSION"));
+            assert!(features.iter().any(|feature| feature == "key_lock"));
+        });
+    }
+
     #[test]
     fn publish_loaded_sample_rejects_full_queue_without_cache_insert() {
         let (mut producer, _consumer) = RingBuffer::new(1);
         producer.push(ControlMessage::Ping()).unwrap();
-        let producer = Arc::new(Mutex::new(producer));
+        let producer = Arc::new(Mutex::new(ControlProducer::new(producer)));
         let sample_cache = Arc::new(Mutex::new(vec![None; 1]));
         let sample = SampleBuffer {
             channels: 1,
             samples: Arc::from([0.0_f32, 0.0].as_slice()),
         };
 
-        let result = publish_loaded_sample(&producer, &sample_cache, 0, sample);
+        let result = publish_loaded_sample(&producer, &sample_cache, 0, sample, None);
 
         assert_eq!(
             result.expect_err("full command queue should reject publication"),
@@ -1924,6 +4483,160 @@ mod tests {
         assert!(pad_request_matches(&ids, 1, 0));
     }
 
+    #[test]
+    fn late_analysis_of_replaced_sample_is_ignored() {
+        Python::initialize();
+
+        let engine = AudioEngine::new().unwrap();
+        let analysis = |bpm: f32| SampleAnalysis {
+            bpm,
+            key: "C".to_string(),
+            beat_grid: stratum_dsp::BeatGrid {
+                downbeats: Vec::new(),
+                beats: Vec::new(),
+                bars: Vec::new(),
+            },
+            bpm_confidence: 1.0,
+            key_confidence: 1.0,
+            grid_stability: 1.0,
+            onsets_s: Vec::new(),
+        };
+
+        let load_a = next_pad_request_id(&engine.pad_request_ids, 0).unwrap();
+        let analysis_a = current_pad_request_id(&engine.pad_request_ids, 0).unwrap();
+        assert_eq!(analysis_a, load_a);
+        let load_b = next_pad_request_id(&engine.pad_request_ids, 0).unwrap();
+
+        for (request_id, bpm) in [(analysis_a, 90.0), (load_b, 120.0)] {
+            engine
+                .loader_tx
+                .send(LoaderEvent::TaskSuccess {
+                    id: 0,
+                    request_id,
+                    task: BackgroundTaskKind::Analysis,
+                    analysis: Some(analysis(bpm)),
+                })
+                .unwrap();
+        }
+
+        Python::attach(|py| {
+            let event = engine.poll_loader_events(py).unwrap().unwrap();
+            let event = event.bind(py);
+            let request_id: u64 = event.get_item("request_id").unwrap().extract().unwrap();
+            let bpm: f32 = event
+                .get_item("analysis")
+                .unwrap()
+                .get_item("bpm")
+                .unwrap()
+                .extract()
+                .unwrap();
+
+            assert_eq!(request_id, load_b);
+            assert_eq!(bpm, 120.0);
+            assert!(engine.poll_loader_events(py).unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn out_of_range_buffer_sizes_are_rejected_before_opening_a_device() {
+        Python::initialize();
+
+        let mut engine = AudioEngine::new().unwrap();
+        for buffer_capacity in [0, CONTROL_BUFFER_CAPACITY_MAX + 1] {
+            let error = engine
+                .run(None, "looper", buffer_capacity, None)
+                .unwrap_err();
+            Python::attach(|py| assert!(error.is_instance_of::<PyValueError>(py)));
+        }
+
+        let error = engine
+            .run(None, "looper", CONTROL_BUFFER_CAPACITY_DEFAULT, Some(0))
+            .unwrap_err();
+        Python::attach(|py| assert!(error.is_instance_of::<PyValueError>(py)));
+        assert!(engine.stream_handle.is_none());
+    }
+
+    #[test]
+    fn load_sample_from_bytes_holds_the_pad_while_loading() {
+        Python::initialize();
+
+        let engine = AudioEngine::new().unwrap();
+        Python::attach(|py| {
+            let data = PyBytes::new(py, &[]);
+            engine.loading_sample_ids.lock().unwrap().insert(3);
+            let error = engine
+                .load_sample_from_bytes(py, 3, &data, None)
+                .unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+            assert!(engine.loading_sample_ids.lock().unwrap().contains(&3));
+
+            engine.loading_sample_ids.lock().unwrap().clear();
+            let guard = PadLoadingGuard::acquire(&engine.loading_sample_ids, 3).unwrap();
+            assert!(PadLoadingGuard::acquire(&engine.loading_sample_ids, 3).is_err());
+            drop(guard);
+
+            // Without a running engine the load fails after claiming the pad, and releases it.
+            let error = engine
+                .load_sample_from_bytes(py, 3, &data, None)
+                .unwrap_err();
+            assert!(error.is_instance_of::<PyRuntimeError>(py));
+            assert!(engine.loading_sample_ids.lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn play_sample_takes_loop_mode_as_the_loop_override() {
+        Python::initialize();
+
+        let mut engine = AudioEngine::new().unwrap();
+        let error = engine
+            .play_sample(0, 1.0, None, Some(true), None, None, Some(false))
+            .unwrap_err();
+        Python::attach(|py| assert!(error.is_instance_of::<PyValueError>(py)));
+
+        // Agreeing or lone overrides only fail for want of a running engine.
+        for (r#loop, loop_mode) in [(Some(false), Some(false)), (None, Some(false))] {
+            let error = engine
+                .play_sample(0, 1.0, None, r#loop, None, None, loop_mode)
+                .unwrap_err();
+            Python::attach(|py| assert!(error.is_instance_of::<PyRuntimeError>(py)));
+        }
+    }
+
+    #[test]
+    fn monitor_pairs_overlapping_the_main_mix_are_rejected() {
+        Python::initialize();
+
+        let mut engine = AudioEngine::new().unwrap();
+        for first_channel in [0, 1, usize::from(MAX_OUTPUT_CHANNELS) - 1] {
+            let error = engine
+                .set_monitor_output("cue", Some(first_channel))
+                .unwrap_err();
+            Python::attach(|py| assert!(error.is_instance_of::<PyValueError>(py)));
+        }
+
+        // Valid pairs only fail for want of a running engine.
+        let error = engine.set_monitor_output("preview", Some(2)).unwrap_err();
+        Python::attach(|py| assert!(error.is_instance_of::<PyRuntimeError>(py)));
+    }
+
+    #[test]
+    fn output_config_getters_need_a_running_engine() {
+        Python::initialize();
+
+        let engine = AudioEngine::new().unwrap();
+        let errors = [
+            engine.output_sample_rate().unwrap_err(),
+            engine.output_channels().unwrap_err(),
+        ];
+
+        Python::attach(|py| {
+            for error in errors {
+                assert!(error.is_instance_of::<PyRuntimeError>(py));
+            }
+        });
+    }
+
     #[test]
     fn pad_request_ids_do_not_wrap_to_zero() {
         let ids = Arc::new(Mutex::new(vec![u64::MAX]));
@@ -0,0 +1,335 @@
+//! Per-pad playlists: several files queued on one sample slot.
+//!
+//! Entries decode and are analyzed on a loader thread, one ahead of the active entry, so
+//! stepping through a playlist usually only publishes a ready buffer. The mixer swaps it in at
+//! the slot's next loop boundary and reports the entry with
+//! `AudioMessage::PlaylistEntryActive`.
+
+use crate::audio_engine::analysis::analyze_sample;
+use crate::audio_engine::deferred_control::ControlProducer;
+use crate::audio_engine::sample_loader::{SampleDecodeOptions, decode_audio_file_to_sample_buffer};
+use crate::messages::{ControlMessage, LoaderEvent, SampleAnalysis, SampleBuffer};
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Playlists of all slots, shared with the loader threads.
+pub(crate) type PadPlaylists = Arc<Mutex<Vec<Option<PadPlaylist>>>>;
+
+/// Files queued on one slot and the entry last published to it.
+#[derive(Debug)]
+pub(crate) struct PadPlaylist {
+    paths: Vec<String>,
+    position: usize,
+    /// Bumped on every step, so loads started for an earlier step are dropped.
+    generation: u64,
+    preloaded: Option<PreloadedEntry>,
+}
+
+/// A decoded playlist entry with its analysis, if that succeeded.
+#[derive(Debug)]
+struct DecodedEntry {
+    entry: usize,
+    sample: SampleBuffer,
+    analysis: Option<SampleAnalysis>,
+}
+
+/// Decoded entry following the position in one direction.
+#[derive(Debug)]
+struct PreloadedEntry {
+    forward: bool,
+    decoded: DecodedEntry,
+}
+
+impl PadPlaylist {
+    /// Returns `None` for an empty playlist or a position past its end.
+    pub(crate) fn new(paths: Vec<String>, position: usize) -> Option<Self> {
+        (position < paths.len()).then_some(Self {
+            paths,
+            position,
+            generation: 0,
+            preloaded: None,
+        })
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Entry next to `entry`, wrapping around at either end.
+    fn neighbour(&self, entry: usize, forward: bool) -> usize {
+        let len = self.paths.len();
+        if forward {
+            (entry + 1) % len
+        } else {
+            (entry + len - 1) % len
+        }
+    }
+
+    /// Starts a step and returns its generation, the entry to activate and the preloaded
+    /// entry, if the loader already has one.
+    fn begin_step(&mut self, forward: bool) -> (u64, usize, Option<DecodedEntry>) {
+        self.generation = self.generation.wrapping_add(1);
+        let preloaded = self
+            .preloaded
+            .take()
+            .filter(|preloaded| preloaded.forward == forward)
+            .map(|preloaded| preloaded.decoded);
+        (
+            self.generation,
+            self.neighbour(self.position, forward),
+            preloaded,
+        )
+    }
+}
+
+/// Everything a loader thread needs to decode and publish playlist entries.
+#[derive(Clone)]
+pub(crate) struct PlaylistLoader {
+    pub playlists: PadPlaylists,
+    pub producer: Arc<Mutex<ControlProducer>>,
+    pub sample_cache: Arc<Mutex<Vec<Option<SampleBuffer>>>>,
+    pub channels: usize,
+    pub sample_rate: u32,
+    pub decode_options: SampleDecodeOptions,
+    pub loader_tx: Sender<LoaderEvent>,
+}
+
+impl PlaylistLoader {
+    /// Activates the slot's current entry, for a playlist that was just set.
+    pub(crate) fn start(self, id: usize) {
+        let Some((generation, position)) = self.with_playlist(id, |playlist| {
+            playlist.generation = playlist.generation.wrapping_add(1);
+            (playlist.generation, playlist.position)
+        }) else {
+            return;
+        };
+        self.spawn_activate(id, generation, position, true);
+    }
+
+    /// Steps the slot's playlist one entry forward or back.
+    ///
+    /// Publishes the preloaded entry right away if there is one, and otherwise decodes it on
+    /// a loader thread first. Returns false if the slot has no playlist.
+    pub(crate) fn step(self, id: usize, forward: bool) -> Result<bool, String> {
+        let Some((generation, target, preloaded)) =
+            self.with_playlist(id, |playlist| playlist.begin_step(forward))
+        else {
+            return Ok(false);
+        };
+
+        match preloaded {
+            Some(decoded) => {
+                let entry = decoded.entry;
+                if self.publish(id, generation, decoded)? {
+                    self.spawn_preload(id, generation, entry, forward);
+                }
+            }
+            None => self.spawn_activate(id, generation, target, forward),
+        }
+        Ok(true)
+    }
+
+    fn spawn_activate(self, id: usize, generation: u64, entry: usize, forward: bool) {
+        thread::spawn(move || {
+            let Some(decoded) = self.decode_first_playable(id, generation, entry, forward) else {
+                return;
+            };
+            let entry = decoded.entry;
+            match self.publish(id, generation, decoded) {
+                Ok(true) => self.preload(id, generation, entry, forward),
+                Ok(false) => {}
+                Err(error) => log::warn!("Playlist entry {entry} of pad {id}: {error}"),
+            }
+        });
+    }
+
+    fn spawn_preload(self, id: usize, generation: u64, after: usize, forward: bool) {
+        thread::spawn(move || self.preload(id, generation, after, forward));
+    }
+
+    /// Decodes the first playable entry after `after` and keeps it for the next step.
+    fn preload(&self, id: usize, generation: u64, after: usize, forward: bool) {
+        let Some(start) = self.with_playlist(id, |playlist| playlist.neighbour(after, forward))
+        else {
+            return;
+        };
+        let Some(decoded) = self.decode_first_playable(id, generation, start, forward) else {
+            return;
+        };
+        self.with_playlist(id, |playlist| {
+            if playlist.generation == generation {
+                playlist.preloaded = Some(PreloadedEntry { forward, decoded });
+            }
+        });
+    }
+
+    /// Decodes entries from `start` on in the given direction until one loads, and analyzes it.
+    ///
+    /// Entries that fail are skipped with a warning; an entry whose analysis fails still
+    /// plays, without it. Gives up after one pass over the playlist, or as soon as a newer
+    /// step makes the result useless.
+    fn decode_first_playable(
+        &self,
+        id: usize,
+        generation: u64,
+        start: usize,
+        forward: bool,
+    ) -> Option<DecodedEntry> {
+        let mut entry = start;
+        loop {
+            let (path, next) = self.with_playlist(id, |playlist| {
+                (playlist.generation == generation).then(|| {
+                    (
+                        playlist.paths[entry].clone(),
+                        playlist.neighbour(entry, forward),
+                    )
+                })
+            })??;
+
+            match decode_audio_file_to_sample_buffer(
+                Path::new(&path),
+                self.channels,
+                self.sample_rate,
+                self.decode_options,
+                |_| {},
+            ) {
+                Ok(sample) => {
+                    let analysis = analyze_sample(&sample, self.sample_rate)
+                        .inspect_err(|err| {
+                            log::warn!("Analysis of playlist entry {entry} of pad {id}: {err}")
+                        })
+                        .ok();
+                    return Some(DecodedEntry {
+                        entry,
+                        sample,
+                        analysis,
+                    });
+                }
+                Err(err) => {
+                    log::warn!("Skipping playlist entry {entry} of pad {id} ({path}): {err}")
+                }
+            }
+
+            if next == start {
+                log::warn!("No entry of the playlist of pad {id} could be loaded");
+                return None;
+            }
+            entry = next;
+        }
+    }
+
+    /// Queues the decoded sample as the slot's next sample, moves the playlist position to its
+    /// entry and reports the entry's duration and analysis to Python.
+    ///
+    /// Returns false without publishing if a newer step or playlist took over meanwhile.
+    fn publish(&self, id: usize, generation: u64, decoded: DecodedEntry) -> Result<bool, String> {
+        let DecodedEntry {
+            entry,
+            sample,
+            analysis,
+        } = decoded;
+        let mut playlists = self
+            .playlists
+            .lock()
+            .map_err(|_| "Failed to acquire playlist lock".to_string())?;
+        let Some(playlist) = playlists
+            .get_mut(id)
+            .and_then(Option::as_mut)
+            .filter(|playlist| playlist.generation == generation)
+        else {
+            return Ok(false);
+        };
+
+        self.producer
+            .lock()
+            .map_err(|_| "Failed to acquire producer lock".to_string())?
+            .push(ControlMessage::QueueSampleSwap {
+                id,
+                entry,
+                sample: sample.clone(),
+                bpm: analysis.as_ref().map(|analysis| analysis.bpm),
+            })
+            .map_err(|_| "Failed to send QueueSampleSwap - buffer may be full".to_string())?;
+        playlist.position = entry;
+
+        let frames = sample.samples.len() / sample.channels.max(1);
+        let _ = self.loader_tx.send(LoaderEvent::PlaylistEntryLoaded {
+            id,
+            entry,
+            duration_s: frames as f32 / self.sample_rate as f32,
+            analysis,
+        });
+
+        if let Ok(mut cache) = self.sample_cache.lock()
+            && let Some(slot) = cache.get_mut(id)
+        {
+            *slot = Some(sample);
+        }
+        Ok(true)
+    }
+
+    fn with_playlist<T>(&self, id: usize, f: impl FnOnce(&mut PadPlaylist) -> T) -> Option<T> {
+        let mut playlists = self.playlists.lock().ok()?;
+        playlists.get_mut(id)?.as_mut().map(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(len: usize, position: usize) -> PadPlaylist {
+        let paths = (0..len).map(|entry| format!("{entry}.wav")).collect();
+        PadPlaylist::new(paths, position).unwrap()
+    }
+
+    fn decoded(entry: usize) -> DecodedEntry {
+        DecodedEntry {
+            entry,
+            sample: SampleBuffer {
+                channels: 1,
+                samples: Arc::from([0.0_f32].as_slice()),
+            },
+            analysis: None,
+        }
+    }
+
+    #[test]
+    fn playlists_need_an_entry_at_the_position() {
+        assert!(PadPlaylist::new(Vec::new(), 0).is_none());
+        assert!(PadPlaylist::new(vec!["a.wav".to_string()], 1).is_none());
+        assert_eq!(playlist(3, 2).position(), 2);
+    }
+
+    #[test]
+    fn steps_wrap_around_both_ends() {
+        let playlist = playlist(3, 0);
+        assert_eq!(playlist.neighbour(2, true), 0);
+        assert_eq!(playlist.neighbour(0, false), 2);
+        assert_eq!(playlist.neighbour(1, true), 2);
+    }
+
+    #[test]
+    fn a_step_uses_only_the_entry_preloaded_in_its_direction() {
+        let mut playlist = playlist(4, 1);
+        playlist.preloaded = Some(PreloadedEntry {
+            forward: true,
+            decoded: decoded(3),
+        });
+
+        let (generation, target, preloaded) = playlist.begin_step(false);
+        assert_eq!((generation, target), (1, 0));
+        assert!(preloaded.is_none());
+        assert!(playlist.preloaded.is_none());
+
+        playlist.preloaded = Some(PreloadedEntry {
+            forward: true,
+            decoded: decoded(3),
+        });
+        let (generation, target, preloaded) = playlist.begin_step(true);
+        assert_eq!((generation, target), (2, 2));
+        assert_eq!(preloaded.map(|decoded| decoded.entry), Some(3));
+    }
+}
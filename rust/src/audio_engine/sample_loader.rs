@@ -5,19 +5,29 @@
 
 use audioadapter::AdapterMut;
 use audioadapter_buffers::owned::InterleavedOwned;
-use rubato::{Fft, FixedSync, Indexing, Resampler};
+use rubato::{
+    Async, Fft, FixedAsync, FixedSync, Indexing, Resampler, SincInterpolationParameters,
+    SincInterpolationType, WindowFunction, calculate_cutoff,
+};
 use std::fs;
 use std::fs::File;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::audio_engine::channels::map_channels;
+use crate::audio_engine::channels::{map_channels, map_channels_into, widen_mono_upmix};
+use crate::audio_engine::constants::{MAX_SAMPLE_FRAMES, MONO_WIDENING_MS_MAX};
 use crate::audio_engine::errors::SampleLoadError;
+use crate::audio_engine::silence_trim::{SilenceTrim, trim_silence};
 use crate::messages::SampleBuffer;
 use symphonia::core::{
-    audio::SampleBuffer as SymphoniaSampleBuffer, codecs::DecoderOptions,
-    errors::Error as SymphoniaError, formats::FormatOptions, io::MediaSourceStream,
-    meta::MetadataOptions, probe::Hint,
+    audio::SampleBuffer as SymphoniaSampleBuffer,
+    codecs::DecoderOptions,
+    errors::Error as SymphoniaError,
+    formats::FormatOptions,
+    io::{MediaSource, MediaSourceStream},
+    meta::MetadataOptions,
+    probe::Hint,
 };
 use symphonia::default::{get_codecs, get_probe};
 
@@ -36,6 +46,79 @@ pub struct SampleLoadProgress {
     pub percent: f32,
 }
 
+/// Quality preset for converting a file to the output sample rate at load time.
+///
+/// The filter delay is trimmed from the result, so no preset adds playback latency; they only
+/// trade load time and memory against how cleanly content near Nyquist is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResamplerQuality {
+    /// Short 32-tap Hann sinc with linear interpolation between 64 oversampled points. Lowest
+    /// CPU cost, for quick session restores; audibly aliases and rolls off the top octave.
+    Fast,
+    /// Fixed-ratio FFT resampler. Fast for the common 44.1/48 kHz ratios with low aliasing.
+    #[default]
+    Balanced,
+    /// 256-tap Blackman-Harris² sinc with cubic interpolation. Several times slower than
+    /// `Balanced`, with the lowest aliasing and the flattest passband.
+    High,
+}
+
+impl ResamplerQuality {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "fast" => Some(Self::Fast),
+            "balanced" => Some(Self::Balanced),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    fn sinc_parameters(self) -> Option<SincInterpolationParameters> {
+        let (sinc_len, oversampling_factor, interpolation, window) = match self {
+            Self::Fast => (32, 64, SincInterpolationType::Linear, WindowFunction::Hann),
+            Self::Balanced => return None,
+            Self::High => (
+                256,
+                256,
+                SincInterpolationType::Cubic,
+                WindowFunction::BlackmanHarris2,
+            ),
+        };
+
+        Some(SincInterpolationParameters {
+            sinc_len,
+            f_cutoff: calculate_cutoff(sinc_len, window),
+            oversampling_factor,
+            interpolation,
+            window,
+        })
+    }
+}
+
+/// Optional processing applied while decoding a file into a [`SampleBuffer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SampleDecodeOptions {
+    /// Comb delay in milliseconds for widening mono files upmixed to stereo; `None` keeps them
+    /// dead center. See [`widen_mono_upmix`] for the mono-compatibility trade-off.
+    pub mono_widening_ms: Option<f32>,
+    /// Sample-rate conversion preset used when the file rate differs from the output rate.
+    pub resampler_quality: ResamplerQuality,
+    /// Leading and trailing silence trim; `None` keeps the file whole.
+    pub silence_trim: Option<SilenceTrim>,
+}
+
+impl SampleDecodeOptions {
+    fn mono_widening_delay_frames(&self, sample_rate_hz: u32) -> usize {
+        match self.mono_widening_ms {
+            Some(ms) if ms.is_finite() && ms > 0.0 => {
+                let ms = ms.min(MONO_WIDENING_MS_MAX);
+                (f64::from(ms) * f64::from(sample_rate_hz) / 1000.0).round() as usize
+            }
+            _ => 0,
+        }
+    }
+}
+
 fn clamp_progress(percent: f32) -> f32 {
     if percent.is_finite() {
         percent.clamp(0.0, 1.0)
@@ -46,6 +129,17 @@ fn clamp_progress(percent: f32) -> f32 {
 
 const MAX_CONSECUTIVE_PACKET_DECODE_ERRORS: usize = 64;
 
+/// Upper bound for the frames of a single decoded packet; real codecs stay far below this.
+const MAX_PACKET_FRAMES: u64 = 1 << 20;
+
+/// File extensions decodable with the Symphonia features enabled in this build.
+///
+/// Keep in sync with the `symphonia` features in `Cargo.toml`.
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "aac", "aif", "aifc", "aiff", "caf", "flac", "m4a", "mka", "mkv", "mp1", "mp2", "mp3", "mp4",
+    "oga", "ogg", "wav", "webm",
+];
+
 fn append_decode_error_silence(
     decoded: &mut Vec<f32>,
     channels: Option<usize>,
@@ -110,6 +204,7 @@ fn update_decoded_stream_config(
 /// * `channels` - Number of channels in the audio data
 /// * `from_rate` - Current sample rate in Hz
 /// * `to_rate` - Target sample rate in Hz
+/// * `quality` - Resampler preset
 ///
 /// # Returns
 ///
@@ -120,6 +215,7 @@ fn resample_audio<F>(
     channels: usize,
     from_rate: u32,
     to_rate: u32,
+    quality: ResamplerQuality,
     mut progress: F,
 ) -> Result<Vec<f32>, SampleLoadError>
 where
@@ -136,15 +232,25 @@ where
         return Ok(Vec::new());
     }
 
-    // Create resampler with fixed I/O size.
-    let mut resampler = Fft::<f32>::new(
-        from_rate as usize,
-        to_rate as usize,
-        1024, // chunk_size
-        1,    // sub_chunks
-        channels,
-        FixedSync::Input,
-    )?;
+    // Create resampler with fixed input size.
+    let mut resampler: Box<dyn Resampler<f32>> = match quality.sinc_parameters() {
+        Some(parameters) => Box::new(Async::<f32>::new_sinc(
+            f64::from(to_rate) / f64::from(from_rate),
+            1.0,
+            &parameters,
+            1024, // chunk_size
+            channels,
+            FixedAsync::Input,
+        )?),
+        None => Box::new(Fft::<f32>::new(
+            from_rate as usize,
+            to_rate as usize,
+            1024, // chunk_size
+            1,    // sub_chunks
+            channels,
+            FixedSync::Input,
+        )?),
+    };
 
     // Create input buffer adapter.
     let input_buffer = InterleavedOwned::new_from(samples, channels, input_frames).unwrap();
@@ -238,6 +344,7 @@ where
 /// - `path`: Path to the audio file to load
 /// - `output_channels`: Number of output channels (1 for mono, 2 for stereo)
 /// - `output_rate_hz`: Output sample rate in Hz
+/// - `options`: Optional processing, such as mono widening
 /// - `progress`: Progress callback
 ///
 /// # Returns
@@ -253,20 +360,100 @@ where
 /// - Unsupported channel count
 /// - Resampling errors
 /// - Invalid or corrupt audio data
+/// - Audio longer than `MAX_SAMPLE_FRAMES`
 pub fn decode_audio_file_to_sample_buffer<F>(
     path: &Path,
     output_channels: usize,
     output_rate_hz: u32,
-    mut progress: F,
+    options: SampleDecodeOptions,
+    progress: F,
+) -> Result<SampleBuffer, SampleLoadError>
+where
+    F: FnMut(SampleLoadProgress),
+{
+    decode_audio_file_with_frame_limit(
+        path,
+        output_channels,
+        output_rate_hz,
+        MAX_SAMPLE_FRAMES,
+        options,
+        progress,
+    )
+}
+
+/// Decodes in-memory audio, such as a downloaded file, like
+/// [`decode_audio_file_to_sample_buffer`] without touching the filesystem.
+///
+/// `hint_extension` (e.g. `"wav"`) helps Symphonia pick the demuxer; formats are still
+/// probed from the data without it.
+pub fn decode_audio_bytes_to_sample_buffer<F>(
+    data: &[u8],
+    hint_extension: Option<&str>,
+    output_channels: usize,
+    output_rate_hz: u32,
+    options: SampleDecodeOptions,
+    progress: F,
+) -> Result<SampleBuffer, SampleLoadError>
+where
+    F: FnMut(SampleLoadProgress),
+{
+    // Symphonia needs an owned source.
+    decode_media_source_with_frame_limit(
+        Box::new(Cursor::new(data.to_vec())),
+        hint_extension,
+        output_channels,
+        output_rate_hz,
+        MAX_SAMPLE_FRAMES,
+        options,
+        progress,
+    )
+}
+
+/// Like [`decode_audio_file_to_sample_buffer`], failing with `TooLarge` once more than
+/// `max_frames` source frames have been decoded.
+fn decode_audio_file_with_frame_limit<F>(
+    path: &Path,
+    output_channels: usize,
+    output_rate_hz: u32,
+    max_frames: u64,
+    options: SampleDecodeOptions,
+    progress: F,
 ) -> Result<SampleBuffer, SampleLoadError>
 where
     F: FnMut(SampleLoadProgress),
 {
     let file = File::open(path)?;
-    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    decode_media_source_with_frame_limit(
+        Box::new(file),
+        path.extension().and_then(|e| e.to_str()),
+        output_channels,
+        output_rate_hz,
+        max_frames,
+        options,
+        progress,
+    )
+}
+
+/// Decodes a file or in-memory source, failing with `TooLarge` after `max_frames` frames.
+///
+/// The container frame count is only used to size the initial reservation, so files with a
+/// wrong length header are bounded by what actually decodes.
+fn decode_media_source_with_frame_limit<F>(
+    source: Box<dyn MediaSource>,
+    hint_extension: Option<&str>,
+    output_channels: usize,
+    output_rate_hz: u32,
+    max_frames: u64,
+    options: SampleDecodeOptions,
+    mut progress: F,
+) -> Result<SampleBuffer, SampleLoadError>
+where
+    F: FnMut(SampleLoadProgress),
+{
+    let mss = MediaSourceStream::new(source, Default::default());
 
     let mut hint = Hint::new();
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+    if let Some(ext) = hint_extension {
         hint.with_extension(ext);
     }
 
@@ -293,6 +480,9 @@ where
 
     let mut file_rate_hz: Option<u32> = None;
     let mut file_channels: Option<usize> = None;
+    // Channel layout of `decoded`: the output layout when packets are mapped while decoding
+    // (no resampling needed), otherwise the file layout.
+    let mut decoded_channels: Option<usize> = None;
     let mut decoded_frames: u64 = 0;
     let mut consecutive_packet_decode_errors: usize = 0;
     progress(SampleLoadProgress {
@@ -302,6 +492,21 @@ where
     });
 
     let mut decoded: Vec<f32> = Vec::new();
+    let expected_channels = if initial_resampling_required {
+        codec_params.channels.map(|channels| channels.count())
+    } else {
+        Some(output_channels)
+    };
+    if let Some(expected_samples) = total_frames
+        .map(|frames| frames.min(max_frames))
+        .and_then(|frames| usize::try_from(frames).ok())
+        .zip(expected_channels)
+        .and_then(|(frames, channels)| frames.checked_mul(channels))
+    {
+        // Container frame counts are only a hint; a failed reservation just falls back to growth.
+        let _ = decoded.try_reserve_exact(expected_samples);
+    }
+    let mut sample_buf: Option<SymphoniaSampleBuffer<f32>> = None;
     loop {
         let packet = match format.next_packet() {
             Ok(packet) => packet,
@@ -330,8 +535,11 @@ where
         let audio_buf = match decoder.decode(&packet) {
             Ok(audio_buf) => audio_buf,
             Err(SymphoniaError::DecodeError(_)) => {
+                if packet.dur > max_frames.saturating_sub(decoded_frames) {
+                    return Err(SampleLoadError::TooLarge { max_frames });
+                }
                 if let Some(silence_frames) =
-                    append_decode_error_silence(&mut decoded, file_channels, packet.dur)
+                    append_decode_error_silence(&mut decoded, decoded_channels, packet.dur)
                 {
                     decoded_frames = decoded_frames.saturating_add(silence_frames);
                     if let Some(total_frames) = total_frames {
@@ -348,8 +556,12 @@ where
             Err(err) => return Err(SampleLoadError::Decode(err)),
         };
         let spec = *audio_buf.spec();
-        let duration = audio_buf.capacity() as u64;
         let packet_frames = audio_buf.frames() as u64;
+        if packet_frames > MAX_PACKET_FRAMES
+            || packet_frames > max_frames.saturating_sub(decoded_frames)
+        {
+            return Err(SampleLoadError::TooLarge { max_frames });
+        }
         update_decoded_stream_config(
             &mut file_rate_hz,
             &mut file_channels,
@@ -357,9 +569,26 @@ where
             spec.channels.count(),
         )?;
 
-        let mut sample_buf = SymphoniaSampleBuffer::<f32>::new(duration, spec);
+        let spec_channels = spec.channels.count();
+        let target_channels = *decoded_channels.get_or_insert(if spec.rate == output_rate_hz {
+            output_channels
+        } else {
+            spec_channels
+        });
+
+        // Size the scratch buffer by decoded frames, not the decoder-reported capacity.
+        let packet_samples = audio_buf.frames() * spec_channels;
+        let sample_buf = match &mut sample_buf {
+            Some(sample_buf) if sample_buf.capacity() >= packet_samples => sample_buf,
+            sample_buf => sample_buf.insert(SymphoniaSampleBuffer::<f32>::new(packet_frames, spec)),
+        };
         sample_buf.copy_interleaved_ref(audio_buf);
-        decoded.extend_from_slice(sample_buf.samples());
+        map_channels_into(
+            sample_buf.samples(),
+            spec_channels,
+            target_channels,
+            &mut decoded,
+        )?;
 
         decoded_frames = decoded_frames.saturating_add(packet_frames);
         if let Some(total_frames) = total_frames {
@@ -377,7 +606,7 @@ where
     }
 
     let file_rate_hz = file_rate_hz.ok_or(SampleLoadError::MissingSampleRate)?;
-    let file_channels = file_channels.ok_or(SampleLoadError::MissingChannels)?;
+    let decoded_channels = decoded_channels.ok_or(SampleLoadError::MissingChannels)?;
     let resampling_required = file_rate_hz != output_rate_hz;
 
     progress(SampleLoadProgress {
@@ -396,9 +625,10 @@ where
         });
         resample_audio(
             decoded,
-            file_channels,
+            decoded_channels,
             file_rate_hz,
             output_rate_hz,
+            options.resampler_quality,
             |percent| {
                 progress(SampleLoadProgress {
                     subtask: SampleLoadSubtask::Resampling,
@@ -414,7 +644,16 @@ where
         resampling_required,
         percent: 0.0,
     });
-    let mapped = map_channels(resampled, file_channels, output_channels)?;
+    let mut mapped = map_channels(resampled, decoded_channels, output_channels)?;
+    if file_channels == Some(1) && output_channels == 2 {
+        widen_mono_upmix(
+            &mut mapped,
+            options.mono_widening_delay_frames(output_rate_hz),
+        );
+    }
+    if let Some(mode) = options.silence_trim {
+        trim_silence(&mut mapped, output_channels, output_rate_hz, mode);
+    }
     progress(SampleLoadProgress {
         subtask: SampleLoadSubtask::ChannelMapping,
         resampling_required,
@@ -423,7 +662,9 @@ where
 
     Ok(SampleBuffer {
         channels: output_channels,
-        samples: Arc::from(mapped.into_boxed_slice()),
+        // `From<Vec<_>>` copies once into the shared allocation; going through
+        // `into_boxed_slice` first could add a shrinking reallocation.
+        samples: Arc::from(mapped),
     })
 }
 
@@ -538,6 +779,7 @@ mod tests {
     use std::io::Write;
 
     use super::*;
+    use crate::audio_engine::alloc_tracking::track_peak_allocation;
 
     /// Helper function to create a PCM16 WAV file for testing.
     fn write_pcm16_wav(
@@ -812,12 +1054,63 @@ mod tests {
         let samples = [0i16, 16_384i16, -16_384i16, 32_767i16];
         write_pcm16_wav(&path, 1, 44_100, &samples).unwrap();
 
-        let decoded = decode_audio_file_to_sample_buffer(&path, 1, 44_100, |_| {}).unwrap();
+        let decoded = decode_audio_file_to_sample_buffer(
+            &path,
+            1,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
         assert_eq!(decoded.channels, 1);
         assert_eq!(decoded.samples.len(), samples.len());
         assert!(decoded.samples.iter().all(|s| (-1.0..=1.0).contains(s)));
     }
 
+    #[test]
+    fn test_decode_bytes_matches_file_decode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("test.wav");
+
+        let samples = [0i16, 16_384i16, -16_384i16, 32_767i16, -32_768i16];
+        write_pcm16_wav(&path, 1, 22_050, &samples).unwrap();
+        let data = fs::read(&path).unwrap();
+
+        let from_file = decode_audio_file_to_sample_buffer(
+            &path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        for hint_extension in [Some("wav"), None] {
+            let from_bytes = decode_audio_bytes_to_sample_buffer(
+                &data,
+                hint_extension,
+                2,
+                44_100,
+                SampleDecodeOptions::default(),
+                |_| {},
+            )
+            .unwrap();
+            assert_eq!(from_bytes.channels, from_file.channels);
+            assert_eq!(from_bytes.samples[..], from_file.samples[..]);
+        }
+
+        assert!(
+            decode_audio_bytes_to_sample_buffer(
+                b"not audio",
+                Some("wav"),
+                2,
+                44_100,
+                SampleDecodeOptions::default(),
+                |_| {},
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_decode_channel_mapping_mono_to_stereo() {
         let tmp = tempfile::tempdir().unwrap();
@@ -826,7 +1119,14 @@ mod tests {
         let samples = [0i16, 16_384i16, -16_384i16];
         write_pcm16_wav(&path, 1, 44_100, &samples).unwrap();
 
-        let decoded = decode_audio_file_to_sample_buffer(&path, 2, 44_100, |_| {}).unwrap();
+        let decoded = decode_audio_file_to_sample_buffer(
+            &path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
         assert_eq!(decoded.channels, 2);
         assert_eq!(decoded.samples.len(), samples.len() * 2);
 
@@ -845,11 +1145,48 @@ mod tests {
         write_pcm16_wav(&path, 1, 44_100, &samples).unwrap();
 
         // Decode at same sample rate (no resampling needed)
-        let decoded = decode_audio_file_to_sample_buffer(&path, 1, 44_100, |_| {}).unwrap();
+        let decoded = decode_audio_file_to_sample_buffer(
+            &path,
+            1,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
         assert_eq!(decoded.channels, 1);
         assert_eq!(decoded.samples.len(), samples.len());
     }
 
+    #[test]
+    fn test_high_quality_resampler_aliases_less_than_fast() {
+        // A sweep entirely above the 16 kHz output Nyquist should vanish; what remains is
+        // aliasing.
+        let from_rate = 48_000_u32;
+        let frames = 48_000;
+        let sweep: Vec<f32> = (0..frames)
+            .map(|frame| {
+                let t = frame as f64 / f64::from(from_rate);
+                let duration = frames as f64 / f64::from(from_rate);
+                let (start_hz, end_hz) = (17_000.0, 23_000.0);
+                let phase = 2.0
+                    * std::f64::consts::PI
+                    * (start_hz * t + (end_hz - start_hz) * t * t / (2.0 * duration));
+                (0.5 * phase.sin()) as f32
+            })
+            .collect();
+        let alias_rms = |quality| {
+            let output =
+                resample_audio(sweep.clone(), 1, from_rate, 32_000, quality, |_| {}).unwrap();
+            let body = &output[1_000..output.len() - 1_000];
+            (body.iter().map(|sample| sample * sample).sum::<f32>() / body.len() as f32).sqrt()
+        };
+
+        let fast = alias_rms(ResamplerQuality::Fast);
+        let high = alias_rms(ResamplerQuality::High);
+
+        assert!(high < fast * 0.1, "high {high}, fast {fast}");
+    }
+
     #[test]
     fn test_resample_48000_to_44100() {
         let tmp = tempfile::tempdir().unwrap();
@@ -862,7 +1199,14 @@ mod tests {
         write_pcm16_wav(&path, 1, 48_000, &samples).unwrap();
 
         // Decode at 44.1kHz (requires resampling)
-        let decoded = decode_audio_file_to_sample_buffer(&path, 1, 44_100, |_| {}).unwrap();
+        let decoded = decode_audio_file_to_sample_buffer(
+            &path,
+            1,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
         assert_eq!(decoded.channels, 1);
         // For 48kHz->44.1kHz, we expect fewer output samples (44100/48000 = 0.91875)
         // With 1024 input samples (1024 frames), we expect ~945.35 output frames = ~945 output samples
@@ -874,4 +1218,268 @@ mod tests {
         );
         assert!(decoded.samples.iter().all(|s| (-1.0..=1.0).contains(s)));
     }
+
+    #[test]
+    fn test_resample_44100_sine_to_48000_reports_resampling_progress() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("sine.wav");
+
+        let frames = 44_100;
+        let samples: Vec<i16> = (0..frames)
+            .map(|frame| {
+                let phase = std::f32::consts::TAU * 1_000.0 * frame as f32 / 44_100.0;
+                (phase.sin() * 16_384.0) as i16
+            })
+            .collect();
+        write_pcm16_wav(&path, 1, 44_100, &samples).unwrap();
+
+        let mut resampling_updates = 0;
+        let decoded = decode_audio_file_to_sample_buffer(
+            &path,
+            1,
+            48_000,
+            SampleDecodeOptions::default(),
+            |update| {
+                if update.subtask == SampleLoadSubtask::Resampling {
+                    assert!(update.resampling_required);
+                    resampling_updates += 1;
+                }
+            },
+        )
+        .unwrap();
+
+        let expected = frames as f64 * 48_000.0 / 44_100.0;
+        let ratio = decoded.samples.len() as f64 / expected;
+        assert!(
+            (ratio - 1.0).abs() < 0.01,
+            "{} frames",
+            decoded.samples.len()
+        );
+        assert!(resampling_updates > 0);
+    }
+
+    fn pcm16_test_signal(samples: usize) -> Vec<i16> {
+        (0..samples)
+            .map(|index| ((index * 7_919) % 65_536) as i32 - 32_768)
+            .map(|value| value as i16)
+            .collect()
+    }
+
+    #[test]
+    fn test_decode_channel_mapping_matches_mapping_after_decode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mono_path = tmp.path().join("mono.wav");
+        let stereo_path = tmp.path().join("stereo.wav");
+        write_pcm16_wav(&mono_path, 1, 44_100, &pcm16_test_signal(5_000)).unwrap();
+        write_pcm16_wav(&stereo_path, 2, 44_100, &pcm16_test_signal(10_000)).unwrap();
+
+        let mono = decode_audio_file_to_sample_buffer(
+            &mono_path,
+            1,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        let stereo = decode_audio_file_to_sample_buffer(
+            &stereo_path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        let upmixed = decode_audio_file_to_sample_buffer(
+            &mono_path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        let downmixed = decode_audio_file_to_sample_buffer(
+            &stereo_path,
+            1,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            upmixed.samples.to_vec(),
+            map_channels(mono.samples.to_vec(), 1, 2).unwrap()
+        );
+        assert_eq!(
+            downmixed.samples.to_vec(),
+            map_channels(stereo.samples.to_vec(), 2, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_mono_widening_only_applies_to_mono_upmix() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mono_path = tmp.path().join("mono.wav");
+        let stereo_path = tmp.path().join("stereo.wav");
+        write_pcm16_wav(&mono_path, 1, 44_100, &pcm16_test_signal(5_000)).unwrap();
+        write_pcm16_wav(&stereo_path, 2, 44_100, &pcm16_test_signal(10_000)).unwrap();
+        let options = SampleDecodeOptions {
+            mono_widening_ms: Some(1.0),
+            ..SampleDecodeOptions::default()
+        };
+
+        let widened =
+            decode_audio_file_to_sample_buffer(&mono_path, 2, 44_100, options, |_| {}).unwrap();
+        let mut expected = decode_audio_file_to_sample_buffer(
+            &mono_path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap()
+        .samples
+        .to_vec();
+        widen_mono_upmix(&mut expected, 44);
+        assert_eq!(widened.samples.to_vec(), expected);
+
+        let stereo =
+            decode_audio_file_to_sample_buffer(&stereo_path, 2, 44_100, options, |_| {}).unwrap();
+        let plain = decode_audio_file_to_sample_buffer(
+            &stereo_path,
+            2,
+            44_100,
+            SampleDecodeOptions::default(),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(stereo.samples.to_vec(), plain.samples.to_vec());
+    }
+
+    #[test]
+    fn test_decode_peak_allocation_stays_near_two_output_buffers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("long.wav");
+        let frames = 44_100 * 10;
+        write_pcm16_wav(&path, 1, 44_100, &pcm16_test_signal(frames)).unwrap();
+
+        let (decoded, peak_bytes) = track_peak_allocation(|| {
+            decode_audio_file_to_sample_buffer(
+                &path,
+                2,
+                44_100,
+                SampleDecodeOptions::default(),
+                |_| {},
+            )
+            .unwrap()
+        });
+
+        let output_bytes = std::mem::size_of_val(&*decoded.samples);
+        assert_eq!(decoded.samples.len(), frames * 2);
+        assert!(
+            peak_bytes < output_bytes * 5 / 2,
+            "peak {peak_bytes} bytes for {output_bytes} output bytes"
+        );
+    }
+
+    /// Decode timing for a 10-minute stereo file; run with `cargo test -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_decode_ten_minute_stereo_wav() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("ten_minutes.wav");
+        let frames = 44_100 * 600;
+        write_pcm16_wav(&path, 2, 44_100, &pcm16_test_signal(frames * 2)).unwrap();
+
+        let started = std::time::Instant::now();
+        let (decoded, peak_bytes) = track_peak_allocation(|| {
+            decode_audio_file_to_sample_buffer(
+                &path,
+                2,
+                44_100,
+                SampleDecodeOptions::default(),
+                |_| {},
+            )
+            .unwrap()
+        });
+        let elapsed = started.elapsed();
+
+        let output_bytes = std::mem::size_of_val(&*decoded.samples);
+        println!(
+            "decoded {frames} stereo frames in {elapsed:?}; peak {peak_bytes} bytes ({:.2}x output)",
+            peak_bytes as f64 / output_bytes as f64
+        );
+    }
+
+    #[test]
+    fn test_supported_extensions_are_sorted_unique_and_lowercase() {
+        assert!(
+            SUPPORTED_EXTENSIONS
+                .windows(2)
+                .all(|pair| pair[0] < pair[1])
+        );
+        assert!(
+            SUPPORTED_EXTENSIONS
+                .iter()
+                .all(|ext| !ext.starts_with('.') && *ext == ext.to_ascii_lowercase())
+        );
+        assert!(SUPPORTED_EXTENSIONS.contains(&"wav"));
+    }
+
+    /// Writes a PCM16 WAV whose RIFF and data chunk lengths claim the maximum size,
+    /// like a streamed encoder output that never patched its header.
+    fn write_pcm16_wav_with_unbounded_header(
+        path: &Path,
+        sample_rate_hz: u32,
+        samples: &[i16],
+    ) -> std::io::Result<()> {
+        write_pcm16_wav(path, 1, sample_rate_hz, samples)?;
+        let mut bytes = std::fs::read(path)?;
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(path, bytes)
+    }
+
+    #[test]
+    fn test_decode_rejects_audio_beyond_frame_limit_during_decode() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("lying.wav");
+        write_pcm16_wav_with_unbounded_header(&path, 44_100, &pcm16_test_signal(10_000)).unwrap();
+
+        let result = decode_audio_file_with_frame_limit(
+            &path,
+            1,
+            44_100,
+            4_096,
+            SampleDecodeOptions::default(),
+            |_| {},
+        );
+
+        assert!(matches!(
+            result,
+            Err(SampleLoadError::TooLarge { max_frames: 4_096 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_lying_length_header_is_bounded_by_decoded_audio() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("lying.wav");
+        write_pcm16_wav_with_unbounded_header(&path, 44_100, &pcm16_test_signal(10_000)).unwrap();
+
+        let (decoded, peak_bytes) = track_peak_allocation(|| {
+            decode_audio_file_with_frame_limit(
+                &path,
+                1,
+                44_100,
+                20_000,
+                SampleDecodeOptions::default(),
+                |_| {},
+            )
+            .unwrap()
+        });
+
+        assert_eq!(decoded.samples.len(), 10_000);
+        assert!(peak_bytes < 20_000 * std::mem::size_of::<f32>() * 4);
+    }
 }
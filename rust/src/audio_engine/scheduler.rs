@@ -7,15 +7,56 @@
 #![allow(dead_code)]
 
 use crate::audio_engine::constants::MAX_SCHEDULED_EVENTS;
+use crate::messages::TriggerOptions;
 
 pub(crate) type TransportScheduler = FixedCapacityScheduler<MAX_SCHEDULED_EVENTS>;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) enum ScheduledCommand {
-    PlaySample { id: usize, volume: f32 },
-    StopAllThenPlaySample { id: usize, volume: f32 },
-    StopSample { id: usize },
+    PlaySample {
+        id: usize,
+        volume: f32,
+    },
+    StopAllThenPlaySample {
+        id: usize,
+        volume: f32,
+    },
+    /// Start a voice that only feeds the cue bus until it is promoted.
+    PlaySampleCued {
+        id: usize,
+        volume: f32,
+    },
+    /// Start a voice with settings overriding its pad's.
+    PlaySampleWith {
+        id: usize,
+        volume: f32,
+        options: TriggerOptions,
+        handle: u64,
+    },
+    /// Start a voice that [`ScheduledCommand::AutoStopSample`] stops at `stop_frame`.
+    PlaySampleUntil {
+        id: usize,
+        volume: f32,
+        stop_frame: u64,
+    },
+    StopSample {
+        id: usize,
+    },
+    StopVoice {
+        handle: u64,
+    },
+    /// Stop voices of `id` that are still due to stop at `stop_frame`; retriggered voices are
+    /// left alone.
+    AutoStopSample {
+        id: usize,
+        stop_frame: u64,
+    },
     StopAll,
+    /// Bring bank `bank` to the scene in `pads`, where bit `i` marks pad `i` as playing.
+    LaunchScene {
+        bank: usize,
+        pads: u64,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -129,6 +170,30 @@ impl<const CAPACITY: usize> FixedCapacityScheduler<CAPACITY> {
         })
     }
 
+    /// Remove pending events whose command matches `predicate`, keeping the rest in order.
+    pub(crate) fn cancel_matching(
+        &mut self,
+        predicate: impl Fn(&ScheduledCommand) -> bool,
+    ) -> usize {
+        let mut kept = 0;
+        for index in 0..self.len {
+            let Some(event) = self.events[index] else {
+                break;
+            };
+            if !predicate(&event.command) {
+                self.events[kept] = Some(event);
+                kept += 1;
+            }
+        }
+
+        let cancelled = self.len - kept;
+        for event in &mut self.events[kept..self.len] {
+            *event = None;
+        }
+        self.len = kept;
+        cancelled
+    }
+
     fn insertion_index(&self, new_event: ScheduledEvent) -> usize {
         let mut index = 0;
         while index < self.len {
@@ -305,6 +370,28 @@ mod tests {
         assert_eq!(scheduler.pop_due_through(0, 20), None);
     }
 
+    #[test]
+    fn cancel_matching_removes_only_matching_events_in_order() {
+        let mut scheduler = FixedCapacityScheduler::<8>::new();
+
+        scheduler.schedule(10, play(1)).unwrap();
+        scheduler
+            .schedule(20, ScheduledCommand::StopSample { id: 2 })
+            .unwrap();
+        scheduler.schedule(30, play(3)).unwrap();
+        scheduler
+            .schedule(40, ScheduledCommand::StopSample { id: 4 })
+            .unwrap();
+
+        let cancelled = scheduler
+            .cancel_matching(|command| matches!(command, ScheduledCommand::StopSample { .. }));
+
+        assert_eq!(cancelled, 2);
+        assert_eq!(scheduler.len(), 2);
+        assert_eq!(drain_commands(&mut scheduler, 40), vec![play(1), play(3)]);
+        assert!(scheduler.is_empty());
+    }
+
     #[test]
     fn zero_capacity_scheduler_rejects_without_panic() {
         let mut scheduler = FixedCapacityScheduler::<0>::new();
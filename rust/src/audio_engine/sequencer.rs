@@ -0,0 +1,287 @@
+//! Audio-thread step sequencer on the transport's 1/16 grid.
+//!
+//! Patterns are edited step by step through ordered control messages and stored inline, so
+//! the callback never allocates or frees. Steps line up with the transport downbeat and follow
+//! its swing; a committed pattern replaces the running one only at the next bar boundary.
+
+use crate::audio_engine::constants::{
+    SEQUENCER_STEPS_MAX, SEQUENCER_STEPS_PER_BAR, SEQUENCER_TRACKS,
+};
+use crate::audio_engine::transport::{QuantizeGrid, TransportTimeline};
+use crate::messages::SequencerStep;
+
+const SIXTEENTH_NOTE_64THS: u16 = 4;
+
+#[derive(Debug, Clone, Copy)]
+struct SequencerPattern {
+    steps: [Option<SequencerStep>; SEQUENCER_STEPS_MAX],
+    len: usize,
+}
+
+impl SequencerPattern {
+    const EMPTY: Self = Self {
+        steps: [None; SEQUENCER_STEPS_MAX],
+        len: 0,
+    };
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SequencerTrack {
+    active: SequencerPattern,
+    staged: SequencerPattern,
+    pending: Option<SequencerPattern>,
+    muted: bool,
+}
+
+/// A sequencer step that is due at `frame`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SequencerTick {
+    pub(crate) frame: u64,
+    /// 1/16 index counted from the transport downbeat.
+    pub(crate) grid_index: i64,
+}
+
+pub(crate) struct StepSequencer {
+    tracks: [SequencerTrack; SEQUENCER_TRACKS],
+    running: bool,
+    next_grid_index: Option<i64>,
+}
+
+/// Whether `steps` is a whole number of bars within the sequencer's maximum length.
+pub(crate) fn is_valid_sequence_len(steps: usize) -> bool {
+    steps > 0 && steps <= SEQUENCER_STEPS_MAX && steps.is_multiple_of(SEQUENCER_STEPS_PER_BAR)
+}
+
+impl StepSequencer {
+    pub(crate) fn new() -> Self {
+        Self {
+            tracks: [SequencerTrack {
+                active: SequencerPattern::EMPTY,
+                staged: SequencerPattern::EMPTY,
+                pending: None,
+                muted: false,
+            }; SEQUENCER_TRACKS],
+            running: false,
+            next_grid_index: None,
+        }
+    }
+
+    pub(crate) fn begin_sequence(&mut self, track: usize, steps: usize) -> bool {
+        let Some(track) = self.tracks.get_mut(track) else {
+            return false;
+        };
+        if !is_valid_sequence_len(steps) {
+            return false;
+        }
+
+        track.staged = SequencerPattern::EMPTY;
+        track.staged.len = steps;
+        true
+    }
+
+    pub(crate) fn set_sequence_step(
+        &mut self,
+        track: usize,
+        step: usize,
+        event: Option<SequencerStep>,
+    ) -> bool {
+        let Some(track) = self.tracks.get_mut(track) else {
+            return false;
+        };
+        if step >= track.staged.len {
+            return false;
+        }
+
+        track.staged.steps[step] = event;
+        true
+    }
+
+    /// Publish the staged pattern: immediately while stopped, at the next bar while running.
+    pub(crate) fn commit_sequence(&mut self, track: usize) -> bool {
+        let Some(track) = self.tracks.get_mut(track) else {
+            return false;
+        };
+
+        if self.running {
+            track.pending = Some(track.staged);
+        } else {
+            track.active = track.staged;
+            track.pending = None;
+        }
+        true
+    }
+
+    pub(crate) fn set_track_muted(&mut self, track: usize, muted: bool) -> bool {
+        let Some(track) = self.tracks.get_mut(track) else {
+            return false;
+        };
+
+        track.muted = muted;
+        true
+    }
+
+    pub(crate) fn set_running(&mut self, running: bool) {
+        if !running {
+            self.apply_pending_patterns();
+        }
+        self.running = running;
+        self.next_grid_index = None;
+    }
+
+    /// Return the next step due before `end_frame` and move past it.
+    ///
+    /// The first step after starting, or after the transport jumped past the cursor, is the
+    /// next grid position at or after the current output frame; missed steps are skipped
+    /// rather than fired late.
+    pub(crate) fn next_tick(
+        &mut self,
+        transport: &TransportTimeline,
+        end_frame: u64,
+    ) -> Option<SequencerTick> {
+        if !self.running {
+            return None;
+        }
+
+        let grid = QuantizeGrid::from_step_64ths(SIXTEENTH_NOTE_64THS)?;
+        let now = transport.output_frame();
+        let cursor = self.next_grid_index.filter(|grid_index| {
+            transport
+                .grid_index_frame(grid, *grid_index)
+                .is_some_and(|frame| frame >= now)
+        });
+        let grid_index = match cursor {
+            Some(grid_index) => grid_index,
+            None => transport.grid_index_at_or_after_frame(grid, now)?,
+        };
+        self.next_grid_index = Some(grid_index);
+
+        let frame = transport.grid_index_frame(grid, grid_index)?;
+        if frame >= end_frame {
+            return None;
+        }
+
+        if grid_index.rem_euclid(SEQUENCER_STEPS_PER_BAR as i64) == 0 {
+            self.apply_pending_patterns();
+        }
+        self.next_grid_index = Some(grid_index + 1);
+        Some(SequencerTick { frame, grid_index })
+    }
+
+    /// Step of `track` at `grid_index` and its trigger unless muted or a rest, or `None` for
+    /// tracks without a pattern.
+    pub(crate) fn track_step(
+        &self,
+        track: usize,
+        grid_index: i64,
+    ) -> Option<(usize, Option<SequencerStep>)> {
+        let track = self.tracks.get(track)?;
+        let len = track.active.len;
+        if len == 0 {
+            return None;
+        }
+
+        let step = grid_index.rem_euclid(len as i64) as usize;
+        let event = track.active.steps[step].filter(|_| !track.muted);
+        Some((step, event))
+    }
+
+    fn apply_pending_patterns(&mut self) {
+        for track in &mut self.tracks {
+            if let Some(pattern) = track.pending.take() {
+                track.active = pattern;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transport_at(frame: u64) -> TransportTimeline {
+        let mut transport = TransportTimeline::new(48_000);
+        assert!(transport.set_master_bpm(120.0));
+        transport.advance_by_rendered_frames(frame as usize);
+        transport
+    }
+
+    fn step(id: usize) -> Option<SequencerStep> {
+        Some(SequencerStep { id, volume: 1.0 })
+    }
+
+    #[test]
+    fn ticks_follow_sixteenth_grid_and_swing() {
+        let mut sequencer = StepSequencer::new();
+        sequencer.set_running(true);
+        let mut transport = transport_at(1);
+
+        assert_eq!(
+            sequencer.next_tick(&transport, 13_000),
+            Some(SequencerTick {
+                frame: 6_000,
+                grid_index: 1
+            })
+        );
+        assert_eq!(
+            sequencer.next_tick(&transport, 13_000),
+            Some(SequencerTick {
+                frame: 12_000,
+                grid_index: 2
+            })
+        );
+        assert_eq!(sequencer.next_tick(&transport, 13_000), None);
+
+        assert!(transport.set_swing_percent(50.0));
+        transport.advance_by_rendered_frames(12_999);
+        assert_eq!(
+            sequencer.next_tick(&transport, 30_000),
+            Some(SequencerTick {
+                frame: 19_500,
+                grid_index: 3
+            })
+        );
+    }
+
+    #[test]
+    fn committed_pattern_waits_for_next_bar_while_running() {
+        let mut sequencer = StepSequencer::new();
+        assert!(sequencer.begin_sequence(0, 16));
+        assert!(sequencer.set_sequence_step(0, 0, step(1)));
+        assert!(sequencer.commit_sequence(0));
+        assert_eq!(sequencer.track_step(0, 16), Some((0, step(1))));
+
+        sequencer.set_running(true);
+        let transport = transport_at(6_000);
+        let tick = sequencer.next_tick(&transport, 96_001).unwrap();
+        assert_eq!(tick.grid_index, 1);
+
+        assert!(sequencer.begin_sequence(0, 32));
+        assert!(sequencer.set_sequence_step(0, 16, step(2)));
+        assert!(sequencer.commit_sequence(0));
+        assert_eq!(sequencer.track_step(0, 16), Some((0, step(1))));
+
+        let bar = loop {
+            let tick = sequencer.next_tick(&transport, 96_001).unwrap();
+            if tick.grid_index == 16 {
+                break tick;
+            }
+        };
+        assert_eq!(bar.frame, 96_000);
+        assert_eq!(sequencer.track_step(0, 16), Some((16, step(2))));
+    }
+
+    #[test]
+    fn muted_tracks_report_steps_without_triggers() {
+        let mut sequencer = StepSequencer::new();
+        assert!(sequencer.begin_sequence(2, 16));
+        assert!(sequencer.set_sequence_step(2, 4, step(7)));
+        assert!(sequencer.commit_sequence(2));
+        assert!(sequencer.set_track_muted(2, true));
+
+        assert_eq!(sequencer.track_step(2, 20), Some((4, None)));
+        assert_eq!(sequencer.track_step(1, 20), None);
+        assert!(!sequencer.begin_sequence(0, 24));
+        assert!(!sequencer.begin_sequence(SEQUENCER_TRACKS, 16));
+        assert!(!sequencer.set_sequence_step(2, 16, step(1)));
+    }
+}
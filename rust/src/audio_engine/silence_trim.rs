@@ -0,0 +1,169 @@
+//! Leading and trailing silence trim applied while decoding a file.
+//!
+//! The level is measured as the RMS of short windows across all channels. Audio outside the
+//! first and last window above the threshold is removed; a file with no window above it is
+//! kept whole.
+
+/// Length of one RMS window in milliseconds.
+const WINDOW_MS: f64 = 10.0;
+
+/// Percentile of the window levels taken as the noise floor in auto mode.
+const AUTO_NOISE_FLOOR_PERCENTILE: f64 = 0.1;
+
+/// Headroom in dB above the measured noise floor for the auto threshold.
+const AUTO_MARGIN_DB: f32 = 6.0;
+
+/// Lowest auto threshold, so digital silence does not make every quiet intro count as sound.
+const AUTO_THRESHOLD_DB_MIN: f32 = -80.0;
+
+/// Highest auto threshold, so files without any silence keep their quiet passages.
+const AUTO_THRESHOLD_DB_MAX: f32 = -30.0;
+
+/// How the level below which leading and trailing audio counts as silence is chosen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SilenceTrim {
+    /// A fixed threshold in dBFS.
+    FixedDbfs(f32),
+    /// A threshold a few dB above the file's own noise floor, measured as a low percentile of
+    /// its RMS envelope.
+    Auto,
+}
+
+/// Removes leading and trailing silence from interleaved `samples` in place.
+///
+/// Returns the number of frames removed from the start.
+pub fn trim_silence(
+    samples: &mut Vec<f32>,
+    channels: usize,
+    sample_rate_hz: u32,
+    mode: SilenceTrim,
+) -> usize {
+    if channels == 0 {
+        return 0;
+    }
+
+    let frames = samples.len() / channels;
+    let window_frames = ((f64::from(sample_rate_hz) * WINDOW_MS / 1000.0).round() as usize).max(1);
+    let levels_db: Vec<f32> = samples[..frames * channels]
+        .chunks(window_frames * channels)
+        .map(window_rms_db)
+        .collect();
+
+    let threshold_db = match mode {
+        SilenceTrim::FixedDbfs(threshold_db) => threshold_db,
+        SilenceTrim::Auto => auto_threshold_db(&levels_db),
+    };
+
+    let Some(first) = levels_db.iter().position(|level| *level > threshold_db) else {
+        return 0;
+    };
+    let last = levels_db
+        .iter()
+        .rposition(|level| *level > threshold_db)
+        .unwrap_or(first);
+
+    let start_frame = first * window_frames;
+    let end_frame = ((last + 1) * window_frames).min(frames);
+    samples.truncate(end_frame * channels);
+    samples.drain(..start_frame * channels);
+    start_frame
+}
+
+fn window_rms_db(window: &[f32]) -> f32 {
+    let mean_square = window
+        .iter()
+        .map(|s| f64::from(*s) * f64::from(*s))
+        .sum::<f64>()
+        / window.len() as f64;
+    (10.0 * mean_square.log10()) as f32
+}
+
+fn auto_threshold_db(levels_db: &[f32]) -> f32 {
+    if levels_db.is_empty() {
+        return AUTO_THRESHOLD_DB_MIN;
+    }
+
+    let mut sorted = levels_db.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let index = ((sorted.len() - 1) as f64 * AUTO_NOISE_FLOOR_PERCENTILE).round() as usize;
+    let noise_floor_db = sorted[index].max(AUTO_THRESHOLD_DB_MIN);
+    (noise_floor_db + AUTO_MARGIN_DB).clamp(AUTO_THRESHOLD_DB_MIN, AUTO_THRESHOLD_DB_MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 8_000;
+
+    fn sine(frames: usize, amplitude: f32) -> Vec<f32> {
+        (0..frames)
+            .map(|frame| amplitude * (frame as f32 * 0.7).sin())
+            .collect()
+    }
+
+    /// Deterministic white noise with the given RMS level.
+    fn noise(frames: usize, rms: f32) -> Vec<f32> {
+        let mut state = 0x2545_f491_u32;
+        (0..frames)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state as f32 / u32::MAX as f32 * 2.0 - 1.0) * rms * 3.0_f32.sqrt()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn auto_threshold_keeps_quiet_intro_of_a_clean_file() {
+        // 0.3 s digital silence, 0.5 s intro at about -50 dBFS, 1 s body, 0.2 s silence.
+        let original = [
+            vec![0.0; 2_400],
+            sine(4_000, 0.004),
+            sine(8_000, 0.5),
+            vec![0.0; 1_600],
+        ]
+        .concat();
+
+        let mut fixed = original.clone();
+        assert_eq!(
+            trim_silence(&mut fixed, 1, RATE, SilenceTrim::FixedDbfs(-40.0)),
+            6_400
+        );
+        assert_eq!(fixed.len(), 8_000);
+
+        let mut auto = original;
+        assert_eq!(trim_silence(&mut auto, 1, RATE, SilenceTrim::Auto), 2_400);
+        assert_eq!(auto.len(), 12_000);
+    }
+
+    #[test]
+    fn auto_threshold_rises_above_the_noise_floor_of_a_noisy_file() {
+        // Hiss at -45 dBFS around a 1 s body.
+        let body = sine(8_000, 0.5)
+            .iter()
+            .zip(noise(8_000, 0.0056))
+            .map(|(body, hiss)| body + hiss)
+            .collect();
+        let original = [noise(3_200, 0.0056), body, noise(2_400, 0.0056)].concat();
+
+        let mut fixed = original.clone();
+        assert_eq!(
+            trim_silence(&mut fixed, 1, RATE, SilenceTrim::FixedDbfs(-60.0)),
+            0
+        );
+        assert_eq!(fixed.len(), original.len());
+
+        let mut auto = original;
+        assert_eq!(trim_silence(&mut auto, 1, RATE, SilenceTrim::Auto), 3_200);
+        assert_eq!(auto.len(), 8_000);
+    }
+
+    #[test]
+    fn trim_keeps_a_file_without_any_window_above_the_threshold() {
+        let mut samples = vec![0.0; 2 * 4_000];
+        assert_eq!(trim_silence(&mut samples, 2, RATE, SilenceTrim::Auto), 0);
+        assert_eq!(samples.len(), 8_000);
+    }
+}
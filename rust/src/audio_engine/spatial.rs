@@ -0,0 +1,145 @@
+//! Panning of pads across a ring of speakers on outputs wider than stereo.
+//!
+//! Gains follow 2D vector base amplitude panning (VBAP): a source between two neighbouring
+//! speakers of the ring plays on that pair only, at constant power.
+
+use crate::audio_engine::constants::MAX_OUTPUT_CHANNELS;
+use crate::messages::{SPEAKER_LAYOUT_MAX, SpeakerLayout};
+
+const _: () = assert!(SPEAKER_LAYOUT_MAX == MAX_OUTPUT_CHANNELS as usize);
+
+/// Per-output-channel gains of one panned source.
+pub(crate) type SpeakerGains = [f32; SPEAKER_LAYOUT_MAX];
+
+impl SpeakerLayout {
+    /// Returns `None` unless there are 3 to `MAX_OUTPUT_CHANNELS` finite, distinct azimuths.
+    pub(crate) fn new(azimuths_degrees: &[f32]) -> Option<Self> {
+        let speakers = azimuths_degrees.len();
+        if !(3..=usize::from(MAX_OUTPUT_CHANNELS)).contains(&speakers)
+            || azimuths_degrees.iter().any(|azimuth| !azimuth.is_finite())
+        {
+            return None;
+        }
+
+        let mut azimuths = [0.0; SPEAKER_LAYOUT_MAX];
+        for (slot, azimuth) in azimuths.iter_mut().zip(azimuths_degrees) {
+            *slot = azimuth.rem_euclid(360.0);
+        }
+        let distinct = (0..speakers).all(|a| (a + 1..speakers).all(|b| azimuths[a] != azimuths[b]));
+
+        distinct.then_some(Self { azimuths, speakers })
+    }
+
+    /// Constant-power gains for a source at `azimuth_degrees`.
+    ///
+    /// Only the two speakers enclosing the source sound. Across a gap of half the circle or
+    /// more, where VBAP has no solution, the pair crossfades by angle instead.
+    pub(crate) fn gains(&self, azimuth_degrees: f32) -> SpeakerGains {
+        let mut gains = [0.0; SPEAKER_LAYOUT_MAX];
+        let azimuths = &self.azimuths[..self.speakers];
+        let source = azimuth_degrees.rem_euclid(360.0);
+
+        // The enclosing pair is the speaker at or last before the source, going clockwise,
+        // and the one following it.
+        let offset_from = |speaker: usize| (source - azimuths[speaker]).rem_euclid(360.0);
+        let Some(first) =
+            (0..self.speakers).min_by(|&a, &b| offset_from(a).total_cmp(&offset_from(b)))
+        else {
+            return gains;
+        };
+        let span_from_first =
+            |speaker: usize| (azimuths[speaker] - azimuths[first]).rem_euclid(360.0);
+        let Some(second) = (0..self.speakers)
+            .filter(|&speaker| speaker != first)
+            .min_by(|&a, &b| span_from_first(a).total_cmp(&span_from_first(b)))
+        else {
+            return gains;
+        };
+
+        let span = span_from_first(second);
+        let offset = offset_from(first);
+        let (first_gain, second_gain) = if span < 180.0 {
+            let direction = unit_vector(source);
+            let first_speaker = unit_vector(azimuths[first]);
+            let second_speaker = unit_vector(azimuths[second]);
+            let determinant =
+                first_speaker.0 * second_speaker.1 - first_speaker.1 * second_speaker.0;
+            (
+                (direction.0 * second_speaker.1 - direction.1 * second_speaker.0) / determinant,
+                (direction.1 * first_speaker.0 - direction.0 * first_speaker.1) / determinant,
+            )
+        } else {
+            let position = offset / span * std::f32::consts::FRAC_PI_2;
+            (position.cos(), position.sin())
+        };
+
+        let first_gain = first_gain.max(0.0);
+        let second_gain = second_gain.max(0.0);
+        let norm = first_gain.hypot(second_gain);
+        if norm > 0.0 {
+            gains[first] = first_gain / norm;
+            gains[second] = second_gain / norm;
+        }
+        gains
+    }
+}
+
+fn unit_vector(azimuth_degrees: f32) -> (f32, f32) {
+    let radians = azimuth_degrees.to_radians();
+    (radians.cos(), radians.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUAD: [f32; 4] = [-45.0, 45.0, -135.0, 135.0];
+
+    fn quad_gains(azimuth_degrees: f32) -> [f32; 4] {
+        let gains = SpeakerLayout::new(&QUAD).unwrap().gains(azimuth_degrees);
+        [gains[0], gains[1], gains[2], gains[3]]
+    }
+
+    fn assert_gains(actual: [f32; 4], expected: [f32; 4]) {
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-5,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn quad_layout_pans_between_neighbouring_speakers_at_constant_power() {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        assert_gains(quad_gains(0.0), [half, half, 0.0, 0.0]);
+        assert_gains(quad_gains(45.0), [0.0, 1.0, 0.0, 0.0]);
+        assert_gains(quad_gains(90.0), [0.0, half, 0.0, half]);
+        assert_gains(quad_gains(180.0), [0.0, 0.0, half, half]);
+        assert_gains(quad_gains(-90.0), [half, 0.0, half, 0.0]);
+        assert_gains(quad_gains(-135.0 + 360.0), [0.0, 0.0, 1.0, 0.0]);
+
+        let [front_left, front_right, rear_left, rear_right] = quad_gains(20.0);
+        assert!(front_right > front_left && front_left > 0.0);
+        assert_eq!((rear_left, rear_right), (0.0, 0.0));
+        assert!((front_left.hypot(front_right) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn wide_gaps_crossfade_by_angle() {
+        // Speakers at 0, 60 and 120 degrees leave a 240 degree gap behind.
+        let layout = SpeakerLayout::new(&[0.0, 60.0, 120.0]).unwrap();
+        let gains = layout.gains(240.0);
+        assert!((gains[2] - gains[0]).abs() < 1e-5);
+        assert!((gains[0].hypot(gains[2]) - 1.0).abs() < 1e-5);
+        assert_eq!(gains[1], 0.0);
+    }
+
+    #[test]
+    fn layouts_need_three_distinct_finite_speakers() {
+        assert!(SpeakerLayout::new(&[-30.0, 30.0]).is_none());
+        assert!(SpeakerLayout::new(&[0.0, 90.0, f32::NAN]).is_none());
+        assert!(SpeakerLayout::new(&[0.0, 90.0, 360.0]).is_none());
+        assert_eq!(SpeakerLayout::new(&QUAD).unwrap().speakers, 4);
+    }
+}
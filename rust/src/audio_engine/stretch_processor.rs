@@ -7,6 +7,14 @@ use crate::audio_engine::rubberband_backend::RubberBandLiveShifter;
 /// resizing.
 pub const DEFAULT_BLOCK_SAMPLES: usize = 1024;
 
+/// Shortest loop, in source frames, that is routed through the pitch-preserving stretcher.
+///
+/// Rubber Band needs about a block of input plus its start delay before it emits anything, so a
+/// one-shot or chop shorter than one block would come out delayed and smeared across loop
+/// iterations. Such loops play through the varispeed path even with key lock enabled, which
+/// keeps the transient on the trigger frame at the cost of pitch following the tempo ratio.
+pub const KEY_LOCK_MIN_LOOP_FRAMES: usize = DEFAULT_BLOCK_SAMPLES;
+
 const DEFAULT_SAMPLE_RATE_HZ: f32 = 48_000.0;
 const RUBBERBAND_MIN_SAMPLE_RATE_HZ: f32 = 8_000.0;
 const PITCH_SCALE_EPSILON: f64 = 0.001;
@@ -100,12 +108,32 @@ impl StretchProcessor {
         &mut self.input
     }
 
+    #[cfg(test)]
     pub fn process(
         &mut self,
         input_samples: usize,
         output_samples: usize,
         tempo_ratio: f32,
         preserve_pitch: bool,
+    ) {
+        self.process_transposed(
+            input_samples,
+            output_samples,
+            tempo_ratio,
+            preserve_pitch,
+            1.0,
+        );
+    }
+
+    /// Like [`StretchProcessor::process`], additionally shifting the pitch by `pitch_ratio`
+    /// on the pitch-preserving path.
+    pub(crate) fn process_transposed(
+        &mut self,
+        input_samples: usize,
+        output_samples: usize,
+        tempo_ratio: f32,
+        preserve_pitch: bool,
+        pitch_ratio: f32,
     ) {
         if self.channels == 0 {
             return;
@@ -113,7 +141,7 @@ impl StretchProcessor {
 
         let input_samples = input_samples.clamp(1, DEFAULT_BLOCK_SAMPLES);
         let output_samples = output_samples.min(DEFAULT_BLOCK_SAMPLES);
-        let pitch_scale = rubberband_pitch_scale(tempo_ratio);
+        let pitch_scale = transposed_pitch_scale(tempo_ratio, pitch_ratio);
 
         for channel in 0..self.channels {
             render_varispeed(
@@ -137,6 +165,22 @@ impl StretchProcessor {
         &self.output
     }
 
+    /// Output frames by which the processed signal trails its input.
+    ///
+    /// Varispeed rendering is sample-aligned, so this is 0 unless the Rubber Band path is
+    /// active, where it covers one input block of buffering plus the shifter's start delay.
+    pub fn latency_frames(&self) -> usize {
+        if !self.rubberband_active {
+            return 0;
+        }
+
+        self.rubberband_block_size
+            + self
+                .rubberband
+                .as_ref()
+                .map_or(0, RubberBandLiveShifter::start_delay)
+    }
+
     fn process_rubberband(&mut self, output_samples: usize, pitch_scale: f64) {
         if !self.rubberband_active {
             self.reset_rubberband_state();
@@ -311,6 +355,14 @@ fn rubberband_pitch_scale(tempo_ratio: f32) -> f64 {
     f64::from((1.0 / tempo_ratio).clamp(0.5, 2.0))
 }
 
+fn transposed_pitch_scale(tempo_ratio: f32, pitch_ratio: f32) -> f64 {
+    if !pitch_ratio.is_finite() || pitch_ratio <= 0.0 {
+        return rubberband_pitch_scale(tempo_ratio);
+    }
+
+    rubberband_pitch_scale(tempo_ratio) * f64::from(pitch_ratio)
+}
+
 fn render_varispeed(input: &[f32], output: &mut [f32]) {
     if input.is_empty() || output.is_empty() {
         return;
@@ -457,6 +509,8 @@ mod tests {
         assert!((rubberband_pitch_scale(0.5) - 2.0).abs() < f64::EPSILON);
         assert!((rubberband_pitch_scale(1.0) - 1.0).abs() < f64::EPSILON);
         assert!((rubberband_pitch_scale(f32::NAN) - 1.0).abs() < f64::EPSILON);
+        assert!((transposed_pitch_scale(2.0, 2.0) - 1.0).abs() < f64::EPSILON);
+        assert!((transposed_pitch_scale(1.0, 0.5) - 0.5).abs() < f64::EPSILON);
     }
 
     #[test]
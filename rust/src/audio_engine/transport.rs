@@ -5,6 +5,8 @@
 
 #![allow(dead_code)]
 
+use crate::audio_engine::constants::SWING_PERCENT_MAX;
+
 const DEFAULT_SAMPLE_RATE_HZ: u32 = 44_100;
 const DEFAULT_MASTER_BPM: f32 = 120.0;
 const BEATS_PER_BAR_4_4: u32 = 4;
@@ -50,6 +52,11 @@ pub(crate) struct TransportTimeline {
     master_bpm: Option<f32>,
     beats_per_bar: u32,
     downbeat_frame: u64,
+    /// Fraction of half a grid step that off-beat sub-beat grid positions are delayed by.
+    swing: f64,
+    latency_compensation: bool,
+    /// Measured frames between rendering a frame and hearing it; 0 when unknown.
+    output_latency_frames: u64,
 }
 
 impl TransportTimeline {
@@ -66,6 +73,9 @@ impl TransportTimeline {
             master_bpm: Some(DEFAULT_MASTER_BPM),
             beats_per_bar: BEATS_PER_BAR_4_4,
             downbeat_frame: 0,
+            swing: 0.0,
+            latency_compensation: false,
+            output_latency_frames: 0,
         }
     }
 
@@ -89,6 +99,39 @@ impl TransportTimeline {
         self.downbeat_frame
     }
 
+    pub(crate) fn swing_percent(&self) -> f32 {
+        (self.swing * 100.0) as f32
+    }
+
+    /// Set MPC-style swing: `0` is straight, `100` delays every off-beat position of a
+    /// sub-beat grid by half a step (a dotted feel). Only affects grid frames computed later.
+    pub(crate) fn set_swing_percent(&mut self, percent: f32) -> bool {
+        if !percent.is_finite() || !(0.0..=SWING_PERCENT_MAX).contains(&percent) {
+            return false;
+        }
+
+        self.swing = f64::from(percent) / 100.0;
+        true
+    }
+
+    /// Whether grid targets are moved earlier by the measured output latency.
+    pub(crate) fn set_latency_compensation(&mut self, enabled: bool) {
+        self.latency_compensation = enabled;
+    }
+
+    /// Updates the measured output latency; `None` when the backend reports none.
+    pub(crate) fn set_output_latency_frames(&mut self, frames: Option<u64>) {
+        self.output_latency_frames = frames.unwrap_or(0);
+    }
+
+    fn compensation_frames(&self) -> u64 {
+        if self.latency_compensation {
+            self.output_latency_frames
+        } else {
+            0
+        }
+    }
+
     pub(crate) fn set_downbeat_frame(&mut self, frame: u64) {
         self.downbeat_frame = frame;
     }
@@ -239,27 +282,65 @@ impl TransportTimeline {
         self.next_grid_frame(QuantizeGrid::bar())
     }
 
+    /// Render frame of the next grid position.
+    ///
+    /// With latency compensation on, this is the next grid position that can still be
+    /// *heard* on time, moved earlier by the measured output latency.
     pub(crate) fn next_grid_frame(&self, grid: QuantizeGrid) -> Option<u64> {
-        let frames_per_grid =
-            self.frames_per_beat()? * grid.step_64ths() as f64 / GRID_64THS_PER_BEAT as f64;
-
-        if !frames_per_grid.is_finite() || frames_per_grid <= 0.0 {
+        let compensation = self.compensation_frames();
+        let heard_frame = self.output_frame.saturating_add(compensation);
+        let grid_index = self.grid_index_at_or_after_frame(grid, heard_frame)?;
+        let target_frame =
+            self.downbeat_frame as f64 + self.grid_frames_from_downbeat(grid, grid_index)?;
+        Some(frame_at_or_after(target_frame, heard_frame) - compensation)
+    }
+
+    /// Index (counted from the downbeat) of the first swung grid position at or after
+    /// `output_frame`.
+    pub(crate) fn grid_index_at_or_after_frame(
+        &self,
+        grid: QuantizeGrid,
+        output_frame: u64,
+    ) -> Option<i64> {
+        let frames_per_grid = self.frames_per_grid(grid)?;
+        let relative_frames = self.relative_frames_from_downbeat_at_frame(output_frame);
+
+        // Swung positions stay in order, so the first one at or after the frame is at most
+        // one step past the straight floor.
+        let first_grid = (relative_frames / frames_per_grid).floor() as i64 - 1;
+        (first_grid..first_grid + 3).find(|grid_index| {
+            self.grid_frames_from_downbeat(grid, *grid_index)
+                .is_some_and(|frames| frames >= relative_frames - GRID_EPSILON_FRAMES)
+        })
+    }
+
+    /// Output frame of the swung grid position `grid_index`, rounded up to a whole frame.
+    pub(crate) fn grid_index_frame(&self, grid: QuantizeGrid, grid_index: i64) -> Option<u64> {
+        let target_frame =
+            self.downbeat_frame as f64 + self.grid_frames_from_downbeat(grid, grid_index)?;
+        if !target_frame.is_finite() || target_frame < 0.0 || target_frame >= u64::MAX as f64 {
             return None;
         }
 
-        let relative_frames = self.relative_frames_from_downbeat();
-        let grid_position = relative_frames / frames_per_grid;
-        let rounded_grid = grid_position.round();
-        let distance_frames = (grid_position - rounded_grid).abs() * frames_per_grid;
+        Some((target_frame - GRID_EPSILON_FRAMES).ceil().max(0.0) as u64)
+    }
 
-        let target_grid = if distance_frames <= GRID_EPSILON_FRAMES {
-            rounded_grid
+    fn frames_per_grid(&self, grid: QuantizeGrid) -> Option<f64> {
+        let frames_per_grid =
+            self.frames_per_beat()? * grid.step_64ths() as f64 / GRID_64THS_PER_BEAT as f64;
+
+        (frames_per_grid.is_finite() && frames_per_grid > 0.0).then_some(frames_per_grid)
+    }
+
+    fn grid_frames_from_downbeat(&self, grid: QuantizeGrid, grid_index: i64) -> Option<f64> {
+        let frames_per_grid = self.frames_per_grid(grid)?;
+        let swing_frames = if grid.step_64ths() < GRID_64THS_PER_BEAT && grid_index % 2 != 0 {
+            self.swing * frames_per_grid / 2.0
         } else {
-            grid_position.floor() + 1.0
+            0.0
         };
 
-        let target_frame = self.downbeat_frame as f64 + target_grid * frames_per_grid;
-        Some(frame_at_or_after(target_frame, self.output_frame))
+        Some(grid_index as f64 * frames_per_grid + swing_frames)
     }
 
     fn relative_frames_from_downbeat(&self) -> f64 {
@@ -553,6 +634,70 @@ mod tests {
         assert_eq!(transport.next_grid_frame(sixteenth_note), Some(12_000));
     }
 
+    #[test]
+    fn swing_delays_only_off_beat_sub_beat_grid_positions() {
+        let sixteenth_note = QuantizeGrid::from_step_64ths(4).unwrap();
+        let mut transport = transport_at(1);
+
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(6_000));
+        assert!(transport.set_swing_percent(50.0));
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(7_500));
+        assert!(transport.set_swing_percent(100.0));
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(9_000));
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(24_000)
+        );
+
+        transport.output_frame = 7_000;
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(9_000));
+        transport.output_frame = 9_000;
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(9_000));
+        transport.output_frame = 9_001;
+        assert_eq!(transport.next_grid_frame(sixteenth_note), Some(12_000));
+
+        assert!(!transport.set_swing_percent(101.0));
+        assert!(!transport.set_swing_percent(f32::NAN));
+        assert_eq!(transport.swing_percent(), 100.0);
+    }
+
+    #[test]
+    fn latency_compensation_moves_grid_targets_earlier_by_the_output_latency() {
+        let mut transport = transport_at(1);
+        transport.set_output_latency_frames(Some(480));
+
+        // Measured latency alone does not change the targets.
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(24_000)
+        );
+
+        transport.set_latency_compensation(true);
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(23_520)
+        );
+
+        // A beat that can no longer be heard on time moves to the next one.
+        transport.output_frame = 23_600;
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(47_520)
+        );
+        transport.output_frame = 23_520;
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(23_520)
+        );
+
+        // Backends without timestamps fall back to uncompensated targets.
+        transport.set_output_latency_frames(None);
+        assert_eq!(
+            transport.next_grid_frame(QuantizeGrid::beat()),
+            Some(24_000)
+        );
+    }
+
     #[test]
     fn master_bpm_anchor_can_use_arbitrary_output_frame() {
         let mut transport = TransportTimeline::new(48_000);
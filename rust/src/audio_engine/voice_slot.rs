@@ -1,9 +1,9 @@
-use crate::audio_engine::buffer_retirement::AudioBufferRetirement;
 use crate::audio_engine::constants::{SPEED_MAX, SPEED_MIN};
-use crate::audio_engine::stretch_processor::StretchProcessor;
-use crate::messages::SampleBuffer;
+use crate::audio_engine::stretch_processor::{DEFAULT_BLOCK_SAMPLES, StretchProcessor};
+use crate::messages::LoopMode;
 
-const KEY_LOCK_TEMPO_SMOOTHING_STEP: f32 = 0.05;
+/// Remaining tempo ratio difference below which a glide snaps onto its target.
+const TEMPO_GLIDE_SNAP: f32 = 1.0e-4;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum ExplicitSeekMode {
@@ -18,17 +18,57 @@ pub(crate) struct PlaybackTimelineAnchor {
     pub(crate) source_frame: usize,
 }
 
+/// Source slice replayed by the stutter effect.
+///
+/// The slice indexes the voice's immutable sample buffer, so capturing it never copies audio.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct StutterSlice {
+    pub(crate) start_frame: usize,
+    pub(crate) len: usize,
+    pub(crate) phase: usize,
+}
+
+impl StutterSlice {
+    pub(crate) fn frame_at(self, offset: usize) -> usize {
+        self.start_frame + (self.phase + offset) % self.len
+    }
+
+    pub(crate) fn advance(&mut self, frames: usize) {
+        self.phase = (self.phase + frames) % self.len;
+    }
+}
+
+/// A playing voice.
+///
+/// Voices own no audio: they reference the mixer's sample bank by slot id and the slot
+/// generation they were started with, so the callback never performs the final drop of a
+/// sample buffer.
 pub struct VoiceSlot {
     pub active: bool,
     pub sample_id: usize,
-    pub sample: Option<SampleBuffer>,
+    pub(crate) sample_generation: u64,
     pub frame_pos: usize,
+    /// Fractional source frames carried between blocks, so the playback phase is
+    /// `frame_pos + source_phase_fraction` and varispeed does not drift over long runs.
+    pub(crate) source_phase_fraction: f64,
     pub volume: f32,
     tempo_ratio_smoothed: f32,
+    sample_rate_hz: f32,
     pub stretch: StretchProcessor,
     pub paused: bool,
     pub(crate) explicit_seek_mode: ExplicitSeekMode,
     pub(crate) timeline_anchor: Option<PlaybackTimelineAnchor>,
+    pub(crate) stutter: Option<StutterSlice>,
+    /// Output frame at which a timed trigger stops this voice.
+    pub(crate) auto_stop_frame: Option<u64>,
+    /// Rate multiplier of this voice on top of its pad's tempo ratio.
+    pub(crate) speed: f32,
+    /// Loop mode of this voice, overriding its pad's.
+    pub(crate) loop_mode: Option<LoopMode>,
+    /// Handle Python got for the trigger that started this voice.
+    pub(crate) handle: Option<u64>,
+    /// Stereo pan of this voice; `None` leaves both channels untouched.
+    pub(crate) pan: Option<f32>,
 }
 
 impl VoiceSlot {
@@ -36,45 +76,29 @@ impl VoiceSlot {
         Self {
             active: false,
             sample_id: 0,
-            sample: None,
+            sample_generation: 0,
             frame_pos: 0,
+            source_phase_fraction: 0.0,
             volume: 0.0,
             tempo_ratio_smoothed: 1.0,
+            sample_rate_hz,
             stretch: StretchProcessor::with_sample_rate(channels, sample_rate_hz),
             paused: false,
             explicit_seek_mode: ExplicitSeekMode::Normal,
             timeline_anchor: None,
+            stutter: None,
+            auto_stop_frame: None,
+            speed: 1.0,
+            loop_mode: None,
+            handle: None,
+            pan: None,
         }
     }
 
-    pub(crate) fn start_rt(
+    pub(crate) fn start(
         &mut self,
         sample_id: usize,
-        sample: SampleBuffer,
-        initial_frame_pos: usize,
-        volume: f32,
-        initial_tempo_ratio: f32,
-        start_output_frame: Option<u64>,
-        retirement: &mut impl AudioBufferRetirement,
-    ) {
-        if let Some(old_sample) = self.sample.take() {
-            retirement.retire_sample(old_sample);
-        }
-
-        self.start_inner(
-            sample_id,
-            sample,
-            initial_frame_pos,
-            volume,
-            initial_tempo_ratio,
-            start_output_frame,
-        );
-    }
-
-    fn start_inner(
-        &mut self,
-        sample_id: usize,
-        sample: SampleBuffer,
+        sample_generation: u64,
         initial_frame_pos: usize,
         volume: f32,
         initial_tempo_ratio: f32,
@@ -82,8 +106,9 @@ impl VoiceSlot {
     ) {
         self.active = true;
         self.sample_id = sample_id;
-        self.sample = Some(sample);
+        self.sample_generation = sample_generation;
         self.frame_pos = initial_frame_pos;
+        self.source_phase_fraction = 0.0;
         self.volume = volume;
         self.tempo_ratio_smoothed = initial_tempo_ratio;
         self.paused = false;
@@ -92,31 +117,30 @@ impl VoiceSlot {
             output_frame,
             source_frame: initial_frame_pos,
         });
+        self.stutter = None;
+        self.auto_stop_frame = None;
+        self.speed = 1.0;
+        self.loop_mode = None;
+        self.handle = None;
+        self.pan = None;
         self.stretch.reset();
     }
 
-    #[cfg(test)]
     pub(crate) fn stop(&mut self) {
-        self.sample = None;
-        self.stop_inner();
-    }
-
-    pub(crate) fn stop_rt(&mut self, retirement: &mut impl AudioBufferRetirement) {
-        if let Some(sample) = self.sample.take() {
-            retirement.retire_sample(sample);
-        }
-
-        self.stop_inner();
-    }
-
-    fn stop_inner(&mut self) {
         self.active = false;
         self.frame_pos = 0;
+        self.source_phase_fraction = 0.0;
         self.volume = 0.0;
         self.tempo_ratio_smoothed = 1.0;
         self.paused = false;
         self.explicit_seek_mode = ExplicitSeekMode::Normal;
         self.timeline_anchor = None;
+        self.stutter = None;
+        self.auto_stop_frame = None;
+        self.speed = 1.0;
+        self.loop_mode = None;
+        self.handle = None;
+        self.pan = None;
         self.stretch.reset();
     }
 
@@ -128,6 +152,7 @@ impl VoiceSlot {
         start_output_frame: Option<u64>,
     ) {
         self.frame_pos = initial_frame_pos;
+        self.source_phase_fraction = 0.0;
         self.volume = volume;
         self.tempo_ratio_smoothed = initial_tempo_ratio;
         self.paused = false;
@@ -136,9 +161,21 @@ impl VoiceSlot {
             output_frame,
             source_frame: initial_frame_pos,
         });
+        self.stutter = None;
+        self.auto_stop_frame = None;
+        self.speed = 1.0;
+        self.loop_mode = None;
+        self.handle = None;
+        self.pan = None;
         self.stretch.reset();
     }
 
+    /// Plays a just started voice `speed` times faster than its pad, from the first frame.
+    pub(crate) fn set_trigger_speed(&mut self, speed: f32) {
+        self.speed = speed;
+        self.tempo_ratio_smoothed = (self.tempo_ratio_smoothed * speed).clamp(SPEED_MIN, SPEED_MAX);
+    }
+
     pub(crate) fn seek(
         &mut self,
         frame_pos: usize,
@@ -146,6 +183,7 @@ impl VoiceSlot {
         output_frame: Option<u64>,
     ) {
         self.frame_pos = frame_pos;
+        self.source_phase_fraction = 0.0;
         self.explicit_seek_mode = mode;
         self.timeline_anchor = output_frame.map(|output_frame| PlaybackTimelineAnchor {
             output_frame,
@@ -154,27 +192,49 @@ impl VoiceSlot {
         self.stretch.reset();
     }
 
+    /// Whole source frames to consume for `output_frames` at `tempo_ratio`.
+    ///
+    /// The exact f64 phase advance is rounded and the remainder carried to the next block.
+    /// Blocks are clamped to `1..=DEFAULT_BLOCK_SAMPLES` for the stretcher; frames a clamped
+    /// block over- or under-consumes stay in the remainder and are paid back by later blocks.
+    pub(crate) fn advance_source_phase(&mut self, output_frames: usize, tempo_ratio: f32) -> usize {
+        let exact = self.source_phase_fraction + output_frames as f64 * f64::from(tempo_ratio);
+        let input_frames = (exact.round().max(0.0) as usize).clamp(1, DEFAULT_BLOCK_SAMPLES);
+        let limit = DEFAULT_BLOCK_SAMPLES as f64;
+        self.source_phase_fraction = (exact - input_frames as f64).clamp(-limit, limit);
+        input_frames
+    }
+
     pub(crate) fn clear_explicit_seek(&mut self) {
         self.explicit_seek_mode = ExplicitSeekMode::Normal;
     }
 
-    pub fn smooth_tempo_ratio(&mut self, target: f32) -> f32 {
+    /// Glide toward `target` over `frames` output frames with an exponential time constant of
+    /// `glide_ms`, so the glide time does not depend on the block size. `0` jumps.
+    pub fn smooth_tempo_ratio(&mut self, target: f32, frames: usize, glide_ms: f32) -> f32 {
         if !target.is_finite() {
             return self.tempo_ratio_smoothed;
         }
 
-        let mut target = target.clamp(SPEED_MIN, SPEED_MAX);
-        if !self.tempo_ratio_smoothed.is_finite() {
+        let target = target.clamp(SPEED_MIN, SPEED_MAX);
+        let time_constant_frames = glide_ms * self.sample_rate_hz / 1000.0;
+        if !self.tempo_ratio_smoothed.is_finite()
+            || !time_constant_frames.is_finite()
+            || time_constant_frames <= 0.0
+        {
             self.tempo_ratio_smoothed = target;
             return self.tempo_ratio_smoothed;
         }
 
-        let max_step = KEY_LOCK_TEMPO_SMOOTHING_STEP;
-        let delta = (target - self.tempo_ratio_smoothed).clamp(-max_step, max_step);
-        self.tempo_ratio_smoothed = (self.tempo_ratio_smoothed + delta).clamp(SPEED_MIN, SPEED_MAX);
-        target = self.tempo_ratio_smoothed;
+        let coefficient = 1.0 - (-(frames as f32) / time_constant_frames).exp();
+        let next = self.tempo_ratio_smoothed + (target - self.tempo_ratio_smoothed) * coefficient;
+        self.tempo_ratio_smoothed = if (target - next).abs() < TEMPO_GLIDE_SNAP {
+            target
+        } else {
+            next.clamp(SPEED_MIN, SPEED_MAX)
+        };
 
-        target
+        self.tempo_ratio_smoothed
     }
 
     pub(crate) fn tempo_ratio_smoothed(&self) -> f32 {
@@ -192,6 +252,11 @@ impl VoiceSlot {
         self.active && self.sample_id == sample_id
     }
 
+    /// Whether the voice was started from the given generation of its sample slot.
+    pub(crate) fn plays_generation(&self, sample_generation: u64) -> bool {
+        self.active && self.sample_generation == sample_generation
+    }
+
     /// Pause playback: set the paused flag. Does not change frame_pos.
     pub fn pause(&mut self) {
         self.paused = true;
@@ -201,4 +266,62 @@ impl VoiceSlot {
     pub fn resume(&mut self) {
         self.paused = false;
     }
+
+    /// Start replaying the `slice_frames` preceding the current position.
+    ///
+    /// Near the sample start the slice extends forward instead. The slice is clamped to
+    /// `sample_frames`.
+    pub(crate) fn start_stutter(&mut self, slice_frames: usize, sample_frames: usize) {
+        let start_frame = self
+            .frame_pos
+            .min(sample_frames)
+            .saturating_sub(slice_frames);
+        let len = slice_frames.min(sample_frames - start_frame);
+        self.stutter = (len > 0).then_some(StutterSlice {
+            start_frame,
+            len,
+            phase: 0,
+        });
+    }
+
+    /// Stop the stutter effect; playback continues from the regular position.
+    pub(crate) fn stop_stutter(&mut self) {
+        self.stutter = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glide(block_frames: usize, total_frames: usize) -> f32 {
+        let mut voice = VoiceSlot::with_sample_rate(1, 48_000.0);
+        let mut rendered = 0;
+        let mut ratio = 1.0;
+        while rendered < total_frames {
+            let frames = block_frames.min(total_frames - rendered);
+            ratio = voice.smooth_tempo_ratio(2.0, frames, 100.0);
+            rendered += frames;
+        }
+        ratio
+    }
+
+    #[test]
+    fn tempo_glide_reaches_63_percent_in_one_time_constant_at_any_block_size() {
+        let one_time_constant = 4_800;
+        let expected = 1.0 + (1.0 - (-1.0_f32).exp());
+
+        for block_frames in [32, 256, 480, 1_000] {
+            let ratio = glide(block_frames, one_time_constant);
+            assert!((ratio - expected).abs() < 1e-4, "{block_frames}: {ratio}");
+        }
+        assert_eq!(glide(512, 20 * one_time_constant), 2.0);
+    }
+
+    #[test]
+    fn zero_tempo_glide_jumps_to_target() {
+        let mut voice = VoiceSlot::with_sample_rate(1, 48_000.0);
+
+        assert_eq!(voice.smooth_tempo_ratio(1.5, 64, 0.0), 1.5);
+    }
 }
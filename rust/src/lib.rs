@@ -9,6 +9,17 @@ mod flitzis_looper_audio {
     #[pymodule_export]
     use super::audio_engine::AudioEngine;
 
+    #[pymodule_export]
+    use super::audio_engine::QueueBusy;
+
     #[pymodule_export]
     use super::messages::AudioMessage;
+
+    use pyo3::prelude::*;
+
+    #[pymodule_init]
+    fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        super::audio_engine::setup_logger(m.py());
+        Ok(())
+    }
 }
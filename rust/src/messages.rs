@@ -3,11 +3,14 @@
 //! This module defines the enums that serve as the wire format for messages passed through the
 //! ring buffer between the Python thread and the real-time audio thread.
 
+use crate::audio_engine::constants::{NUM_SAMPLES, SLOT_MASK_WORDS};
 use pyo3::prelude::*;
 use std::sync::Arc;
 use stratum_dsp::BeatGrid;
 
 pub(crate) const STEM_BUFFER_COUNT: usize = 5;
+/// Most speakers of a [`SpeakerLayout`]; one per accepted output channel.
+pub(crate) const SPEAKER_LAYOUT_MAX: usize = 32;
 pub(crate) const STEM_MASK_VOCALS: u8 = 1 << 0;
 pub(crate) const STEM_MASK_MELODY: u8 = 1 << 1;
 pub(crate) const STEM_MASK_BASS: u8 = 1 << 2;
@@ -31,6 +34,12 @@ pub(crate) struct PreparedStemSet {
     pub stems: [SampleBuffer; STEM_BUFFER_COUNT],
 }
 
+/// Sorted onset positions of a slot in output frames.
+pub(crate) type OnsetTable = Arc<[usize]>;
+
+/// Interleaved output frames of a pad's delay line, allocated off the audio thread.
+pub(crate) type PadDelayLine = Box<[f32]>;
+
 /// Message that is emitted from the audio thread.
 #[derive(Debug, Clone)]
 #[pyclass]
@@ -50,8 +59,78 @@ pub enum AudioMessage {
     /// Master output peak meter update (mono peak, post-sum and post-master volume).
     MasterPeak { peak: f32 },
 
+    /// Master limiter gain reduction in dB, current and recent peak; 0.0 while idle.
+    LimiterMeter {
+        reduction_db: f32,
+        peak_reduction_db: f32,
+    },
+
     /// Per-pad playback position in seconds (best-effort, low-rate).
     PadPlayhead { id: usize, position_s: f32 },
+
+    /// The audio callback panicked. The engine outputs silence until it is restarted.
+    EngineFault(),
+
+    /// The trigger that `play_sample` returned `handle` for did not start a voice, e.g.
+    /// because no voice was free.
+    TriggerDropped { handle: u64 },
+
+    /// The output stream reported an error, e.g. the device was disconnected.
+    ///
+    /// Playback does not recover on its own; restart the engine, possibly on another device.
+    DeviceError(String),
+
+    /// A `SeekSample` moved the voices of pad `id`; idle pads send no acknowledgement.
+    SeekAcknowledged { id: usize },
+
+    /// Pad `id`'s one-shot voices played to their end; sent right before its `SampleStopped`.
+    VoiceFinished { id: usize },
+
+    /// Response to `QueryPadLatency`: the pad's current processing latency in output frames.
+    PadLatency { id: usize, frames: usize },
+
+    /// Response to `QueryState`: the number of active voices and a bitmask of the sample slots
+    /// holding a sample, bit `id % 64` of word `id / 64` per slot.
+    StateSnapshot {
+        active_voices: u8,
+        loaded_mask: [u64; SLOT_MASK_WORDS],
+    },
+
+    /// Response to `QueryRemaining`: output frames and seconds until the pad's one-shot voices
+    /// end, both `None` while the pad loops indefinitely. Idle pads report 0.
+    PadRemaining {
+        id: usize,
+        remaining_frames: Option<usize>,
+        remaining_s: Option<f32>,
+    },
+
+    /// Playlist entry `entry` took over sample slot `id`.
+    PlaylistEntryActive { id: usize, entry: usize },
+
+    /// A running sequencer track reached `step` of its pattern.
+    SequencerStep { track: usize, step: usize },
+
+    /// A scene launched with `LaunchScene` was applied to `bank`.
+    SceneLaunched { bank: usize },
+
+    /// A scene launch for `bank` was dropped because the scheduler was full.
+    SceneDropped { bank: usize },
+
+    /// Pad `id` took a voice from `stolen_id` because the voice pool was full.
+    ///
+    /// Carries both pad priorities; `stolen_priority` is below `priority` unless the voice steal
+    /// policy took an equal-priority voice.
+    VoiceStolen {
+        id: usize,
+        priority: u8,
+        stolen_id: usize,
+        stolen_priority: u8,
+    },
+
+    /// Debug event: a start or seek position of pad `id` was moved onto an onset.
+    ///
+    /// `offset_frames` is the applied adjustment; negative values moved the start earlier.
+    TransientSnap { id: usize, offset_frames: i64 },
 }
 
 #[pymethods]
@@ -62,6 +141,16 @@ impl AudioMessage {
             AudioMessage::SampleStopped { id } => Some(*id),
             AudioMessage::PadPeak { id, peak: _ } => Some(*id),
             AudioMessage::PadPlayhead { id, position_s: _ } => Some(*id),
+            AudioMessage::PadLatency { id, frames: _ } => Some(*id),
+            AudioMessage::PadRemaining { id, .. } => Some(*id),
+            AudioMessage::PlaylistEntryActive { id, entry: _ } => Some(*id),
+            AudioMessage::TransientSnap {
+                id,
+                offset_frames: _,
+            } => Some(*id),
+            AudioMessage::VoiceStolen { id, .. } => Some(*id),
+            AudioMessage::SeekAcknowledged { id } => Some(*id),
+            AudioMessage::VoiceFinished { id } => Some(*id),
             AudioMessage::MasterPeak { peak: _ } => None,
             _ => None,
         }
@@ -81,12 +170,119 @@ impl AudioMessage {
         }
     }
 
+    /// `(reduction_db, peak_reduction_db)` of a `LimiterMeter` event.
+    pub fn limiter_reduction(&self) -> Option<(f32, f32)> {
+        match self {
+            AudioMessage::LimiterMeter {
+                reduction_db,
+                peak_reduction_db,
+            } => Some((*reduction_db, *peak_reduction_db)),
+            _ => None,
+        }
+    }
+
     pub fn pad_playhead(&self) -> Option<f32> {
         match self {
             AudioMessage::PadPlayhead { id: _, position_s } => Some(*position_s),
             _ => None,
         }
     }
+
+    pub fn pad_latency(&self) -> Option<usize> {
+        match self {
+            AudioMessage::PadLatency { id: _, frames } => Some(*frames),
+            _ => None,
+        }
+    }
+
+    /// `(remaining_frames, remaining_s)` of a `PadRemaining` answer; `(None, None)` means the
+    /// pad loops indefinitely.
+    pub fn pad_remaining(&self) -> Option<(Option<usize>, Option<f32>)> {
+        match self {
+            AudioMessage::PadRemaining {
+                id: _,
+                remaining_frames,
+                remaining_s,
+            } => Some((*remaining_frames, *remaining_s)),
+            _ => None,
+        }
+    }
+
+    pub fn playlist_entry(&self) -> Option<usize> {
+        match self {
+            AudioMessage::PlaylistEntryActive { id: _, entry } => Some(*entry),
+            _ => None,
+        }
+    }
+
+    pub fn sequencer_step(&self) -> Option<(usize, usize)> {
+        match self {
+            AudioMessage::SequencerStep { track, step } => Some((*track, *step)),
+            _ => None,
+        }
+    }
+
+    pub fn scene_bank(&self) -> Option<usize> {
+        match self {
+            AudioMessage::SceneLaunched { bank } | AudioMessage::SceneDropped { bank } => {
+                Some(*bank)
+            }
+            _ => None,
+        }
+    }
+
+    /// `(stolen_id, priority, stolen_priority)` of a `VoiceStolen` event.
+    pub fn voice_stolen(&self) -> Option<(usize, u8, u8)> {
+        match self {
+            AudioMessage::VoiceStolen {
+                id: _,
+                priority,
+                stolen_id,
+                stolen_priority,
+            } => Some((*stolen_id, *priority, *stolen_priority)),
+            _ => None,
+        }
+    }
+
+    /// Per sample slot, whether it holds a sample, from a `StateSnapshot`.
+    pub fn loaded_slots(&self) -> Option<Vec<bool>> {
+        match self {
+            AudioMessage::StateSnapshot {
+                active_voices: _,
+                loaded_mask,
+            } => Some(
+                (0..NUM_SAMPLES)
+                    .map(|id| loaded_mask[id / 64] & (1 << (id % 64)) != 0)
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn transient_snap(&self) -> Option<i64> {
+        match self {
+            AudioMessage::TransientSnap {
+                id: _,
+                offset_frames,
+            } => Some(*offset_frames),
+            _ => None,
+        }
+    }
+}
+
+impl AudioMessage {
+    /// High-rate meter and playhead updates that may be dropped under backpressure.
+    pub(crate) fn is_telemetry(&self) -> bool {
+        matches!(
+            self,
+            AudioMessage::PadPeak { .. }
+                | AudioMessage::MasterPeak { .. }
+                | AudioMessage::LimiterMeter { .. }
+                | AudioMessage::PadPlayhead { .. }
+                | AudioMessage::SequencerStep { .. }
+                | AudioMessage::TransientSnap { .. }
+        )
+    }
 }
 
 /// Quantization mode used by Rust-side pad trigger scheduling.
@@ -96,6 +292,46 @@ pub enum TriggerQuantization {
     Grid { step_64ths: u16 },
 }
 
+/// Whether a voice wraps at its loop end or stops there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    Loop,
+    OneShot,
+}
+
+/// How a trigger finds a voice when the pool is full and no lower-priority pad is playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VoiceStealPolicy {
+    /// Drop the trigger.
+    #[default]
+    Silent,
+    /// Take the voice of an equal-priority pad furthest through its sample.
+    StealOldest,
+    /// Take the voice of an equal-priority pad with the lowest gain.
+    StealQuietest,
+}
+
+/// Settings of one trigger that override its pad's; `None` keeps the pad's setting.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TriggerOptions {
+    /// Rate multiplier of the voice on top of its pad's rate.
+    pub speed: Option<f32>,
+    pub loop_mode: Option<LoopMode>,
+    /// Source position in seconds to start from instead of the pad's start.
+    pub start_s: Option<f32>,
+    /// Equal-power stereo pan from -1.0 (left) to 1.0 (right); mono outputs ignore it.
+    pub pan: Option<f32>,
+}
+
+/// Monitor signal that can be routed to its own pair of output channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorSource {
+    /// The preview voice.
+    Preview,
+    /// The pre-fader cue bus.
+    Cue,
+}
+
 /// Per-pad stem render source selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StemMixMode {
@@ -114,6 +350,7 @@ pub(crate) struct PadTimingMetadata {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ControlMessageClass {
     Test,
+    Query,
     PlaybackEvent,
     OrderedState,
     Publication,
@@ -129,9 +366,62 @@ pub(crate) enum ControlParameterKey {
     PadBpm(usize),
     PadGain(usize),
     PadEq(usize),
+    PadParams(usize),
+}
+
+/// Per-pad parameters bundled into one `SetPadParams` update.
+///
+/// `None` fields leave the current value unchanged. The payload is `Copy` and stays inline
+/// so the audio thread never has to free it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct PadParams {
+    /// Gain/Trim in dB.
+    pub gain_db: Option<f32>,
+    /// 3-band EQ gains in dB as `[low, mid, high]`.
+    pub eq_db: Option<[f32; 3]>,
+    /// Mute state, ramped like `SetPadFadeMute`.
+    pub mute: Option<bool>,
+}
+
+/// One sequencer step that triggers a pad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SequencerStep {
+    pub id: usize,
+    pub volume: f32,
+}
+
+/// Per-pad fade times overriding the instant trigger/stop and the default mute ramp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PadFades {
+    pub fade_in_ms: f32,
+    pub fade_out_ms: f32,
+}
+
+/// Trigger fade-in length mapped linearly from the trigger velocity.
+///
+/// Velocity 0 fades in over `soft_ms`, full velocity over `hard_ms`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct VelocityAttack {
+    pub soft_ms: f32,
+    pub hard_ms: f32,
+}
+
+/// Speaker azimuths in degrees, indexed by output channel, for outputs wider than stereo.
+///
+/// 0 is straight ahead and positive angles turn clockwise, so a quad rig in the usual
+/// FL, FR, RL, RR channel order is `[-45, 45, -135, 135]`. Only the first `speakers`
+/// azimuths are used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpeakerLayout {
+    pub azimuths: [f32; SPEAKER_LAYOUT_MAX],
+    pub speakers: usize,
 }
 
 /// Continuous or frequently updated audio parameters.
+///
+/// The callback applies these ahead of the control messages that arrived in the same block.
+/// Updates sent while control messages still wait travel behind them as
+/// [`ControlMessage::ApplyParameter`], so everything applies in the order it was sent.
 #[derive(Debug, Clone, Copy, PartialEq)]
 // Keep stable Set* message names aligned with the existing command/parameter API.
 #[allow(clippy::enum_variant_names)]
@@ -158,6 +448,9 @@ pub(crate) enum ControlParameterMessage {
         mid_db: f32,
         high_db: f32,
     },
+
+    /// Set several per-pad parameters at once; they take effect in the same callback.
+    SetPadParams { id: usize, params: PadParams },
 }
 
 #[cfg(test)]
@@ -177,31 +470,137 @@ impl ControlParameterMessage {
                 mid_db: _,
                 high_db: _,
             } => ControlParameterKey::PadEq(*id),
+            ControlParameterMessage::SetPadParams { id, params: _ } => {
+                ControlParameterKey::PadParams(*id)
+            }
         }
     }
 }
 
 /// Message that is emitted from the Python side.
+///
+/// The callback applies control messages strictly in the order they were sent.
 #[derive(Debug, Clone)]
 pub enum ControlMessage {
     /// Used for testing message passing functionality.
     Ping(),
 
+    /// Test-only hook that makes the audio thread panic while processing it.
+    #[cfg(test)]
+    PanicForTest(),
+
+    /// Apply the next message in the queue at the given output frame instead of on arrival.
+    ///
+    /// The callback renders up to that frame before applying it. Frames already passed apply
+    /// at the start of the next buffer.
+    AtFrame(u64),
+
+    /// Apply a parameter update through the ordered queue, so it can carry a frame stamp.
+    ///
+    /// Used to replay recorded automation. Live updates use the parameter queue instead.
+    SetParameter(ControlParameterMessage),
+
+    /// Apply a live parameter update after the control messages sent before it.
+    ///
+    /// Sent instead of using the parameter queue while control messages still wait, and
+    /// recorded like an update from that queue.
+    ApplyParameter(ControlParameterMessage),
+
+    /// Ask the audio thread for a pad's processing latency.
+    ///
+    /// Answered with `AudioMessage::PadLatency`. Key-locked voices trail their trigger by the
+    /// stretcher latency while dry voices report 0, so pads can be aligned against each other.
+    /// The pad's delay adds to either.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the pad to query
+    QueryPadLatency { id: usize },
+
+    /// Query the time left until a one-shot pad's voices end.
+    ///
+    /// Answered with `AudioMessage::PadRemaining`.
+    QueryRemaining { id: usize },
+
+    /// Query the number of active voices and which sample slots are loaded.
+    ///
+    /// Answered with `AudioMessage::StateSnapshot`.
+    QueryState(),
+
     /// Enable or disable BPM lock.
     SetBpmLock(bool),
 
     /// Enable or disable key lock (preserve pitch under tempo changes).
     SetKeyLock(bool),
 
+    /// Enable or disable the DC blocker on the final master output.
+    SetMasterDcBlocker(bool),
+
+    /// Enable or disable the peak limiter on the final master output.
+    SetMasterLimiter(bool),
+
+    /// Enable or disable periodic `LimiterMeter` events.
+    SetLimiterMeterEvents(bool),
+
+    /// Enable or disable summing the master to mono on both stereo output channels.
+    SetMonoOutput(bool),
+
+    /// Route a monitor signal to output channels `first_channel` and `first_channel + 1`,
+    /// or back to its default with `None`.
+    SetMonitorOutput {
+        source: MonitorSource,
+        first_channel: Option<usize>,
+    },
+
+    /// Set the time constant in milliseconds for voices gliding to a new tempo ratio.
+    SetTempoGlide(f32),
+
+    /// Set how many output frames stopped voices fade out over when their pad has no
+    /// fade-out time; 0 stops them instantly.
+    SetFadeSamples(u32),
+
+    /// Set the calibration trim of one physical output channel, applied after the master.
+    SetOutputTrim { channel: usize, gain: f32 },
+
     /// Enable or disable Key Lock for one pad.
     SetPadKeyLock { id: usize, enabled: bool },
 
+    /// Set a pad's voice priority; a full voice pool only steals from lower priorities.
+    SetPadPriority { id: usize, priority: u8 },
+
+    /// Set whether a full voice pool steals from equal-priority pads.
+    SetVoiceStealPolicy(VoiceStealPolicy),
+
+    /// Set the loop mode of pads without their own, e.g. one-shot for a sampler setup.
+    SetDefaultLoopMode(LoopMode),
+
+    /// Override a pad's loop mode, or follow the default again with `None`.
+    SetPadLoopMode { id: usize, mode: Option<LoopMode> },
+
+    /// Send a pad's pre-fader signal to the cue bus as well as the master mix.
+    SetPadCue { id: usize, enabled: bool },
+
+    /// Split cue: the master mix summed to mono on the first output channel, the cue bus on
+    /// the second.
+    SetCueSplit(bool),
+
+    /// Assign a pad to a choke group, or remove it from its group with `None`.
+    SetPadChokeGroup { id: usize, group: Option<u8> },
+
+    /// Set whether stopping one member of a choke group stops all of its members.
+    SetChokeGroupStopAll { group: u8, enabled: bool },
+
     /// Set bounded per-pad beatgrid/downbeat timing metadata.
     SetPadTimingMetadata {
         id: usize,
         metadata: PadTimingMetadata,
     },
 
+    /// Snap computed start and seek positions of a pad to its nearest onset.
+    ///
+    /// `window_ms` bounds the adjustment; `None` disables snapping. Pads without a published
+    /// onset table start where they would have anyway.
+    SetPadTransientSnap { id: usize, window_ms: Option<f32> },
+
     /// Request transport downbeat anchoring from a selected playing pad.
     AnchorTransportPhaseFromPad { id: usize },
 
@@ -214,16 +613,119 @@ pub enum ControlMessage {
         end_s: Option<f32>,
     },
 
+    /// Fade a pad out to silence or back in while its voices keep running.
+    ///
+    /// Unlike stopping or pausing, the pad stays in time, so it can drop out and come
+    /// back on the beat.
+    SetPadFadeMute { id: usize, muted: bool },
+
+    /// Chop a pad into equal slices of its loop region.
+    ///
+    /// With `slices > 1`, each trigger starts at the next slice (`index * len / slices`) and
+    /// wraps around; `1` turns slicing off.
+    SetPadSlices { id: usize, slices: u16 },
+
+    /// Scale a pad's synced or speed-scaled rate by an exact `(numerator, denominator)`;
+    /// `None` returns the pad to normal sync.
+    SetPadRateRatio {
+        id: usize,
+        ratio: Option<(u16, u16)>,
+    },
+
+    /// Put a bank into keyboard mode, where each pad plays `source` transposed by its semitone
+    /// distance from `root_pad`; `None` restores the bank's own slots. Stops the bank's voices.
+    SetKeyboardMode {
+        bank: usize,
+        mode: Option<(usize, usize)>,
+    },
+
+    /// Override a pad's fade-in (trigger, unmute) and fade-out (stop, mute) times; `None`
+    /// restores the engine defaults.
+    SetPadFades { id: usize, fades: Option<PadFades> },
+
+    /// Scale the trigger fade-in of pads without their own fades with velocity, or restore
+    /// the fixed instant attack with `None`.
+    SetVelocityAttack(Option<VelocityAttack>),
+
+    /// Soft-clip a pad's voices to `ceiling` (linear) before they reach the master.
+    ///
+    /// Disabled pads stay linear.
+    SetPadClip {
+        id: usize,
+        enabled: bool,
+        ceiling: f32,
+    },
+
+    /// Delay a pad's output by `delay_ms` for time alignment; 0 bypasses the delay line.
+    ///
+    /// The first nonzero delay of a pad carries its `line`; later updates reuse it.
+    SetPadDelay {
+        id: usize,
+        delay_ms: f32,
+        line: Option<PadDelayLine>,
+    },
+
+    /// Pan placed pads across the speakers of an output wider than stereo, or play every pad
+    /// on the first two channels with `None`.
+    SetSpeakerLayout(Option<SpeakerLayout>),
+
+    /// Place a pad at an azimuth in degrees on the speaker layout, or back on the first two
+    /// channels with `None`.
+    SetPadPosition {
+        id: usize,
+        azimuth_degrees: Option<f32>,
+    },
+
+    /// Ramp one EQ band (0 low, 1 mid, 2 high) of a pad linearly in dB from its current gain
+    /// to `target_db` over `duration_frames`. A later `SetPadEq` cancels the ramp.
+    AutomatePadEq {
+        id: usize,
+        band: usize,
+        target_db: f32,
+        duration_frames: u64,
+    },
+
+    /// Store the current volume, pad gains, pad EQ, mutes and cue routing as a snapshot.
+    StoreSnapshot { slot: usize },
+
+    /// Morph from the current parameters to a stored snapshot over `duration_frames`.
+    MorphToSnapshot { slot: usize, duration_frames: u64 },
+
     /// Set Rust-side trigger quantization mode for future pad triggers.
     SetTriggerQuantization(TriggerQuantization),
 
-    /// Publish a loaded sample into an audio-thread slot.
+    /// Set MPC-style swing in percent for quantized grid targets computed from now on.
+    SetSwing(f32),
+
+    /// Move quantized grid targets earlier by the measured output latency, so triggers are
+    /// heard on the grid. Ignored while the backend reports no timestamps.
+    SetLatencyCompensation(bool),
 
+    /// Publish a loaded sample into an audio-thread slot.
+    ///
+    /// Voices of the replaced sample fade out on it and their pads report `SampleStopped`.
     ///
     /// # Parameters
     /// * `id` - Unique identifier for the sample slot (0..NUM_SAMPLES)
     /// * `sample` - Pre-decoded immutable sample buffer (shared handle)
-    LoadSample { id: usize, sample: SampleBuffer },
+    /// * `onsets` - Analyzed onset table for the sample, in output frames, if any
+    LoadSample {
+        id: usize,
+        sample: SampleBuffer,
+        onsets: Option<OnsetTable>,
+    },
+
+    /// Publish playlist entry `entry` to replace a slot's sample at its next loop boundary.
+    ///
+    /// Idle slots swap right away; playing voices carry on into the new sample. The entry's
+    /// analyzed `bpm`, if any, becomes the pad BPM with the swap. Reported with
+    /// `AudioMessage::PlaylistEntryActive` once the swap happened.
+    QueueSampleSwap {
+        id: usize,
+        entry: usize,
+        sample: SampleBuffer,
+        bpm: Option<f32>,
+    },
 
     /// Publish validated prepared stems into an audio-thread slot.
     ///
@@ -231,6 +733,11 @@ pub enum ControlMessage {
     /// contain file paths, Python objects, or copied full audio payloads.
     PublishPreparedStems { id: usize, stems: PreparedStemSet },
 
+    /// Publish a slot's onset table for transient snapping.
+    ///
+    /// Loading or unloading the slot drops the table again.
+    PublishPadOnsets { id: usize, onsets: OnsetTable },
+
     /// Select whether a pad renders from the full mix or all prepared stems.
     ///
     /// The source-version hash is used by all-stems mode to reject stale updates. Full-mix mode
@@ -265,12 +772,52 @@ pub enum ControlMessage {
     /// * `volume` - Playback volume (0.0 to 1.0)
     PlaySampleExclusive { id: usize, volume: f32 },
 
+    /// Play a loaded sample and stop it automatically after a number of output frames.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the sample to play
+    /// * `volume` - Playback volume (0.0 to 1.0)
+    /// * `frames` - Output frames to play before the voice stops
+    PlaySampleFor { id: usize, volume: f32, frames: u64 },
+
+    /// Play a loaded sample on the cue bus only, until it is promoted to the master mix.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the sample to play
+    /// * `volume` - Playback volume (0.0 to 1.0)
+    PlaySampleCued { id: usize, volume: f32 },
+
+    /// Play a loaded sample with per-trigger settings, leaving other voices alone.
+    ///
+    /// A speed override multiplies the pad's rate, clamped to the supported speed range.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the sample to play
+    /// * `volume` - Playback volume (0.0 to 1.0)
+    /// * `options` - Settings of this voice that override the pad's
+    /// * `handle` - Voice handle for `StopVoice`, reported in `TriggerDropped` if the voice
+    ///   does not start
+    PlaySampleWith {
+        id: usize,
+        volume: f32,
+        options: TriggerOptions,
+        handle: u64,
+    },
+
+    /// Fade a sample's cue-only voices into the master mix without restarting them.
+    PromoteToMaster { id: usize },
+
     /// Stop all active voices for a sample.
     ///
     /// # Parameters
     /// * `id` - Identifier of the sample to stop
     StopSample { id: usize },
 
+    /// Stop the voice started with `handle`, leaving other voices of its sample alone.
+    ///
+    /// Unknown or finished handles are ignored.
+    StopVoice { handle: u64 },
+
     /// Stop all currently active voices.
     StopAll(),
 
@@ -286,14 +833,68 @@ pub enum ControlMessage {
     /// If the sample was not paused, this has no effect.
     ResumeSample { id: usize },
 
+    /// Start or stop the stutter effect on a playing sample.
+    ///
+    /// While on, the voice repeatedly replays the last `slice_frames` before the capture
+    /// position. Turning it off resumes from where regular playback would have been.
+    Stutter {
+        id: usize,
+        slice_frames: usize,
+        on: bool,
+    },
+
+    /// Clear a pad's filter and time-stretch state without stopping its voices.
+    ResetPadDsp { id: usize },
+
     /// Seek an active or paused sample voice to a source position in seconds.
     ///
     /// If the sample has no active or paused voice, this has no effect.
     SeekSample { id: usize, position_s: f32 },
 
+    /// Loop a region of a loaded sample on the preview voice without changing the pad.
+    ///
+    /// # Parameters
+    /// * `id` - Identifier of the sample to audition
+    /// * `start_s` - Region start in seconds
+    /// * `end_s` - Region end in seconds (exclusive)
+    PreviewLoopRegion { id: usize, start_s: f32, end_s: f32 },
+
+    /// Stop the preview voice.
+    StopPreviewLoop(),
+
+    /// Switch a bank to a scene at the next bar, replacing any scene launch still pending.
+    ///
+    /// # Parameters
+    /// * `bank` - Bank whose pads are switched
+    /// * `pads` - Bit `i` set if pad `i` of the bank plays after the switch
+    LaunchScene { bank: usize, pads: u64 },
+
+    /// Cancel a scene launch that has not fired yet.
+    CancelScene(),
+
+    /// Start staging a step sequence of `steps` 1/16 notes for `track`, all rests.
+    BeginSequence { track: usize, steps: usize },
+
+    /// Set one step of the sequence staged by `BeginSequence`; `None` is a rest.
+    SetSequenceStep {
+        track: usize,
+        step: usize,
+        event: Option<SequencerStep>,
+    },
+
+    /// Publish the staged sequence of `track`; a running sequencer swaps it in at the next bar.
+    CommitSequence { track: usize },
+
+    /// Mute or unmute a sequencer track without touching its pattern.
+    SetSequencerTrackMute { track: usize, muted: bool },
+
+    /// Start or stop the step sequencer.
+    SetSequencerRunning(bool),
+
     /// Unload a sample slot.
     ///
-    /// This stops all active voices for the sample and clears the sample buffer in the slot.
+    /// This stops all active voices for the sample, clears the sample buffer in the slot and
+    /// resets the pad gain to 0 dB.
     ///
     /// # Parameters
     /// * `id` - Identifier of the sample slot to unload
@@ -304,24 +905,79 @@ pub enum ControlMessage {
 impl ControlMessage {
     pub(crate) fn class(&self) -> ControlMessageClass {
         match self {
-            ControlMessage::Ping() => ControlMessageClass::Test,
+            ControlMessage::Ping() | ControlMessage::PanicForTest() => ControlMessageClass::Test,
+            ControlMessage::QueryPadLatency { .. }
+            | ControlMessage::QueryRemaining { .. }
+            | ControlMessage::QueryState() => ControlMessageClass::Query,
+            ControlMessage::AtFrame(_)
+            | ControlMessage::SetParameter(_)
+            | ControlMessage::ApplyParameter(_) => ControlMessageClass::OrderedState,
             ControlMessage::PlaySample { .. }
             | ControlMessage::PlaySampleExclusive { .. }
+            | ControlMessage::PlaySampleFor { .. }
+            | ControlMessage::PlaySampleCued { .. }
+            | ControlMessage::PlaySampleWith { .. }
+            | ControlMessage::PromoteToMaster { .. }
             | ControlMessage::StopSample { .. }
+            | ControlMessage::StopVoice { .. }
             | ControlMessage::StopAll()
             | ControlMessage::PauseSample { .. }
             | ControlMessage::ResumeSample { .. }
-            | ControlMessage::SeekSample { .. } => ControlMessageClass::PlaybackEvent,
-            ControlMessage::LoadSample { .. } | ControlMessage::PublishPreparedStems { .. } => {
-                ControlMessageClass::Publication
-            }
+            | ControlMessage::Stutter { .. }
+            | ControlMessage::ResetPadDsp { .. }
+            | ControlMessage::SeekSample { .. }
+            | ControlMessage::PreviewLoopRegion { .. }
+            | ControlMessage::StopPreviewLoop()
+            | ControlMessage::LaunchScene { .. }
+            | ControlMessage::CancelScene() => ControlMessageClass::PlaybackEvent,
+            ControlMessage::LoadSample { .. }
+            | ControlMessage::QueueSampleSwap { .. }
+            | ControlMessage::PublishPreparedStems { .. }
+            | ControlMessage::PublishPadOnsets { .. } => ControlMessageClass::Publication,
             ControlMessage::SetBpmLock(_)
             | ControlMessage::SetKeyLock(_)
+            | ControlMessage::SetMasterDcBlocker(_)
+            | ControlMessage::SetMasterLimiter(_)
+            | ControlMessage::SetLimiterMeterEvents(_)
+            | ControlMessage::SetMonoOutput(_)
+            | ControlMessage::SetMonitorOutput { .. }
+            | ControlMessage::SetOutputTrim { .. }
+            | ControlMessage::SetTempoGlide(_)
+            | ControlMessage::SetFadeSamples(_)
             | ControlMessage::SetPadKeyLock { .. }
+            | ControlMessage::SetPadChokeGroup { .. }
+            | ControlMessage::SetPadPriority { .. }
+            | ControlMessage::SetVoiceStealPolicy(_)
+            | ControlMessage::SetDefaultLoopMode(_)
+            | ControlMessage::SetPadLoopMode { .. }
+            | ControlMessage::SetPadCue { .. }
+            | ControlMessage::SetCueSplit(_)
+            | ControlMessage::SetChokeGroupStopAll { .. }
             | ControlMessage::SetPadTimingMetadata { .. }
+            | ControlMessage::SetPadTransientSnap { .. }
             | ControlMessage::AnchorTransportPhaseFromPad { .. }
             | ControlMessage::SetPadLoopRegion { .. }
+            | ControlMessage::SetPadFadeMute { .. }
+            | ControlMessage::SetPadSlices { .. }
+            | ControlMessage::SetPadRateRatio { .. }
+            | ControlMessage::SetPadFades { .. }
+            | ControlMessage::SetVelocityAttack(_)
+            | ControlMessage::SetPadClip { .. }
+            | ControlMessage::SetPadDelay { .. }
+            | ControlMessage::SetSpeakerLayout(_)
+            | ControlMessage::SetPadPosition { .. }
+            | ControlMessage::AutomatePadEq { .. }
+            | ControlMessage::StoreSnapshot { .. }
+            | ControlMessage::MorphToSnapshot { .. }
+            | ControlMessage::SetKeyboardMode { .. }
             | ControlMessage::SetTriggerQuantization(_)
+            | ControlMessage::SetSwing(_)
+            | ControlMessage::SetLatencyCompensation(_)
+            | ControlMessage::BeginSequence { .. }
+            | ControlMessage::SetSequenceStep { .. }
+            | ControlMessage::CommitSequence { .. }
+            | ControlMessage::SetSequencerTrackMute { .. }
+            | ControlMessage::SetSequencerRunning(_)
             | ControlMessage::SetStemMixMode { .. }
             | ControlMessage::SetStemEnabledMask { .. }
             | ControlMessage::UnloadSample { .. } => ControlMessageClass::OrderedState,
@@ -334,6 +990,15 @@ pub(crate) struct SampleAnalysis {
     pub bpm: f32,
     pub key: String,
     pub beat_grid: BeatGrid,
+    /// Confidence of the detector's raw tempo estimate (0.0..=1.0). Low values mean the
+    /// BPM should be confirmed by the user.
+    pub bpm_confidence: f32,
+    /// Key detection confidence (0.0..=1.0).
+    pub key_confidence: f32,
+    /// Beat grid stability (0.0..=1.0).
+    pub grid_stability: f32,
+    /// Onset times in seconds from the start of the sample.
+    pub onsets_s: Vec<f32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -382,6 +1047,17 @@ pub enum LoaderEvent {
         error: String,
     },
 
+    /// A playlist entry was decoded, analyzed and queued on its slot.
+    ///
+    /// The slot switches to it at its next loop boundary, reported by
+    /// `AudioMessage::PlaylistEntryActive`.
+    PlaylistEntryLoaded {
+        id: usize,
+        entry: usize,
+        duration_s: f32,
+        analysis: Option<SampleAnalysis>,
+    },
+
     /// A per-pad background task started.
     TaskStarted {
         id: usize,
@@ -452,6 +1128,52 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn play_sample_for_message_carries_fixed_size_duration() {
+        let message = ControlMessage::PlaySampleFor {
+            id: 2,
+            volume: 0.5,
+            frames: 48_000,
+        };
+
+        assert!(matches!(
+            message,
+            ControlMessage::PlaySampleFor {
+                id: 2,
+                frames: 48_000,
+                ..
+            }
+        ));
+        assert_eq!(message.class(), ControlMessageClass::PlaybackEvent);
+    }
+
+    #[test]
+    fn reset_pad_dsp_message_is_a_playback_event() {
+        let message = ControlMessage::ResetPadDsp { id: 5 };
+
+        assert!(matches!(message, ControlMessage::ResetPadDsp { id: 5 }));
+        assert_eq!(message.class(), ControlMessageClass::PlaybackEvent);
+    }
+
+    #[test]
+    fn stutter_message_carries_fixed_size_slice() {
+        let message = ControlMessage::Stutter {
+            id: 3,
+            slice_frames: 512,
+            on: true,
+        };
+
+        assert!(matches!(
+            message,
+            ControlMessage::Stutter {
+                id: 3,
+                slice_frames: 512,
+                on: true
+            }
+        ));
+        assert_eq!(message.class(), ControlMessageClass::PlaybackEvent);
+    }
+
     #[test]
     fn pad_timing_metadata_message_carries_fixed_size_anchor() {
         let message = ControlMessage::SetPadTimingMetadata {
@@ -578,6 +1300,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn choke_group_messages_carry_bounded_state() {
+        let membership = ControlMessage::SetPadChokeGroup {
+            id: 3,
+            group: Some(2),
+        };
+        let stop_all = ControlMessage::SetChokeGroupStopAll {
+            group: 2,
+            enabled: true,
+        };
+
+        assert!(matches!(
+            membership,
+            ControlMessage::SetPadChokeGroup {
+                id: 3,
+                group: Some(2)
+            }
+        ));
+        assert!(matches!(
+            stop_all,
+            ControlMessage::SetChokeGroupStopAll {
+                group: 2,
+                enabled: true
+            }
+        ));
+        assert_eq!(membership.class(), ControlMessageClass::OrderedState);
+        assert_eq!(stop_all.class(), ControlMessageClass::OrderedState);
+    }
+
     #[test]
     fn pad_key_lock_message_carries_bounded_state() {
         let message = ControlMessage::SetPadKeyLock {
@@ -615,10 +1366,40 @@ mod tests {
                     channels: 1,
                     samples: Arc::from([0.0_f32].as_slice()),
                 },
+                onsets: None,
+            }
+            .class(),
+            ControlMessageClass::Publication
+        );
+        assert_eq!(
+            ControlMessage::PublishPadOnsets {
+                id: 1,
+                onsets: Arc::from([0_usize, 512].as_slice()),
             }
             .class(),
             ControlMessageClass::Publication
         );
+        assert_eq!(
+            ControlMessage::QueueSampleSwap {
+                id: 1,
+                entry: 2,
+                sample: SampleBuffer {
+                    channels: 1,
+                    samples: Arc::from([0.0_f32].as_slice()),
+                },
+                bpm: None,
+            }
+            .class(),
+            ControlMessageClass::Publication
+        );
+        assert_eq!(
+            ControlMessage::SetPadTransientSnap {
+                id: 1,
+                window_ms: Some(20.0),
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
         assert_eq!(
             ControlMessage::SetPadLoopRegion {
                 id: 1,
@@ -628,10 +1409,270 @@ mod tests {
             .class(),
             ControlMessageClass::OrderedState
         );
+        assert_eq!(
+            ControlMessage::SetPadFadeMute { id: 1, muted: true }.class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadSlices { id: 1, slices: 16 }.class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadRateRatio {
+                id: 1,
+                ratio: Some((3, 2))
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadFades {
+                id: 1,
+                fades: Some(PadFades {
+                    fade_in_ms: 2_000.0,
+                    fade_out_ms: 0.0
+                })
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetVelocityAttack(Some(VelocityAttack {
+                soft_ms: 20.0,
+                hard_ms: 2.0
+            }))
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadPriority {
+                id: 1,
+                priority: 200
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetVoiceStealPolicy(VoiceStealPolicy::StealOldest).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadClip {
+                id: 1,
+                enabled: true,
+                ceiling: 0.5
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadDelay {
+                id: 1,
+                delay_ms: 12.5,
+                line: None,
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetSpeakerLayout(None).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadPosition {
+                id: 1,
+                azimuth_degrees: Some(90.0)
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::AutomatePadEq {
+                id: 1,
+                band: 0,
+                target_db: -60.0,
+                duration_frames: 48_000
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetOutputTrim {
+                channel: 1,
+                gain: 0.8
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetKeyboardMode {
+                bank: 2,
+                mode: Some((72, 12))
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetTempoGlide(120.0).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetFadeSamples(256).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::AtFrame(48_000).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::ApplyParameter(ControlParameterMessage::SetVolume(0.5)).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetParameter(ControlParameterMessage::SetVolume(0.5)).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::QueryPadLatency { id: 1 }.class(),
+            ControlMessageClass::Query
+        );
+        assert_eq!(
+            ControlMessage::QueryRemaining { id: 1 }.class(),
+            ControlMessageClass::Query
+        );
+        assert_eq!(
+            ControlMessage::QueryState().class(),
+            ControlMessageClass::Query
+        );
         assert_eq!(
             ControlMessage::SetTriggerQuantization(TriggerQuantization::Immediate).class(),
             ControlMessageClass::OrderedState
         );
+        assert_eq!(
+            ControlMessage::SetSwing(55.0).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetLatencyCompensation(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetDefaultLoopMode(LoopMode::OneShot).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetPadLoopMode {
+                id: 1,
+                mode: Some(LoopMode::Loop)
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetSequenceStep {
+                track: 0,
+                step: 3,
+                event: Some(SequencerStep { id: 1, volume: 0.8 })
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetSequencerRunning(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::LaunchScene {
+                bank: 1,
+                pads: 0b101
+            }
+            .class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::CancelScene().class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::PlaySampleCued { id: 1, volume: 1.0 }.class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::PlaySampleWith {
+                id: 1,
+                volume: 1.0,
+                options: TriggerOptions {
+                    speed: Some(0.5),
+                    loop_mode: Some(LoopMode::OneShot),
+                    start_s: Some(1.5),
+                    pan: Some(-0.5),
+                },
+                handle: 3,
+            }
+            .class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::StopVoice { handle: 3 }.class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::PromoteToMaster { id: 1 }.class(),
+            ControlMessageClass::PlaybackEvent
+        );
+        assert_eq!(
+            ControlMessage::SetPadCue {
+                id: 1,
+                enabled: true
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetCueSplit(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetMasterLimiter(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetLimiterMeterEvents(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetMonoOutput(true).class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::SetMonitorOutput {
+                source: MonitorSource::Cue,
+                first_channel: Some(2)
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::StoreSnapshot { slot: 0 }.class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::MorphToSnapshot {
+                slot: 1,
+                duration_frames: 48_000
+            }
+            .class(),
+            ControlMessageClass::OrderedState
+        );
+        assert_eq!(
+            ControlMessage::PreviewLoopRegion {
+                id: 1,
+                start_s: 0.5,
+                end_s: 1.5
+            }
+            .class(),
+            ControlMessageClass::PlaybackEvent
+        );
         assert_eq!(
             ControlMessage::SetPadKeyLock {
                 id: 1,
@@ -666,5 +1707,13 @@ mod tests {
             .key(),
             ControlParameterKey::PadEq(4)
         );
+        assert_eq!(
+            ControlParameterMessage::SetPadParams {
+                id: 5,
+                params: PadParams::default(),
+            }
+            .key(),
+            ControlParameterKey::PadParams(5)
+        );
     }
 }
@@ -25,6 +25,9 @@ PAD_EQ_DB_MAX = 6.0
 PAD_LOOP_BARS_DEFAULT = 8.0
 PAD_LOOP_BARS_MIN = 0.5
 PAD_LOOP_BARS_GRANULARITY = 0.5
+AUTO_LOOP_MAX_ERROR_BARS = 0.25
+AUTO_LOOP_PREFERRED_BARS = (1.0, 2.0, 4.0, 8.0)
+PAD_RATE_RATIO_TERM_MAX = 16
 
 # Offline Demucs stem generation
 DEFAULT_DEMUCS_SHIFTS = 1
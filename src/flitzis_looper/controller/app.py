@@ -126,6 +126,9 @@ class AppController:
         if isinstance(msg, AudioMessage.SampleStopped):
             self.transport.playback.handle_sample_stopped_message(msg)
 
+        if isinstance(msg, AudioMessage.PlaylistEntryActive):
+            self.loader.handle_playlist_entry_active_message(msg)
+
     @property
     def project(self) -> ProjectState:
         return self._project
@@ -18,7 +18,7 @@ if TYPE_CHECKING:
     from collections.abc import Callable
 
     from flitzis_looper.models import StemCacheEntry
-    from flitzis_looper_audio import AudioEngine
+    from flitzis_looper_audio import AudioEngine, AudioMessage
 
 
 _PadValue = TypeVar("_PadValue")
@@ -77,8 +77,12 @@ class LoaderController(BaseController):
             return
 
         changed = False
+        for sample_id, playlist in enumerate(self._project.pad_playlist):
+            if playlist is not None and self._restore_playlist(sample_id, playlist):
+                changed = True
+
         for sample_id, path in enumerate(self._project.sample_paths):
-            if path is None:
+            if path is None or self._project.pad_playlist[sample_id] is not None:
                 continue
 
             rel = self._parse_cached_sample_path(path)
@@ -186,6 +190,35 @@ class LoaderController(BaseController):
         with suppress(OSError):
             (Path.cwd() / rel).unlink(missing_ok=True)
 
+    def set_playlist(self, sample_id: int, paths: list[str], position: int = 0) -> None:
+        """Let a list of audio files take turns on a pad, starting at `position`."""
+        validate_sample_id(sample_id)
+        self._audio.set_playlist(sample_id, paths, position)
+        self._project.pad_playlist[sample_id] = list(paths)
+        self._project.pad_playlist_position[sample_id] = position
+        self._mark_project_changed()
+
+    def clear_playlist(self, sample_id: int) -> None:
+        """Drop a pad's playlist; the active entry stays loaded."""
+        validate_sample_id(sample_id)
+        if self._project.pad_playlist[sample_id] is None:
+            return
+
+        self._audio.clear_playlist(sample_id)
+        self._project.pad_playlist[sample_id] = None
+        self._project.pad_playlist_position[sample_id] = 0
+        self._mark_project_changed()
+
+    def handle_playlist_entry_active_message(self, msg: AudioMessage.PlaylistEntryActive) -> None:
+        sample_id = msg.sample_id()
+        entry = msg.playlist_entry()
+        if sample_id is None or entry is None or self._project.pad_playlist[sample_id] is None:
+            return
+
+        if self._project.pad_playlist_position[sample_id] != entry:
+            self._project.pad_playlist_position[sample_id] = entry
+            self._mark_project_changed()
+
     def analyze_sample_async(self, sample_id: int) -> None:
         """Analyze a previously loaded sample asynchronously."""
         validate_sample_id(sample_id)
@@ -331,6 +364,19 @@ class LoaderController(BaseController):
                 sample_id,
                 defaults.pad_key_lock[sample_id],
             ),
+            _reset_pad_value(
+                self._project.pad_rate_ratio,
+                sample_id,
+                defaults.pad_rate_ratio[sample_id],
+            ),
+            _reset_pad_value(
+                self._project.pad_playlist, sample_id, defaults.pad_playlist[sample_id]
+            ),
+            _reset_pad_value(
+                self._project.pad_playlist_position,
+                sample_id,
+                defaults.pad_playlist_position[sample_id],
+            ),
             _reset_pad_value(self._project.manual_bpm, sample_id, defaults.manual_bpm[sample_id]),
             _reset_pad_value(self._project.manual_key, sample_id, defaults.manual_key[sample_id]),
         ))
@@ -386,6 +432,7 @@ class LoaderController(BaseController):
             defaults.pad_loop_end_s[sample_id],
         )
         self._audio.set_pad_key_lock(sample_id, defaults.pad_key_lock[sample_id])
+        self._audio.clear_pad_rate_ratio(sample_id)
 
     def _handle_loader_started(self, sample_id: int, _event: dict[str, object]) -> None:
         if not self._matches_load_request(sample_id, _event):
@@ -681,6 +728,19 @@ class LoaderController(BaseController):
         self._on_pad_bpm_changed(sample_id)
         self._mark_project_changed()
 
+    def _restore_playlist(self, sample_id: int, playlist: list[str]) -> bool:
+        saved_position = self._project.pad_playlist_position[sample_id]
+        position = min(saved_position, len(playlist) - 1)
+        try:
+            self._audio.set_playlist(sample_id, playlist, position)
+        except RuntimeError, ValueError:
+            self._project.pad_playlist[sample_id] = None
+            self._project.pad_playlist_position[sample_id] = 0
+            return True
+
+        self._project.pad_playlist_position[sample_id] = position
+        return position != saved_position
+
     def _clear_restored_pad(self, sample_id: int) -> None:
         self._reset_unloaded_pad_defaults(sample_id)
         self._on_pad_bpm_changed(sample_id)
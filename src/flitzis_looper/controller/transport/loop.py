@@ -2,6 +2,8 @@ import math
 from typing import TYPE_CHECKING
 
 from flitzis_looper.constants import (
+    AUTO_LOOP_MAX_ERROR_BARS,
+    AUTO_LOOP_PREFERRED_BARS,
     PAD_LOOP_BARS_DEFAULT,
     PAD_LOOP_BARS_GRANULARITY,
     PAD_LOOP_BARS_MIN,
@@ -14,6 +16,35 @@ if TYPE_CHECKING:
     from flitzis_looper.controller.transport import TransportController
 
 
+def nearest_whole_loop_bars(
+    duration_s: float,
+    bpm: float,
+    *,
+    max_error_bars: float = AUTO_LOOP_MAX_ERROR_BARS,
+    preferred_bars: tuple[float, ...] = AUTO_LOOP_PREFERRED_BARS,
+) -> float | None:
+    """Return the whole bar count a loop of ``duration_s`` most likely spans.
+
+    Counts from ``preferred_bars`` win over other whole counts when both are within
+    ``max_error_bars`` of the measured length. Returns None when no whole count is close
+    enough.
+    """
+    if not math.isfinite(duration_s) or duration_s <= 0.0 or not math.isfinite(bpm) or bpm <= 0.0:
+        return None
+
+    measured_bars = duration_s * bpm / 240.0
+    preferred = [
+        bars for bars in preferred_bars if abs(bars - measured_bars) <= max_error_bars + 1e-9
+    ]
+    if preferred:
+        return float(min(preferred, key=lambda bars: abs(bars - measured_bars)))
+
+    nearest = float(max(1, round(measured_bars)))
+    if abs(nearest - measured_bars) > max_error_bars + 1e-9:
+        return None
+    return nearest
+
+
 class PadLoopController:
     """Per-pad loop region manipulation."""
 
@@ -258,6 +289,53 @@ class PadLoopController:
             return None
         return remaining_s / bar_s
 
+    def auto_loop(
+        self,
+        sample_id: int,
+        *,
+        max_error_bars: float = AUTO_LOOP_MAX_ERROR_BARS,
+        preferred_bars: tuple[float, ...] = AUTO_LOOP_PREFERRED_BARS,
+    ) -> float | None:
+        """Auto-loop a pad over the whole bar count closest to its length from loop start.
+
+        Bars are measured at the pad's effective BPM, so the loop stays locked when BPM lock
+        stretches it to the master tempo. Returns the applied bar count, or None when the
+        length is too far from a whole number of bars.
+        """
+        validate_sample_id(sample_id)
+
+        if self._project.sample_paths[sample_id] is None:
+            return None
+
+        bpm = normalize_bpm(self._bpm.effective_bpm(sample_id))
+        duration_s = self._project.sample_durations[sample_id]
+        if bpm is None or duration_s is None:
+            return None
+
+        start_s = self._quantize_time_to_cached_samples(
+            float(self._project.pad_loop_start_s[sample_id])
+        )
+        bars = nearest_whole_loop_bars(
+            float(duration_s) - start_s,
+            bpm,
+            max_error_bars=max_error_bars,
+            preferred_bars=preferred_bars,
+        )
+        if bars is None:
+            return None
+
+        changed = (
+            not self._project.pad_loop_auto[sample_id]
+            or self._project.pad_loop_bars[sample_id] != bars
+        )
+        self._project.pad_loop_auto[sample_id] = True
+        self._project.pad_loop_bars[sample_id] = bars
+        if changed:
+            self._transport._mark_project_changed()
+
+        self._apply_effective_pad_loop_region_to_audio(sample_id)
+        return bars
+
     def _effective_pad_loop_region(self, sample_id: int) -> tuple[float, float | None]:
         start_s = float(self._project.pad_loop_start_s[sample_id])
         end_s = self._project.pad_loop_end_s[sample_id]
@@ -3,7 +3,7 @@ from typing import TYPE_CHECKING
 from flitzis_looper.audio_gain import clamp_gain_db
 from flitzis_looper.constants import PAD_EQ_DB_MAX, PAD_EQ_DB_MIN
 from flitzis_looper.controller.validation import ensure_finite
-from flitzis_looper.models import validate_sample_id
+from flitzis_looper.models import validate_pad_rate_ratio, validate_sample_id
 
 if TYPE_CHECKING:
     from flitzis_looper.controller.transport import TransportController
@@ -65,6 +65,27 @@ class PadController:
         validate_sample_id(sample_id)
         self.set_pad_key_lock(sample_id, enabled=not self._project.pad_key_lock[sample_id])
 
+    def set_pad_rate_ratio(self, sample_id: int, numerator: int, denominator: int) -> None:
+        """Lock one pad to the exact rate `numerator / denominator`, overriding BPM sync."""
+        validate_sample_id(sample_id)
+        ratio = validate_pad_rate_ratio(numerator, denominator)
+        if self._project.pad_rate_ratio[sample_id] == ratio:
+            return
+
+        self._audio.set_pad_rate_ratio(sample_id, numerator, denominator)
+        self._project.pad_rate_ratio[sample_id] = ratio
+        self._transport._mark_project_changed()
+
+    def clear_pad_rate_ratio(self, sample_id: int) -> None:
+        """Return one pad to normal speed and BPM sync."""
+        validate_sample_id(sample_id)
+        if self._project.pad_rate_ratio[sample_id] is None:
+            return
+
+        self._audio.clear_pad_rate_ratio(sample_id)
+        self._project.pad_rate_ratio[sample_id] = None
+        self._transport._mark_project_changed()
+
     def set_manual_key(self, sample_id: int, key: str) -> None:
         """Set a pad's manual key override."""
         validate_sample_id(sample_id)
@@ -25,6 +25,7 @@ class ApplyProjectState:
         self._apply_per_pad_mixing(defaults)
         self._apply_pad_loop_regions(defaults)
         self._apply_pad_bpm_settings()
+        self._apply_pad_rate_ratios()
         self._apply_bpm_lock_settings()
 
     def _apply_global_audio_settings(self, defaults: ProjectState) -> None:
@@ -110,6 +111,11 @@ class ApplyProjectState:
                 continue
             self._bpm.on_pad_bpm_changed(sample_id)
 
+    def _apply_pad_rate_ratios(self) -> None:
+        for sample_id, ratio in enumerate(self._project.pad_rate_ratio):
+            if ratio is not None:
+                self._audio.set_pad_rate_ratio(sample_id, *ratio)
+
     def _apply_bpm_lock_settings(self) -> None:
         if self._project.bpm_lock:
             anchor_pad_id = self._project.selected_pad
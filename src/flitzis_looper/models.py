@@ -28,6 +28,7 @@ from flitzis_looper.constants import (
     PAD_LOOP_BARS_DEFAULT,
     PAD_LOOP_BARS_GRANULARITY,
     PAD_LOOP_BARS_MIN,
+    PAD_RATE_RATIO_TERM_MAX,
     SPEED_MAX,
     SPEED_MIN,
     VOLUME_MAX,
@@ -112,6 +113,17 @@ def validate_sample_id(sample_id: int) -> int:
     return sample_id
 
 
+def validate_pad_rate_ratio(numerator: int, denominator: int) -> tuple[int, int]:
+    for term in (numerator, denominator):
+        if not 1 <= term <= PAD_RATE_RATIO_TERM_MAX:
+            msg = f"rate ratio terms must be >= 1 and <= {PAD_RATE_RATIO_TERM_MAX}, got {term}"
+            raise ValueError(msg)
+    if not SPEED_MIN <= numerator / denominator <= SPEED_MAX:
+        msg = f"rate ratio must be >= {SPEED_MIN} and <= {SPEED_MAX}, got {numerator}/{denominator}"
+        raise ValueError(msg)
+    return numerator, denominator
+
+
 class BeatGrid(BaseModel):
     beats: list[float]
     downbeats: list[float]
@@ -122,6 +134,11 @@ class SampleAnalysis(BaseModel):
     bpm: float
     key: str
     beat_grid: BeatGrid
+    # Optional detector extras; analyses saved before they existed load with `None`.
+    bpm_confidence: float | None = None
+    key_confidence: float | None = None
+    grid_stability: float | None = None
+    onsets: list[float] | None = None
 
 
 class StemFileSet(BaseModel):
@@ -182,6 +199,18 @@ def _default_pad_key_lock() -> list[bool]:
     return [False] * NUM_SAMPLES
 
 
+def _default_pad_rate_ratio() -> list[tuple[int, int] | None]:
+    return [None] * NUM_SAMPLES
+
+
+def _default_pad_playlist() -> list[list[str] | None]:
+    return [None] * NUM_SAMPLES
+
+
+def _default_pad_playlist_position() -> list[int]:
+    return [0] * NUM_SAMPLES
+
+
 def _default_manual_bpm() -> list[float | None]:
     return [None] * NUM_SAMPLES
 
@@ -305,6 +334,15 @@ class ProjectState(BaseModel):
     pad_key_lock: list[bool] = Field(default_factory=_default_pad_key_lock)
     """Per-pad durable Key Lock intent."""
 
+    pad_rate_ratio: list[tuple[int, int] | None] = Field(default_factory=_default_pad_rate_ratio)
+    """Optional per-pad exact playback rate `(numerator, denominator)` overriding BPM sync."""
+
+    pad_playlist: list[list[str] | None] = Field(default_factory=_default_pad_playlist)
+    """Optional per-pad playlist of audio file paths that take turns on the pad."""
+
+    pad_playlist_position: list[int] = Field(default_factory=_default_pad_playlist_position)
+    """Per-pad index of the active playlist entry, so a reload resumes there."""
+
     manual_bpm: list[float | None] = Field(default_factory=_default_manual_bpm)
     """Optional per-pad BPM override. When set, used for effective BPM display."""
 
@@ -491,6 +529,41 @@ class ProjectState(BaseModel):
             raise ValueError(msg)
         return value
 
+    @field_validator("pad_rate_ratio", mode="after")
+    @classmethod
+    def _validate_pad_rate_ratio(
+        cls, value: list[tuple[int, int] | None]
+    ) -> list[tuple[int, int] | None]:
+        if len(value) != NUM_SAMPLES:
+            msg = f"pad_rate_ratio must have length {NUM_SAMPLES}, got {len(value)}"
+            raise ValueError(msg)
+        for ratio in value:
+            if ratio is not None:
+                validate_pad_rate_ratio(*ratio)
+        return value
+
+    @field_validator("pad_playlist", mode="after")
+    @classmethod
+    def _validate_pad_playlist(cls, value: list[list[str] | None]) -> list[list[str] | None]:
+        if len(value) != NUM_SAMPLES:
+            msg = f"pad_playlist must have length {NUM_SAMPLES}, got {len(value)}"
+            raise ValueError(msg)
+        if any(playlist is not None and not playlist for playlist in value):
+            msg = "pad_playlist entries must not be empty"
+            raise ValueError(msg)
+        return value
+
+    @field_validator("pad_playlist_position", mode="after")
+    @classmethod
+    def _validate_pad_playlist_position(cls, value: list[int]) -> list[int]:
+        if len(value) != NUM_SAMPLES:
+            msg = f"pad_playlist_position must have length {NUM_SAMPLES}, got {len(value)}"
+            raise ValueError(msg)
+        if any(position < 0 for position in value):
+            msg = "pad_playlist_position must not be negative"
+            raise ValueError(msg)
+        return value
+
     @field_validator("demucs_overlap", mode="after")
     @classmethod
     def _validate_demucs_overlap(cls, value: float) -> float:
@@ -107,6 +107,9 @@ class PadSelectors:  # noqa: PLR0904 - selector facade intentionally mirrors pad
             return False
         return bool(self._project.pad_key_lock[pad_id])
 
+    def rate_ratio(self, pad_id: int) -> tuple[int, int] | None:
+        return self._project.pad_rate_ratio[pad_id]
+
     def effective_loop_region(self, pad_id: int) -> tuple[float, float | None]:
         return self._controller.transport.loop.effective_region(pad_id)
 
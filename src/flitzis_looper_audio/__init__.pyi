@@ -1,11 +1,24 @@
+from typing import Literal
+
 import numpy as np
 from numpy.typing import NDArray
 
+class QueueBusy(RuntimeError): ...
+
 class AudioMessage:
     def sample_id(self) -> int | None: ...
     def pad_peak(self) -> float | None: ...
     def master_peak(self) -> float | None: ...
+    def limiter_reduction(self) -> tuple[float, float] | None: ...
     def pad_playhead(self) -> float | None: ...
+    def pad_latency(self) -> int | None: ...
+    def pad_remaining(self) -> tuple[int | None, float | None] | None: ...
+    def playlist_entry(self) -> int | None: ...
+    def sequencer_step(self) -> tuple[int, int] | None: ...
+    def scene_bank(self) -> int | None: ...
+    def voice_stolen(self) -> tuple[int, int, int] | None: ...
+    def transient_snap(self) -> int | None: ...
+    def loaded_slots(self) -> list[bool] | None: ...
 
     class Pong(AudioMessage):
         def __init__(self) -> None: ...
@@ -22,14 +35,113 @@ class AudioMessage:
     class MasterPeak(AudioMessage):
         def __init__(self, peak: float) -> None: ...
 
+    class LimiterMeter(AudioMessage):
+        def __init__(self, reduction_db: float, peak_reduction_db: float) -> None: ...
+
     class PadPlayhead(AudioMessage):
         def __init__(self, pad_id: int, position_s: float) -> None: ...
 
+    class EngineFault(AudioMessage):
+        def __init__(self) -> None: ...
+
+    class TriggerDropped(AudioMessage):
+        def __init__(self, handle: int) -> None: ...
+
+    class DeviceError(AudioMessage):
+        _0: str
+        def __init__(self, _0: str) -> None: ...
+
+    class SeekAcknowledged(AudioMessage):
+        def __init__(self, pad_id: int) -> None: ...
+
+    class VoiceFinished(AudioMessage):
+        def __init__(self, pad_id: int) -> None: ...
+
+    class StateSnapshot(AudioMessage):
+        active_voices: int
+        loaded_mask: list[int]
+        def __init__(self, active_voices: int, loaded_mask: list[int]) -> None: ...
+
+    class PadLatency(AudioMessage):
+        def __init__(self, pad_id: int, frames: int) -> None: ...
+
+    class PadRemaining(AudioMessage):
+        def __init__(
+            self, pad_id: int, remaining_frames: int | None, remaining_s: float | None
+        ) -> None: ...
+
+    class PlaylistEntryActive(AudioMessage):
+        def __init__(self, pad_id: int, entry: int) -> None: ...
+
+    class SequencerStep(AudioMessage):
+        def __init__(self, track: int, step: int) -> None: ...
+
+    class SceneLaunched(AudioMessage):
+        def __init__(self, bank: int) -> None: ...
+
+    class SceneDropped(AudioMessage):
+        def __init__(self, bank: int) -> None: ...
+
+    class VoiceStolen(AudioMessage):
+        def __init__(
+            self, pad_id: int, priority: int, stolen_id: int, stolen_priority: int
+        ) -> None: ...
+
+    class TransientSnap(AudioMessage):
+        def __init__(self, pad_id: int, offset_frames: int) -> None: ...
+
 class AudioEngine:
     def __init__(self) -> None: ...
-    def run(self) -> None: ...
+    @staticmethod
+    def supported_extensions() -> list[str]: ...
+    @staticmethod
+    def build_info() -> dict[str, object]: ...
+    def run(
+        self,
+        sample_rate: int | None = None,
+        loop_mode: Literal["looper", "sampler"] = "looper",
+        buffer_capacity: int = 1024,
+        frames: int | None = None,
+    ) -> None: ...
+    @staticmethod
+    def list_output_devices() -> list[str]: ...
+    def run_with_device_index(
+        self,
+        index: int,
+        sample_rate: int | None = None,
+        loop_mode: Literal["looper", "sampler"] = "looper",
+        buffer_capacity: int = 1024,
+        frames: int | None = None,
+    ) -> None: ...
+    def run_with_device(
+        self,
+        name: str,
+        sample_rate: int | None = None,
+        loop_mode: Literal["looper", "sampler"] = "looper",
+        buffer_capacity: int = 1024,
+        frames: int | None = None,
+    ) -> None: ...
     def output_sample_rate(self) -> int: ...
+    def output_channels(self) -> int: ...
+    def get_output_config(self) -> dict[str, object]: ...
+    def get_clip_count(self) -> int: ...
+    def get_limiter_reduction(self) -> tuple[float, float]: ...
+    def audio_clock(self) -> tuple[int, float] | None: ...
+    def get_clock_info(self) -> dict[str, int | float | None] | None: ...
+    def schedule_next_message(self, frame: int) -> None: ...
+    def automation_record_start(self) -> None: ...
+    def automation_record_stop(self) -> list[tuple[int, str, int | None, list[float]]]: ...
+    def automation_play(
+        self,
+        events: list[tuple[int, str, int | None, list[float]]],
+        offset_frames: int = 0,
+        loop_frames: int | None = None,
+    ) -> None: ...
+    def automation_stop(self) -> None: ...
+    def is_automation_playing(self) -> bool: ...
     def loaded_sample_shape(self, sample_id: int) -> tuple[int, int, int]: ...
+    def is_running(self) -> bool: ...
+    def health(self) -> Literal["stopped", "running", "faulted"]: ...
     def shut_down(self) -> None: ...
     def set_input_mapping_enabled(self, enabled: bool) -> None: ...
     def set_input_learn_active(self, active: bool) -> None: ...
@@ -46,6 +158,9 @@ class AudioEngine:
     def inject_midi_input_for_test(self, message: list[int]) -> bool: ...
     def poll_input_events(self) -> dict[str, object] | None: ...
     def load_sample_async(self, sample_id: int, path: str, run_analysis: bool = True) -> int: ...
+    def load_sample_from_bytes(
+        self, sample_id: int, data: bytes, hint_extension: str | None = None
+    ) -> float: ...
     def analyze_sample_async(self, sample_id: int) -> int: ...
     def generate_stems_async(self, sample_id: int, source_version: str, cache_dir: str) -> None: ...
     def publish_prepared_stems(
@@ -58,27 +173,121 @@ class AudioEngine:
         self, sample_id: int, enabled_stem_mask: int, source_version: str
     ) -> None: ...
     def poll_loader_events(self) -> dict[str, object] | None: ...
-    def play_sample(self, sample_id: int, volume: float) -> None: ...
+    def play_sample(
+        self,
+        sample_id: int,
+        volume: float,
+        speed: float | None = None,
+        loop: bool | None = None,
+        start_seconds: float | None = None,
+        pan: float | None = None,
+        *,
+        loop_mode: bool | None = None,
+    ) -> int: ...
     def play_sample_exclusive(self, sample_id: int, volume: float) -> None: ...
+    def play_sample_for(self, sample_id: int, volume: float, frames: int) -> None: ...
+    def play_sample_cued(self, sample_id: int, volume: float = 1.0) -> None: ...
+    def promote_to_master(self, sample_id: int) -> None: ...
     def stop_sample(self, sample_id: int) -> None: ...
+    def stop_voice(self, handle: int) -> None: ...
     def pause_sample(self, sample_id: int) -> None: ...
     def resume_sample(self, sample_id: int) -> None: ...
     def seek_sample(self, sample_id: int, position_s: float) -> None: ...
+    def set_mono_widening(self, delay_ms: float | None = None) -> None: ...
+    def set_resampler_quality(self, quality: Literal["fast", "balanced", "high"]) -> None: ...
+    def set_silence_trim(
+        self, mode: Literal["fixed", "auto"] | None = None, threshold_db: float = -60.0
+    ) -> None: ...
+    def reset_pad_dsp(self, sample_id: int) -> None: ...
+    def stutter(self, sample_id: int, slice_frames: int, on: bool) -> None: ...
     def stop_all(self) -> None: ...
     def set_volume(self, volume: float) -> None: ...
     def set_speed(self, speed: float) -> None: ...
     def set_pad_gain(self, sample_id: int, gain_db: float) -> None: ...
     def set_pad_eq(self, sample_id: int, low_db: float, mid_db: float, high_db: float) -> None: ...
+    def auto_trim_pads(self, target_dbfs: float) -> dict[int, float]: ...
+    def set_pad_params(
+        self,
+        sample_id: int,
+        gain_db: float | None = None,
+        eq_db: tuple[float, float, float] | None = None,
+        mute: bool | None = None,
+    ) -> None: ...
     def set_pad_loop_region(self, sample_id: int, start_s: float, end_s: float | None) -> None: ...
+    def preview_loop_region(self, sample_id: int, start_s: float, end_s: float) -> None: ...
+    def stop_preview_loop(self) -> None: ...
+    def set_pad_fade_mute(self, sample_id: int, muted: bool) -> None: ...
+    def set_pad_slices(self, sample_id: int, slices: int) -> None: ...
+    def set_pad_rate_ratio(self, sample_id: int, numerator: int, denominator: int) -> None: ...
+    def clear_pad_rate_ratio(self, sample_id: int) -> None: ...
+    def set_pad_fades(self, sample_id: int, fade_in_ms: float, fade_out_ms: float) -> None: ...
+    def clear_pad_fades(self, sample_id: int) -> None: ...
+    def set_velocity_attack(self, soft_ms: float, hard_ms: float) -> None: ...
+    def clear_velocity_attack(self) -> None: ...
+    def automate_pad_eq(
+        self,
+        sample_id: int,
+        band: Literal["low", "mid", "high"],
+        target_db: float,
+        duration_s: float,
+    ) -> None: ...
+    def store_snapshot(self, snapshot: Literal["a", "b"]) -> None: ...
+    def morph_to(self, snapshot: Literal["a", "b"], seconds: float) -> None: ...
+    def set_pad_clip(self, sample_id: int, enabled: bool, ceiling: float = 1.0) -> None: ...
+    def set_pad_delay_ms(self, sample_id: int, ms: float) -> None: ...
+    def set_speaker_layout(self, angles: list[float]) -> None: ...
+    def clear_speaker_layout(self) -> None: ...
+    def set_pad_position(self, sample_id: int, azimuth_degrees: float) -> None: ...
+    def clear_pad_position(self, sample_id: int) -> None: ...
+    def set_keyboard_mode(self, bank: int, source_slot: int, root_pad: int) -> None: ...
+    def clear_keyboard_mode(self, bank: int) -> None: ...
     def set_trigger_quantization(self, mode: str) -> None: ...
+    def set_swing(self, amount: float) -> None: ...
+    def set_latency_compensation(self, enabled: bool) -> None: ...
+    def set_sequence(self, track: int, steps: list[tuple[int, float] | None]) -> None: ...
+    def set_sequencer_track_mute(self, track: int, muted: bool) -> None: ...
+    def sequencer_start(self) -> None: ...
+    def sequencer_stop(self) -> None: ...
+    def launch_scene(self, bank: int, slot_states: list[bool]) -> None: ...
+    def cancel_scene(self) -> None: ...
     def set_bpm_lock(self, enabled: bool) -> None: ...
+    def set_master_dc_blocker(self, enabled: bool) -> None: ...
+    def set_master_limiter(self, enabled: bool) -> None: ...
+    def set_limiter_meter_events(self, enabled: bool) -> None: ...
+    def set_mono_output(self, enabled: bool) -> None: ...
+    def set_monitor_output(
+        self, source: Literal["preview", "cue"], first_channel: int | None = None
+    ) -> None: ...
+    def set_output_trim(self, trims: list[float]) -> None: ...
+    def set_tempo_glide(self, glide_ms: float) -> None: ...
+    def set_stop_fade_samples(self, samples: int) -> None: ...
     def set_key_lock(self, enabled: bool) -> None: ...
     def set_pad_key_lock(self, sample_id: int, enabled: bool) -> None: ...
+    def set_pad_priority(self, sample_id: int, priority: int) -> None: ...
+    def set_voice_steal_policy(self, policy: Literal["silent", "oldest", "quietest"]) -> None: ...
+    def set_loop_mode(self, mode: Literal["looper", "sampler"]) -> None: ...
+    def set_pad_loop_mode(
+        self, sample_id: int, mode: Literal["loop", "one_shot"] | None = None
+    ) -> None: ...
+    def set_pad_cue(self, sample_id: int, enabled: bool) -> None: ...
+    def set_cue_split(self, enabled: bool) -> None: ...
+    def set_pad_choke_group(self, sample_id: int, group: int | None = None) -> None: ...
+    def set_choke_group_stop_all(self, group: int, enabled: bool) -> None: ...
     def set_master_bpm(self, bpm: float) -> None: ...
     def set_pad_bpm(self, sample_id: int, bpm: float | None) -> None: ...
     def set_pad_timing_metadata(self, sample_id: int, phase_anchor_s: float) -> None: ...
+    def set_pad_onsets(self, sample_id: int, onsets_s: list[float]) -> None: ...
+    def set_snap_to_transient(self, sample_id: int, enabled: bool, window_ms: float) -> None: ...
     def anchor_transport_phase_from_pad(self, sample_id: int) -> None: ...
     def unload_sample(self, sample_id: int) -> None: ...
+    def set_playlist(self, sample_id: int, paths: list[str], position: int = 0) -> None: ...
+    def clear_playlist(self, sample_id: int) -> None: ...
+    def playlist_next(self, sample_id: int) -> None: ...
+    def playlist_prev(self, sample_id: int) -> None: ...
+    def playlist_position(self, sample_id: int) -> int | None: ...
+    def query_pad_latency(self, sample_id: int) -> None: ...
+    def query_remaining(self, sample_id: int) -> None: ...
+    def query_state(self) -> None: ...
     def ping(self) -> None: ...
     def receive_msg(self) -> AudioMessage | None: ...
     def get_waveform_render_data(
@@ -1,4 +1,5 @@
 from typing import TYPE_CHECKING
+from unittest.mock import Mock
 
 import pytest
 
@@ -24,7 +25,6 @@ from tests.conftest import write_mono_pcm16_wav
 
 if TYPE_CHECKING:
     from pathlib import Path
-    from unittest.mock import Mock
 
     from flitzis_looper.controller import AppController
 
@@ -515,6 +515,52 @@ def test_is_sample_loaded_false(controller: AppController) -> None:
     assert controller.loader.is_sample_loaded(sample_id) is False
 
 
+def test_restore_resumes_playlist_at_saved_position(
+    controller: AppController, audio_engine_mock: Mock
+) -> None:
+    controller.project.sample_paths[1] = "samples/a.wav"
+    controller.project.pad_playlist[1] = ["a.wav", "b.wav", "c.wav"]
+    controller.project.pad_playlist_position[1] = 2
+
+    controller.loader.restore_samples_from_project_state()
+
+    audio_engine_mock.set_playlist.assert_called_once_with(1, ["a.wav", "b.wav", "c.wav"], 2)
+    audio_engine_mock.load_sample_async.assert_not_called()
+
+
+def test_restore_drops_playlist_the_engine_rejects(
+    controller: AppController, audio_engine_mock: Mock
+) -> None:
+    audio_engine_mock.set_playlist.side_effect = ValueError("sample is currently loading")
+    controller.project.pad_playlist[1] = ["a.wav", "b.wav"]
+    controller.project.pad_playlist_position[1] = 1
+
+    controller.loader.restore_samples_from_project_state()
+
+    assert controller.project.pad_playlist[1] is None
+    assert controller.project.pad_playlist_position[1] == 0
+
+
+def test_playlist_entry_active_message_updates_saved_position(
+    controller: AppController, audio_engine_mock: Mock
+) -> None:
+    controller.loader.set_playlist(4, ["a.wav", "b.wav"])
+    audio_engine_mock.set_playlist.assert_called_once_with(4, ["a.wav", "b.wav"], 0)
+
+    msg = Mock()
+    msg.sample_id.return_value = 4
+    msg.playlist_entry.return_value = 1
+    controller.loader.handle_playlist_entry_active_message(msg)
+
+    assert controller.project.pad_playlist_position[4] == 1
+
+    controller.loader.clear_playlist(4)
+
+    audio_engine_mock.clear_playlist.assert_called_once_with(4)
+    assert controller.project.pad_playlist[4] is None
+    assert controller.project.pad_playlist_position[4] == 0
+
+
 def test_restore_sample_does_not_copy_file(tmp_path: Path, monkeypatch: pytest.MonkeyPatch) -> None:
     monkeypatch.chdir(tmp_path)
 
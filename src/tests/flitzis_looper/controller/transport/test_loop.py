@@ -3,7 +3,7 @@ from typing import TYPE_CHECKING
 
 import pytest
 
-from flitzis_looper.controller.transport.loop import PadLoopController
+from flitzis_looper.controller.transport.loop import PadLoopController, nearest_whole_loop_bars
 from flitzis_looper.models import BeatGrid, SampleAnalysis
 
 if TYPE_CHECKING:
@@ -646,6 +646,66 @@ def test_max_auto_loop_bars_uses_remaining_duration_and_effective_bpm(
     assert controller.transport.loop.max_auto_loop_bars(sample_id) == pytest.approx(4.0)
 
 
+@pytest.mark.parametrize(
+    ("duration_s", "bpm", "expected"),
+    [
+        (3.98, 120.3, 2.0),
+        (8.1, 120.0, 4.0),
+        (2.0, 120.0, 1.0),
+        (12.0, 120.0, 6.0),
+        (5.0, 120.0, None),
+        (0.3, 120.0, None),
+        (0.0, 120.0, None),
+        (4.0, 0.0, None),
+    ],
+)
+def test_nearest_whole_loop_bars_rounds_synthetic_durations(
+    duration_s: float, bpm: float, expected: float | None
+) -> None:
+    assert nearest_whole_loop_bars(duration_s, bpm) == expected
+
+
+def test_nearest_whole_loop_bars_prefers_configured_counts_within_tolerance() -> None:
+    # 3.4 bars: 3 is nearer, but 4 is preferred and still within the wider tolerance.
+    assert nearest_whole_loop_bars(6.8, 120.0, max_error_bars=0.75) == 4.0
+    assert nearest_whole_loop_bars(6.8, 120.0, max_error_bars=0.5, preferred_bars=()) == 3.0
+    assert nearest_whole_loop_bars(6.8, 120.0) is None
+
+
+def test_auto_loop_sets_whole_bars_from_loop_start(
+    controller: AppController,
+    audio_engine_mock: Mock,
+) -> None:
+    audio_engine_mock.output_sample_rate.return_value = 1_000
+
+    sample_id = 0
+    controller.project.sample_paths[sample_id] = "samples/foo.wav"
+    controller.project.sample_durations[sample_id] = 9.98
+    controller.project.pad_loop_start_s[sample_id] = 2.0
+    controller.project.pad_loop_auto[sample_id] = False
+    controller.transport.bpm.set_manual_bpm(sample_id, 120.0)
+
+    assert controller.transport.loop.auto_loop(sample_id) == 4.0
+
+    assert controller.project.pad_loop_auto[sample_id]
+    assert controller.project.pad_loop_bars[sample_id] == 4.0
+    audio_engine_mock.set_pad_loop_region.assert_called_with(sample_id, 2.0, 10.0)
+
+
+def test_auto_loop_refuses_lengths_between_bars(
+    controller: AppController,
+    audio_engine_mock: Mock,
+) -> None:
+    sample_id = 0
+    controller.project.sample_paths[sample_id] = "samples/foo.wav"
+    controller.project.sample_durations[sample_id] = 5.0
+    controller.transport.bpm.set_manual_bpm(sample_id, 120.0)
+    controller.project.pad_loop_bars[sample_id] = 8.0
+
+    assert controller.transport.loop.auto_loop(sample_id) is None
+    assert controller.project.pad_loop_bars[sample_id] == 8.0
+
+
 def test_set_bars_no_op(controller: AppController, audio_engine_mock: Mock) -> None:
     sample_id = 0
     controller.project.sample_paths[sample_id] = "samples/foo.wav"
@@ -98,6 +98,29 @@ def test_set_pad_key_lock_resets_stale_unloaded_pad(
     assert controller.project.pad_key_lock[3] is False
 
 
+def test_set_and_clear_pad_rate_ratio(controller: AppController, audio_engine_mock: Mock) -> None:
+    controller.transport.pad.set_pad_rate_ratio(3, 3, 2)
+
+    audio_engine_mock.set_pad_rate_ratio.assert_called_once_with(3, 3, 2)
+    assert controller.project.pad_rate_ratio[3] == (3, 2)
+
+    controller.transport.pad.clear_pad_rate_ratio(3)
+
+    audio_engine_mock.clear_pad_rate_ratio.assert_called_once_with(3)
+    assert controller.project.pad_rate_ratio[3] is None
+
+
+@pytest.mark.parametrize(("numerator", "denominator"), [(0, 1), (17, 16), (3, 1)])
+def test_set_pad_rate_ratio_rejects_invalid_ratio(
+    controller: AppController, audio_engine_mock: Mock, numerator: int, denominator: int
+) -> None:
+    with pytest.raises(ValueError, match="rate ratio"):
+        controller.transport.pad.set_pad_rate_ratio(3, numerator, denominator)
+
+    audio_engine_mock.set_pad_rate_ratio.assert_not_called()
+    assert controller.project.pad_rate_ratio[3] is None
+
+
 def test_poll_audio_messages_updates_session_peak(
     controller: AppController, audio_engine_mock: Mock, monkeypatch: pytest.MonkeyPatch
 ) -> None:
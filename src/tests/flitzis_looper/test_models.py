@@ -297,6 +297,9 @@ def test_project_state_defaults(project_state: ProjectState) -> None:
     assert not any(project_state.pad_loop_auto)
     assert len(project_state.pad_loop_bars) == NUM_SAMPLES
     assert all(bars == PAD_LOOP_BARS_DEFAULT for bars in project_state.pad_loop_bars)
+    assert len(project_state.pad_playlist) == NUM_SAMPLES
+    assert all(playlist is None for playlist in project_state.pad_playlist)
+    assert project_state.pad_playlist_position == [0] * NUM_SAMPLES
     assert project_state.sidebar_left_expanded is True
     assert project_state.sidebar_right_expanded is True
 
@@ -314,6 +317,31 @@ def test_pad_gain_db_validation(project_state: ProjectState) -> None:
         ProjectState(pad_gain_db=[PAD_GAIN_DB_MAX + 0.1] * NUM_SAMPLES)
 
 
+def test_pad_playlist_round_trips_with_its_position(project_state: ProjectState) -> None:
+    project_state.pad_playlist[2] = ["a.wav", "b.wav", "c.wav"]
+    project_state.pad_playlist_position[2] = 1
+
+    reconstructed = ProjectState.model_validate_json(project_state.model_dump_json())
+
+    assert reconstructed.pad_playlist[2] == ["a.wav", "b.wav", "c.wav"]
+    assert reconstructed.pad_playlist_position[2] == 1
+    assert reconstructed.pad_playlist[0] is None
+
+
+def test_pad_playlist_validation() -> None:
+    with pytest.raises(ValidationError, match="pad_playlist must have length"):
+        ProjectState(pad_playlist=[])
+
+    with pytest.raises(ValidationError, match="must not be empty"):
+        ProjectState(pad_playlist=[[]] + [None] * (NUM_SAMPLES - 1))
+
+    with pytest.raises(ValidationError, match="pad_playlist_position must have length"):
+        ProjectState(pad_playlist_position=[])
+
+    with pytest.raises(ValidationError, match="must not be negative"):
+        ProjectState(pad_playlist_position=[-1] + [0] * (NUM_SAMPLES - 1))
+
+
 def test_legacy_pad_gain_migrates_to_db_defaults_and_unity() -> None:
     project = ProjectState.model_validate({})
     assert project.pad_gain_db[0] == 0.0
@@ -479,6 +507,26 @@ def test_loaded_pad_key_lock_preserved_on_load() -> None:
     assert project.pad_key_lock[3] is True
 
 
+def test_sample_analysis_extras_are_optional() -> None:
+    grid = {"beats": [0.0, 0.5], "downbeats": [0.0], "bars": [0.0]}
+
+    legacy = SampleAnalysis.model_validate({"bpm": 120.0, "key": "C", "beat_grid": grid})
+    extended = SampleAnalysis.model_validate({
+        "bpm": 120.0,
+        "key": "C",
+        "beat_grid": grid,
+        "bpm_confidence": 0.25,
+        "key_confidence": 0.8,
+        "grid_stability": 0.9,
+        "onsets": [0.0, 0.5],
+    })
+
+    assert legacy.bpm_confidence is None
+    assert legacy.onsets is None
+    assert extended.bpm_confidence == pytest.approx(0.25)
+    assert extended.onsets == [0.0, 0.5]
+
+
 def test_stem_cache_entry_represents_expected_kinds() -> None:
     files = StemFileSet(
         vocals="samples/stems/a/vocals.wav",
@@ -0,0 +1,25 @@
+import logging
+import os
+
+import pytest
+
+from flitzis_looper_audio import AudioEngine
+
+
+@pytest.mark.skipif(
+    "FLITZIS_LOOPER_ENV_LOGGER" in os.environ,
+    reason="Rust logs go to env_logger instead of Python logging",
+)
+def test_rust_log_records_reach_python_logging(caplog: pytest.LogCaptureFixture) -> None:
+    engine = AudioEngine()
+    try:
+        with caplog.at_level(logging.INFO, logger="flitzis_looper"):
+            try:
+                engine.run()
+            except RuntimeError as exc:
+                pytest.skip(f"AudioEngine unavailable: {exc}")
+    finally:
+        engine.shut_down()
+
+    records = [record for record in caplog.records if record.name.startswith("flitzis_looper.")]
+    assert any("Starting AudioEngine" in record.getMessage() for record in records)